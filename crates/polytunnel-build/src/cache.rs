@@ -0,0 +1,222 @@
+//! Artifact cache inspection and maintenance
+
+use crate::error::Result;
+use polytunnel_core::ProjectConfig;
+use polytunnel_maven::{CONTENT_DIR, Coordinate};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Files at the cache root holding build metadata rather than artifacts
+const METADATA_FILES: &[&str] = &["build-cache.json"];
+
+/// Summary of the artifact cache contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheInfo {
+    /// Cache directory
+    pub location: PathBuf,
+    /// Total size of all files in bytes
+    pub total_size: u64,
+    /// Number of cached artifact files
+    pub artifact_count: usize,
+}
+
+/// Outcome of a cache clean or prune
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheCleanup {
+    /// Number of artifact files removed
+    pub removed: usize,
+    /// Bytes freed
+    pub freed_bytes: u64,
+}
+
+/// Downloaded artifact cache rooted at `build.cache_dir`
+#[derive(Debug, Clone)]
+pub struct ArtifactCache {
+    root: PathBuf,
+}
+
+impl ArtifactCache {
+    /// Create a cache handle for the given directory
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Create a cache handle for the project's configured `build.cache_dir`
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        Self::new(&config.build.cache_dir)
+    }
+
+    /// Cache directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Location of a coordinate's JAR inside the cache
+    pub fn artifact_path(&self, coord: &Coordinate) -> PathBuf {
+        self.root.join(coord.repo_path()).join(coord.jar_filename())
+    }
+
+    /// Compute total size and artifact count
+    ///
+    /// A missing cache directory is reported as empty. Hard-linked copies
+    /// (see `build.cache_dedup`) are only counted once towards the size.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If the cache directory cannot be walked
+    pub fn info(&self) -> Result<CacheInfo> {
+        let mut info = CacheInfo {
+            location: self.root.clone(),
+            total_size: 0,
+            artifact_count: 0,
+        };
+
+        let mut seen = HashSet::new();
+        for (path, metadata) in self.files()? {
+            if first_link(&metadata, &mut seen) {
+                info.total_size += metadata.len();
+            }
+            if self.is_artifact(&path) {
+                info.artifact_count += 1;
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Remove the whole cache directory
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If the directory cannot be removed
+    pub fn clean(&self) -> Result<CacheCleanup> {
+        let info = self.info()?;
+        if self.root.exists() {
+            std::fs::remove_dir_all(&self.root)?;
+        }
+        Ok(CacheCleanup {
+            removed: info.artifact_count,
+            freed_bytes: info.total_size,
+        })
+    }
+
+    /// Remove artifacts whose modification time is older than `older_than`
+    ///
+    /// Nothing in the directory of a coordinate in `keep` (typically the
+    /// lockfile packages) is removed, so its POM and checksums stay with the JAR. Deduplicated content no longer linked from any
+    /// artifact and directories left empty are deleted afterwards.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If a file cannot be inspected or removed
+    pub fn prune(&self, older_than: Duration, keep: &[Coordinate]) -> Result<CacheCleanup> {
+        let cutoff = SystemTime::now()
+            .checked_sub(older_than)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let keep: Vec<PathBuf> = keep.iter().map(|c| self.root.join(c.repo_path())).collect();
+
+        let mut cleanup = CacheCleanup::default();
+        for (path, metadata) in self.files()? {
+            if !self.is_artifact(&path) || keep.iter().any(|dir| path.starts_with(dir)) {
+                continue;
+            }
+            if metadata.modified()? < cutoff {
+                std::fs::remove_file(&path)?;
+                cleanup.removed += 1;
+                // Shared content is freed below once its last artifact is gone
+                if link_count(&metadata).is_none_or(|links| links <= 1) {
+                    cleanup.freed_bytes += metadata.len();
+                }
+            }
+        }
+        cleanup.freed_bytes += self.remove_orphaned_content()?;
+
+        self.remove_empty_dirs()?;
+        Ok(cleanup)
+    }
+
+    fn files(&self) -> Result<Vec<(PathBuf, std::fs::Metadata)>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        for entry in WalkDir::new(&self.root) {
+            let entry = entry.map_err(std::io::Error::from)?;
+            if entry.file_type().is_file() {
+                let metadata = entry.metadata().map_err(std::io::Error::from)?;
+                files.push((entry.into_path(), metadata));
+            }
+        }
+        Ok(files)
+    }
+
+    fn is_artifact(&self, path: &Path) -> bool {
+        let at_root = path.parent() == Some(self.root.as_path());
+        let is_metadata = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| METADATA_FILES.contains(&n));
+        !(at_root && is_metadata || self.is_content(path))
+    }
+
+    fn is_content(&self, path: &Path) -> bool {
+        path.starts_with(self.root.join(CONTENT_DIR))
+    }
+
+    /// Remove deduplicated content that no artifact links to, returning the bytes freed
+    ///
+    /// Where link counts are unavailable nothing is removed, since content
+    /// still in use cannot be told apart.
+    fn remove_orphaned_content(&self) -> Result<u64> {
+        let mut freed = 0;
+        for (path, metadata) in self.files()? {
+            if self.is_content(&path) && link_count(&metadata) == Some(1) {
+                std::fs::remove_file(&path)?;
+                freed += metadata.len();
+            }
+        }
+        Ok(freed)
+    }
+
+    fn remove_empty_dirs(&self) -> Result<()> {
+        if !self.root.exists() {
+            return Ok(());
+        }
+
+        // Children are visited before parents, so nested empty dirs collapse
+        for entry in WalkDir::new(&self.root).min_depth(1).contents_first(true) {
+            let entry = entry.map_err(std::io::Error::from)?;
+            if entry.file_type().is_dir() && std::fs::read_dir(entry.path())?.next().is_none() {
+                std::fs::remove_dir(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Number of hard links to a file, `None` where this cannot be queried
+#[cfg(unix)]
+fn link_count(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.nlink())
+}
+
+#[cfg(not(unix))]
+fn link_count(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+/// Whether this is the first hard link of a file seen, so shared content is counted once
+#[cfg(unix)]
+fn first_link(metadata: &std::fs::Metadata, seen: &mut HashSet<(u64, u64)>) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() <= 1 || seen.insert((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn first_link(_metadata: &std::fs::Metadata, _seen: &mut HashSet<(u64, u64)>) -> bool {
+    true
+}
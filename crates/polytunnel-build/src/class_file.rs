@@ -0,0 +1,146 @@
+//! Reading the public shape of compiled classes
+//!
+//! Only what other classes can compile against is kept: the class header,
+//! and the non-private fields and methods with their descriptors and
+//! compile-time constants. Method bodies and private members are ignored, so
+//! editing them leaves the signature unchanged.
+
+const ACC_PRIVATE: u16 = 0x0002;
+const ACC_SYNTHETIC: u16 = 0x1000;
+
+/// Minimal big-endian reader over class file bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn u2(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u4(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+/// Constant pool entry; numeric constants and references keep their raw bytes
+enum Constant<'a> {
+    Utf8(&'a [u8]),
+    Class(u16),
+    String(u16),
+    Other(&'a [u8]),
+    /// Second slot of a long or double
+    Unusable,
+}
+
+/// Canonical description of the members other classes can see
+///
+/// Returns `None` if `bytes` is not a well-formed class file.
+pub(crate) fn signature(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.u4()? != 0xCAFE_BABE {
+        return None;
+    }
+    reader.take(4)?; // minor and major version
+
+    let pool_count = reader.u2()?;
+    let mut pool = vec![Constant::Unusable];
+    while pool.len() < usize::from(pool_count) {
+        let tag = reader.take(1)?[0];
+        let constant = match tag {
+            1 => {
+                let len = reader.u2()?;
+                Constant::Utf8(reader.take(usize::from(len))?)
+            }
+            7 => Constant::Class(reader.u2()?),
+            3 | 4 => Constant::Other(reader.take(4)?),
+            5 | 6 => Constant::Other(reader.take(8)?),
+            8 => Constant::String(reader.u2()?),
+            16 | 19 | 20 => Constant::Other(reader.take(2)?),
+            15 => Constant::Other(reader.take(3)?),
+            9..=12 | 17 | 18 => Constant::Other(reader.take(4)?),
+            _ => return None,
+        };
+        pool.push(constant);
+        if matches!(tag, 5 | 6) {
+            pool.push(Constant::Unusable);
+        }
+    }
+
+    let utf8 = |index: u16| match pool.get(usize::from(index)) {
+        Some(Constant::Utf8(bytes)) => Some(*bytes),
+        _ => None,
+    };
+    let class_name = |index: u16| match pool.get(usize::from(index)) {
+        Some(Constant::Class(name)) => utf8(*name),
+        _ if index == 0 => Some(&b""[..]),
+        _ => None,
+    };
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&reader.u2()?.to_be_bytes());
+    let this_class = reader.u2()?;
+    out.extend_from_slice(class_name(this_class)?);
+    out.push(0);
+    let super_class = reader.u2()?;
+    out.extend_from_slice(class_name(super_class)?);
+    out.push(0);
+    let mut interfaces = Vec::new();
+    for _ in 0..reader.u2()? {
+        interfaces.push(class_name(reader.u2()?)?);
+    }
+    interfaces.sort();
+    for interface in interfaces {
+        out.extend_from_slice(interface);
+        out.push(0);
+    }
+
+    for _ in 0..2 {
+        // Fields, then methods
+        let mut members: Vec<Vec<u8>> = Vec::new();
+        for _ in 0..reader.u2()? {
+            let access = reader.u2()?;
+            let name = utf8(reader.u2()?)?;
+            let descriptor = utf8(reader.u2()?)?;
+            let mut member = Vec::new();
+            member.extend_from_slice(&access.to_be_bytes());
+            member.extend_from_slice(name);
+            member.push(0);
+            member.extend_from_slice(descriptor);
+            member.push(0);
+            for _ in 0..reader.u2()? {
+                let attribute = utf8(reader.u2()?)?;
+                let len = reader.u4()?;
+                let info = reader.take(usize::try_from(len).ok()?)?;
+                // Constants are inlined into the classes that use them
+                if attribute == b"ConstantValue" && info.len() == 2 {
+                    let index = u16::from_be_bytes([info[0], info[1]]);
+                    match pool.get(usize::from(index))? {
+                        Constant::Other(bytes) => member.extend_from_slice(bytes),
+                        Constant::String(value) => member.extend_from_slice(utf8(*value)?),
+                        _ => return None,
+                    }
+                }
+            }
+            if access & (ACC_PRIVATE | ACC_SYNTHETIC) == 0 {
+                members.push(member);
+            }
+        }
+        members.sort();
+        for member in members {
+            out.extend_from_slice(&member);
+            out.push(0);
+        }
+        out.push(0xFF);
+    }
+
+    Some(out)
+}
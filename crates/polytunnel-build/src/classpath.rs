@@ -1,28 +1,204 @@
 //! Classpath management and dependency resolution
 
 use crate::error::{BuildError, Result};
-use futures::future::try_join_all;
+use crate::timing::{BuildPhase, PhaseTiming};
+use futures::{StreamExt, TryStreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
 use polytunnel_core::ProjectConfig;
-use polytunnel_maven::Coordinate;
-use std::path::PathBuf;
+use polytunnel_maven::{
+    ArtifactStore, Coordinate, FileSystemStore, MavenClient, MavenError, jar_key,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Ordered, duplicate-free list of classpath entries
+///
+/// Entries keep their first-seen position so that precedence (e.g. project
+/// classes before dependency JARs) is preserved. Empty paths are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Classpath {
+    entries: Vec<PathBuf>,
+}
+
+impl Classpath {
+    /// Create an empty classpath
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an entry unless it is empty or already present
+    pub fn push(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        if !path.as_os_str().is_empty() && !self.entries.contains(&path) {
+            self.entries.push(path);
+        }
+    }
+
+    /// Entries in classpath order
+    pub fn entries(&self) -> &[PathBuf] {
+        &self.entries
+    }
+
+    /// Consume the classpath, returning its entries
+    pub fn into_entries(self) -> Vec<PathBuf> {
+        self.entries
+    }
+
+    /// Whether the classpath has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Join entries with an explicit separator
+    pub fn join_with(&self, separator: char) -> String {
+        self.entries
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join(&separator.to_string())
+    }
+
+    /// Format as a `-cp` argument using the platform separator
+    pub fn to_arg_string(&self) -> String {
+        self.join_with(crate::platform::classpath_separator())
+    }
+}
+
+impl<P: AsRef<Path>> From<&[P]> for Classpath {
+    fn from(paths: &[P]) -> Self {
+        paths.iter().map(|p| p.as_ref().to_path_buf()).collect()
+    }
+}
+
+impl<P: Into<PathBuf>> FromIterator<P> for Classpath {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        let mut classpath = Self::new();
+        for path in iter {
+            classpath.push(path);
+        }
+        classpath
+    }
+}
 
 /// Result of classpath construction
 #[derive(Debug, Clone)]
 pub struct ClasspathResult {
     /// Classpath for compilation (compile + provided scope)
     pub compile_classpath: Vec<PathBuf>,
-    /// Classpath for testing (compile + test + provided scope)
+    /// Classpath for running and compiling tests (compile + provided + runtime + test scope)
     pub test_classpath: Vec<PathBuf>,
     /// Classpath for runtime (compile + runtime scope)
     pub runtime_classpath: Vec<PathBuf>,
 }
 
+/// A dependency JAR on a classpath, with the artifact and scope it was added for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClasspathEntry {
+    /// Resolved artifact
+    pub coordinate: Coordinate,
+    /// JAR in the dependency cache
+    pub path: PathBuf,
+    /// Effective scope, which decides the classpaths the JAR is on
+    pub scope: polytunnel_maven::DependencyScope,
+}
+
+/// Where a dependency JAR came from while building the classpath
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactSource {
+    /// Already present in the local cache
+    Cache,
+    /// Downloaded from a repository
+    Network {
+        /// Size of the downloaded JAR in bytes
+        bytes: u64,
+        /// Time spent downloading and verifying the JAR
+        elapsed: Duration,
+    },
+}
+
+/// Cache hit or miss for a single dependency JAR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactFetch {
+    /// Coordinate of the dependency
+    pub coordinate: Coordinate,
+    /// Whether the JAR was cached or downloaded
+    pub source: ArtifactSource,
+}
+
+/// Cache hits and misses recorded by the last [`ClasspathBuilder::build_classpath`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheReport {
+    /// One entry per dependency JAR: cache hits first, then downloads
+    pub fetches: Vec<ArtifactFetch>,
+}
+
+impl CacheReport {
+    /// Number of JARs served from the local cache
+    pub fn hits(&self) -> usize {
+        self.fetches
+            .iter()
+            .filter(|fetch| fetch.source == ArtifactSource::Cache)
+            .count()
+    }
+
+    /// Number of JARs downloaded
+    pub fn misses(&self) -> usize {
+        self.fetches.len() - self.hits()
+    }
+
+    /// Fraction of JARs served from the cache (`1.0` when nothing was needed)
+    pub fn hit_ratio(&self) -> f64 {
+        if self.fetches.is_empty() {
+            1.0
+        } else {
+            self.hits() as f64 / self.fetches.len() as f64
+        }
+    }
+
+    /// Total bytes downloaded
+    pub fn downloaded_bytes(&self) -> u64 {
+        self.fetches
+            .iter()
+            .map(|fetch| match fetch.source {
+                ArtifactSource::Cache => 0,
+                ArtifactSource::Network { bytes, .. } => bytes,
+            })
+            .sum()
+    }
+}
+
+/// How `polytunnel.lock` constrains dependency resolution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LockMode {
+    /// Resolve normally; the lockfile is not consulted
+    #[default]
+    Unlocked,
+    /// Resolve, then fail if the result differs from the lockfile (`--locked`)
+    Locked,
+    /// Use the lockfile and local cache only, without network access (`--frozen`)
+    Frozen,
+}
+
 /// Builds and manages classpaths for compilation and execution
 #[derive(Debug, Clone)]
 pub struct ClasspathBuilder {
     config: ProjectConfig,
     cached_result: Option<ClasspathResult>,
+    entries: Vec<ClasspathEntry>,
+    resolved_tree: Option<polytunnel_resolver::ResolvedTree>,
+    lockfile: PathBuf,
+    lock_mode: LockMode,
+    cache_report: CacheReport,
+    timings: Vec<PhaseTiming>,
+    store: Option<Arc<dyn ArtifactStore>>,
+    client: Option<MavenClient>,
+    full: bool,
+    compile_only: bool,
+    offline: bool,
+    /// Effective scope of each resolved artifact, keyed by coordinate
+    scopes: HashMap<String, polytunnel_maven::DependencyScope>,
 }
 
 impl ClasspathBuilder {
@@ -31,7 +207,122 @@ impl ClasspathBuilder {
         Self {
             config,
             cached_result: None,
+            entries: Vec::new(),
+            resolved_tree: None,
+            lockfile: PathBuf::from("polytunnel.lock"),
+            lock_mode: LockMode::Unlocked,
+            cache_report: CacheReport::default(),
+            timings: Vec::new(),
+            store: None,
+            client: None,
+            full: false,
+            compile_only: false,
+            offline: false,
+            scopes: HashMap::new(),
+        }
+    }
+
+    /// Keep dependency JARs in `store` instead of files below `cache_dir`
+    pub fn with_artifact_store(mut self, store: Arc<dyn ArtifactStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Fetch POMs and JARs with `client` instead of one built from the project config
+    pub fn with_maven_client(mut self, client: MavenClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Build the fullest possible classpath, for analysis and IDE indexing
+    ///
+    /// Optional dependencies at any depth and `provided` dependencies of
+    /// transitive artifacts are resolved and added (see
+    /// [`polytunnel_resolver::Resolver::with_full_closure`]). Such a classpath
+    /// is not suitable for building; `LockMode::Frozen` ignores this setting.
+    pub fn set_full_classpath(&mut self, full: bool) {
+        self.full = full;
+    }
+
+    /// Resolve only dependencies on the main compile classpath (`pt build --scope compile`)
+    ///
+    /// `runtime` and `test` root dependencies and their transitives are neither
+    /// resolved nor downloaded. In `LockMode::Locked`, lockfile entries missing
+    /// from the narrower resolution are not reported as removed.
+    pub fn set_compile_only(&mut self, compile_only: bool) {
+        self.compile_only = compile_only;
+    }
+
+    /// Resolve and download nothing; use only POMs and JARs already in the cache (`--offline`)
+    ///
+    /// POMs are cached by every online build, so a project built once can be
+    /// rebuilt offline.
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    /// Check resolution against the lockfile at `path` according to `mode`
+    pub fn set_lock_mode(&mut self, path: impl Into<PathBuf>, mode: LockMode) {
+        self.lockfile = path.into();
+        self.lock_mode = mode;
+    }
+
+    /// Cache hits and misses of the last classpath build
+    pub fn cache_report(&self) -> &CacheReport {
+        &self.cache_report
+    }
+
+    /// Path of `coord`'s JAR in the cache below `cache_dir`, downloading it if missing
+    ///
+    /// Used for tooling JARs that are not project dependencies, e.g. the JUnit
+    /// console launcher. The JAR is not added to the classpath.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::FrozenArtifactMissing` - If the JAR is not cached in `--frozen` mode
+    /// * `BuildError::OfflineArtifactMissing` - If the JAR is not cached in `--offline` mode
+    /// * `BuildError::Maven` - If the download fails
+    pub async fn fetch_artifact(
+        &self,
+        coord: &Coordinate,
+        cache_dir: &str,
+        verbose: bool,
+    ) -> Result<PathBuf> {
+        let store = self.artifact_store(Path::new(cache_dir));
+        let key = jar_key(coord);
+
+        if !store.contains(&key) {
+            if self.lock_mode == LockMode::Frozen {
+                return Err(BuildError::FrozenArtifactMissing {
+                    coordinate: coord.to_string(),
+                });
+            }
+            if self.offline {
+                return Err(BuildError::OfflineArtifactMissing {
+                    coordinate: coord.to_string(),
+                });
+            }
+            if verbose {
+                println!("   Downloading {}", coord);
+            }
+            self.maven_client(&store)
+                .download_jar_to_store(coord, store.as_ref(), false)
+                .await?;
         }
+        Ok(store.path_for(&key))
+    }
+
+    /// Resolve and download durations of the last classpath build
+    pub fn phase_timings(&self) -> &[PhaseTiming] {
+        &self.timings
+    }
+
+    fn load_lockfile(&self) -> Result<polytunnel_resolver::Lockfile> {
+        polytunnel_resolver::Lockfile::load(&self.lockfile)?.ok_or_else(|| {
+            BuildError::LockfileMissing {
+                path: self.lockfile.display().to_string(),
+            }
+        })
     }
 
     /// Build and cache classpath from dependencies
@@ -43,6 +334,13 @@ impl ClasspathBuilder {
     /// # Returns
     ///
     /// ClasspathResult with separate classpaths for compile, test, and runtime
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::LockfileMissing` - If a lock mode is set but there is no lockfile
+    /// * `BuildError::LockfileOutdated` - If `LockMode::Locked` resolution differs from the lockfile
+    /// * `BuildError::FrozenArtifactMissing` - If `LockMode::Frozen` needs an uncached JAR
+    /// * `BuildError::OfflineArtifactMissing` - If an offline build needs an uncached POM or JAR
     pub async fn build_classpath(
         &mut self,
         cache_dir: &str,
@@ -53,33 +351,178 @@ impl ClasspathBuilder {
         if !cache_path.exists() {
             std::fs::create_dir_all(&cache_path)?;
         }
+        let resolve_start = Instant::now();
+        self.timings.clear();
+
+        if self.lock_mode == LockMode::Frozen {
+            let lockfile = self.load_lockfile()?;
+            let resolved_tree = lockfile.to_tree();
+            self.scopes = lockfile.scopes.into_iter().collect();
+            let all_dependencies = resolved_tree.all_dependencies.clone();
+            self.resolved_tree = Some(resolved_tree);
+            self.timings.push(PhaseTiming::new(
+                BuildPhase::Resolve,
+                resolve_start.elapsed(),
+            ));
+            return self
+                .build_classpath_from_resolved_tree(cache_path, all_dependencies, verbose)
+                .await;
+        }
 
-        // Step 2: Parse root dependencies from polytunnel.toml
+        // Step 2: Parse root dependencies from polytunnel.toml and fill in missing versions
         let root_coords = self.get_root_coordinates()?;
+        let client = self.maven_client(&self.artifact_store(&cache_path));
+        let root_coords = Self::resolve_missing_versions(&client, root_coords).await?;
 
         // Step 3: Resolve dependency tree (parallel, includes transitives)
-        let mut resolver = polytunnel_resolver::Resolver::new();
-        let resolved_tree = resolver
-            .resolve(&root_coords)
-            .await
-            .map_err(Self::map_resolver_error)?;
+        let resolver_config = polytunnel_resolver::ResolverConfig {
+            full: self.full,
+            ..polytunnel_resolver::ResolverConfig::from_project(&self.config)?
+        };
+        let mut resolver = polytunnel_resolver::Resolver::with_config(client, resolver_config);
+        let resolved_tree = resolver.resolve(&root_coords).await.map_err(|error| {
+            match error.offline_resource() {
+                Some(resource) => BuildError::OfflineArtifactMissing {
+                    coordinate: resource.to_string(),
+                },
+                None => error.into(),
+            }
+        })?;
+
+        if self.lock_mode == LockMode::Locked {
+            let mut diff = self.load_lockfile()?.to_tree().diff(&resolved_tree);
+            if self.compile_only {
+                diff.removed.clear();
+            }
+            if !diff.is_empty() {
+                return Err(BuildError::LockfileOutdated { diff });
+            }
+        }
 
-        self.build_classpath_from_resolved_tree(cache_path, resolved_tree.all_dependencies, verbose)
+        let roots: Vec<_> = resolved_tree
+            .root_dependencies
+            .iter()
+            .map(|coord| {
+                let scope = self
+                    .get_dependency_scope(coord)
+                    .unwrap_or(polytunnel_maven::DependencyScope::Compile);
+                (coord.clone(), scope)
+            })
+            .collect();
+        self.scopes = resolver.graph.effective_scopes(&roots);
+
+        let all_dependencies = resolved_tree.all_dependencies.clone();
+        self.resolved_tree = Some(resolved_tree);
+        self.timings.push(PhaseTiming::new(
+            BuildPhase::Resolve,
+            resolve_start.elapsed(),
+        ));
+
+        self.build_classpath_from_resolved_tree(cache_path, all_dependencies, verbose)
             .await
     }
 
-    fn map_resolver_error(error: polytunnel_resolver::ResolverError) -> BuildError {
+    /// Fill in versions for root coordinates declared without one
+    ///
+    /// A missing version is taken from the `<dependencyManagement>` of any
+    /// declared BOM (a versioned root dependency with `pom` packaging) first,
+    /// then from the `<release>` element of the artifact's `maven-metadata.xml`.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::MissingVersion` - If neither source yields a version
+    /// * `BuildError::OfflineArtifactMissing` - If an offline build needs uncached metadata or a BOM
+    /// * `BuildError::VersionLookupFailed` - If fetching the metadata or a BOM failed otherwise
+    pub async fn resolve_missing_versions(
+        client: &MavenClient,
+        coords: Vec<Coordinate>,
+    ) -> Result<Vec<Coordinate>> {
+        if coords.iter().all(|c| !c.version.is_empty()) {
+            return Ok(coords);
+        }
+
+        // Collect managed versions from declared BOMs
+        let mut managed: HashMap<String, String> = HashMap::new();
+        for coord in coords.iter().filter(|c| !c.version.is_empty()) {
+            let pom = match client.fetch_pom(coord).await {
+                Ok(pom) => pom,
+                Err(error) => match Self::version_lookup_error(coord.to_string(), error) {
+                    Some(error) => return Err(error),
+                    None => continue,
+                },
+            };
+            if pom.packaging != "pom" {
+                continue;
+            }
+            for dm in pom.dependency_management {
+                if let Some(version) = dm.version {
+                    managed
+                        .entry(format!("{}:{}", dm.group_id, dm.artifact_id))
+                        .or_insert(version);
+                }
+            }
+        }
+
+        let mut resolved = Vec::with_capacity(coords.len());
+        for mut coord in coords {
+            if coord.version.is_empty() {
+                let ga = format!("{}:{}", coord.group_id, coord.artifact_id);
+                let version = match managed.get(&ga) {
+                    Some(v) => Some(v.clone()),
+                    None => match client
+                        .fetch_metadata(&coord.group_id, &coord.artifact_id)
+                        .await
+                    {
+                        Ok(metadata) => metadata.release,
+                        Err(error) => match Self::version_lookup_error(ga.clone(), error) {
+                            Some(error) => return Err(error),
+                            None => None,
+                        },
+                    },
+                };
+                coord.version = version.ok_or(BuildError::MissingVersion { coordinate: ga })?;
+            }
+            resolved.push(coord);
+        }
+        Ok(resolved)
+    }
+
+    /// Error for a failed version lookup of `coordinate`, or `None` if it is just not published
+    fn version_lookup_error(coordinate: String, error: MavenError) -> Option<BuildError> {
         match error {
-            polytunnel_resolver::ResolverError::Io(e) => BuildError::Io(e),
-            polytunnel_resolver::ResolverError::Maven(e) => BuildError::from(e),
-            polytunnel_resolver::ResolverError::Config(e) => BuildError::Core(e),
-            other => BuildError::Resolver(other),
+            MavenError::PomNotFound { .. }
+            | MavenError::ArtifactNotFound { .. }
+            | MavenError::HttpStatus { status: 404, .. } => None,
+            MavenError::Offline { .. } => Some(BuildError::OfflineArtifactMissing { coordinate }),
+            error => Some(BuildError::VersionLookupFailed {
+                coordinate,
+                error: Box::new(error),
+            }),
         }
     }
 
-    /// Expose resolver error mapping for integration tests
-    pub fn map_resolver_error_for_tests(error: polytunnel_resolver::ResolverError) -> BuildError {
-        Self::map_resolver_error(error)
+    /// Client for this build, caching POMs in `store`
+    fn maven_client(&self, store: &Arc<dyn ArtifactStore>) -> MavenClient {
+        let client = match &self.client {
+            Some(client) => client.clone(),
+            None => self.default_client(),
+        };
+        client
+            .with_pom_cache(store.clone())
+            .with_offline(self.offline)
+    }
+
+    /// Artifact store of this build: the configured one, or files below `cache_path`
+    fn artifact_store(&self, cache_path: &Path) -> Arc<dyn ArtifactStore> {
+        self.store.clone().unwrap_or_else(|| {
+            Arc::new(FileSystemStore::new(cache_path).with_dedup(self.config.build.cache_dedup))
+        })
+    }
+
+    fn default_client(&self) -> MavenClient {
+        MavenClient::new()
+            .with_http_config(&self.config.http)
+            .with_repositories(&self.config.repositories)
     }
 
     async fn build_classpath_from_resolved_tree(
@@ -88,30 +531,45 @@ impl ClasspathBuilder {
         resolved_dependencies: Vec<Coordinate>,
         verbose: bool,
     ) -> Result<ClasspathResult> {
-        if !cache_path.exists() {
+        if self.store.is_none() && !cache_path.exists() {
             std::fs::create_dir_all(&cache_path)?;
         }
 
         // Step 4: Collect download targets (check cache)
-        let client = polytunnel_maven::MavenClient::new();
-        let mut download_tasks: Vec<(Coordinate, PathBuf)> = Vec::new();
+        let download_start = Instant::now();
+        let store = self.artifact_store(&cache_path);
+        let client = self.maven_client(&store);
+        let mut download_tasks: Vec<Coordinate> = Vec::new();
         let mut jar_paths: std::collections::HashMap<String, PathBuf> =
             std::collections::HashMap::new();
+        let mut fetches = Vec::new();
 
         for coord in &resolved_dependencies {
-            let artifact_path = cache_path
-                .join(coord.repo_path())
-                .join(coord.jar_filename());
+            // `pom` packaging (aggregators, BOMs) has no artifact; its dependencies
+            // were already followed during resolution
+            if coord.extension().is_none() {
+                continue;
+            }
+            let key = jar_key(coord);
 
-            if artifact_path.exists() {
+            if store.contains(&key) {
                 // Already cached, skip download
-                jar_paths.insert(coord.to_string(), artifact_path);
+                jar_paths.insert(coord.to_string(), store.path_for(&key));
+                fetches.push(ArtifactFetch {
+                    coordinate: coord.clone(),
+                    source: ArtifactSource::Cache,
+                });
+            } else if self.lock_mode == LockMode::Frozen {
+                return Err(BuildError::FrozenArtifactMissing {
+                    coordinate: coord.to_string(),
+                });
+            } else if self.offline {
+                return Err(BuildError::OfflineArtifactMissing {
+                    coordinate: coord.to_string(),
+                });
             } else {
                 // Need to download
-                if let Some(parent) = artifact_path.parent() {
-                    std::fs::create_dir_all(parent)?;
-                }
-                download_tasks.push((coord.clone(), artifact_path));
+                download_tasks.push(coord.clone());
             }
         }
 
@@ -130,75 +588,92 @@ impl ClasspathBuilder {
 
             // Print package names in verbose mode before starting parallel downloads
             if verbose {
-                for (coord, _) in &download_tasks {
+                for coord in &download_tasks {
                     pb.println(format!("   Downloading {}", coord));
                 }
             }
 
-            let download_futures: Vec<_> = download_tasks
-                .into_iter()
-                .map(|(coord, artifact_path)| {
+            // At most `[http] download_concurrency` JARs in flight, results in task order
+            let downloaded: Vec<_> = futures::stream::iter(download_tasks)
+                .map(|coord| {
                     let client = client.clone();
+                    let store = store.clone();
                     let pb = pb.clone();
 
                     async move {
-                        client
-                            .download_jar(&coord, &artifact_path, false)
+                        let started = Instant::now();
+                        let bytes = client
+                            .download_jar_to_store(&coord, store.as_ref(), false)
                             .await
                             .map_err(BuildError::from)?;
+                        let path = store.path_for(&jar_key(&coord));
+                        let fetch = ArtifactFetch {
+                            source: ArtifactSource::Network {
+                                bytes,
+                                elapsed: started.elapsed(),
+                            },
+                            coordinate: coord,
+                        };
 
                         pb.inc(1);
-                        Ok::<_, BuildError>((coord.to_string(), artifact_path))
+                        Ok::<_, BuildError>((fetch, path))
                     }
                 })
-                .collect();
-
-            let downloaded = try_join_all(download_futures).await?;
+                .buffered(self.config.http.download_concurrency())
+                .try_collect()
+                .await?;
 
             pb.finish_and_clear();
 
-            for (key, path) in downloaded {
-                jar_paths.insert(key, path);
+            for (fetch, path) in downloaded {
+                jar_paths.insert(fetch.coordinate.to_string(), path);
+                fetches.push(fetch);
             }
         }
+        self.cache_report = CacheReport { fetches };
+        self.timings.push(PhaseTiming::new(
+            BuildPhase::Download,
+            download_start.elapsed(),
+        ));
 
         // Step 6: Construct Classpath vectors
-        let mut compile_cp = Vec::new();
-        let mut test_cp = Vec::new();
-        let mut runtime_cp = Vec::new();
+        let mut compile_cp = Classpath::new();
+        let mut test_cp = Classpath::new();
+        let mut runtime_cp = Classpath::new();
+        self.entries.clear();
 
+        // Transitives take the scope propagated from their roots, as resolved
+        // or as recorded in the lockfile (frozen builds)
         for coord in &resolved_dependencies {
             if let Some(path) = jar_paths.get(&coord.to_string()) {
                 let scope = self
-                    .get_dependency_scope(coord)
+                    .scopes
+                    .get(&coord.to_string())
+                    .copied()
+                    .or_else(|| self.get_dependency_scope(coord))
                     .unwrap_or(polytunnel_maven::DependencyScope::Compile);
 
-                match scope {
-                    polytunnel_maven::DependencyScope::Compile => {
-                        compile_cp.push(path.clone());
-                        test_cp.push(path.clone());
-                        runtime_cp.push(path.clone());
-                    }
-                    polytunnel_maven::DependencyScope::Provided => {
-                        compile_cp.push(path.clone());
-                        test_cp.push(path.clone());
-                    }
-                    polytunnel_maven::DependencyScope::Runtime => {
-                        runtime_cp.push(path.clone());
-                        test_cp.push(path.clone());
-                    }
-                    polytunnel_maven::DependencyScope::Test => {
-                        test_cp.push(path.clone());
-                    }
-                    _ => {} // Ignore System and Import scopes for now
+                if scope.on_compile_classpath() {
+                    compile_cp.push(path.clone());
                 }
+                if scope.on_test_classpath() {
+                    test_cp.push(path.clone());
+                }
+                if scope.on_runtime_classpath() {
+                    runtime_cp.push(path.clone());
+                }
+                self.entries.push(ClasspathEntry {
+                    coordinate: coord.clone(),
+                    path: path.clone(),
+                    scope,
+                });
             }
         }
 
         let result = ClasspathResult {
-            compile_classpath: compile_cp,
-            test_classpath: test_cp,
-            runtime_classpath: runtime_cp,
+            compile_classpath: compile_cp.into_entries(),
+            test_classpath: test_cp.into_entries(),
+            runtime_classpath: runtime_cp.into_entries(),
         };
 
         self.cached_result = Some(result.clone());
@@ -223,6 +698,11 @@ impl ClasspathBuilder {
     fn get_root_coordinates(&self) -> Result<Vec<Coordinate>> {
         let mut coords = Vec::new();
         for (key, dep) in &self.config.dependencies {
+            if self.compile_only
+                && !polytunnel_maven::DependencyScope::from(dep.scope()).on_compile_classpath()
+            {
+                continue;
+            }
             let coord = Self::parse_coordinate(key)?;
             let version = dep.version();
             let full_coord = Coordinate::new(&coord.group_id, &coord.artifact_id, version);
@@ -240,26 +720,27 @@ impl ClasspathBuilder {
                 && root_coord.group_id == coord.group_id
                 && root_coord.artifact_id == coord.artifact_id
             {
-                let core_scope = dep.scope();
-                return Some(match core_scope {
-                    polytunnel_core::DependencyScope::Compile => {
-                        polytunnel_maven::DependencyScope::Compile
-                    }
-                    polytunnel_core::DependencyScope::Test => {
-                        polytunnel_maven::DependencyScope::Test
-                    }
-                    polytunnel_core::DependencyScope::Runtime => {
-                        polytunnel_maven::DependencyScope::Runtime
-                    }
-                    polytunnel_core::DependencyScope::Provided => {
-                        polytunnel_maven::DependencyScope::Provided
-                    }
-                });
+                return Some(dep.scope().into());
             }
         }
         None
     }
 
+    /// Get the dependency tree from the last call to [`ClasspathBuilder::build_classpath`]
+    pub fn get_resolved_tree(&self) -> Option<&polytunnel_resolver::ResolvedTree> {
+        self.resolved_tree.as_ref()
+    }
+
+    /// Effective scope of each dependency from the last classpath build, keyed by coordinate
+    pub fn effective_scopes(&self) -> &HashMap<String, polytunnel_maven::DependencyScope> {
+        &self.scopes
+    }
+
+    /// Dependency JARs from the last classpath construction, in resolution order
+    pub fn classpath_entries(&self) -> &[ClasspathEntry] {
+        &self.entries
+    }
+
     /// Get the cached classpath result
     pub fn get_cached_classpath(&self) -> ClasspathResult {
         self.cached_result
@@ -272,6 +753,9 @@ impl ClasspathBuilder {
     }
 
     /// Parse Maven coordinate from dependency key
+    ///
+    /// A key without a version yields an empty version, which is filled in by
+    /// [`ClasspathBuilder::resolve_missing_versions`] before resolution.
     pub fn parse_coordinate(key: &str) -> Result<Coordinate> {
         let parts: Vec<&str> = key.split(':').collect();
         if parts.len() < 2 {
@@ -280,10 +764,16 @@ impl ClasspathBuilder {
             });
         }
 
-        Ok(Coordinate::new(
+        let coord = Coordinate::new(
             parts[0],
             parts[1],
-            if parts.len() > 2 { parts[2] } else { "LATEST" },
-        ))
+            if parts.len() > 2 { parts[2] } else { "" },
+        );
+        coord
+            .validate()
+            .map_err(|_| BuildError::InvalidDependency {
+                input: key.to_string(),
+            })?;
+        Ok(coord)
     }
 }
@@ -1,9 +1,13 @@
 //! Java compiler wrapper for javac
 
+use crate::compiler_daemon::CompilerDaemon;
+use crate::diagnostic::{Diagnostic, Severity, parse_javac_diagnostics};
 use crate::error::{BuildError, Result};
 use polytunnel_core::ProjectConfig;
+use std::ffi::OsString;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::Mutex;
 
 /// Result of a compilation operation
 #[derive(Debug, Clone)]
@@ -14,12 +18,29 @@ pub struct CompilationResult {
     pub stdout: String,
     /// Standard error from compiler
     pub stderr: String,
+    /// Warnings and notes parsed from the compiler output
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CompilationResult {
+    /// Diagnostics with [`Severity::Warning`]
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+    }
 }
 
 /// A Java compiler wrapper around javac
 pub struct JavaCompiler {
     javac_path: PathBuf,
     java_version: String,
+    /// Source encoding added as `-encoding` unless the caller passes one
+    encoding: String,
+    /// Working directory for the helper JVM when `build.compiler_daemon` is enabled
+    daemon_dir: Option<PathBuf>,
+    daemon: Mutex<Option<CompilerDaemon>>,
+    explain: bool,
 }
 
 impl JavaCompiler {
@@ -38,6 +59,7 @@ impl JavaCompiler {
     /// # Errors
     ///
     /// * `BuildError::JavacNotFound` - If javac cannot be found
+    /// * `BuildError::JdkTooOld` - If javac is older than `project.java_version`
     ///
     /// # Example
     ///
@@ -46,21 +68,37 @@ impl JavaCompiler {
     /// ```
     pub fn new(config: &ProjectConfig) -> Result<Self> {
         let javac_path = Self::find_javac()?;
+        Self::check_jdk_version(&javac_path, &config.project.java_version)?;
+
+        let daemon_dir = config
+            .build
+            .compiler_daemon
+            .then(|| PathBuf::from(&config.build.cache_dir).join("compiler-daemon"));
 
         Ok(Self {
             javac_path,
             java_version: config.project.java_version.clone(),
+            encoding: config.build.encoding.clone(),
+            daemon_dir,
+            daemon: Mutex::new(None),
+            explain: false,
         })
     }
 
     /// Compile Java source files
     ///
+    /// All sources are passed to a single javac invocation. With
+    /// `build.compiler_daemon` enabled the invocation runs inside a helper JVM
+    /// that is reused by later calls, falling back to a fresh `javac` process
+    /// if the helper cannot be started.
+    ///
     /// # Arguments
     ///
     /// * `source_files` - Paths to .java source files to compile
     /// * `classpath` - JAR files for classpath
     /// * `output_dir` - Directory to place compiled .class files
-    /// * `args` - Additional compiler arguments (encoding, debug flags, etc.)
+    /// * `args` - Additional compiler arguments (debug flags, etc.); `-encoding`
+    ///   from `build.encoding` is added unless already present
     ///
     /// # Returns
     ///
@@ -78,7 +116,7 @@ impl JavaCompiler {
     ///     vec![PathBuf::from("src/Main.java")],
     ///     vec![],
     ///     PathBuf::from("target/classes"),
-    ///     vec!["-encoding".to_string(), "UTF-8".to_string()],
+    ///     vec!["-g".to_string()],
     /// )?;
     /// ```
     pub fn compile(
@@ -91,38 +129,53 @@ impl JavaCompiler {
         // Create output directory if it doesn't exist
         std::fs::create_dir_all(&output_dir)?;
 
-        // Build javac command
-        let mut cmd = Command::new(&self.javac_path);
+        let mut javac_args: Vec<OsString> = Vec::new();
 
         // Set source/target version
-        cmd.arg("-source").arg(&self.java_version);
-        cmd.arg("-target").arg(&self.java_version);
+        javac_args.extend(["-source".into(), self.java_version.clone().into()]);
+        javac_args.extend(["-target".into(), self.java_version.clone().into()]);
 
         // Set output directory
-        cmd.arg("-d").arg(&output_dir);
+        javac_args.extend(["-d".into(), output_dir.into_os_string()]);
 
         // Add classpath if not empty
         if !classpath.is_empty() {
-            let classpath_str = crate::format_classpath(&classpath);
-            cmd.arg("-cp").arg(classpath_str);
+            let classpath_str = crate::Classpath::from(classpath.as_slice()).to_arg_string();
+            javac_args.extend(["-cp".into(), classpath_str.into()]);
         }
 
         // Add additional compiler arguments
-        for arg in args {
-            cmd.arg(arg);
-        }
+        javac_args.extend(
+            Self::with_encoding(args, &self.encoding)
+                .into_iter()
+                .map(OsString::from),
+        );
 
         // Add source files
-        for file in &source_files {
-            cmd.arg(file);
+        javac_args.extend(source_files.iter().map(|f| f.clone().into_os_string()));
+
+        let mut cmd = Command::new(&self.javac_path);
+        cmd.args(&javac_args);
+        if self.explain {
+            println!("$ {}", crate::format_command(&cmd));
+            if self.uses_daemon() {
+                println!("  (arguments passed to the compiler daemon as an @argfile)");
+            }
         }
 
-        // Execute compilation
-        let output = cmd.output()?;
+        let (success, stdout, stderr) = match self.compile_with_daemon(&javac_args) {
+            Some(output) => output,
+            None => {
+                let output = cmd.output()?;
+                (
+                    output.status.success(),
+                    String::from_utf8_lossy(&output.stdout).to_string(),
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                )
+            }
+        };
 
-        let success = output.status.success();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let diagnostics = parse_javac_diagnostics(&format!("{}\n{}", stdout, stderr));
 
         if !success {
             return Err(BuildError::CompilationFailed {
@@ -131,6 +184,7 @@ impl JavaCompiler {
                     source_files.len(),
                     stderr
                 ),
+                diagnostics,
             });
         }
 
@@ -138,12 +192,105 @@ impl JavaCompiler {
             success,
             stdout,
             stderr,
+            diagnostics,
         })
     }
 
+    /// Append `-encoding <encoding>` to `args` unless it already sets one
+    ///
+    /// An empty `encoding` leaves `args` unchanged (platform default charset).
+    pub fn with_encoding(mut args: Vec<String>, encoding: &str) -> Vec<String> {
+        if !encoding.is_empty() && !args.iter().any(|arg| arg == "-encoding") {
+            args.extend(["-encoding".to_string(), encoding.to_string()]);
+        }
+        args
+    }
+
+    /// Print each `javac` command line before running it
+    pub fn set_explain(&mut self, explain: bool) {
+        self.explain = explain;
+    }
+
+    /// Whether compilations are routed through the helper JVM
+    pub fn uses_daemon(&self) -> bool {
+        self.daemon_dir.is_some()
+    }
+
+    /// Run javac in the helper JVM, starting it on first use
+    ///
+    /// Returns `None` when the daemon is disabled or unusable, in which case
+    /// the caller spawns `javac` directly.
+    fn compile_with_daemon(&self, javac_args: &[OsString]) -> Option<(bool, String, String)> {
+        let daemon_dir = self.daemon_dir.as_ref()?;
+        let mut guard = self.daemon.lock().ok()?;
+
+        if guard.is_none() {
+            *guard = CompilerDaemon::start(&self.javac_path, daemon_dir).ok();
+        }
+        match guard.as_mut()?.compile(javac_args) {
+            Ok(output) => Some((output.success, output.stdout, output.stderr)),
+            Err(_) => {
+                // Drop the broken daemon; the next call starts a fresh one
+                *guard = None;
+                None
+            }
+        }
+    }
+
+    /// Parse the major version from `javac -version` output
+    ///
+    /// Handles both modern (`javac 17.0.2`) and legacy (`javac 1.8.0_392`) formats.
+    pub fn parse_javac_version(output: &str) -> Option<u32> {
+        let version = output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("javac "))?
+            .trim();
+        Self::parse_major_version(version)
+    }
+
+    /// Parse a Java major version such as `"17"`, `"21.0.1"`, or `"1.8"`
+    pub fn parse_major_version(version: &str) -> Option<u32> {
+        let mut parts = version.split(['.', '_', '-', '+']);
+        match parts.next()?.parse::<u32>().ok()? {
+            1 => parts.next()?.parse().ok(),
+            major => Some(major),
+        }
+    }
+
+    /// Fail with `BuildError::JdkTooOld` if `found` cannot target `required`
+    pub fn ensure_jdk_supports(found: u32, required: u32) -> Result<()> {
+        if found < required {
+            return Err(BuildError::JdkTooOld { found, required });
+        }
+        Ok(())
+    }
+
+    /// Compare the installed javac against `java_version`
+    ///
+    /// Versions that cannot be determined are not checked; javac reports
+    /// any remaining problem itself.
+    fn check_jdk_version(javac_path: &std::path::Path, java_version: &str) -> Result<()> {
+        let Some(required) = Self::parse_major_version(java_version) else {
+            return Ok(());
+        };
+        let Ok(output) = Command::new(javac_path).arg("-version").output() else {
+            return Ok(());
+        };
+        // JDK 8 prints the version on stderr, newer JDKs on stdout
+        let text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        match Self::parse_javac_version(&text) {
+            Some(found) => Self::ensure_jdk_supports(found, required),
+            None => Ok(()),
+        }
+    }
+
     /// Find javac executable in PATH or JAVA_HOME
     #[allow(clippy::collapsible_if)]
-    fn find_javac() -> Result<PathBuf> {
+    pub(crate) fn find_javac() -> Result<PathBuf> {
         // Try to find javac in PATH
         // Supports:
         // - Windows (amd64, arm64, x86)
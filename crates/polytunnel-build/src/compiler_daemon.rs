@@ -0,0 +1,190 @@
+//! Long-lived helper JVM running `javax.tools.JavaCompiler`
+//!
+//! Each request is a javac `@argfile`; the helper writes the compiler output
+//! next to it and answers with the exit code on stdout, one line per request.
+
+use crate::error::Result;
+use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const SERVER_CLASS: &str = "PolytunnelCompilerServer";
+
+const SERVER_SOURCE: &str = r#"import java.io.*;
+import java.nio.file.*;
+import javax.tools.*;
+
+public class PolytunnelCompilerServer {
+    public static void main(String[] args) throws IOException {
+        JavaCompiler javac = ToolProvider.getSystemJavaCompiler();
+        BufferedReader in = new BufferedReader(new InputStreamReader(System.in));
+        String argfile;
+        while ((argfile = in.readLine()) != null) {
+            ByteArrayOutputStream out = new ByteArrayOutputStream();
+            ByteArrayOutputStream err = new ByteArrayOutputStream();
+            int code = javac.run(null, out, err, "@" + argfile);
+            Files.write(Paths.get(argfile + ".out"), out.toByteArray());
+            Files.write(Paths.get(argfile + ".err"), err.toByteArray());
+            System.out.println(code);
+            System.out.flush();
+        }
+    }
+}
+"#;
+
+/// Output of a single compilation run by the daemon
+pub(crate) struct DaemonOutput {
+    pub(crate) success: bool,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+}
+
+/// Running helper JVM
+pub(crate) struct CompilerDaemon {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    work_dir: PathBuf,
+    requests: usize,
+}
+
+impl CompilerDaemon {
+    /// Compile the helper into `work_dir` if needed and start it
+    pub(crate) fn start(javac_path: &Path, work_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(work_dir)?;
+        Self::ensure_server_compiled(javac_path, work_dir)?;
+
+        let mut child = Command::new(Self::java_path(javac_path))
+            .arg("-cp")
+            .arg(work_dir)
+            .arg(SERVER_CLASS)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "daemon stdin unavailable")
+        })?;
+        let stdout = child.stdout.take().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "daemon stdout unavailable")
+        })?;
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            work_dir: work_dir.to_path_buf(),
+            requests: 0,
+        })
+    }
+
+    /// Run one javac invocation inside the helper JVM
+    pub(crate) fn compile(&mut self, args: &[OsString]) -> Result<DaemonOutput> {
+        self.requests += 1;
+        // Other `pt` processes may share work_dir
+        let argfile = self.work_dir.join(format!(
+            "request-{}-{}.args",
+            std::process::id(),
+            self.requests
+        ));
+        let content: Vec<String> = args
+            .iter()
+            .map(|a| quote_argfile_arg(&a.to_string_lossy()))
+            .collect();
+        std::fs::write(&argfile, content.join("\n"))?;
+
+        writeln!(self.stdin, "{}", argfile.display())?;
+        self.stdin.flush()?;
+
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "compiler daemon exited unexpectedly",
+            )
+            .into());
+        }
+        let code: i32 = line.trim().parse().map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unexpected compiler daemon reply: {}", line.trim()),
+            )
+        })?;
+
+        let out_file = PathBuf::from(format!("{}.out", argfile.display()));
+        let err_file = PathBuf::from(format!("{}.err", argfile.display()));
+        let output = DaemonOutput {
+            success: code == 0,
+            stdout: std::fs::read_to_string(&out_file).unwrap_or_default(),
+            stderr: std::fs::read_to_string(&err_file).unwrap_or_default(),
+        };
+
+        for file in [&argfile, &out_file, &err_file] {
+            let _ = std::fs::remove_file(file);
+        }
+        Ok(output)
+    }
+
+    fn ensure_server_compiled(javac_path: &Path, work_dir: &Path) -> Result<()> {
+        let source = work_dir.join(format!("{SERVER_CLASS}.java"));
+        let class = work_dir.join(format!("{SERVER_CLASS}.class"));
+        let up_to_date =
+            class.exists() && std::fs::read_to_string(&source).is_ok_and(|s| s == SERVER_SOURCE);
+        if up_to_date {
+            return Ok(());
+        }
+
+        // Compile in a private directory and move the results into place, so a
+        // concurrent `pt` never sees a partial source or class file. The
+        // source goes last since it marks the class as up to date.
+        let staging = work_dir.join(format!("server-{}.tmp", std::process::id()));
+        std::fs::create_dir_all(&staging)?;
+        let staged_source = staging.join(format!("{SERVER_CLASS}.java"));
+        std::fs::write(&staged_source, SERVER_SOURCE)?;
+        let output = Command::new(javac_path)
+            .arg("-d")
+            .arg(&staging)
+            .arg(&staged_source)
+            .output()?;
+        if output.status.success() {
+            std::fs::rename(staging.join(format!("{SERVER_CLASS}.class")), &class)?;
+            std::fs::rename(&staged_source, &source)?;
+        }
+        let _ = std::fs::remove_dir_all(&staging);
+        if !output.status.success() {
+            return Err(crate::BuildError::CompilationFailed {
+                message: format!(
+                    "Failed to compile compiler daemon.\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ),
+                diagnostics: Vec::new(),
+            });
+        }
+        Ok(())
+    }
+
+    fn java_path(javac_path: &Path) -> PathBuf {
+        let java = if cfg!(windows) { "java.exe" } else { "java" };
+        match javac_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.join(java),
+            _ => PathBuf::from(java),
+        }
+    }
+}
+
+impl Drop for CompilerDaemon {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Quote an argument for a javac `@argfile`
+///
+/// Inside double quotes javac treats backslash as an escape character, so
+/// backslashes (Windows paths) and quotes are escaped.
+fn quote_argfile_arg(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
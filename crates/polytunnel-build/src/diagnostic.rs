@@ -0,0 +1,150 @@
+//! Errors, warnings, and notes reported by `javac`
+
+use std::path::PathBuf;
+
+/// Severity of a javac diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The sources could not be compiled
+    Error,
+    /// Problem that did not stop compilation (e.g. `-Xlint` findings)
+    Warning,
+    /// Summary such as `Note: Main.java uses unchecked or unsafe operations.`
+    Note,
+}
+
+impl Severity {
+    /// Lowercase name as printed by javac
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/// A diagnostic parsed from javac output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Source file the diagnostic points at, if any
+    pub file: Option<PathBuf>,
+    /// 1-based line in `file`
+    pub line: Option<u32>,
+    /// 1-based column marked by the `^` under the source excerpt
+    pub column: Option<u32>,
+    /// Severity
+    pub severity: Severity,
+    /// Message without the location and severity prefix
+    ///
+    /// Indented detail lines such as `symbol:` and `location:` follow the
+    /// first line, one per line.
+    pub message: String,
+}
+
+/// `Main.java:7:9: error: cannot find symbol`
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let (Some(file), Some(line)) = (&self.file, self.line) {
+            write!(f, "{}:{}:", file.display(), line)?;
+            if let Some(column) = self.column {
+                write!(f, "{}:", column)?;
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "{}: {}", self.severity.as_str(), self.message)
+    }
+}
+
+/// What the next line of javac output holds
+enum Expect {
+    /// Source line quoted under a located diagnostic
+    Excerpt,
+    /// `^` marking the column in the excerpt
+    Caret,
+    /// Indented details of the last diagnostic
+    Details,
+    /// Anything else; only new diagnostics are picked up
+    Nothing,
+}
+
+/// Parse the diagnostics in javac output
+///
+/// Recognizes `File.java:12: error: ...` with its source excerpt and caret,
+/// location-less `error: ...`/`warning: ...` lines, and `Note: ...` lines.
+/// The closing `N errors` summary and usage text are skipped.
+pub fn parse_javac_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut expect = Expect::Nothing;
+
+    for line in output.lines() {
+        let line = line.trim_end();
+        // Source excerpts are quoted verbatim and may look like anything
+        if !matches!(expect, Expect::Excerpt)
+            && let Some(diagnostic) = parse_diagnostic_line(line)
+        {
+            expect = if diagnostic.file.is_some() {
+                Expect::Excerpt
+            } else {
+                Expect::Details
+            };
+            diagnostics.push(diagnostic);
+            continue;
+        }
+        let Some(last) = diagnostics.last_mut() else {
+            continue;
+        };
+
+        expect = match expect {
+            Expect::Excerpt => Expect::Caret,
+            Expect::Caret if line.trim_start() == "^" => {
+                let offset = line.chars().take_while(|c| *c != '^').count();
+                last.column = u32::try_from(offset + 1).ok();
+                Expect::Details
+            }
+            Expect::Caret | Expect::Details if line.starts_with(char::is_whitespace) => {
+                last.message.push('\n');
+                last.message.push_str(line.trim_start());
+                Expect::Details
+            }
+            _ => Expect::Nothing,
+        };
+    }
+    diagnostics
+}
+
+fn parse_diagnostic_line(line: &str) -> Option<Diagnostic> {
+    if let Some((severity, message)) = split_severity(line) {
+        return Some(Diagnostic {
+            file: None,
+            line: None,
+            column: None,
+            severity,
+            message: message.to_string(),
+        });
+    }
+
+    // `<file>:<line>: <severity>: <message>`; the file may itself contain `:` (Windows drives)
+    let (location, rest) = line.split_once(".java:")?;
+    let (line_number, rest) = rest.split_once(": ")?;
+    let line_number = line_number.parse().ok()?;
+    let (severity, message) = split_severity(rest)?;
+    Some(Diagnostic {
+        file: Some(PathBuf::from(format!("{}.java", location))),
+        line: Some(line_number),
+        column: None,
+        severity,
+        message: message.to_string(),
+    })
+}
+
+fn split_severity(text: &str) -> Option<(Severity, &str)> {
+    let (severity, message) = text.split_once(": ")?;
+    let severity = match severity {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        "Note" | "note" => Severity::Note,
+        _ => return None,
+    };
+    Some((severity, message))
+}
@@ -18,11 +18,40 @@ pub enum BuildError {
     #[error("Java compiler (javac) not found in PATH")]
     JavacNotFound,
 
+    /// A JDK tool other than javac (e.g. `jar`, `javadoc`) could not be found
+    #[error(
+        "JDK tool `{tool}` not found next to javac, in JAVA_HOME, or on PATH. Install a full JDK (not a JRE) and put it on PATH or JAVA_HOME"
+    )]
+    JdkToolNotFound {
+        /// Name of the missing tool
+        tool: String,
+    },
+
+    /// Installed JDK is older than the project's `java_version`
+    #[error(
+        "JDK {found} is older than the project's java_version {required}. Install JDK {required} or newer and put it on PATH or JAVA_HOME"
+    )]
+    JdkTooOld {
+        /// Major version reported by `javac -version`
+        found: u32,
+        /// Major version required by `project.java_version`
+        required: u32,
+    },
+
     /// Compilation failed with error message
     #[error("Compilation failed: {message}")]
     CompilationFailed {
         /// Compiler error output
         message: String,
+        /// Errors, warnings, and notes parsed from the compiler output
+        diagnostics: Vec<crate::Diagnostic>,
+    },
+
+    /// Main sources were out of date for a test compilation and failed to compile
+    #[error("Main sources must be built before tests, but compiling them failed: {error}")]
+    MainNotBuilt {
+        /// Error from compiling the main sources
+        error: Box<BuildError>,
     },
 
     /// Test execution failed
@@ -32,6 +61,13 @@ pub enum BuildError {
         message: String,
     },
 
+    /// `javadoc` exited with an error
+    #[error("Javadoc generation failed: {message}")]
+    JavadocFailed {
+        /// Errors reported by javadoc
+        message: String,
+    },
+
     /// Source directory not found
     #[error("Source directory not found: {path}")]
     SourceDirNotFound {
@@ -39,6 +75,39 @@ pub enum BuildError {
         path: String,
     },
 
+    /// A file selected for compilation does not exist
+    #[error("Source file not found: {path}")]
+    SourceFileNotFound {
+        /// Path as given by the caller
+        path: String,
+    },
+
+    /// A file selected for compilation is not inside any configured source directory
+    #[error("{path} is not inside a source directory ({source_dirs})")]
+    SourceFileOutsideSourceDirs {
+        /// Path as given by the caller
+        path: String,
+        /// Comma-separated configured `build.source_dirs`
+        source_dirs: String,
+    },
+
+    /// `pt run` without a main class, and no source declares `main`
+    #[error("No main class found in {source_dirs}; pass one to `pt run` or set build.main_class")]
+    MainClassNotFound {
+        /// Comma-separated source directories that were scanned
+        source_dirs: String,
+    },
+
+    /// `pt run` without a main class, and several sources declare `main`
+    #[error(
+        "Several main classes found ({}); pass one to `pt run` or set build.main_class",
+        candidates.join(", ")
+    )]
+    AmbiguousMainClass {
+        /// Fully qualified names of the classes declaring `main`, sorted
+        candidates: Vec<String>,
+    },
+
     /// Test framework could not be detected
     #[error("Test framework not detected. Available: {available}")]
     TestFrameworkNotDetected {
@@ -46,6 +115,20 @@ pub enum BuildError {
         available: String,
     },
 
+    /// `pt test --class` named a class that is not in the test output directory
+    #[error(
+        "Test class {class} not found in {test_output_dir}{}",
+        format_similar(similar)
+    )]
+    TestClassNotFound {
+        /// Fully qualified class name as given
+        class: String,
+        /// Directory of compiled test classes that was checked
+        test_output_dir: String,
+        /// Compiled classes with the same simple name
+        similar: Vec<String>,
+    },
+
     /// Invalid dependency format
     #[error("Invalid dependency format: {input}")]
     InvalidDependency {
@@ -53,15 +136,118 @@ pub enum BuildError {
         input: String,
     },
 
+    /// Dependency declared without a version that could not be resolved
+    #[error(
+        "No version found for {coordinate}: declare one, import a BOM, or check the repository metadata"
+    )]
+    MissingVersion {
+        /// `groupId:artifactId` of the versionless dependency
+        coordinate: String,
+    },
+
+    /// Looking up the version of a versionless dependency failed
+    #[error("Could not look up a version for {coordinate}: {error}")]
+    VersionLookupFailed {
+        /// `groupId:artifactId` being looked up, or the BOM read for it
+        coordinate: String,
+        /// Error from the repository
+        error: Box<polytunnel_maven::MavenError>,
+    },
+
+    /// `--locked`/`--frozen` build without a `polytunnel.lock`
+    #[error("{path} not found; run `pt sync` to create it")]
+    LockfileMissing {
+        /// Expected lockfile location
+        path: String,
+    },
+
+    /// `--locked` resolution differs from the committed lockfile
+    #[error("polytunnel.lock is out of date; run `pt sync` to update it:\n{diff}")]
+    LockfileOutdated {
+        /// Changes resolution would make to the lockfile
+        diff: polytunnel_resolver::TreeDiff,
+    },
+
+    /// `--frozen` build needs an artifact that is not in the local cache
+    #[error("{coordinate} is not in the local cache and --frozen forbids downloading it")]
+    FrozenArtifactMissing {
+        /// Coordinate of the uncached artifact
+        coordinate: String,
+    },
+
+    /// `--offline` build needs an artifact that is not in the local cache
+    #[error("{coordinate} is not in the local cache and --offline forbids downloading it")]
+    OfflineArtifactMissing {
+        /// Coordinate (or URL) of the uncached artifact
+        coordinate: String,
+    },
+
+    /// `--keep-going` build in which one or more compilation steps failed
+    #[error("{} build step(s) failed:{}", failures.len(), format_failures(failures))]
+    StepsFailed {
+        /// Each failed step with its error, in execution order
+        failures: Vec<(crate::BuildPhase, BuildError)>,
+    },
+
+    /// `--fail-on-warning` build in which javac reported warnings
+    #[error(
+        "{} compiler warning(s) with --fail-on-warning:{}",
+        warnings.len(),
+        format_warnings(warnings)
+    )]
+    CompilerWarnings {
+        /// Every warning from the main and test compilations
+        warnings: Vec<crate::Diagnostic>,
+    },
+
     /// Maven error
     #[error("Maven error: {0}")]
     Maven(#[from] polytunnel_maven::MavenError),
 
     /// Resolver error
+    ///
+    /// Only resolver-specific failures end up here; IO, Maven, and
+    /// configuration errors are flattened into [`BuildError::Io`],
+    /// [`BuildError::Maven`], and [`BuildError::Core`] by the `From` impl.
     #[error("Dependency resolution error: {0}")]
-    Resolver(#[from] polytunnel_resolver::ResolverError),
+    Resolver(#[source] polytunnel_resolver::ResolverError),
 
     /// Core error
     #[error("Core error: {0}")]
     Core(#[from] polytunnel_core::CoreError),
 }
+
+fn format_failures(failures: &[(crate::BuildPhase, BuildError)]) -> String {
+    failures
+        .iter()
+        .map(|(phase, error)| format!("\n[{}] {}", phase, error))
+        .collect()
+}
+
+fn format_warnings(warnings: &[crate::Diagnostic]) -> String {
+    warnings
+        .iter()
+        .map(|warning| format!("\n{}", warning))
+        .collect()
+}
+
+fn format_similar(similar: &[String]) -> String {
+    if similar.is_empty() {
+        "; is it compiled from a test source directory?".to_string()
+    } else {
+        format!("; did you mean {}?", similar.join(", "))
+    }
+}
+
+impl From<polytunnel_resolver::ResolverError> for BuildError {
+    fn from(error: polytunnel_resolver::ResolverError) -> Self {
+        use polytunnel_resolver::ResolverError;
+
+        match error {
+            ResolverError::Io(e) => BuildError::Io(e),
+            ResolverError::Maven(e) => BuildError::Maven(e),
+            ResolverError::Config(e) => BuildError::Core(e),
+            other => BuildError::Resolver(other),
+        }
+    }
+}
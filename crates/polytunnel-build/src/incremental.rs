@@ -3,8 +3,9 @@
 use crate::error::Result;
 use polytunnel_core::ProjectConfig;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 /// Cache entry for a source file
@@ -16,6 +17,31 @@ pub struct BuildCacheEntry {
     pub last_modified: u64,
     /// Output .class file path
     pub output_file: PathBuf,
+    /// Every .class file compiled from this source, including nested classes
+    #[serde(default)]
+    pub output_files: Vec<PathBuf>,
+    /// Digest of the classpath the source was compiled against
+    #[serde(default)]
+    pub classpath_digest: String,
+    /// Hex SHA-256 of the source contents that were compiled
+    #[serde(default)]
+    pub content_digest: String,
+    /// Hex SHA-256 of the non-private signatures in `output_files`
+    #[serde(default)]
+    pub api_digest: String,
+}
+
+/// State of a source file, read before it is handed to javac
+///
+/// An edit saved while javac runs then still differs from what is recorded.
+#[derive(Debug, Clone)]
+pub struct SourceSnapshot {
+    /// Source file path
+    pub source_file: PathBuf,
+    /// Last modification time (unix timestamp)
+    pub last_modified: u64,
+    /// Hex SHA-256 of the file contents
+    pub content_digest: String,
 }
 
 /// Build cache for incremental compilation
@@ -85,25 +111,8 @@ impl BuildCache {
     /// ```ignore
     /// cache.update_for_sources(&[PathBuf::from("src/Main.java")])?;
     /// ```
-    #[allow(clippy::collapsible_if)]
     pub fn update_for_sources(&mut self, source_files: &[PathBuf]) -> Result<()> {
-        for source_file in source_files {
-            if let Ok(metadata) = std::fs::metadata(source_file) {
-                if let Ok(modified) = metadata.modified() {
-                    if let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH) {
-                        let key = source_file.to_string_lossy().to_string();
-                        self.entries.insert(
-                            key,
-                            BuildCacheEntry {
-                                source_file: source_file.clone(),
-                                last_modified: duration.as_secs(),
-                                output_file: PathBuf::new(), // Will be set by compiler
-                            },
-                        );
-                    }
-                }
-            }
-        }
+        self.insert_entries(&Self::snapshot(source_files));
 
         // Save cache to disk
         self.save()?;
@@ -111,9 +120,204 @@ impl BuildCache {
         Ok(())
     }
 
+    /// Read the current state of `source_files`, skipping unreadable ones
+    ///
+    /// Take it before compiling and pass it to
+    /// [`Self::update_for_compiled_sources`] afterwards.
+    pub fn snapshot(source_files: &[PathBuf]) -> Vec<SourceSnapshot> {
+        source_files
+            .iter()
+            .filter_map(|source_file| {
+                let content = std::fs::read(source_file).ok()?;
+                let last_modified = std::fs::metadata(source_file)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+                    .map_or(0, |duration| duration.as_secs());
+                Some(SourceSnapshot {
+                    source_file: source_file.clone(),
+                    last_modified,
+                    content_digest: hex(Sha256::digest(&content)),
+                })
+            })
+            .collect()
+    }
+
+    /// Update cache for sources compiled into `output_dir` against `classpath`, recording their class files
+    ///
+    /// `sources` is the [`Self::snapshot`] taken before compiling. The outputs
+    /// of a source are the `.class` files named after it (plus `$`-suffixed
+    /// nested classes) in the directory of its `package`.
+    ///
+    /// Returns whether the non-private signatures of a previously compiled
+    /// source changed. Classes depending on it are not tracked, so the caller
+    /// has to recompile every source to catch the ones it breaks.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If cache cannot be saved
+    pub fn update_for_compiled_sources(
+        &mut self,
+        sources: &[SourceSnapshot],
+        output_dir: &Path,
+        classpath: &[PathBuf],
+    ) -> Result<bool> {
+        let classpath_digest = classpath_digest(classpath);
+        let mut api_changed = false;
+        for source in sources {
+            let key = source.source_file.to_string_lossy().to_string();
+            let output_files = Self::class_outputs(&source.source_file, output_dir);
+            let api_digest = api_digest(&output_files);
+            if let Some(previous) = self.entries.get(&key) {
+                api_changed |= previous.api_digest != api_digest;
+            }
+            self.entries.insert(
+                key,
+                BuildCacheEntry {
+                    source_file: source.source_file.clone(),
+                    last_modified: source.last_modified,
+                    output_file: output_files.first().cloned().unwrap_or_default(),
+                    output_files,
+                    classpath_digest: classpath_digest.clone(),
+                    content_digest: source.content_digest.clone(),
+                    api_digest,
+                },
+            );
+        }
+        self.save()?;
+        Ok(api_changed)
+    }
+
+    /// Forget `source_files` if any of them was compiled against a different classpath
+    ///
+    /// A changed dependency can break or change the meaning of code that did
+    /// not change itself, so the whole set is recompiled. Returns whether the
+    /// entries were dropped.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If cache cannot be saved
+    pub fn invalidate_if_classpath_changed(
+        &mut self,
+        source_files: &[PathBuf],
+        classpath: &[PathBuf],
+    ) -> Result<bool> {
+        let classpath_digest = classpath_digest(classpath);
+        let keys: Vec<String> = source_files
+            .iter()
+            .map(|source_file| source_file.to_string_lossy().to_string())
+            .collect();
+        let changed = keys.iter().any(|key| {
+            self.entries
+                .get(key)
+                .is_some_and(|entry| entry.classpath_digest != classpath_digest)
+        });
+        if !changed {
+            return Ok(false);
+        }
+
+        for key in &keys {
+            self.entries.remove(key);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Delete the recorded class files of sources that no longer exist
+    ///
+    /// Their cache entries are dropped as well. Returns the deleted files.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If a class file or the cache cannot be written
+    pub fn remove_orphaned_outputs(&mut self) -> Result<Vec<PathBuf>> {
+        let orphans: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.source_file.exists())
+            .map(|(key, _)| key.clone())
+            .collect();
+        if orphans.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut removed = Vec::new();
+        for key in orphans {
+            let Some(entry) = self.entries.remove(&key) else {
+                continue;
+            };
+            for output in entry.output_files {
+                match std::fs::remove_file(&output) {
+                    Ok(()) => removed.push(output),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        self.save()?;
+
+        Ok(removed)
+    }
+
+    /// Class files in `output_dir` produced from `source_file`
+    fn class_outputs(source_file: &Path, output_dir: &Path) -> Vec<PathBuf> {
+        let Some(stem) = source_file.file_stem().and_then(|s| s.to_str()) else {
+            return Vec::new();
+        };
+        let package = std::fs::read_to_string(source_file)
+            .ok()
+            .and_then(|source| {
+                source.lines().find_map(|line| {
+                    line.trim()
+                        .strip_prefix("package ")
+                        .map(|p| p.trim().trim_end_matches(';').trim().to_string())
+                })
+            })
+            .unwrap_or_default();
+        let class_dir = package
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .fold(output_dir.to_path_buf(), |dir, segment| dir.join(segment));
+
+        let nested_prefix = format!("{}$", stem);
+        let top_level = format!("{}.class", stem);
+        let mut outputs: Vec<PathBuf> = std::fs::read_dir(&class_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name == top_level || (name.starts_with(&nested_prefix) && name.ends_with(".class"))
+            })
+            .map(|entry| entry.path())
+            .collect();
+        outputs.sort();
+        outputs
+    }
+
+    fn insert_entries(&mut self, sources: &[SourceSnapshot]) {
+        for source in sources {
+            let key = source.source_file.to_string_lossy().to_string();
+            self.entries.insert(
+                key,
+                BuildCacheEntry {
+                    source_file: source.source_file.clone(),
+                    last_modified: source.last_modified,
+                    output_file: PathBuf::new(), // Will be set by compiler
+                    output_files: Vec::new(),
+                    classpath_digest: String::new(),
+                    content_digest: source.content_digest.clone(),
+                    api_digest: String::new(),
+                },
+            );
+        }
+    }
+
     /// Determine which files need recompilation
     ///
-    /// Compares modification times of source files with cached values.
+    /// Compares the contents of source files with the digests recorded when
+    /// they were compiled, so edits within the same second (or that keep the
+    /// old timestamp) are still found.
     ///
     /// # Arguments
     ///
@@ -136,19 +340,10 @@ impl BuildCache {
 
             // Check if file needs recompilation
             let needs_compile = if let Some(cached) = self.entries.get(&key) {
-                if let Ok(metadata) = std::fs::metadata(source_file) {
-                    if let Ok(modified) = metadata.modified() {
-                        if let Ok(duration) = modified.duration_since(SystemTime::UNIX_EPOCH) {
-                            duration.as_secs() > cached.last_modified
-                        } else {
-                            true
-                        }
-                    } else {
-                        true
-                    }
-                } else {
+                match std::fs::read(source_file) {
+                    Ok(content) => hex(Sha256::digest(&content)) != cached.content_digest,
                     // File doesn't exist, skip
-                    false
+                    Err(_) => false,
                 }
             } else {
                 // Not in cache, needs compilation
@@ -163,6 +358,27 @@ impl BuildCache {
         Ok(to_compile)
     }
 
+    /// Sources that must be compiled again
+    ///
+    /// These are sources that were never compiled, were modified since, or
+    /// whose recorded class files were deleted (e.g. by `pt clean`).
+    pub fn needs_recompile(&self, source_files: &[PathBuf]) -> Vec<PathBuf> {
+        let mut stale = self
+            .get_files_to_compile(source_files)
+            .unwrap_or_else(|_| source_files.to_vec());
+        for source_file in source_files {
+            let key = source_file.to_string_lossy().to_string();
+            let outputs_missing = self
+                .entries
+                .get(&key)
+                .is_some_and(|entry| entry.output_files.iter().any(|output| !output.exists()));
+            if outputs_missing && !stale.contains(source_file) {
+                stale.push(source_file.clone());
+            }
+        }
+        stale
+    }
+
     /// Save cache to disk
     fn save(&self) -> Result<()> {
         // Create cache directory if needed
@@ -177,8 +393,45 @@ impl BuildCache {
     }
 
     /// Clear all cache entries
+    ///
+    /// Resets the persisted incremental state (`build-cache.json`) without
+    /// touching compiled outputs or the downloaded JARs next to it.
     pub fn clear(&mut self) -> Result<()> {
         self.entries.clear();
         self.save()
     }
 }
+
+/// Hex SHA-256 of the classpath entries, in order
+fn classpath_digest(classpath: &[PathBuf]) -> String {
+    let mut hasher = Sha256::new();
+    for entry in classpath {
+        hasher.update(entry.to_string_lossy().as_bytes());
+        hasher.update([0]);
+    }
+    hex(hasher.finalize())
+}
+
+/// Hex SHA-256 of what other classes can see of `class_files`
+///
+/// Files that cannot be parsed as class files are hashed whole.
+fn api_digest(class_files: &[PathBuf]) -> String {
+    let mut hasher = Sha256::new();
+    for class_file in class_files {
+        let name = class_file.file_name().unwrap_or_default();
+        hasher.update(name.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        let bytes = std::fs::read(class_file).unwrap_or_default();
+        hasher.update(crate::class_file::signature(&bytes).unwrap_or(bytes));
+        hasher.update([0]);
+    }
+    hex(hasher.finalize())
+}
+
+fn hex(digest: impl AsRef<[u8]>) -> String {
+    digest
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
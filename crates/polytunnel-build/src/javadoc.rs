@@ -0,0 +1,155 @@
+//! API documentation generation with the JDK `javadoc` tool
+
+use polytunnel_maven::Coordinate;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Options for [`crate::BuildOrchestrator::javadoc`]
+#[derive(Debug, Clone, Default)]
+pub struct JavadocOptions {
+    /// Link types from direct dependencies to their javadoc.io documentation
+    pub with_dependencies: bool,
+    /// Verbose output
+    pub verbose: bool,
+    /// Print the exact `javadoc` command line before running it
+    pub explain: bool,
+}
+
+/// Severity of a javadoc diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The documentation could not be generated
+    Error,
+    /// Problem in a doc comment that did not stop generation
+    Warning,
+}
+
+/// A warning or error reported by `javadoc`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavadocDiagnostic {
+    /// Severity
+    pub kind: DiagnosticKind,
+    /// Source file the diagnostic points at, if any
+    pub file: Option<PathBuf>,
+    /// 1-based line in `file`
+    pub line: Option<u32>,
+    /// Message without the location and severity prefix
+    pub message: String,
+}
+
+impl std::fmt::Display for JavadocDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            DiagnosticKind::Error => "error",
+            DiagnosticKind::Warning => "warning",
+        };
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => {
+                write!(f, "{}:{}: {}: {}", file.display(), line, kind, self.message)
+            }
+            _ => write!(f, "{}: {}", kind, self.message),
+        }
+    }
+}
+
+/// Result of a successful `pt javadoc`
+#[derive(Debug, Clone)]
+pub struct JavadocResult {
+    /// Directory the HTML was written to
+    pub output_dir: PathBuf,
+    /// Number of source files documented
+    pub documented_files: usize,
+    /// Warnings reported by `javadoc`
+    pub diagnostics: Vec<JavadocDiagnostic>,
+}
+
+/// Arguments for a `javadoc` run over `sources`
+///
+/// `-link` is added for each entry of `links`, before the source files.
+pub fn javadoc_args(
+    sources: &[PathBuf],
+    classpath: &[PathBuf],
+    output_dir: &Path,
+    java_version: &str,
+    encoding: &str,
+    links: &[String],
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec![
+        "-d".into(),
+        output_dir.into(),
+        "--release".into(),
+        java_version.into(),
+    ];
+    if !encoding.is_empty() {
+        args.extend(["-encoding".into(), encoding.into()]);
+    }
+    if !classpath.is_empty() {
+        let classpath = crate::Classpath::from(classpath).to_arg_string();
+        args.extend(["-classpath".into(), classpath.into()]);
+    }
+    for link in links {
+        args.extend(["-link".into(), link.into()]);
+    }
+    args.extend(sources.iter().map(OsString::from));
+    args
+}
+
+/// javadoc.io URL of the API documentation for `coord`
+pub fn javadoc_link(coord: &Coordinate) -> String {
+    format!(
+        "https://javadoc.io/doc/{}/{}/{}/",
+        coord.group_id, coord.artifact_id, coord.version
+    )
+}
+
+/// Parse the warnings and errors in `javadoc` output
+///
+/// Recognizes `File.java:12: warning: ...` as well as location-less
+/// `error: ...` and `javadoc: error - ...` lines. Progress messages, source
+/// excerpts, and the closing `N warnings` summary are skipped.
+pub fn parse_javadoc_diagnostics(output: &str) -> Vec<JavadocDiagnostic> {
+    output.lines().filter_map(parse_diagnostic_line).collect()
+}
+
+fn parse_diagnostic_line(line: &str) -> Option<JavadocDiagnostic> {
+    let line = line.trim_end();
+    if let Some(rest) = line.strip_prefix("javadoc: ") {
+        let (kind, message) = split_kind(rest, " - ")?;
+        return Some(JavadocDiagnostic {
+            kind,
+            file: None,
+            line: None,
+            message: message.to_string(),
+        });
+    }
+    if let Some((kind, message)) = split_kind(line, ": ") {
+        return Some(JavadocDiagnostic {
+            kind,
+            file: None,
+            line: None,
+            message: message.to_string(),
+        });
+    }
+
+    // `<file>:<line>: <kind>: <message>`; the file may itself contain `:` (Windows drives)
+    let (location, rest) = line.split_once(".java:")?;
+    let (line_number, rest) = rest.split_once(": ")?;
+    let line_number = line_number.parse().ok()?;
+    let (kind, message) = split_kind(rest, ": ")?;
+    Some(JavadocDiagnostic {
+        kind,
+        file: Some(PathBuf::from(format!("{}.java", location))),
+        line: Some(line_number),
+        message: message.to_string(),
+    })
+}
+
+fn split_kind<'a>(text: &'a str, separator: &str) -> Option<(DiagnosticKind, &'a str)> {
+    let (kind, message) = text.split_once(separator)?;
+    let kind = match kind {
+        "error" => DiagnosticKind::Error,
+        "warning" => DiagnosticKind::Warning,
+        _ => return None,
+    };
+    Some((kind, message))
+}
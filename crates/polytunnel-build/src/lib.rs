@@ -5,29 +5,44 @@
 
 #![warn(missing_docs)]
 
+mod cache;
+mod class_file;
 mod classpath;
 mod compiler;
+mod compiler_daemon;
+mod diagnostic;
 pub mod error;
 mod incremental;
+mod javadoc;
+mod main_class;
 mod orchestrator;
+mod platform;
 mod test_runner;
+mod timing;
 
 // Re-exports for convenience
-pub use classpath::{ClasspathBuilder, ClasspathResult};
+pub use cache::{ArtifactCache, CacheCleanup, CacheInfo};
+pub use classpath::{
+    ArtifactFetch, ArtifactSource, CacheReport, Classpath, ClasspathBuilder, ClasspathEntry,
+    ClasspathResult, LockMode,
+};
 pub use compiler::{CompilationResult, JavaCompiler};
+pub use diagnostic::{Diagnostic, Severity, parse_javac_diagnostics};
 pub use error::{BuildError, Result};
-pub use incremental::{BuildCache, BuildCacheEntry};
-pub use orchestrator::{BuildOptions, BuildOrchestrator, BuildResult, TestOptions};
-pub use test_runner::{TestFramework, TestResult, TestRunner};
+pub use incremental::{BuildCache, BuildCacheEntry, SourceSnapshot};
+pub use javadoc::{
+    DiagnosticKind, JavadocDiagnostic, JavadocOptions, JavadocResult, javadoc_args, javadoc_link,
+    parse_javadoc_diagnostics,
+};
+pub use main_class::{detect_main_class, find_main_classes};
+pub use orchestrator::{BuildOptions, BuildOrchestrator, BuildResult, BuildScope, TestOptions};
+pub use platform::{classpath_separator, find_jdk_tool, format_command, shell_quote};
+pub use test_runner::{JUNIT_CONSOLE_ARTIFACT, TestFailure, TestFramework, TestResult, TestRunner};
+pub use timing::{BuildPhase, PhaseTiming};
 
 /// Format classpath paths with OS-specific separator.
 ///
-/// Uses `;` on Windows and `:` on Unix-like systems.
+/// Shorthand for [`Classpath::to_arg_string`]; duplicate and empty entries are dropped.
 pub fn format_classpath(paths: &[std::path::PathBuf]) -> String {
-    let separator = if cfg!(windows) { ";" } else { ":" };
-    paths
-        .iter()
-        .map(|p| p.to_string_lossy().to_string())
-        .collect::<Vec<_>>()
-        .join(separator)
+    Classpath::from(paths).to_arg_string()
 }
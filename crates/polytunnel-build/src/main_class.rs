@@ -0,0 +1,142 @@
+//! Main class detection for `pt run`
+//!
+//! Scans Java sources for a `public static void main(String[])` method so
+//! simple applications can be run without naming their entry point.
+
+use crate::error::{BuildError, Result};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// The only class in `source_dirs` that declares a `main` method
+///
+/// # Errors
+///
+/// * `BuildError::MainClassNotFound` - If no source declares `main`
+/// * `BuildError::AmbiguousMainClass` - If more than one source declares `main`
+pub fn detect_main_class(source_dirs: &[PathBuf]) -> Result<String> {
+    let mut candidates = find_main_classes(source_dirs);
+    match candidates.len() {
+        0 => Err(BuildError::MainClassNotFound {
+            source_dirs: source_dirs
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }),
+        1 => Ok(candidates.remove(0)),
+        _ => Err(BuildError::AmbiguousMainClass { candidates }),
+    }
+}
+
+/// Fully qualified names of top-level classes in `source_dirs` that declare `main`, sorted
+///
+/// Missing directories and unreadable files are skipped. Each `.java` file is
+/// taken to declare a class named after the file.
+pub fn find_main_classes(source_dirs: &[PathBuf]) -> Vec<String> {
+    let mut classes: Vec<String> = source_dirs
+        .iter()
+        .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "java"))
+        .filter_map(|entry| main_class_of(entry.path()))
+        .collect();
+    classes.sort();
+    classes.dedup();
+    classes
+}
+
+fn main_class_of(path: &Path) -> Option<String> {
+    let source = std::fs::read_to_string(path).ok()?;
+    let code = strip_comments(&source);
+    if !declares_main(&code) {
+        return None;
+    }
+    let class = path.file_stem()?.to_string_lossy().to_string();
+    Some(match package_of(&code) {
+        Some(package) => format!("{}.{}", package, class),
+        None => class,
+    })
+}
+
+/// Whether `code` (without comments) declares `public static void main(String[] ...)`
+fn declares_main(code: &str) -> bool {
+    let code: String = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    code.match_indices("void main").any(|(index, _)| {
+        let modifiers = code[..index]
+            .rsplit(['{', '}', ';'])
+            .next()
+            .unwrap_or_default();
+        let is_public_static = modifiers.split(' ').any(|m| m == "public")
+            && modifiers.split(' ').any(|m| m == "static");
+        let params = code[index + "void main".len()..].trim_start();
+        let params = params.strip_prefix('(').unwrap_or_default().trim_start();
+        let params = params.strip_prefix("final ").unwrap_or(params);
+        let Some(params) = params
+            .strip_prefix("String")
+            .or_else(|| params.strip_prefix("java.lang.String"))
+            .filter(|rest| !rest.starts_with(is_identifier_char))
+        else {
+            return false;
+        };
+        // `String[] args`, `String... args`, or C-style `String args[]`
+        let params = params.trim_start();
+        let after_name = params.trim_start_matches(is_identifier_char).trim_start();
+        is_public_static
+            && (params.starts_with('[') || params.starts_with("...") || after_name.starts_with('['))
+    })
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Package named by the `package` declaration of `code`, if any
+fn package_of(code: &str) -> Option<String> {
+    let declaration = code.trim_start().strip_prefix("package")?;
+    let (name, _) = declaration.split_once(';')?;
+    let name: String = name.split_whitespace().collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// `source` with `//` and `/* */` comments removed; string and char literals are kept
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                out.push(' ');
+            }
+            '"' | '\'' => {
+                out.push(c);
+                while let Some(next) = chars.next() {
+                    out.push(next);
+                    if next == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            out.push(escaped);
+                        }
+                    } else if next == c || next == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
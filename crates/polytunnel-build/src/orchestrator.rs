@@ -3,8 +3,13 @@
 //! Coordinates compilation, testing, and artifact management.
 
 use crate::error::{BuildError, Result};
-use crate::{BuildCache, ClasspathBuilder, JavaCompiler, TestResult};
+use crate::{
+    BuildCache, BuildPhase, ClasspathBuilder, CompilationResult, DiagnosticKind, JavaCompiler,
+    JavadocOptions, JavadocResult, LockMode, PhaseTiming, TestResult,
+};
 use polytunnel_core::ProjectConfig;
+use polytunnel_maven::{Coordinate, DependencyScope};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Instant;
 
@@ -13,14 +18,67 @@ use std::time::Instant;
 pub struct BuildOptions {
     /// Clean build (remove existing outputs)
     pub clean: bool,
+    /// Reset the incremental build state before compiling
+    ///
+    /// Unlike `clean`, compiled outputs and downloaded JARs are kept; only the
+    /// record of which sources produced which class files is discarded.
+    pub no_incremental: bool,
     /// Skip test compilation and execution
     pub skip_tests: bool,
     /// Verbose output
     pub verbose: bool,
+    /// Print the exact `javac`/`java` command lines before running them
+    pub explain: bool,
+    /// Test selection (pattern, tags, fail-fast) when tests are not skipped
+    ///
+    /// `verbose` and `explain` are taken from the build options.
+    pub test: TestOptions,
+    /// Extra `javac` arguments for main sources, appended after `build.compiler_args`
+    pub compiler_args: Vec<String>,
+    /// Extra `javac` arguments for test sources, appended after `build.test_compiler_args`
+    pub test_compiler_args: Vec<String>,
+    /// Compile only these main source files instead of scanning `build.source_dirs`
+    ///
+    /// Empty compiles everything. Files that depend on the selection are not recompiled.
+    pub only: Vec<PathBuf>,
+    /// Keep compiling the remaining steps after one fails
+    ///
+    /// Failures are collected into [`BuildError::StepsFailed`] once all
+    /// compilation steps have run; tests are not run if any step failed.
+    pub keep_going: bool,
+    /// Fail with [`BuildError::CompilerWarnings`] once compilation is done if javac warned
+    ///
+    /// Unlike `-Werror`, every source is still compiled so all warnings are
+    /// reported together; tests are not run. This forces a full recompile of
+    /// the main sources, since javac only warns about the sources it compiles.
+    pub fail_on_warning: bool,
+    /// Which sources compile and which dependencies resolve
+    pub scope: BuildScope,
+    /// Use only POMs and JARs already in `build.cache_dir`, without network access
+    ///
+    /// Anything missing fails with [`BuildError::OfflineArtifactMissing`].
+    pub offline: bool,
+}
+
+/// Part of the project a build covers (`pt build --scope`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BuildScope {
+    /// Main sources only; test, runtime, and test-only dependencies are not resolved
+    Compile,
+    /// Main and test sources with every dependency, then the tests (default)
+    #[default]
+    All,
+}
+
+impl BuildScope {
+    /// Whether test sources are compiled and run
+    pub fn includes_tests(self) -> bool {
+        self != Self::Compile
+    }
 }
 
 /// Options for test command
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TestOptions {
     /// Test pattern/filter (optional)
     pub pattern: Option<String>,
@@ -28,6 +86,26 @@ pub struct TestOptions {
     pub verbose: bool,
     /// Stop on first failure
     pub fail_fast: bool,
+    /// Print the exact `java` test-launch command line before running it
+    pub explain: bool,
+    /// Only run tests with one of these JUnit 5 tags
+    pub include_tags: Vec<String>,
+    /// Skip tests with any of these JUnit 5 tags
+    pub exclude_tags: Vec<String>,
+    /// Capture each test's stdout/stderr and keep it only for failing tests
+    pub capture_output: bool,
+    /// Extra arguments passed through to the JUnit console launcher
+    pub launcher_args: Vec<String>,
+    /// Run exactly these fully qualified test classes instead of discovering them
+    pub classes: Vec<String>,
+    /// Environment variables for the test JVM, overriding `[test.env]`
+    pub env: Vec<(String, String)>,
+    /// Arguments for the test JVM, appended to `[test] jvm_args`
+    pub jvm_args: Vec<String>,
+    /// Fail instead of downloading a missing JUnit console launcher
+    ///
+    /// Set from the build options when tests run as part of a build.
+    pub offline: bool,
 }
 
 /// Result of a build operation
@@ -35,10 +113,16 @@ pub struct TestOptions {
 pub struct BuildResult {
     /// Number of compiled files
     pub compiled_files: usize,
+    /// `javac` output for main sources (`None` if there were none)
+    pub main_compilation: Option<CompilationResult>,
+    /// `javac` output for test sources (`None` if skipped or there were none)
+    pub test_compilation: Option<CompilationResult>,
     /// Test results (if tests ran)
     pub test_result: Option<TestResult>,
     /// Build duration
     pub duration: std::time::Duration,
+    /// Duration of each phase that ran, in execution order
+    pub timings: Vec<PhaseTiming>,
 }
 
 /// Central orchestrator for build operations
@@ -84,11 +168,27 @@ impl BuildOrchestrator {
         })
     }
 
+    /// Check dependency resolution against the lockfile at `path`
+    ///
+    /// See [`LockMode`] for what each mode enforces.
+    pub fn with_lock_mode(mut self, path: impl Into<PathBuf>, mode: LockMode) -> Self {
+        self.classpath_builder.set_lock_mode(path, mode);
+        self
+    }
+
+    /// Resolve the fullest possible classpath for analysis instead of building
+    ///
+    /// See [`ClasspathBuilder::set_full_classpath`].
+    pub fn with_full_classpath(mut self, full: bool) -> Self {
+        self.classpath_builder.set_full_classpath(full);
+        self
+    }
+
     /// Execute full build (compile + tests)
     ///
     /// # Arguments
     ///
-    /// * `options` - Build options (clean, skip_tests, verbose)
+    /// * `options` - Build options (clean, skip_tests, verbose, explain)
     ///
     /// # Returns
     ///
@@ -99,26 +199,43 @@ impl BuildOrchestrator {
     /// * `BuildError::CompilationFailed` - If compilation fails
     /// * `BuildError::TestExecutionFailed` - If test execution fails (when not skipped)
     /// * `BuildError::SourceDirNotFound` - If source directories don't exist
+    /// * `BuildError::StepsFailed` - If compilation steps failed with `keep_going` set
+    /// * `BuildError::CompilerWarnings` - If javac warned with `fail_on_warning` set
     ///
     /// # Example
     ///
     /// ```ignore
     /// let options = BuildOptions {
     ///     clean: false,
+    ///     no_incremental: false,
     ///     skip_tests: false,
     ///     verbose: true,
+    ///     explain: false,
+    ///     test: TestOptions::default(),
+    ///     compiler_args: vec!["-Xlint:all".to_string()],
+    ///     test_compiler_args: vec![],
+    ///     only: vec![],
+    ///     keep_going: false,
+    ///     fail_on_warning: false,
+    ///     scope: BuildScope::All,
+    ///     offline: false,
     /// };
     /// let result = orchestrator.build(&options).await?;
     /// println!("Compiled {} files", result.compiled_files);
     /// ```
     pub async fn build(&mut self, options: &BuildOptions) -> Result<BuildResult> {
         let start = Instant::now();
+        self.compiler.set_explain(options.explain);
 
         // 1. Resolve and download dependencies
         if options.verbose {
             println!("Resolving dependencies...");
         }
+        self.classpath_builder
+            .set_compile_only(options.scope == BuildScope::Compile);
+        self.classpath_builder.set_offline(options.offline);
         self.resolve_dependencies(options.verbose).await?;
+        let mut timings = self.classpath_builder.phase_timings().to_vec();
 
         // 2. Clean if requested
         if options.clean {
@@ -128,41 +245,127 @@ impl BuildOrchestrator {
             self.clean()?;
         }
 
+        if options.no_incremental {
+            if options.verbose {
+                println!("Resetting incremental build state...");
+            }
+            self.incremental.clear()?;
+        }
+
         // 3. Compile main sources
         if options.verbose {
             println!("Compiling main sources...");
         }
-        let compiled = self.compile_sources()?;
+        let phase_start = Instant::now();
+        let only = (!options.only.is_empty()).then_some(options.only.as_slice());
+        let mut failures = Vec::new();
+        let (compiled, main_compilation) = Self::collect_failure(
+            // `fail_on_warning` forces a full compile so no source's warnings are missed
+            self.compile_main(&options.compiler_args, only, options.fail_on_warning),
+            BuildPhase::CompileMain,
+            options.keep_going,
+            &mut failures,
+        )?
+        .unwrap_or_default();
+        timings.push(PhaseTiming::new(
+            BuildPhase::CompileMain,
+            phase_start.elapsed(),
+        ));
 
         // 4. Compile and run tests (if not skipped)
-        let test_result = if !options.skip_tests {
-            if options.verbose {
-                println!("Compiling test sources...");
-            }
-            self.compile_tests()?;
+        let (test_compilation, test_result) =
+            if !options.skip_tests && options.scope.includes_tests() {
+                if options.verbose {
+                    println!("Compiling test sources...");
+                }
+                let phase_start = Instant::now();
+                let test_compilation = Self::collect_failure(
+                    self.compile_test_sources(&options.test_compiler_args),
+                    BuildPhase::CompileTest,
+                    options.keep_going,
+                    &mut failures,
+                )?
+                .flatten();
+                timings.push(PhaseTiming::new(
+                    BuildPhase::CompileTest,
+                    phase_start.elapsed(),
+                ));
+                if !failures.is_empty() {
+                    return Err(BuildError::StepsFailed { failures });
+                }
+                if options.fail_on_warning {
+                    Self::check_warnings([&main_compilation, &test_compilation])?;
+                }
 
-            if options.verbose {
-                println!("Running tests...");
-            }
-            let test_opts = TestOptions {
-                pattern: None,
-                verbose: options.verbose,
-                fail_fast: false,
+                if options.verbose {
+                    println!("Running tests...");
+                }
+                let test_opts = TestOptions {
+                    verbose: options.verbose,
+                    explain: options.explain,
+                    offline: options.offline,
+                    ..options.test.clone()
+                };
+                let phase_start = Instant::now();
+                let test_result = self.run_tests(&test_opts).await?;
+                timings.push(PhaseTiming::new(
+                    BuildPhase::RunTests,
+                    phase_start.elapsed(),
+                ));
+                (test_compilation, Some(test_result))
+            } else {
+                if !failures.is_empty() {
+                    return Err(BuildError::StepsFailed { failures });
+                }
+                if options.fail_on_warning {
+                    Self::check_warnings([&main_compilation, &None])?;
+                }
+                (None, None)
             };
-            Some(self.run_tests(&test_opts).await?)
-        } else {
-            None
-        };
 
         let duration = start.elapsed();
 
         Ok(BuildResult {
             compiled_files: compiled,
+            main_compilation,
+            test_compilation,
             test_result,
             duration,
+            timings,
         })
     }
 
+    /// Fail with every warning of `compilations` if there are any
+    fn check_warnings(compilations: [&Option<CompilationResult>; 2]) -> Result<()> {
+        let warnings: Vec<_> = compilations
+            .into_iter()
+            .flatten()
+            .flat_map(|compilation| compilation.warnings().cloned())
+            .collect();
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(BuildError::CompilerWarnings { warnings })
+        }
+    }
+
+    /// With `keep_going`, record a failed step and carry on instead of returning the error
+    fn collect_failure<T>(
+        result: Result<T>,
+        phase: BuildPhase,
+        keep_going: bool,
+        failures: &mut Vec<(BuildPhase, BuildError)>,
+    ) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if keep_going => {
+                failures.push((phase, error));
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
     /// Resolve dependencies
     pub async fn resolve_dependencies(&mut self, verbose: bool) -> Result<()> {
         self.classpath_builder
@@ -171,43 +374,128 @@ impl BuildOrchestrator {
             .map(|_| ())
     }
 
-    /// Compile main sources only
-    pub fn compile_sources(&mut self) -> Result<usize> {
+    /// Compile main sources only, returning the number of files compiled
+    ///
+    /// Only sources changed since the last compilation (or whose class files
+    /// are missing) are compiled; all of them if the compile classpath
+    /// changed, a source was deleted, or a recompiled source changed its
+    /// non-private signatures. Nothing compiled returns 0. `only` instead compiles exactly the
+    /// given files, skipping the source directory scan; each must exist inside
+    /// one of `build.source_dirs`.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::SourceFileNotFound` - If a selected file does not exist
+    /// * `BuildError::SourceFileOutsideSourceDirs` - If a selected file is outside the source dirs
+    pub fn compile_sources(&mut self, only: Option<&[PathBuf]>) -> Result<usize> {
+        self.compile_main(&[], only, false).map(|(count, _)| count)
+    }
+
+    /// `extra_args` follow `build.compiler_args` so they can override them;
+    /// `full` compiles every source even if unchanged
+    fn compile_main(
+        &mut self,
+        extra_args: &[String],
+        only: Option<&[PathBuf]>,
+        full: bool,
+    ) -> Result<(usize, Option<CompilationResult>)> {
         let source_dirs = &self.config.build.source_dirs;
         let output_dir = PathBuf::from(&self.config.build.output_dir);
-        let compiler_args = self.config.build.compiler_args.clone();
+        let mut compiler_args = self.config.build.compiler_args.clone();
+        compiler_args.extend_from_slice(extra_args);
 
         // Get compile classpath
         let classpaths = self.classpath_builder.get_cached_classpath();
         let classpath = &classpaths.compile_classpath;
 
-        // Find all Java source files
-        let source_files = self.find_java_files(source_dirs)?;
+        // Find all Java source files, or check the explicit selection
+        let source_files = match only {
+            Some(files) => self.select_source_files(files)?,
+            None => self.find_java_files(source_dirs)?,
+        };
+
+        // Class files of deleted sources would otherwise linger in output_dir
+        let removed = self.incremental.remove_orphaned_outputs()?;
+
+        let incremental = only.is_none() && !full;
+        let all_sources = source_files.clone();
+        let mut javac_classpath = classpath.clone();
+        let source_files = if !incremental {
+            source_files
+        } else {
+            self.incremental
+                .invalidate_if_classpath_changed(&source_files, classpath)?;
+            // Unchanged sources are found as their previously compiled classes
+            javac_classpath.push(output_dir.clone());
+            if removed.is_empty() {
+                self.incremental.needs_recompile(&source_files)
+            } else {
+                // Sources using a deleted class must fail to compile
+                source_files
+            }
+        };
 
         if source_files.is_empty() {
-            return Ok(0);
+            return Ok((0, None));
         }
 
         // Compile
-        let _result = self.compiler.compile(
+        let snapshot = BuildCache::snapshot(&source_files);
+        let result = self.compiler.compile(
             source_files.clone(),
-            classpath.clone(),
-            output_dir,
-            compiler_args,
+            javac_classpath,
+            output_dir.clone(),
+            compiler_args.clone(),
         )?;
 
         // Update cache
-        self.incremental.update_for_sources(&source_files)?;
+        let api_changed =
+            self.incremental
+                .update_for_compiled_sources(&snapshot, &output_dir, classpath)?;
+
+        // Dependents are not tracked: when a signature changed, recheck every source
+        if incremental && api_changed && source_files.len() < all_sources.len() {
+            let snapshot = BuildCache::snapshot(&all_sources);
+            let result = self.compiler.compile(
+                all_sources.clone(),
+                classpath.clone(),
+                output_dir.clone(),
+                compiler_args,
+            )?;
+            self.incremental
+                .update_for_compiled_sources(&snapshot, &output_dir, classpath)?;
+            return Ok((all_sources.len(), Some(result)));
+        }
 
-        Ok(source_files.len())
+        Ok((source_files.len(), Some(result)))
     }
 
     /// Compile test sources only
+    ///
+    /// Tests compile against `build.output_dir`, so main sources that were
+    /// never compiled, changed since, or lost their class files are compiled
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::MainNotBuilt` - If the main sources needed compiling and failed
     pub fn compile_tests(&mut self) -> Result<()> {
+        let main_sources = self.find_java_files(&self.config.build.source_dirs)?;
+        if !self.incremental.needs_recompile(&main_sources).is_empty() {
+            self.compile_main(&[], None, false)
+                .map_err(|error| BuildError::MainNotBuilt {
+                    error: Box::new(error),
+                })?;
+        }
+        self.compile_test_sources(&[]).map(|_| ())
+    }
+
+    /// `extra_args` follow `build.test_compiler_args` so they can override them
+    fn compile_test_sources(&mut self, extra_args: &[String]) -> Result<Option<CompilationResult>> {
         let test_source_dirs = &self.config.build.test_source_dirs;
-        // ... (rest is unchanged logic, just ensuring pub)
         let test_output_dir = PathBuf::from(&self.config.build.test_output_dir);
-        let test_compiler_args = self.config.build.test_compiler_args.clone();
+        let mut test_compiler_args = self.config.build.test_compiler_args.clone();
+        test_compiler_args.extend_from_slice(extra_args);
 
         // Get test classpath
         let classpaths = self.classpath_builder.get_cached_classpath();
@@ -220,22 +508,29 @@ impl BuildOrchestrator {
         // Find all test Java source files
         let test_files = self.find_java_files(test_source_dirs)?;
 
+        self.incremental.remove_orphaned_outputs()?;
+
         if test_files.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
         // Compile tests
-        let _result = self.compiler.compile(
+        let snapshot = BuildCache::snapshot(&test_files);
+        let result = self.compiler.compile(
             test_files.clone(),
             test_classpath.clone(),
-            test_output_dir,
+            test_output_dir.clone(),
             test_compiler_args,
         )?;
 
         // Update cache
-        self.incremental.update_for_sources(&test_files)?;
+        self.incremental.update_for_compiled_sources(
+            &snapshot,
+            &test_output_dir,
+            &classpaths.test_classpath,
+        )?;
 
-        Ok(())
+        Ok(Some(result))
     }
 
     /// Get the resolved classpath
@@ -243,6 +538,26 @@ impl BuildOrchestrator {
         self.classpath_builder.get_cached_classpath()
     }
 
+    /// Dependency JARs with their coordinates and scopes, once dependencies have been resolved
+    pub fn classpath_entries(&self) -> &[crate::classpath::ClasspathEntry] {
+        self.classpath_builder.classpath_entries()
+    }
+
+    /// Get the resolved dependency tree, if dependencies have been resolved
+    pub fn get_resolved_tree(&self) -> Option<&polytunnel_resolver::ResolvedTree> {
+        self.classpath_builder.get_resolved_tree()
+    }
+
+    /// Effective scope of each dependency, keyed by coordinate, once dependencies have been resolved
+    pub fn effective_scopes(&self) -> &HashMap<String, DependencyScope> {
+        self.classpath_builder.effective_scopes()
+    }
+
+    /// Dependency JAR cache hits and misses from the last resolution
+    pub fn cache_report(&self) -> &crate::classpath::CacheReport {
+        self.classpath_builder.cache_report()
+    }
+
     /// Run tests
     pub async fn run_tests(&mut self, options: &TestOptions) -> Result<TestResult> {
         let test_output_dir = PathBuf::from(&self.config.build.test_output_dir);
@@ -276,27 +591,194 @@ impl BuildOrchestrator {
             println!("Detected test framework: {}", framework.name());
         }
 
+        // JUnit 5 needs the console launcher; fetch a pinned one if the project lacks it
+        let console_launcher = if framework == crate::test_runner::TestFramework::JUnit5
+            && !crate::test_runner::TestRunner::has_console_launcher(&full_classpath)
+        {
+            let launcher = Coordinate::new(
+                "org.junit.platform",
+                crate::test_runner::JUNIT_CONSOLE_ARTIFACT,
+                &self.config.test.junit_console_version,
+            );
+            self.classpath_builder.set_offline(options.offline);
+            Some(
+                self.classpath_builder
+                    .fetch_artifact(&launcher, &self.config.build.cache_dir, options.verbose)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let mut env = self.config.test.env.clone();
+        env.extend(options.env.iter().cloned());
+        let mut jvm_args = self.config.test.jvm_args.clone();
+        jvm_args.extend(options.jvm_args.iter().cloned());
+
         let runner =
-            crate::test_runner::TestRunner::new(framework, full_classpath, test_output_dir);
+            crate::test_runner::TestRunner::new(framework, full_classpath, test_output_dir)
+                .with_explain(options.explain)
+                .with_parallelism(self.config.test.parallel)
+                .with_tags(options.include_tags.clone(), options.exclude_tags.clone())
+                .with_output_capture(options.capture_output)
+                .with_console_launcher(console_launcher)
+                .with_launcher_args(options.launcher_args.clone())
+                .with_classes(options.classes.clone())
+                .with_env(env)
+                .with_jvm_args(jvm_args);
 
         runner
             .run(options.pattern.clone(), options.verbose, options.fail_fast)
             .await
     }
 
-    /// Clean build artifacts
-    pub fn clean(&self) -> Result<()> {
-        let output_dir = PathBuf::from(&self.config.build.output_dir);
-        let test_output_dir = PathBuf::from(&self.config.build.test_output_dir);
+    /// Generate API documentation for the main sources with `javadoc`
+    ///
+    /// Compile-scope dependencies are resolved and put on the `-classpath`;
+    /// HTML is written to `build.javadoc_dir`.
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::JdkToolNotFound` - If the JDK has no `javadoc`
+    /// * `BuildError::SourceDirNotFound` - If a source directory doesn't exist
+    /// * `BuildError::JavadocFailed` - If `javadoc` reports errors
+    pub async fn javadoc(&mut self, options: &JavadocOptions) -> Result<JavadocResult> {
+        self.classpath_builder.set_compile_only(true);
+        self.resolve_dependencies(options.verbose).await?;
+
+        let output_dir = PathBuf::from(&self.config.build.javadoc_dir);
+        let sources = self.find_java_files(&self.config.build.source_dirs)?;
+        if sources.is_empty() {
+            return Ok(JavadocResult {
+                output_dir,
+                documented_files: 0,
+                diagnostics: Vec::new(),
+            });
+        }
+
+        let links: Vec<String> = match self.get_resolved_tree() {
+            Some(tree) if options.with_dependencies => tree
+                .root_dependencies
+                .iter()
+                .map(crate::javadoc::javadoc_link)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let classpath = self
+            .classpath_builder
+            .get_cached_classpath()
+            .compile_classpath;
+
+        std::fs::create_dir_all(&output_dir)?;
+        let mut cmd = std::process::Command::new(crate::find_jdk_tool("javadoc")?);
+        cmd.args(crate::javadoc::javadoc_args(
+            &sources,
+            &classpath,
+            &output_dir,
+            &self.config.project.java_version,
+            &self.config.build.encoding,
+            &links,
+        ));
+        if options.explain {
+            println!("$ {}", crate::format_command(&cmd));
+        }
+        let output = cmd.output()?;
 
-        if output_dir.exists() {
-            std::fs::remove_dir_all(&output_dir)?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if options.verbose && !stdout.is_empty() {
+            println!("{}", stdout.trim_end());
         }
-        if test_output_dir.exists() {
-            std::fs::remove_dir_all(&test_output_dir)?;
+        let diagnostics =
+            crate::javadoc::parse_javadoc_diagnostics(&format!("{}\n{}", stdout, stderr));
+
+        if !output.status.success() {
+            let errors: Vec<String> = diagnostics
+                .iter()
+                .filter(|d| d.kind == DiagnosticKind::Error)
+                .map(ToString::to_string)
+                .collect();
+            return Err(BuildError::JavadocFailed {
+                message: if errors.is_empty() {
+                    stderr.trim_end().to_string()
+                } else {
+                    errors.join("\n")
+                },
+            });
+        }
+
+        Ok(JavadocResult {
+            output_dir,
+            documented_files: sources.len(),
+            diagnostics,
+        })
+    }
+
+    /// Clean build artifacts
+    ///
+    /// Removes `build.output_dir` and `build.test_output_dir` and returns the
+    /// ones that existed; missing directories are not an error.
+    pub fn clean(&self) -> Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        for dir in [
+            &self.config.build.output_dir,
+            &self.config.build.test_output_dir,
+        ] {
+            let dir = PathBuf::from(dir);
+            if dir.exists() {
+                std::fs::remove_dir_all(&dir)?;
+                removed.push(dir);
+            }
         }
+        Ok(removed)
+    }
 
-        Ok(())
+    /// Check `files` against `build.source_dirs`, returning each as `<source dir>/<relative path>`
+    ///
+    /// Using the same form as [`Self::find_java_files`] keeps incremental cache
+    /// entries keyed consistently between full and selective builds.
+    fn select_source_files(&self, files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let source_dirs: Vec<(PathBuf, PathBuf)> = self
+            .config
+            .build
+            .source_dirs
+            .iter()
+            .filter_map(|dir| {
+                let dir = PathBuf::from(dir);
+                let canonical = dir.canonicalize().ok()?;
+                Some((dir, canonical))
+            })
+            .collect();
+
+        let mut selected = Vec::new();
+        for file in files {
+            if !file.is_file() {
+                return Err(BuildError::SourceFileNotFound {
+                    path: file.display().to_string(),
+                });
+            }
+            let canonical = file.canonicalize()?;
+            let in_source_dir = source_dirs.iter().find_map(|(dir, canonical_dir)| {
+                canonical
+                    .strip_prefix(canonical_dir)
+                    .ok()
+                    .map(|relative| dir.join(relative))
+            });
+            match in_source_dir {
+                Some(path) => {
+                    if !selected.contains(&path) {
+                        selected.push(path);
+                    }
+                }
+                None => {
+                    return Err(BuildError::SourceFileOutsideSourceDirs {
+                        path: file.display().to_string(),
+                        source_dirs: self.config.build.source_dirs.join(", "),
+                    });
+                }
+            }
+        }
+        Ok(selected)
     }
 
     /// Find all Java files in given directories
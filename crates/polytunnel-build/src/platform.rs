@@ -0,0 +1,73 @@
+//! Platform-specific conventions for invoking the JDK
+
+use crate::error::{BuildError, Result};
+use std::path::{Path, PathBuf};
+
+/// Separator between classpath entries for the current platform
+///
+/// `;` on Windows and `:` on Unix-like systems.
+pub fn classpath_separator() -> char {
+    if cfg!(windows) { ';' } else { ':' }
+}
+
+/// Quote an argument so it survives copy-pasting into the platform shell
+///
+/// Arguments made only of characters that are safe unquoted are returned
+/// as-is. Otherwise Unix uses single quotes and Windows uses double quotes.
+pub fn shell_quote(arg: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c);
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        return arg.to_string();
+    }
+
+    if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Render a [`std::process::Command`] as a copy-pasteable shell command line
+pub fn format_command(cmd: &std::process::Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| shell_quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Locate a JDK tool such as `jar` or `javadoc`
+///
+/// The tool is looked up next to the `javac` used for compilation (following
+/// symlinks such as `/usr/bin/javac`), so both come from the same JDK. Then
+/// `JAVA_HOME/bin` and `PATH` are tried.
+///
+/// # Errors
+///
+/// * `BuildError::JdkToolNotFound` - If no location has the tool
+pub fn find_jdk_tool(name: &str) -> Result<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+
+    let javac_dir = crate::compiler::JavaCompiler::find_javac()
+        .ok()
+        .and_then(|javac| javac.canonicalize().ok())
+        .and_then(|javac| javac.parent().map(Path::to_path_buf));
+    let java_home_bin = std::env::var_os("JAVA_HOME").map(|home| PathBuf::from(home).join("bin"));
+    let path_dirs = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    javac_dir
+        .into_iter()
+        .chain(java_home_bin)
+        .chain(path_dirs)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| BuildError::JdkToolNotFound {
+            tool: name.to_string(),
+        })
+}
@@ -1,7 +1,12 @@
 //! Test framework detection and execution
 
 use crate::error::Result;
-use std::path::PathBuf;
+use futures::stream::{FuturesUnordered, StreamExt};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 
 /// Supported test frameworks
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,10 +41,12 @@ pub struct TestFailure {
     pub message: String,
     /// Stack trace
     pub stacktrace: String,
+    /// Captured stdout/stderr of the test (empty unless output capture is enabled)
+    pub output: String,
 }
 
 /// Result of test execution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TestResult {
     /// Total number of tests
     pub total: usize,
@@ -53,13 +60,45 @@ pub struct TestResult {
     pub failures: Vec<TestFailure>,
 }
 
+impl TestResult {
+    /// Fold the result of another test worker into this one
+    ///
+    /// Counts are summed and failures are appended in order.
+    pub fn merge(&mut self, other: TestResult) {
+        self.total += other.total;
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.skipped += other.skipped;
+        self.failures.extend(other.failures);
+    }
+
+    /// Count `classes` test classes that were never run as one skipped test each
+    fn skip_classes(&mut self, classes: usize) {
+        self.total += classes;
+        self.skipped += classes;
+    }
+}
+
 /// Test runner for Java projects
 pub struct TestRunner {
     framework: TestFramework,
     classpath: Vec<PathBuf>,
     test_output_dir: PathBuf,
+    explain: bool,
+    parallelism: usize,
+    include_tags: Vec<String>,
+    exclude_tags: Vec<String>,
+    capture_output: bool,
+    console_launcher: Option<PathBuf>,
+    launcher_args: Vec<String>,
+    classes: Vec<String>,
+    env: BTreeMap<String, String>,
+    jvm_args: Vec<String>,
 }
 
+/// Artifact ID of the JUnit Platform console launcher
+pub const JUNIT_CONSOLE_ARTIFACT: &str = "junit-platform-console-standalone";
+
 impl TestRunner {
     /// Create a new test runner
     ///
@@ -91,9 +130,120 @@ impl TestRunner {
             framework,
             classpath,
             test_output_dir,
+            explain: false,
+            parallelism: 1,
+            include_tags: Vec::new(),
+            exclude_tags: Vec::new(),
+            capture_output: false,
+            console_launcher: None,
+            launcher_args: Vec::new(),
+            classes: Vec::new(),
+            env: BTreeMap::new(),
+            jvm_args: Vec::new(),
         }
     }
 
+    /// Print the `java` launch command line before running tests
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Split test classes across `workers` concurrent launcher processes
+    ///
+    /// Values below one are treated as one.
+    pub fn with_parallelism(mut self, workers: usize) -> Self {
+        self.parallelism = workers.max(1);
+        self
+    }
+
+    /// Only run tests carrying one of `include` and none of `exclude` (JUnit 5 `@Tag`)
+    ///
+    /// Other frameworks run every test; see [`Self::ignored_tags_warning`].
+    pub fn with_tags(mut self, include: Vec<String>, exclude: Vec<String>) -> Self {
+        self.include_tags = include;
+        self.exclude_tags = exclude;
+        self
+    }
+
+    /// Capture each test's stdout/stderr and attach it to its [`TestFailure`]
+    ///
+    /// Failures are read from the launcher's XML reports; output of passing
+    /// tests is discarded.
+    pub fn with_output_capture(mut self, capture: bool) -> Self {
+        self.capture_output = capture;
+        self
+    }
+
+    /// Launch JUnit 5 tests with the console launcher JAR at `launcher`
+    ///
+    /// Used when the launcher is not on the test classpath; a launcher found on
+    /// the classpath takes precedence.
+    pub fn with_console_launcher(mut self, launcher: Option<PathBuf>) -> Self {
+        self.console_launcher = launcher;
+        self
+    }
+
+    /// Pass `args` to the JUnit console launcher, before the selected classes
+    pub fn with_launcher_args(mut self, args: Vec<String>) -> Self {
+        self.launcher_args = args;
+        self
+    }
+
+    /// Run exactly these fully qualified test classes
+    ///
+    /// Bypasses test class discovery and naming patterns; each class must be
+    /// compiled into the test output directory.
+    pub fn with_classes(mut self, classes: Vec<String>) -> Self {
+        self.classes = classes;
+        self
+    }
+
+    /// Warning that tag filters were given but the framework cannot apply them
+    ///
+    /// `None` for JUnit 5 or when no tags were given.
+    pub fn ignored_tags_warning(&self) -> Option<String> {
+        if self.framework == TestFramework::JUnit5
+            || (self.include_tags.is_empty() && self.exclude_tags.is_empty())
+        {
+            return None;
+        }
+        Some(format!(
+            "warning: --tag and --exclude-tag only apply to JUnit 5; running all {} tests",
+            self.framework.name()
+        ))
+    }
+
+    /// Set these environment variables on the test JVM, on top of the inherited environment
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Pass `args` to the test JVM, before `-jar`
+    pub fn with_jvm_args(mut self, args: Vec<String>) -> Self {
+        self.jvm_args = args;
+        self
+    }
+
+    /// Whether `classpath` contains the JUnit Platform console launcher
+    pub fn has_console_launcher(classpath: &[PathBuf]) -> bool {
+        classpath
+            .iter()
+            .any(|p| p.to_string_lossy().contains(JUNIT_CONSOLE_ARTIFACT))
+    }
+
+    /// Partition test classes round-robin into at most `workers` non-empty groups
+    pub fn partition_classes(classes: &[String], workers: usize) -> Vec<Vec<String>> {
+        let workers = workers.clamp(1, classes.len().max(1));
+        let mut groups = vec![Vec::new(); workers];
+        for (i, class) in classes.iter().enumerate() {
+            groups[i % workers].push(class.clone());
+        }
+        groups.retain(|g| !g.is_empty());
+        groups
+    }
+
     /// Detect test framework from classpath
     ///
     /// Scans classpath JAR filenames to detect which test framework is available.
@@ -142,13 +292,15 @@ impl TestRunner {
 
     /// Run tests
     ///
-    /// Executes tests using the detected framework.
+    /// Executes tests using the detected framework. JUnit 5 classes go to the
+    /// console launcher; JUnit 4 and TestNG classes are run one JVM per class.
     ///
     /// # Arguments
     ///
     /// * `pattern` - Optional test class pattern filter
     /// * `verbose` - Whether to print verbose output
-    /// * `fail_fast` - Stop on first failure
+    /// * `fail_fast` - Stop on first failure; tests that did not run are
+    ///   counted as skipped
     ///
     /// # Returns
     ///
@@ -157,6 +309,8 @@ impl TestRunner {
     /// # Errors
     ///
     /// * `BuildError::TestExecutionFailed` - If test execution fails
+    /// * `BuildError::TestClassNotFound` - If a class selected with
+    ///   [`Self::with_classes`] was not compiled
     ///
     /// # Example
     ///
@@ -168,9 +322,16 @@ impl TestRunner {
         &self,
         _pattern: Option<String>,
         verbose: bool,
-        _fail_fast: bool,
+        fail_fast: bool,
     ) -> Result<TestResult> {
-        let test_classes = self.find_test_classes()?;
+        if let Some(warning) = self.ignored_tags_warning() {
+            eprintln!("{warning}");
+        }
+        let test_classes = if self.classes.is_empty() {
+            self.find_test_classes()?
+        } else {
+            self.selected_classes()?
+        };
         if test_classes.is_empty() {
             return Ok(TestResult {
                 total: 0,
@@ -182,43 +343,169 @@ impl TestRunner {
         }
 
         match self.framework {
-            TestFramework::JUnit5 => self.run_junit5(&test_classes, verbose).await,
-            // Fallback for others (pending implementation)
-            _ => Ok(TestResult {
-                total: 0,
-                passed: 0,
-                failed: 0,
-                skipped: 0,
-                failures: vec![],
-            }),
+            TestFramework::JUnit5 => self.run_junit5(&test_classes, verbose, fail_fast).await,
+            TestFramework::JUnit4 | TestFramework::TestNG => {
+                Self::run_classes_in_order(&test_classes, fail_fast, |class| async move {
+                    self.run_class(&class, verbose).await
+                })
+                .await
+            }
         }
     }
 
-    async fn run_junit5(&self, test_classes: &[String], verbose: bool) -> Result<TestResult> {
-        // Construct classpath string (test_output_dir is appended here)
-        let mut paths = self.classpath.clone();
-        paths.push(self.test_output_dir.clone());
-        let classpath = crate::format_classpath(&paths);
+    /// Run `run_class` for each of `classes` in order, merging the results
+    ///
+    /// With `fail_fast`, no class is started after the first one with a
+    /// failure. Classes that were never started count as one skipped test
+    /// each, since their tests were never discovered.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from `run_class`.
+    pub async fn run_classes_in_order<F, Fut>(
+        classes: &[String],
+        fail_fast: bool,
+        mut run_class: F,
+    ) -> Result<TestResult>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<TestResult>>,
+    {
+        let mut merged = TestResult::default();
+        for (i, class) in classes.iter().enumerate() {
+            let result = run_class(class.clone()).await?;
+            let failed = result.failed > 0;
+            merged.merge(result);
+            if failed && fail_fast {
+                merged.skip_classes(classes.len() - i - 1);
+                break;
+            }
+        }
+        Ok(merged)
+    }
 
-        let mut args = vec![
-            "-jar".to_string(),
-            self.find_junit_console_launcher()?,
-            "-cp".to_string(),
-            classpath,
-        ];
+    /// Launch a JUnit 4 or TestNG run of a single class
+    async fn run_class(&self, class: &str, verbose: bool) -> Result<TestResult> {
+        let mut args = self.jvm_args.clone();
+        args.extend(["-cp".to_string(), self.test_classpath()]);
+        if self.framework == TestFramework::TestNG {
+            let reports_dir = self.reports_root().join("testng");
+            // Reports of the previous class would be read back as this one's failures
+            if reports_dir.exists() {
+                std::fs::remove_dir_all(&reports_dir)?;
+            }
+            args.extend([
+                "org.testng.TestNG".to_string(),
+                "-d".to_string(),
+                reports_dir.to_string_lossy().to_string(),
+                "-testclass".to_string(),
+                class.to_string(),
+            ]);
+            let output = self.launch(args, verbose).await?;
+            let mut result = Self::parse_testng_output(&String::from_utf8_lossy(&output.stdout));
+            // TestNG's default listeners write JUnit-style reports here
+            let junit_reports = reports_dir.join("junitreports");
+            if junit_reports.is_dir() {
+                result.failures = Self::read_xml_reports(&junit_reports)?;
+            }
+            return Ok(result);
+        }
 
-        // Add test classes
-        for class in test_classes {
-            args.push("-c".to_string());
-            args.push(class.clone());
+        args.extend(["org.junit.runner.JUnitCore".to_string(), class.to_string()]);
+        let output = self.launch(args, verbose).await?;
+        Ok(Self::parse_junit4_output(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Parse the output of `org.junit.runner.JUnitCore`
+    ///
+    /// Reads the `OK (N tests)` or `Tests run: N,  Failures: M` summary and
+    /// each numbered `1) method(Class)` failure with the stack trace below it.
+    pub fn parse_junit4_output(stdout: &str) -> TestResult {
+        let mut result = TestResult::default();
+        let mut current: Option<TestFailure> = None;
+
+        for line in stdout.lines() {
+            if let Some(failure) = parse_junit4_failure_header(line) {
+                result.failures.extend(current.replace(failure));
+            } else if let Some(failure) = current.as_mut() {
+                if line.trim().is_empty() {
+                    result.failures.extend(current.take());
+                } else {
+                    if failure.stacktrace.is_empty() {
+                        failure.message = line
+                            .split_once(": ")
+                            .map_or(line, |(_, message)| message)
+                            .to_string();
+                    } else {
+                        failure.stacktrace.push('\n');
+                    }
+                    failure.stacktrace.push_str(line);
+                }
+            } else if let Some(rest) = line.strip_prefix("OK (") {
+                result.total = leading_number(rest);
+            } else if let Some(rest) = line.strip_prefix("Tests run: ") {
+                result.total = leading_number(rest);
+                result.failed = rest
+                    .split_once("Failures: ")
+                    .map_or(0, |(_, failures)| leading_number(failures));
+            }
         }
+        result.failures.extend(current);
+
+        result.passed = result.total.saturating_sub(result.failed);
+        result
+    }
 
-        // Output is captured and can be printed by caller if needed,
-        // or just rely on the test process stdout for tree structure visualization.
+    /// Parse the `Total tests run: N, Passes: P, Failures: F, Skips: S` summary of TestNG
+    pub fn parse_testng_output(stdout: &str) -> TestResult {
+        let mut result = TestResult::default();
+        let Some(summary) = stdout
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Total tests run: "))
+        else {
+            return result;
+        };
 
-        let output = std::process::Command::new("java")
-            .args(&args)
+        result.total = leading_number(summary);
+        for field in summary.split(", ").skip(1) {
+            match field.split_once(": ") {
+                Some(("Failures", n)) => result.failed = leading_number(n),
+                Some(("Skips", n)) => result.skipped = leading_number(n),
+                _ => {}
+            }
+        }
+        result.passed = result.total.saturating_sub(result.failed + result.skipped);
+        result
+    }
+
+    /// Directory the launchers write their XML reports under
+    fn reports_root(&self) -> PathBuf {
+        self.test_output_dir
+            .parent()
+            .unwrap_or(&self.test_output_dir)
+            .join("test-reports")
+    }
+
+    /// Runtime classpath plus the compiled test classes, as a `-cp` argument
+    fn test_classpath(&self) -> String {
+        let mut paths = crate::Classpath::from(self.classpath.as_slice());
+        paths.push(&self.test_output_dir);
+        paths.to_arg_string()
+    }
+
+    /// Run `java` with `args` and the test environment, echoing its output if `verbose`
+    async fn launch(&self, args: Vec<String>, verbose: bool) -> Result<std::process::Output> {
+        let mut cmd = std::process::Command::new("java");
+        cmd.args(&args).envs(&self.env);
+        if self.explain {
+            println!("$ {}", crate::format_command(&cmd));
+        }
+        let output = tokio::process::Command::from(cmd)
+            .kill_on_drop(true)
             .output()
+            .await
             .map_err(crate::error::BuildError::Io)?;
 
         // Only print raw output if it's the tree structure we want
@@ -232,10 +519,233 @@ impl TestRunner {
                 println!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
             }
         }
+        Ok(output)
+    }
+
+    async fn run_junit5(
+        &self,
+        test_classes: &[String],
+        verbose: bool,
+        fail_fast: bool,
+    ) -> Result<TestResult> {
+        let groups = Self::partition_classes(test_classes, self.parallelism);
+        let reports_root = self.reports_root();
+        if groups.len() <= 1 {
+            return self
+                .run_junit5_worker(test_classes, &reports_root, verbose, fail_fast)
+                .await;
+        }
+
+        // Each worker gets its own reports directory so concurrent launchers
+        // don't overwrite each other's XML reports
+
+        let mut workers: FuturesUnordered<_> = groups
+            .iter()
+            .enumerate()
+            .map(|(i, group)| {
+                let reports_dir = reports_root.join(format!("worker-{}", i));
+                async move {
+                    let result = self
+                        .run_junit5_worker(group, &reports_dir, verbose, fail_fast)
+                        .await;
+                    (i, result)
+                }
+            })
+            .collect();
+
+        let mut merged = TestResult::default();
+        let mut finished = vec![false; groups.len()];
+        while let Some((i, result)) = workers.next().await {
+            let result = result?;
+            finished[i] = true;
+            let failed = result.failed > 0;
+            merged.merge(result);
+            if failed && fail_fast {
+                // Dropping the pending futures kills their launcher processes
+                let unfinished = groups
+                    .iter()
+                    .zip(&finished)
+                    .filter(|(_, finished)| !**finished)
+                    .map(|(group, _)| group.len())
+                    .sum();
+                merged.skip_classes(unfinished);
+                break;
+            }
+        }
+
+        Ok(merged)
+    }
+
+    async fn run_junit5_worker(
+        &self,
+        test_classes: &[String],
+        reports_dir: &Path,
+        verbose: bool,
+        fail_fast: bool,
+    ) -> Result<TestResult> {
+        let launcher = self.find_junit_console_launcher()?;
+        let mut args = self.jvm_args.clone();
+        args.extend([
+            "-jar".to_string(),
+            launcher.clone(),
+            "-cp".to_string(),
+            self.test_classpath(),
+        ]);
+
+        // Older launchers reject the option, so they run the whole group
+        if fail_fast && launcher_supports_fail_fast(&launcher) {
+            args.push("--fail-fast".to_string());
+        }
+
+        // Failure details are only available through the XML reports; stale
+        // reports from an earlier run would be read back as failures
+        if reports_dir.exists() {
+            std::fs::remove_dir_all(reports_dir)?;
+        }
+        std::fs::create_dir_all(reports_dir)?;
+        args.push("--reports-dir".to_string());
+        args.push(reports_dir.to_string_lossy().to_string());
+
+        if self.capture_output {
+            args.push("--config=junit.platform.output.capture.stdout=true".to_string());
+            args.push("--config=junit.platform.output.capture.stderr=true".to_string());
+        }
+
+        for tag in &self.include_tags {
+            args.push("--include-tag".to_string());
+            args.push(tag.clone());
+        }
+        for tag in &self.exclude_tags {
+            args.push("--exclude-tag".to_string());
+            args.push(tag.clone());
+        }
+
+        args.extend(self.launcher_args.iter().cloned());
+
+        // Add test classes
+        for class in test_classes {
+            args.push("-c".to_string());
+            args.push(class.clone());
+        }
+
+        let output = self.launch(args, verbose).await?;
+
+        let mut result = Self::parse_junit5_summary(&String::from_utf8_lossy(&output.stdout));
+        result.failures = Self::read_xml_reports(reports_dir)?;
+        Ok(result)
+    }
+
+    /// Collect failures from every `*.xml` report in `reports_dir`, in file name order
+    ///
+    /// # Errors
+    ///
+    /// * `BuildError::Io` - If the directory or a report cannot be read
+    pub fn read_xml_reports(reports_dir: &Path) -> Result<Vec<TestFailure>> {
+        let mut failures = Vec::new();
+        let mut reports: Vec<PathBuf> = std::fs::read_dir(reports_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+            .collect();
+        reports.sort();
+        for report in reports {
+            failures.extend(Self::parse_junit_xml_failures(&std::fs::read_to_string(
+                report,
+            )?));
+        }
+        Ok(failures)
+    }
+
+    /// Parse failed and errored test cases from a JUnit XML report
+    ///
+    /// Each `<testcase>` with a `<failure>` or `<error>` becomes a
+    /// [`TestFailure`]; its `<system-out>`/`<system-err>` text is kept as the
+    /// captured output, minus the `unique-id:`/`display-name:` lines the
+    /// JUnit Platform reporter adds.
+    pub fn parse_junit_xml_failures(xml: &str) -> Vec<TestFailure> {
+        let mut reader = Reader::from_str(xml);
+        let mut failures = Vec::new();
+
+        let mut current: Option<TestFailure> = None;
+        let mut failed = false;
+        let mut element = String::new();
+        let mut text = String::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    let attr = |key: &str| {
+                        e.attributes()
+                            .filter_map(|a| a.ok())
+                            .find(|a| a.key.as_ref() == key.as_bytes())
+                            .and_then(|a| a.unescape_value().ok())
+                            .map(|v| v.to_string())
+                            .unwrap_or_default()
+                    };
+                    match name.as_str() {
+                        "testcase" => {
+                            failed = false;
+                            current = Some(TestFailure {
+                                class_name: attr("classname"),
+                                test_name: attr("name"),
+                                message: String::new(),
+                                stacktrace: String::new(),
+                                output: String::new(),
+                            });
+                        }
+                        "failure" | "error" => {
+                            failed = true;
+                            if let Some(failure) = current.as_mut() {
+                                failure.message = attr("message");
+                            }
+                        }
+                        _ => {}
+                    }
+                    element = name;
+                    text.clear();
+                }
+                Ok(Event::Text(e)) => {
+                    text.push_str(&e.unescape().unwrap_or_default());
+                }
+                Ok(Event::CData(e)) => {
+                    text.push_str(&String::from_utf8_lossy(&e));
+                }
+                Ok(Event::End(e)) => {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    match (name.as_str(), current.as_mut()) {
+                        ("failure" | "error", Some(failure)) if element == name => {
+                            failure.stacktrace = text.trim().to_string();
+                        }
+                        ("system-out" | "system-err", Some(failure)) => {
+                            for line in text.lines().filter(|line| {
+                                !line.starts_with("unique-id: ")
+                                    && !line.starts_with("display-name: ")
+                            }) {
+                                failure.output.push_str(line);
+                                failure.output.push('\n');
+                            }
+                        }
+                        ("testcase", _) => {
+                            if let Some(mut failure) = current.take()
+                                && failed
+                            {
+                                failure.output = failure.output.trim().to_string();
+                                failures.push(failure);
+                            }
+                        }
+                        _ => {}
+                    }
+                    text.clear();
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+        }
 
-        // Parse output
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        failures
+    }
 
+    fn parse_junit5_summary(stdout: &str) -> TestResult {
         let passed = stdout
             .lines()
             .find(|l| l.contains("tests successful"))
@@ -260,21 +770,20 @@ impl TestRunner {
             passed + failed
         };
 
-        Ok(TestResult {
+        TestResult {
             total,
             passed,
             failed,
             skipped: total.saturating_sub(passed + failed),
-            failures: vec![], // Details would require XML report parsing
-        })
+            failures: vec![], // Filled in from the XML reports
+        }
     }
 
     fn find_junit_console_launcher(&self) -> Result<String> {
-        // Ideally this should be resolved from dependencies or bundled
-        // For Phase 3 MVP, we'll try to find it in the classpath
         self.classpath
             .iter()
-            .find(|p| p.to_string_lossy().contains("junit-platform-console-standalone"))
+            .find(|p| p.to_string_lossy().contains(JUNIT_CONSOLE_ARTIFACT))
+            .or(self.console_launcher.as_ref())
             .map(|p| p.to_string_lossy().to_string())
             .ok_or_else(|| crate::error::BuildError::TestExecutionFailed {
                 message: "JUnit Platform Console Standalone JAR not found in classpath. Please add 'org.junit.platform:junit-platform-console-standalone' dependency.".to_string()
@@ -283,10 +792,42 @@ impl TestRunner {
 
     /// Find all test classes in test output directory
     fn find_test_classes(&self) -> Result<Vec<String>> {
+        Ok(self
+            .compiled_classes()
+            .into_iter()
+            .filter(|class_name| self.matches_test_pattern(class_name))
+            .collect())
+    }
+
+    /// Classes selected with [`Self::with_classes`], checked against the test output directory
+    fn selected_classes(&self) -> Result<Vec<String>> {
+        for class in &self.classes {
+            let path = self
+                .test_output_dir
+                .join(format!("{}.class", class.replace('.', "/")));
+            if !path.is_file() {
+                let simple_name = class.rsplit('.').next().unwrap_or(class);
+                let similar = self
+                    .compiled_classes()
+                    .into_iter()
+                    .filter(|c| c.rsplit('.').next() == Some(simple_name))
+                    .collect();
+                return Err(crate::error::BuildError::TestClassNotFound {
+                    class: class.clone(),
+                    test_output_dir: self.test_output_dir.display().to_string(),
+                    similar,
+                });
+            }
+        }
+        Ok(self.classes.clone())
+    }
+
+    /// Fully qualified names of every class in the test output directory, sorted
+    fn compiled_classes(&self) -> Vec<String> {
         let mut classes = Vec::new();
 
         if !self.test_output_dir.exists() {
-            return Ok(classes);
+            return classes;
         }
 
         for entry in walkdir::WalkDir::new(&self.test_output_dir)
@@ -297,19 +838,17 @@ impl TestRunner {
             if path.extension().and_then(|s| s.to_str()) == Some("class") {
                 // Convert file path to fully qualified class name
                 let relative = path.strip_prefix(&self.test_output_dir).unwrap_or(path);
-                let class_name = relative
-                    .to_string_lossy()
-                    .replace(std::path::MAIN_SEPARATOR, ".")
-                    .replace(".class", "");
-
-                // Filter by naming patterns
-                if self.matches_test_pattern(&class_name) {
-                    classes.push(class_name);
-                }
+                classes.push(
+                    relative
+                        .to_string_lossy()
+                        .replace(std::path::MAIN_SEPARATOR, ".")
+                        .replace(".class", ""),
+                );
             }
         }
 
-        Ok(classes)
+        classes.sort();
+        classes
     }
 
     /// Check if class name matches test patterns
@@ -321,3 +860,38 @@ impl TestRunner {
             || class_name.starts_with("Test")
     }
 }
+
+/// `1) method(com.example.Class)` heading of a JUnit 4 failure
+fn parse_junit4_failure_header(line: &str) -> Option<TestFailure> {
+    let (number, test) = line.split_once(") ")?;
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (test_name, class_name) = test.strip_suffix(')')?.split_once('(')?;
+    Some(TestFailure {
+        class_name: class_name.to_string(),
+        test_name: test_name.to_string(),
+        message: String::new(),
+        stacktrace: String::new(),
+        output: String::new(),
+    })
+}
+
+/// Number at the start of `text`, or 0
+fn leading_number(text: &str) -> usize {
+    text.trim_start()
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Whether the console launcher JAR at `launcher` understands `--fail-fast` (JUnit 6 and later)
+fn launcher_supports_fail_fast(launcher: &str) -> bool {
+    Path::new(launcher)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.strip_prefix(JUNIT_CONSOLE_ARTIFACT))
+        .and_then(|version| version.strip_prefix('-'))
+        .is_some_and(|version| leading_number(version) >= 6)
+}
@@ -0,0 +1,54 @@
+//! Wall-clock timings of the individual build phases
+
+use std::fmt;
+use std::time::Duration;
+
+/// A phase of [`BuildOrchestrator::build`](crate::BuildOrchestrator::build)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildPhase {
+    /// Dependency resolution (or reading `polytunnel.lock` with `--frozen`)
+    Resolve,
+    /// Downloading or locating dependency JARs in the cache
+    Download,
+    /// Compiling main sources
+    CompileMain,
+    /// Compiling test sources
+    CompileTest,
+    /// Running the test framework
+    RunTests,
+}
+
+impl BuildPhase {
+    /// Short kebab-case name, as shown in the `--timings` report
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildPhase::Resolve => "resolve",
+            BuildPhase::Download => "download",
+            BuildPhase::CompileMain => "compile-main",
+            BuildPhase::CompileTest => "compile-test",
+            BuildPhase::RunTests => "run-tests",
+        }
+    }
+}
+
+impl fmt::Display for BuildPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Duration of one build phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseTiming {
+    /// The phase that was timed
+    pub phase: BuildPhase,
+    /// Wall-clock time spent in the phase
+    pub duration: Duration,
+}
+
+impl PhaseTiming {
+    /// Create a timing entry
+    pub fn new(phase: BuildPhase, duration: Duration) -> Self {
+        Self { phase, duration }
+    }
+}
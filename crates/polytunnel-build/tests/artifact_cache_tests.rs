@@ -0,0 +1,166 @@
+//! Tests for artifact cache inspection, clean, and prune
+
+use polytunnel_build::ArtifactCache;
+use polytunnel_maven::Coordinate;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tempfile::tempdir;
+
+fn write_artifact(cache: &ArtifactCache, coord: &Coordinate, size: usize, age: Duration) {
+    let path = cache.artifact_path(coord);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, vec![0u8; size]).unwrap();
+    let file = fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() - age).unwrap();
+}
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[test]
+fn test_info_reports_size_and_artifact_count() {
+    let dir = tempdir().unwrap();
+    let cache = ArtifactCache::new(dir.path());
+    write_artifact(
+        &cache,
+        &Coordinate::new("org.a", "a", "1.0"),
+        100,
+        Duration::ZERO,
+    );
+    write_artifact(
+        &cache,
+        &Coordinate::new("org.b", "b", "2.0"),
+        50,
+        Duration::ZERO,
+    );
+    fs::write(dir.path().join("build-cache.json"), "{}").unwrap();
+
+    let info = cache.info().unwrap();
+    assert_eq!(info.location, dir.path());
+    assert_eq!(info.artifact_count, 2);
+    assert_eq!(info.total_size, 152);
+}
+
+#[test]
+fn test_info_on_missing_cache_is_empty() {
+    let dir = tempdir().unwrap();
+    let cache = ArtifactCache::new(dir.path().join("absent"));
+
+    let info = cache.info().unwrap();
+    assert_eq!(info.artifact_count, 0);
+    assert_eq!(info.total_size, 0);
+}
+
+#[test]
+fn test_clean_removes_cache_directory() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("cache");
+    let cache = ArtifactCache::new(&root);
+    write_artifact(
+        &cache,
+        &Coordinate::new("org.a", "a", "1.0"),
+        10,
+        Duration::ZERO,
+    );
+
+    let cleanup = cache.clean().unwrap();
+    assert_eq!(cleanup.removed, 1);
+    assert_eq!(cleanup.freed_bytes, 10);
+    assert!(!root.exists());
+}
+
+#[test]
+fn test_prune_removes_only_old_artifacts() {
+    let dir = tempdir().unwrap();
+    let cache = ArtifactCache::new(dir.path());
+    let old = Coordinate::new("org.old", "old", "1.0");
+    let fresh = Coordinate::new("org.fresh", "fresh", "1.0");
+    write_artifact(&cache, &old, 10, 40 * DAY);
+    write_artifact(&cache, &fresh, 10, DAY);
+
+    let cleanup = cache.prune(30 * DAY, &[]).unwrap();
+    assert_eq!(cleanup.removed, 1);
+    assert!(!cache.artifact_path(&old).exists());
+    assert!(cache.artifact_path(&fresh).exists());
+    assert!(
+        !dir.path().join("org").join("old").exists(),
+        "empty directories left by pruning should be removed"
+    );
+}
+
+#[test]
+fn test_prune_keeps_locked_artifacts() {
+    let dir = tempdir().unwrap();
+    let cache = ArtifactCache::new(dir.path());
+    let locked = Coordinate::new("org.locked", "locked", "1.0");
+    write_artifact(&cache, &locked, 10, 90 * DAY);
+    let version_dir = cache.artifact_path(&locked).parent().unwrap().to_path_buf();
+    let pom = version_dir.join("locked-1.0.pom");
+    let checksum = version_dir.join("locked-1.0.jar.sha1");
+    for path in [&pom, &checksum] {
+        fs::write(path, "x").unwrap();
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - 90 * DAY).unwrap();
+    }
+
+    let cleanup = cache
+        .prune(30 * DAY, std::slice::from_ref(&locked))
+        .unwrap();
+    assert_eq!(cleanup.removed, 0);
+    assert!(cache.artifact_path(&locked).exists());
+    assert!(pom.exists());
+    assert!(checksum.exists());
+}
+
+#[test]
+fn test_prune_never_touches_build_metadata() {
+    let dir = tempdir().unwrap();
+    let cache = ArtifactCache::new(dir.path());
+    let metadata = dir.path().join("build-cache.json");
+    fs::write(&metadata, "{}").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&metadata)
+        .unwrap()
+        .set_modified(SystemTime::now() - 90 * DAY)
+        .unwrap();
+
+    cache.prune(30 * DAY, &[]).unwrap();
+    assert!(Path::new(&metadata).exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_info_and_prune_account_for_deduplicated_content() {
+    use polytunnel_maven::{ArtifactStore, FileSystemStore, jar_key};
+
+    let dir = tempdir().unwrap();
+    let cache = ArtifactCache::new(dir.path());
+    let store = FileSystemStore::new(dir.path()).with_dedup(true);
+    let old = Coordinate::new("org.old", "lib", "1.0");
+    let relocated = Coordinate::new("org.new", "lib", "1.0");
+    store.write(&jar_key(&old), &[1u8; 100]).unwrap();
+    store.write(&jar_key(&relocated), &[1u8; 100]).unwrap();
+
+    let info = cache.info().unwrap();
+    assert_eq!(info.artifact_count, 2);
+    assert_eq!(info.total_size, 100);
+
+    // Pruning one link frees nothing while the other still uses the content
+    let file = fs::File::options()
+        .write(true)
+        .open(cache.artifact_path(&old))
+        .unwrap();
+    file.set_modified(SystemTime::now() - 40 * DAY).unwrap();
+    let cleanup = cache
+        .prune(30 * DAY, std::slice::from_ref(&relocated))
+        .unwrap();
+    assert_eq!(cleanup.removed, 1);
+    assert_eq!(cleanup.freed_bytes, 0);
+    assert!(store.content_path(&[1u8; 100]).is_file());
+
+    let cleanup = cache.prune(Duration::ZERO, &[]).unwrap();
+    assert_eq!(cleanup.removed, 1);
+    assert_eq!(cleanup.freed_bytes, 100);
+    assert!(!dir.path().join(polytunnel_maven::CONTENT_DIR).exists());
+}
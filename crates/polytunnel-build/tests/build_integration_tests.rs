@@ -2,7 +2,7 @@
 //!
 //! Tests actual build functionality using public API only.
 
-use polytunnel_build::{BuildOptions, BuildOrchestrator};
+use polytunnel_build::{BuildError, BuildOptions, BuildOrchestrator};
 use polytunnel_core::{
     BuildConfig, Dependency, DependencyScope, ProjectConfig, ProjectInfo, Repository,
 };
@@ -17,6 +17,13 @@ fn create_test_config() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -75,6 +82,8 @@ fn test_orchestrator_creation_with_dependencies() {
             version: "5.2.0".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -89,10 +98,18 @@ fn test_orchestrator_creation_with_repositories() {
         Repository {
             name: "central".to_string(),
             url: "https://repo1.maven.org/maven2/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         Repository {
             name: "jcenter".to_string(),
             url: "https://jcenter.bintray.com/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
@@ -104,8 +121,18 @@ fn test_orchestrator_creation_with_repositories() {
 fn test_build_options_defaults() {
     let options = BuildOptions {
         clean: false,
+        no_incremental: false,
         skip_tests: false,
         verbose: false,
+        explain: false,
+        compiler_args: Vec::new(),
+        test_compiler_args: Vec::new(),
+        only: Vec::new(),
+        keep_going: false,
+        fail_on_warning: false,
+        scope: Default::default(),
+        offline: false,
+        test: Default::default(),
     };
 
     assert!(!options.clean);
@@ -117,8 +144,18 @@ fn test_build_options_defaults() {
 fn test_build_options_all_enabled() {
     let options = BuildOptions {
         clean: true,
+        no_incremental: false,
         skip_tests: true,
         verbose: true,
+        explain: false,
+        compiler_args: Vec::new(),
+        test_compiler_args: Vec::new(),
+        only: Vec::new(),
+        keep_going: false,
+        fail_on_warning: false,
+        scope: Default::default(),
+        offline: false,
+        test: Default::default(),
     };
 
     assert!(options.clean);
@@ -131,9 +168,12 @@ fn test_orchestrator_preserves_java_version() {
     let mut config = create_test_config();
     config.project.java_version = "21".to_string();
 
-    let orchestrator = BuildOrchestrator::new(config).unwrap();
-    // Orchestrator should be created successfully with Java 21 config
-    drop(orchestrator);
+    // Java 21 config is accepted unless the installed JDK is older than 21
+    match BuildOrchestrator::new(config) {
+        Ok(orchestrator) => assert_eq!(orchestrator.config.project.java_version, "21"),
+        Err(BuildError::JdkTooOld { required, .. }) => assert_eq!(required, 21),
+        Err(other) => panic!("unexpected error: {other:?}"),
+    }
 }
 
 #[test]
@@ -146,6 +186,8 @@ fn test_orchestrator_creation_with_all_dependency_scopes() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Compile,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
     config.dependencies.insert(
@@ -154,6 +196,8 @@ fn test_orchestrator_creation_with_all_dependency_scopes() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
     config.dependencies.insert(
@@ -162,6 +206,8 @@ fn test_orchestrator_creation_with_all_dependency_scopes() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Runtime,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
     config.dependencies.insert(
@@ -170,6 +216,8 @@ fn test_orchestrator_creation_with_all_dependency_scopes() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Provided,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -186,6 +234,8 @@ fn test_orchestrator_creation_with_optional_dependency() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Compile,
             optional: true,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -15,6 +15,13 @@ fn create_test_config() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -120,6 +127,10 @@ fn test_orchestrator_repository_handling() {
     config.repositories = vec![polytunnel_core::Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     }];
 
     let result = BuildOrchestrator::new(config.clone());
@@ -134,10 +145,18 @@ fn test_orchestrator_multiple_repositories() {
         polytunnel_core::Repository {
             name: "central".to_string(),
             url: "https://repo1.maven.org/maven2/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         polytunnel_core::Repository {
             name: "custom".to_string(),
             url: "https://custom.example.com/repo/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
@@ -166,8 +185,12 @@ fn test_orchestrator_java_version_validation() {
         let mut config = create_test_config();
         config.project.java_version = version.to_string();
 
+        // Versions newer than the installed JDK are rejected up front
         let result = BuildOrchestrator::new(config.clone());
-        assert!(result.is_ok());
+        assert!(matches!(
+            result,
+            Ok(_) | Err(polytunnel_build::BuildError::JdkTooOld { .. })
+        ));
         assert_eq!(config.project.java_version, version);
     }
 }
@@ -259,6 +282,8 @@ fn test_orchestrator_dependency_scope_handling() {
             version: "5.10.0".to_string(),
             scope: polytunnel_core::DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -275,6 +300,8 @@ fn test_orchestrator_optional_dependency_handling() {
             version: "1.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Compile,
             optional: true,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -291,6 +318,8 @@ fn test_orchestrator_compile_scope_dependencies() {
             version: "6.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Compile,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -307,6 +336,8 @@ fn test_orchestrator_test_scope_dependencies() {
             version: "5.10.0".to_string(),
             scope: polytunnel_core::DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -323,6 +354,8 @@ fn test_orchestrator_runtime_scope_dependencies() {
             version: "5.0.1".to_string(),
             scope: polytunnel_core::DependencyScope::Runtime,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -339,6 +372,8 @@ fn test_orchestrator_provided_scope_dependencies() {
             version: "2.5".to_string(),
             scope: polytunnel_core::DependencyScope::Provided,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -444,6 +479,8 @@ fn test_orchestrator_with_mixed_dependency_scopes() {
             version: "6.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Compile,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -453,6 +490,8 @@ fn test_orchestrator_with_mixed_dependency_scopes() {
             version: "5.10.0".to_string(),
             scope: polytunnel_core::DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -462,6 +501,8 @@ fn test_orchestrator_with_mixed_dependency_scopes() {
             version: "5.0.1".to_string(),
             scope: polytunnel_core::DependencyScope::Runtime,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -15,6 +15,13 @@ fn create_test_config() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
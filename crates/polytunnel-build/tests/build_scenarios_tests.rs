@@ -14,6 +14,13 @@ fn create_test_config() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -64,6 +71,8 @@ fn test_scenario_build_with_test_dependencies() {
             version: "5.10.0".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -80,6 +89,8 @@ fn test_scenario_build_with_provided_dependencies() {
             version: "2.5".to_string(),
             scope: DependencyScope::Provided,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -96,6 +107,8 @@ fn test_scenario_build_with_runtime_dependencies() {
             version: "42.5.0".to_string(),
             scope: DependencyScope::Runtime,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -112,6 +125,8 @@ fn test_scenario_build_with_optional_dependencies() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Compile,
             optional: true,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -232,11 +247,19 @@ fn test_scenario_build_with_multiple_repositories() {
     config.repositories.push(polytunnel_core::Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     });
 
     config.repositories.push(polytunnel_core::Repository {
         name: "custom".to_string(),
         url: "https://custom.example.com/repo/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     });
 
     assert_eq!(config.repositories.len(), 2);
@@ -379,6 +402,8 @@ fn test_scenario_build_with_test_dependencies_only() {
             version: "4.13.2".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -397,6 +422,8 @@ fn test_scenario_build_with_compile_and_test_dependencies() {
             version: "6.0.0".to_string(),
             scope: DependencyScope::Compile,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -406,6 +433,8 @@ fn test_scenario_build_with_compile_and_test_dependencies() {
             version: "4.13.2".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -0,0 +1,137 @@
+//! Tests for dependency scopes in classpath construction, including resolving
+//! only the compile classpath (`pt build --scope compile`).
+
+mod common;
+
+use common::{MockTransport, dependency};
+use polytunnel_build::{BuildScope, ClasspathBuilder};
+use polytunnel_core::{Dependency, DependencyScope, ProjectConfig};
+use polytunnel_maven::{MavenClient, MemoryArtifactStore};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// `lib` -> `core` and `junit` -> `hamcrest`, plus the leaves `api` and `driver`
+fn client() -> MavenClient {
+    MockTransport::default()
+        .artifact("lib", &dependency("core", ""))
+        .artifact("core", "")
+        .artifact("api", "")
+        .artifact("driver", "")
+        .artifact("junit", &dependency("hamcrest", ""))
+        .artifact("hamcrest", "")
+        .client()
+}
+
+fn detailed(scope: DependencyScope) -> Dependency {
+    Dependency::Simple("1.0".to_string()).with_scope(scope)
+}
+
+fn config() -> ProjectConfig {
+    let mut config = ProjectConfig::new("scoped");
+    config.dependencies.extend([
+        (
+            "com.example:lib".to_string(),
+            detailed(DependencyScope::Compile),
+        ),
+        (
+            "com.example:api".to_string(),
+            detailed(DependencyScope::Provided),
+        ),
+        (
+            "com.example:driver".to_string(),
+            detailed(DependencyScope::Runtime),
+        ),
+        (
+            "com.example:junit".to_string(),
+            detailed(DependencyScope::Test),
+        ),
+    ]);
+    config
+}
+
+async fn fetched_artifacts(compile_only: bool) -> Vec<String> {
+    let temp = tempdir().unwrap();
+    let mut builder = ClasspathBuilder::new(config())
+        .with_maven_client(client())
+        .with_artifact_store(Arc::new(MemoryArtifactStore::new()));
+    builder.set_compile_only(compile_only);
+
+    builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+    let mut artifacts: Vec<_> = builder
+        .cache_report()
+        .fetches
+        .iter()
+        .map(|fetch| fetch.coordinate.artifact_id.clone())
+        .collect();
+    artifacts.sort();
+    artifacts
+}
+
+#[tokio::test]
+async fn test_compile_only_does_not_download_test_or_runtime_dependencies() {
+    assert_eq!(fetched_artifacts(true).await, ["api", "core", "lib"]);
+}
+
+#[tokio::test]
+async fn test_full_resolution_downloads_every_scope() {
+    assert_eq!(
+        fetched_artifacts(false).await,
+        ["api", "core", "driver", "hamcrest", "junit", "lib"]
+    );
+}
+
+#[tokio::test]
+async fn test_transitive_dependencies_take_the_scope_of_their_root() {
+    let temp = tempdir().unwrap();
+    let mut builder = ClasspathBuilder::new(config())
+        .with_maven_client(client())
+        .with_artifact_store(Arc::new(MemoryArtifactStore::new()));
+
+    let result = builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+    let placement = |artifact: &str| {
+        let entry = builder
+            .classpath_entries()
+            .iter()
+            .find(|entry| entry.coordinate.artifact_id == artifact)
+            .unwrap();
+        (
+            entry.scope,
+            result.compile_classpath.contains(&entry.path),
+            result.runtime_classpath.contains(&entry.path),
+            result.test_classpath.contains(&entry.path),
+        )
+    };
+
+    // `junit` is a test root: its transitive `hamcrest` is only for tests
+    assert_eq!(
+        placement("hamcrest"),
+        (polytunnel_maven::DependencyScope::Test, false, false, true)
+    );
+    // `lib` is a compile root: its transitive `core` is everywhere
+    assert_eq!(
+        placement("core"),
+        (polytunnel_maven::DependencyScope::Compile, true, true, true)
+    );
+    assert_eq!(
+        placement("driver"),
+        (
+            polytunnel_maven::DependencyScope::Runtime,
+            false,
+            true,
+            true
+        )
+    );
+}
+
+#[test]
+fn test_build_scope_includes_tests() {
+    assert!(!BuildScope::Compile.includes_tests());
+    assert!(BuildScope::All.includes_tests());
+    assert_eq!(BuildScope::default(), BuildScope::All);
+}
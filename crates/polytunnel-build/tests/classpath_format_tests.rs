@@ -0,0 +1,71 @@
+//! Tests for the `Classpath` newtype and platform separator handling
+
+use polytunnel_build::{Classpath, classpath_separator, format_classpath};
+use std::path::PathBuf;
+
+#[test]
+fn test_separator_matches_platform() {
+    if cfg!(windows) {
+        assert_eq!(classpath_separator(), ';');
+    } else {
+        assert_eq!(classpath_separator(), ':');
+    }
+}
+
+#[test]
+fn test_join_with_unix_separator() {
+    let classpath: Classpath = ["/lib/a.jar", "/lib/b.jar"].into_iter().collect();
+    assert_eq!(classpath.join_with(':'), "/lib/a.jar:/lib/b.jar");
+}
+
+#[test]
+fn test_join_with_windows_separator() {
+    let classpath: Classpath = [r"C:\lib\a.jar", r"C:\lib\b.jar"].into_iter().collect();
+    assert_eq!(classpath.join_with(';'), r"C:\lib\a.jar;C:\lib\b.jar");
+}
+
+#[test]
+fn test_dedup_preserves_first_position() {
+    let classpath: Classpath = [
+        "target/classes",
+        "/lib/a.jar",
+        "target/classes",
+        "/lib/b.jar",
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        classpath.entries(),
+        &[
+            PathBuf::from("target/classes"),
+            PathBuf::from("/lib/a.jar"),
+            PathBuf::from("/lib/b.jar"),
+        ]
+    );
+}
+
+#[test]
+fn test_empty_paths_are_ignored() {
+    let mut classpath = Classpath::new();
+    classpath.push("");
+    classpath.push(PathBuf::new());
+    assert!(classpath.is_empty());
+    assert_eq!(classpath.to_arg_string(), "");
+
+    classpath.push("/lib/a.jar");
+    assert_eq!(classpath.to_arg_string(), "/lib/a.jar");
+}
+
+#[test]
+fn test_format_classpath_uses_classpath_rules() {
+    let paths = vec![
+        PathBuf::from("/lib/a.jar"),
+        PathBuf::new(),
+        PathBuf::from("/lib/a.jar"),
+        PathBuf::from("/lib/b.jar"),
+    ];
+
+    let expected = format!("/lib/a.jar{}/lib/b.jar", classpath_separator());
+    assert_eq!(format_classpath(&paths), expected);
+}
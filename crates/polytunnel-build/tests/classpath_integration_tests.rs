@@ -1,9 +1,11 @@
-use polytunnel_build::ClasspathBuilder;
+use polytunnel_build::{ArtifactFetch, ArtifactSource, CacheReport, ClasspathBuilder};
 use polytunnel_core::{BuildConfig, Dependency, ProjectConfig, ProjectInfo};
-use polytunnel_maven::Coordinate;
+use polytunnel_maven::{ArtifactStore, Coordinate, DependencyScope, MemoryArtifactStore, jar_key};
 use std::collections::HashMap;
 use std::fs;
 use std::io::ErrorKind;
+use std::sync::Arc;
+use std::time::Duration;
 use tempfile::tempdir;
 
 fn config_with_invalid_dependency() -> ProjectConfig {
@@ -22,6 +24,13 @@ fn config_with_invalid_dependency() -> ProjectConfig {
             deps
         },
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -39,6 +48,8 @@ fn config_with_scoped_dependencies() -> ProjectConfig {
             version: "1.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Provided,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -48,6 +59,8 @@ fn config_with_scoped_dependencies() -> ProjectConfig {
             version: "1.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Runtime,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -57,6 +70,8 @@ fn config_with_scoped_dependencies() -> ProjectConfig {
             version: "1.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -66,6 +81,8 @@ fn config_with_scoped_dependencies() -> ProjectConfig {
             version: "1.0.0".to_string(),
             scope: polytunnel_core::DependencyScope::Compile,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -77,6 +94,13 @@ fn config_with_scoped_dependencies() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies,
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -159,6 +183,35 @@ async fn test_build_classpath_from_resolved_tree_handles_all_scopes() {
     assert_eq!(cached.runtime_classpath.len(), 4);
 }
 
+#[tokio::test]
+async fn test_classpath_entries_record_coordinate_and_scope() {
+    let mut builder = ClasspathBuilder::new(config_with_scoped_dependencies());
+    let temp = tempdir().unwrap();
+    let cache_dir = temp.path().join("cache").to_string_lossy().to_string();
+
+    let compile = Coordinate::parse("com.example:compile-lib:1.0.0").unwrap();
+    let runtime = Coordinate::parse("com.example:runtime-lib:1.0.0").unwrap();
+    touch_cached_dependency(temp.path().join("cache").as_path(), &compile);
+    touch_cached_dependency(temp.path().join("cache").as_path(), &runtime);
+
+    let result = builder
+        .build_classpath_from_resolved_tree_for_tests(
+            &cache_dir,
+            vec![compile.clone(), runtime.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+    let entries = builder.classpath_entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].coordinate, compile);
+    assert_eq!(entries[0].scope, DependencyScope::Compile);
+    assert_eq!(entries[0].path, result.compile_classpath[0]);
+    assert_eq!(entries[1].coordinate, runtime);
+    assert_eq!(entries[1].scope, DependencyScope::Runtime);
+}
+
 #[tokio::test]
 async fn test_build_classpath_from_resolved_tree_creates_missing_cache_dir() {
     let mut builder = ClasspathBuilder::new(config_with_scoped_dependencies());
@@ -182,29 +235,121 @@ async fn test_build_classpath_from_resolved_tree_creates_missing_cache_dir() {
 }
 
 #[test]
-fn test_map_resolver_error_variants_are_stable() {
+fn test_resolver_error_conversion_variants_are_stable() {
     let io_error =
         polytunnel_resolver::ResolverError::Io(std::io::Error::new(ErrorKind::NotFound, "missing"));
-    let mapped = ClasspathBuilder::map_resolver_error_for_tests(io_error);
+    let mapped = polytunnel_build::BuildError::from(io_error);
     assert!(matches!(mapped, polytunnel_build::BuildError::Io(_)));
 
     let maven_error =
         polytunnel_resolver::ResolverError::Maven(polytunnel_maven::MavenError::ArtifactNotFound {
             coordinate: "org.test:lib:1.0.0".to_string(),
         });
-    let mapped = ClasspathBuilder::map_resolver_error_for_tests(maven_error);
+    let mapped = polytunnel_build::BuildError::from(maven_error);
     assert!(matches!(mapped, polytunnel_build::BuildError::Maven(_)));
 
     let config_error =
         polytunnel_resolver::ResolverError::Config(polytunnel_core::CoreError::ConfigNotFound {
             path: "polytunnel.toml".to_string(),
         });
-    let mapped = ClasspathBuilder::map_resolver_error_for_tests(config_error);
+    let mapped = polytunnel_build::BuildError::from(config_error);
     assert!(matches!(mapped, polytunnel_build::BuildError::Core(_)));
 
     let other_error = polytunnel_resolver::ResolverError::DependencyNotFound {
         coordinate: "org.test:missing:1.0.0".to_string(),
     };
-    let mapped = ClasspathBuilder::map_resolver_error_for_tests(other_error);
+    let mapped = polytunnel_build::BuildError::from(other_error);
     assert!(matches!(mapped, polytunnel_build::BuildError::Resolver(_)));
 }
+
+#[tokio::test]
+async fn test_build_classpath_records_cache_hits() {
+    let mut builder = ClasspathBuilder::new(config_with_scoped_dependencies());
+    let temp = tempdir().unwrap();
+    let cache_dir = temp.path().join("cache").to_string_lossy().to_string();
+
+    let compile = Coordinate::parse("com.example:compile-lib:1.0.0").unwrap();
+    let test = Coordinate::parse("com.example:test-lib:1.0.0").unwrap();
+    touch_cached_dependency(temp.path().join("cache").as_path(), &compile);
+    touch_cached_dependency(temp.path().join("cache").as_path(), &test);
+
+    builder
+        .build_classpath_from_resolved_tree_for_tests(
+            &cache_dir,
+            vec![compile.clone(), test.clone()],
+            false,
+        )
+        .await
+        .unwrap();
+
+    let report = builder.cache_report();
+    assert_eq!(report.hits(), 2);
+    assert_eq!(report.misses(), 0);
+    assert_eq!(report.hit_ratio(), 1.0);
+    assert_eq!(report.fetches[0].coordinate, compile);
+    assert_eq!(report.fetches[1].source, ArtifactSource::Cache);
+}
+
+#[test]
+fn test_cache_report_ratio_and_bytes() {
+    let fetch = |artifact: &str, source| ArtifactFetch {
+        coordinate: Coordinate::new("com.example", artifact, "1.0"),
+        source,
+    };
+    let report = CacheReport {
+        fetches: vec![
+            fetch("a", ArtifactSource::Cache),
+            fetch(
+                "b",
+                ArtifactSource::Network {
+                    bytes: 1000,
+                    elapsed: Duration::from_millis(20),
+                },
+            ),
+            fetch("c", ArtifactSource::Cache),
+            fetch(
+                "d",
+                ArtifactSource::Network {
+                    bytes: 500,
+                    elapsed: Duration::from_millis(5),
+                },
+            ),
+        ],
+    };
+
+    assert_eq!(report.hits(), 2);
+    assert_eq!(report.misses(), 2);
+    assert_eq!(report.hit_ratio(), 0.5);
+    assert_eq!(report.downloaded_bytes(), 1500);
+    assert_eq!(CacheReport::default().hit_ratio(), 1.0);
+}
+
+#[tokio::test]
+async fn test_build_classpath_uses_configured_artifact_store() {
+    let store = Arc::new(MemoryArtifactStore::new());
+    let compile = Coordinate::parse("com.example:compile-lib:1.0.0").unwrap();
+    store.write(&jar_key(&compile), b"jar").unwrap();
+    let mut builder =
+        ClasspathBuilder::new(config_with_scoped_dependencies()).with_artifact_store(store.clone());
+    let temp = tempdir().unwrap();
+    let cache_path = temp.path().join("cache");
+
+    let result = builder
+        .build_classpath_from_resolved_tree_for_tests(
+            cache_path.to_str().unwrap(),
+            vec![compile.clone()],
+            false,
+        )
+        .await
+        .expect("stored artifact should be a cache hit");
+
+    assert_eq!(
+        result.compile_classpath,
+        vec![store.path_for(&jar_key(&compile))]
+    );
+    assert_eq!(builder.cache_report().hits(), 1);
+    assert!(
+        !cache_path.exists(),
+        "in-memory store must not touch the disk"
+    );
+}
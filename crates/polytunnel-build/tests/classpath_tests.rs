@@ -16,6 +16,13 @@ fn create_test_config() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -153,6 +160,8 @@ async fn test_build_classpath_with_different_scopes() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Compile,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
     config.dependencies.insert(
@@ -161,6 +170,8 @@ async fn test_build_classpath_with_different_scopes() {
             version: "1.0.0".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -215,6 +226,15 @@ fn test_parse_coordinate_invalid() {
     assert!(bad.is_err());
 }
 
+#[test]
+fn test_parse_coordinate_trims_and_rejects_malformed_segments() {
+    let coord = ClasspathBuilder::parse_coordinate("org.foo: bar ").unwrap();
+    assert_eq!(coord.artifact_id, "bar");
+
+    assert!(ClasspathBuilder::parse_coordinate("org.foo:bar/baz").is_err());
+    assert!(ClasspathBuilder::parse_coordinate("org.foo: ").is_err());
+}
+
 #[test]
 fn test_format_classpath() {
     use std::path::PathBuf;
@@ -0,0 +1,48 @@
+//! Tests for rendering external commands as copy-pasteable shell strings
+
+use polytunnel_build::{classpath_separator, format_command, shell_quote};
+use std::process::Command;
+
+#[test]
+fn test_shell_quote_leaves_safe_arguments_bare() {
+    assert_eq!(shell_quote("-d"), "-d");
+    assert_eq!(shell_quote("target/classes"), "target/classes");
+    assert_eq!(shell_quote("com.example.Main"), "com.example.Main");
+}
+
+#[test]
+fn test_shell_quote_wraps_empty_argument() {
+    let quoted = shell_quote("");
+    assert_eq!(quoted.len(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_shell_quote_unix_uses_single_quotes() {
+    assert_eq!(shell_quote("my dir/A.java"), "'my dir/A.java'");
+    assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    assert_eq!(shell_quote("$HOME"), "'$HOME'");
+}
+
+#[cfg(windows)]
+#[test]
+fn test_shell_quote_windows_uses_double_quotes() {
+    assert_eq!(shell_quote("my dir\\A.java"), "\"my dir\\A.java\"");
+}
+
+#[test]
+fn test_format_command_includes_program_and_quoted_classpath() {
+    let sep = classpath_separator();
+    let classpath = format!("libs/a.jar{sep}libs/with space.jar");
+    let mut cmd = Command::new("javac");
+    cmd.arg("-cp")
+        .arg(&classpath)
+        .arg("-d")
+        .arg("out")
+        .arg("Main.java");
+
+    assert_eq!(
+        format_command(&cmd),
+        format!("javac -cp {} -d out Main.java", shell_quote(&classpath))
+    );
+}
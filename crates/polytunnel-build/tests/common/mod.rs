@@ -0,0 +1,84 @@
+//! Mock repository shared by the build integration tests
+#![allow(dead_code)]
+
+use polytunnel_maven::{HttpResponse, HttpTransportFuture, MavenClient, MavenTransport};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub const BASE_URL: &str = "https://repo.example.test";
+
+/// Serves `routes` by URL, 404 for anything else, and records every request
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    routes: HashMap<String, (u16, Vec<u8>)>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockTransport {
+    /// Answer `url` with `status` and `body`
+    pub fn route(mut self, url: impl Into<String>, status: u16, body: impl Into<Vec<u8>>) -> Self {
+        self.routes.insert(url.into(), (status, body.into()));
+        self
+    }
+
+    /// Serve `com.example:<artifact>:1.0` as a jar whose POM lists `dependencies`
+    pub fn artifact(self, artifact: &str, dependencies: &str) -> Self {
+        self.route(
+            url(artifact, "pom"),
+            200,
+            pom(
+                artifact,
+                &format!("<dependencies>{dependencies}</dependencies>"),
+            ),
+        )
+        .route(url(artifact, "jar"), 200, artifact)
+    }
+
+    /// URLs requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Client for a repository at [`BASE_URL`] served by this transport
+    pub fn client(&self) -> MavenClient {
+        MavenClient::with_transport(BASE_URL, Arc::new(self.clone()))
+    }
+}
+
+impl MavenTransport for MockTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        self.requests.lock().unwrap().push(url.clone());
+        let response = self
+            .routes
+            .get(&url)
+            .cloned()
+            .unwrap_or((404, b"not found".to_vec()));
+
+        Box::pin(async move {
+            Ok(HttpResponse {
+                status: response.0,
+                body: response.1,
+                headers: Default::default(),
+            })
+        })
+    }
+}
+
+/// URL of the `extension` file of `com.example:<artifact>:1.0`
+pub fn url(artifact: &str, extension: &str) -> String {
+    format!("{BASE_URL}/com/example/{artifact}/1.0/{artifact}-1.0.{extension}")
+}
+
+/// POM of `com.example:<artifact>:1.0`; `body` follows its `<version>`
+pub fn pom(artifact: &str, body: &str) -> String {
+    format!(
+        "<project><groupId>com.example</groupId><artifactId>{artifact}</artifactId><version>1.0</version>{body}</project>"
+    )
+}
+
+/// `<dependency>` on `com.example:<artifact>:1.0`; `extra` (scope, ...) follows its `<version>`
+pub fn dependency(artifact: &str, extra: &str) -> String {
+    format!(
+        "<dependency><groupId>com.example</groupId><artifactId>{artifact}</artifactId><version>1.0</version>{extra}</dependency>"
+    )
+}
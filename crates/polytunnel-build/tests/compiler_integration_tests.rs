@@ -1,4 +1,4 @@
-use polytunnel_build::{BuildError, JavaCompiler};
+use polytunnel_build::{BuildError, JavaCompiler, Severity};
 use polytunnel_core::{BuildConfig, ProjectConfig, ProjectInfo};
 use std::collections::HashMap;
 use std::fs;
@@ -20,6 +20,13 @@ fn create_config() -> ProjectConfig {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -99,9 +106,122 @@ public class Broken {
         .unwrap_err();
 
     match err {
-        BuildError::CompilationFailed { message } => {
+        BuildError::CompilationFailed { message, .. } => {
             assert!(message.contains("Compilation failed"));
         }
         other => panic!("unexpected error: {other:?}"),
     }
 }
+
+fn create_daemon_config(cache_dir: &Path) -> ProjectConfig {
+    let mut config = create_config();
+    config.build.cache_dir = cache_dir.to_string_lossy().to_string();
+    config.build.compiler_daemon = true;
+    config
+}
+
+#[test]
+fn test_compiler_daemon_reuses_jvm_across_compilations() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_compiler_daemon_reuses_jvm_across_compilations: java/javac not found"
+        );
+        return;
+    }
+
+    let temp = tempdir().unwrap();
+    let main_source = write_java_file(
+        temp.path(),
+        "src/main/java/com/example/Greeter.java",
+        r#"package com.example;
+public class Greeter {
+    public static String greet() { return "hi"; }
+}
+"#,
+    );
+    let test_source = write_java_file(
+        temp.path(),
+        "src/test/java with space/com/example/GreeterTest.java",
+        r#"package com.example;
+public class GreeterTest {
+    String value = Greeter.greet();
+}
+"#,
+    );
+    let classes = temp.path().join("target/classes");
+    let test_classes = temp.path().join("target/test-classes");
+
+    let compiler = JavaCompiler::new(&create_daemon_config(&temp.path().join("cache"))).unwrap();
+    assert!(compiler.uses_daemon());
+
+    compiler
+        .compile(vec![main_source], vec![], classes.clone(), vec![])
+        .unwrap();
+    compiler
+        .compile(
+            vec![test_source],
+            vec![classes.clone()],
+            test_classes.clone(),
+            vec![],
+        )
+        .unwrap();
+
+    assert!(classes.join("com/example/Greeter.class").exists());
+    assert!(test_classes.join("com/example/GreeterTest.class").exists());
+    // Request files and the staging directory are cleaned up
+    let mut work_files: Vec<String> = std::fs::read_dir(temp.path().join("cache/compiler-daemon"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    work_files.sort();
+    assert_eq!(
+        work_files,
+        [
+            "PolytunnelCompilerServer.class",
+            "PolytunnelCompilerServer.java"
+        ]
+    );
+}
+
+#[test]
+fn test_compiler_daemon_reports_compilation_errors() {
+    if !java_tools_available() {
+        eprintln!("skipping test_compiler_daemon_reports_compilation_errors: java/javac not found");
+        return;
+    }
+
+    let temp = tempdir().unwrap();
+    let source = write_java_file(
+        temp.path(),
+        "src/main/java/com/example/Broken.java",
+        r#"package com.example;
+public class Broken {
+    int value = "not an int";
+}
+"#,
+    );
+
+    let compiler = JavaCompiler::new(&create_daemon_config(&temp.path().join("cache"))).unwrap();
+    let err = compiler
+        .compile(
+            vec![source],
+            vec![],
+            temp.path().join("target/classes"),
+            vec![],
+        )
+        .unwrap_err();
+
+    match err {
+        BuildError::CompilationFailed {
+            message,
+            diagnostics,
+        } => {
+            assert!(message.contains("incompatible types"), "{message}");
+            let error = &diagnostics[0];
+            assert_eq!(error.severity, Severity::Error);
+            assert!(error.file.as_ref().unwrap().ends_with("Broken.java"));
+            assert_eq!((error.line, error.column), (Some(3), Some(17)));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -17,3 +17,80 @@ fn test_format_classpath() {
         assert!(result.contains(":"));
     }
 }
+
+#[test]
+fn test_parse_javac_version_modern_and_legacy() {
+    use polytunnel_build::JavaCompiler;
+
+    assert_eq!(
+        JavaCompiler::parse_javac_version("javac 17.0.15\n"),
+        Some(17)
+    );
+    assert_eq!(JavaCompiler::parse_javac_version("javac 21"), Some(21));
+    assert_eq!(
+        JavaCompiler::parse_javac_version("javac 1.8.0_392"),
+        Some(8)
+    );
+    assert_eq!(
+        JavaCompiler::parse_javac_version("Picked up JAVA_TOOL_OPTIONS: -Xmx1g\njavac 11.0.2"),
+        Some(11)
+    );
+    assert_eq!(JavaCompiler::parse_javac_version("not a version"), None);
+}
+
+#[test]
+fn test_parse_major_version_from_config() {
+    use polytunnel_build::JavaCompiler;
+
+    assert_eq!(JavaCompiler::parse_major_version("17"), Some(17));
+    assert_eq!(JavaCompiler::parse_major_version("1.8"), Some(8));
+    assert_eq!(JavaCompiler::parse_major_version("21.0.1+12"), Some(21));
+    assert_eq!(JavaCompiler::parse_major_version("latest"), None);
+}
+
+#[test]
+fn test_ensure_jdk_supports_rejects_older_jdk() {
+    use polytunnel_build::{BuildError, JavaCompiler};
+
+    assert!(JavaCompiler::ensure_jdk_supports(21, 17).is_ok());
+    assert!(JavaCompiler::ensure_jdk_supports(17, 17).is_ok());
+
+    let err = JavaCompiler::ensure_jdk_supports(11, 17).unwrap_err();
+    assert!(matches!(
+        err,
+        BuildError::JdkTooOld {
+            found: 11,
+            required: 17
+        }
+    ));
+    assert!(err.to_string().contains("Install JDK 17 or newer"));
+}
+
+#[test]
+fn test_with_encoding_adds_default_encoding() {
+    use polytunnel_build::JavaCompiler;
+
+    let default = polytunnel_core::BuildConfig::default().encoding;
+    assert_eq!(default, "UTF-8");
+    assert_eq!(
+        JavaCompiler::with_encoding(vec!["-g".to_string()], &default),
+        vec!["-g", "-encoding", "UTF-8"]
+    );
+}
+
+#[test]
+fn test_with_encoding_keeps_explicit_user_encoding() {
+    use polytunnel_build::JavaCompiler;
+
+    let args = vec!["-encoding".to_string(), "ISO-8859-1".to_string()];
+    let result = JavaCompiler::with_encoding(args.clone(), "UTF-8");
+    assert_eq!(result, args);
+    assert_eq!(result.iter().filter(|a| *a == "-encoding").count(), 1);
+}
+
+#[test]
+fn test_with_encoding_empty_leaves_args_unchanged() {
+    use polytunnel_build::JavaCompiler;
+
+    assert!(JavaCompiler::with_encoding(Vec::new(), "").is_empty());
+}
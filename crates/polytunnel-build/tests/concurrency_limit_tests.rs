@@ -0,0 +1,113 @@
+//! Tests for the separate `[http]` limits on concurrent POM fetches and JAR downloads
+
+use polytunnel_build::ClasspathBuilder;
+use polytunnel_core::{Dependency, ProjectConfig};
+use polytunnel_maven::{
+    HttpResponse, HttpTransportFuture, MavenClient, MavenTransport, MemoryArtifactStore,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::tempdir;
+
+const BASE_URL: &str = "https://repo.example.test";
+const LIBRARIES: usize = 8;
+
+/// Requests in flight right now and at most, per file kind
+#[derive(Debug, Default)]
+struct InFlight {
+    poms: (usize, usize),
+    jars: (usize, usize),
+}
+
+impl InFlight {
+    fn counter(&mut self, url: &str) -> Option<&mut (usize, usize)> {
+        if url.ends_with(".pom") {
+            Some(&mut self.poms)
+        } else if url.ends_with(".jar") {
+            Some(&mut self.jars)
+        } else {
+            None
+        }
+    }
+}
+
+/// Serves a leaf POM or a jar for every `com.example` artifact after a short delay
+#[derive(Clone, Default)]
+struct InstrumentedTransport {
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl MavenTransport for InstrumentedTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        let in_flight = self.in_flight.clone();
+        Box::pin(async move {
+            if let Some((current, max)) = in_flight.lock().unwrap().counter(&url) {
+                *current += 1;
+                *max = (*max).max(*current);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            if let Some((current, _)) = in_flight.lock().unwrap().counter(&url) {
+                *current -= 1;
+            }
+
+            let (status, body) = match url.rsplit_once('/') {
+                Some((_, file)) if file.ends_with(".pom") => {
+                    let artifact = file.trim_end_matches("-1.0.pom");
+                    let pom = format!(
+                        "<project><groupId>com.example</groupId><artifactId>{artifact}</artifactId><version>1.0</version></project>"
+                    );
+                    (200, pom.into_bytes())
+                }
+                Some((_, file)) if file.ends_with(".jar") => (200, file.as_bytes().to_vec()),
+                _ => (404, b"not found".to_vec()),
+            };
+            Ok(HttpResponse {
+                status,
+                body,
+                headers: Default::default(),
+            })
+        })
+    }
+}
+
+fn config(resolve_concurrency: usize, download_concurrency: usize) -> ProjectConfig {
+    let mut config = ProjectConfig::new("limited");
+    for i in 0..LIBRARIES {
+        config.dependencies.insert(
+            format!("com.example:lib{i}"),
+            Dependency::Simple("1.0".to_string()),
+        );
+    }
+    config.http.resolve_concurrency = Some(resolve_concurrency);
+    config.http.download_concurrency = Some(download_concurrency);
+    config
+}
+
+/// Build the classpath, returning the most POM fetches and JAR downloads in flight at once
+async fn build(config: ProjectConfig) -> (usize, usize) {
+    let temp = tempdir().unwrap();
+    let transport = InstrumentedTransport::default();
+    let in_flight = transport.in_flight.clone();
+    let mut builder = ClasspathBuilder::new(config)
+        .with_maven_client(MavenClient::with_transport(BASE_URL, Arc::new(transport)))
+        .with_artifact_store(Arc::new(MemoryArtifactStore::new()));
+
+    let result = builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+    assert_eq!(result.compile_classpath.len(), LIBRARIES);
+
+    let in_flight = in_flight.lock().unwrap();
+    (in_flight.poms.1, in_flight.jars.1)
+}
+
+#[tokio::test]
+async fn test_resolve_and_download_limits_are_applied_separately() {
+    assert_eq!(build(config(3, 1)).await, (3, 1));
+}
+
+#[tokio::test]
+async fn test_download_limit_does_not_cap_resolution() {
+    assert_eq!(build(config(LIBRARIES, 2)).await, (LIBRARIES, 2));
+}
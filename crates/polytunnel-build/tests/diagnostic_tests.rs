@@ -0,0 +1,151 @@
+//! Tests for parsing javac diagnostics
+
+use polytunnel_build::{Diagnostic, Severity, parse_javac_diagnostics};
+use std::path::PathBuf;
+
+fn located(file: &str, line: u32, column: u32, severity: Severity, message: &str) -> Diagnostic {
+    Diagnostic {
+        file: Some(PathBuf::from(file)),
+        line: Some(line),
+        column: Some(column),
+        severity,
+        message: message.to_string(),
+    }
+}
+
+fn unlocated(severity: Severity, message: &str) -> Diagnostic {
+    Diagnostic {
+        file: None,
+        line: None,
+        column: None,
+        severity,
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_errors_with_details_warnings_and_notes() {
+    let output = "\
+src/main/java/Main.java:7: error: cannot find symbol
+        foo();
+        ^
+  symbol:   method foo()
+  location: class Main
+src/main/java/Main.java:8: error: incompatible types: String cannot be converted to int
+        int x = \"s\";
+                ^
+src/main/java/Other.java:1: warning: [deprecation] old() in Main has been deprecated
+class Other { void f() { Main.old(); } }
+                             ^
+Note: src/main/java/Main.java uses unchecked or unsafe operations.
+Note: Recompile with -Xlint:unchecked for details.
+2 errors
+1 warning
+";
+    assert_eq!(
+        parse_javac_diagnostics(output),
+        [
+            located(
+                "src/main/java/Main.java",
+                7,
+                9,
+                Severity::Error,
+                "cannot find symbol\nsymbol:   method foo()\nlocation: class Main",
+            ),
+            located(
+                "src/main/java/Main.java",
+                8,
+                17,
+                Severity::Error,
+                "incompatible types: String cannot be converted to int",
+            ),
+            located(
+                "src/main/java/Other.java",
+                1,
+                30,
+                Severity::Warning,
+                "[deprecation] old() in Main has been deprecated",
+            ),
+            unlocated(
+                Severity::Note,
+                "src/main/java/Main.java uses unchecked or unsafe operations.",
+            ),
+            unlocated(
+                Severity::Note,
+                "Recompile with -Xlint:unchecked for details.",
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_column_counts_tabs_as_one_character() {
+    let output = "Main.java:5: warning: [rawtypes] found raw type: List\n\tList l = null;\n\t^\n";
+
+    let diagnostics = parse_javac_diagnostics(output);
+    assert_eq!(diagnostics[0].column, Some(2));
+}
+
+#[test]
+fn test_parse_unlocated_option_warning_and_invalid_flag() {
+    let output = "\
+warning: [options] bootstrap class path not set in conjunction with -source 8
+error: invalid flag: -foo
+Usage: javac <options> <source files>
+use --help for a list of possible options
+";
+    assert_eq!(
+        parse_javac_diagnostics(output),
+        [
+            unlocated(
+                Severity::Warning,
+                "[options] bootstrap class path not set in conjunction with -source 8",
+            ),
+            unlocated(Severity::Error, "invalid flag: -foo"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_windows_path_and_diagnostic_text_in_excerpt() {
+    let output = "\
+C:\\work\\app\\src\\Main.java:3: error: ';' expected
+        String s = \"Main.java:1: error: not a diagnostic\"
+                                                          ^
+1 error
+";
+    let diagnostics = parse_javac_diagnostics(output);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].file,
+        Some(PathBuf::from("C:\\work\\app\\src\\Main.java"))
+    );
+    assert_eq!(diagnostics[0].line, Some(3));
+    assert_eq!(diagnostics[0].column, Some(59));
+    assert_eq!(diagnostics[0].message, "';' expected");
+}
+
+#[test]
+fn test_parse_ignores_plain_output() {
+    assert!(parse_javac_diagnostics("").is_empty());
+    assert!(parse_javac_diagnostics("Compiling 3 files\n1 error\n").is_empty());
+}
+
+#[test]
+fn test_diagnostic_display() {
+    let error = located("Main.java", 7, 9, Severity::Error, "cannot find symbol");
+    assert_eq!(
+        error.to_string(),
+        "Main.java:7:9: error: cannot find symbol"
+    );
+
+    let note = unlocated(
+        Severity::Note,
+        "Recompile with -Xlint:unchecked for details.",
+    );
+    assert_eq!(
+        note.to_string(),
+        "note: Recompile with -Xlint:unchecked for details."
+    );
+}
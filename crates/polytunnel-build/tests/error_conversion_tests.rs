@@ -0,0 +1,109 @@
+use polytunnel_build::BuildError;
+use polytunnel_core::CoreError;
+use polytunnel_maven::MavenError;
+use polytunnel_resolver::ResolverError;
+use std::error::Error;
+use std::io::ErrorKind;
+
+fn propagate<E>(error: E) -> polytunnel_build::Result<()>
+where
+    BuildError: From<E>,
+{
+    Err(error)?;
+    Ok(())
+}
+
+fn not_found() -> MavenError {
+    MavenError::ArtifactNotFound {
+        coordinate: "org.test:lib:1.0.0".to_string(),
+    }
+}
+
+fn config_not_found() -> CoreError {
+    CoreError::ConfigNotFound {
+        path: "polytunnel.toml".to_string(),
+    }
+}
+
+#[test]
+fn test_maven_error_propagates_as_maven_variant() {
+    let error = propagate(not_found()).unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Maven(MavenError::ArtifactNotFound { .. })
+    ));
+    assert!(error.source().unwrap().is::<MavenError>());
+}
+
+#[test]
+fn test_core_error_propagates_as_core_variant() {
+    let error = propagate(config_not_found()).unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Core(CoreError::ConfigNotFound { .. })
+    ));
+    assert!(error.source().unwrap().is::<CoreError>());
+}
+
+#[test]
+fn test_resolver_io_error_is_flattened_into_io() {
+    let error = propagate(ResolverError::Io(std::io::Error::new(
+        ErrorKind::PermissionDenied,
+        "denied",
+    )))
+    .unwrap_err();
+    match &error {
+        BuildError::Io(e) => assert_eq!(e.kind(), ErrorKind::PermissionDenied),
+        other => panic!("expected Io, got {other:?}"),
+    }
+    assert!(error.source().unwrap().is::<std::io::Error>());
+}
+
+#[test]
+fn test_resolver_maven_error_is_flattened_into_maven() {
+    let error = propagate(ResolverError::Maven(not_found())).unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Maven(MavenError::ArtifactNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_resolver_config_error_is_flattened_into_core() {
+    let error = propagate(ResolverError::Config(config_not_found())).unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Core(CoreError::ConfigNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_resolver_specific_error_keeps_resolver_variant_and_source() {
+    let error = propagate(ResolverError::CircularDependency {
+        path: "a -> b -> a".to_string(),
+    })
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Resolver(ResolverError::CircularDependency { .. })
+    ));
+    let source = error.source().unwrap();
+    assert!(source.is::<ResolverError>());
+    assert!(source.to_string().contains("a -> b -> a"));
+}
+
+#[test]
+fn test_resolver_fetch_failure_keeps_maven_cause_in_chain() {
+    let error = propagate(ResolverError::FetchFailed {
+        coordinate: "org.test:lib:1.0.0".to_string(),
+        chain: vec!["org.test:lib:1.0.0".to_string()],
+        source: Box::new(not_found()),
+    })
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Resolver(ResolverError::FetchFailed { .. })
+    ));
+    let cause = error.source().unwrap().source().unwrap();
+    assert_eq!(cause.to_string(), not_found().to_string());
+}
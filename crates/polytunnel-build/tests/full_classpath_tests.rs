@@ -0,0 +1,113 @@
+//! Tests for the analysis-oriented full classpath.
+
+mod common;
+
+use common::{MockTransport, dependency, pom, url};
+use polytunnel_build::ClasspathBuilder;
+use polytunnel_core::{Dependency, ProjectConfig};
+use polytunnel_maven::{ArtifactStore, Coordinate, MavenClient, MemoryArtifactStore, jar_key};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// `(artifactId, scope, optional)` of a `com.example` dependency at version `1.0`
+type Dep<'a> = (&'a str, &'a str, bool);
+
+/// POM of `com.example:<artifact>:1.0` depending on `deps`
+fn pom_with(artifact: &str, deps: &[Dep]) -> String {
+    let deps: String = deps
+        .iter()
+        .map(|(a, scope, optional)| {
+            dependency(
+                a,
+                &format!("<scope>{scope}</scope><optional>{optional}</optional>"),
+            )
+        })
+        .collect();
+    pom(artifact, &format!("<dependencies>{deps}</dependencies>"))
+}
+
+/// `lib` -> `core`, optional `feature` (-> optional `extra`), provided `container`, test `junit`
+fn client() -> MavenClient {
+    let lib = [
+        ("core", "compile", false),
+        ("feature", "compile", true),
+        ("container", "provided", false),
+        ("junit", "test", false),
+    ];
+    MockTransport::default()
+        .route(url("lib", "pom"), 200, pom_with("lib", &lib))
+        .route(url("core", "pom"), 200, pom_with("core", &[]))
+        .route(
+            url("feature", "pom"),
+            200,
+            pom_with("feature", &[("extra", "compile", true)]),
+        )
+        .route(url("extra", "pom"), 200, pom_with("extra", &[]))
+        .route(url("container", "pom"), 200, pom_with("container", &[]))
+        .route(url("junit", "pom"), 200, pom_with("junit", &[]))
+        .client()
+}
+
+fn store() -> Arc<MemoryArtifactStore> {
+    let store = Arc::new(MemoryArtifactStore::new());
+    for artifact in ["lib", "core", "feature", "extra", "container", "junit"] {
+        let coord = Coordinate::new("com.example", artifact, "1.0");
+        store.write(&jar_key(&coord), b"jar").unwrap();
+    }
+    store
+}
+
+fn config() -> ProjectConfig {
+    let mut config = ProjectConfig::new("analysis");
+    config.dependencies.insert(
+        "com.example:lib".to_string(),
+        Dependency::Simple("1.0".to_string()),
+    );
+    config
+}
+
+fn jar_names(classpath: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<_> = classpath
+        .iter()
+        .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+async fn compile_classpath(full: bool) -> Vec<String> {
+    let temp = tempdir().unwrap();
+    let mut builder = ClasspathBuilder::new(config())
+        .with_maven_client(client())
+        .with_artifact_store(store());
+    builder.set_full_classpath(full);
+
+    let result = builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+    jar_names(&result.compile_classpath)
+}
+
+#[tokio::test]
+async fn test_default_compile_classpath_skips_optional_and_provided_transitives() {
+    assert_eq!(
+        compile_classpath(false).await,
+        ["core-1.0.jar", "lib-1.0.jar"]
+    );
+}
+
+#[tokio::test]
+async fn test_full_compile_classpath_includes_optional_and_provided_transitives() {
+    assert_eq!(
+        compile_classpath(true).await,
+        [
+            "container-1.0.jar",
+            "core-1.0.jar",
+            "extra-1.0.jar",
+            "feature-1.0.jar",
+            "lib-1.0.jar",
+        ]
+    );
+}
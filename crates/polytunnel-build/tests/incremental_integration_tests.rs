@@ -3,13 +3,14 @@ use polytunnel_core::{BuildConfig, ProjectConfig, ProjectInfo};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::thread;
-use std::time::Duration;
 use tempfile::tempdir;
 
 fn create_config(cache_dir: &Path) -> ProjectConfig {
     let build = BuildConfig {
         cache_dir: cache_dir.to_string_lossy().to_string(),
+        compiler_daemon: false,
+        resolve_timeout_secs: None,
+        main_class: None,
         ..BuildConfig::default()
     };
 
@@ -21,6 +22,13 @@ fn create_config(cache_dir: &Path) -> ProjectConfig {
         build,
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -105,7 +113,6 @@ fn test_update_get_files_to_compile_and_clear() {
     assert!(first.contains(&source_b));
     assert!(!first.contains(&source_a));
 
-    thread::sleep(Duration::from_secs(1));
     fs::write(&source_a, "class Main { int v = 1; }").unwrap();
     let second = cache
         .get_files_to_compile(std::slice::from_ref(&source_a))
@@ -133,3 +140,250 @@ fn test_update_ignores_missing_sources() {
 
     assert!(cache.entries.is_empty());
 }
+
+#[test]
+fn test_update_for_compiled_sources_records_class_outputs() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let source = write_source(
+        temp.path(),
+        "src/main/java/com/example/Main.java",
+        "// header\npackage com.example;\n\npublic class Main {}\n",
+    );
+    let class_dir = output_dir.join("com/example");
+    for name in [
+        "Main.class",
+        "Main$Inner.class",
+        "Main$1.class",
+        "MainHelper.class",
+    ] {
+        write_source(&class_dir, name, "");
+    }
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    cache
+        .update_for_compiled_sources(
+            &BuildCache::snapshot(std::slice::from_ref(&source)),
+            &output_dir,
+            &[],
+        )
+        .unwrap();
+
+    let entry = &cache.entries[&source.to_string_lossy().to_string()];
+    assert_eq!(
+        entry.output_files,
+        vec![
+            class_dir.join("Main$1.class"),
+            class_dir.join("Main$Inner.class"),
+            class_dir.join("Main.class"),
+        ]
+    );
+}
+
+#[test]
+fn test_needs_recompile_reports_uncompiled_and_deleted_outputs() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let main = write_source(temp.path(), "src/Main.java", "class Main {}");
+    let util = write_source(temp.path(), "src/Util.java", "class Util {}");
+    write_source(&output_dir, "Main.class", "");
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    cache
+        .update_for_compiled_sources(
+            &BuildCache::snapshot(std::slice::from_ref(&main)),
+            &output_dir,
+            &[],
+        )
+        .unwrap();
+    let sources = [main.clone(), util.clone()];
+    assert_eq!(cache.needs_recompile(&sources), vec![util.clone()]);
+
+    fs::remove_file(output_dir.join("Main.class")).unwrap();
+    assert_eq!(cache.needs_recompile(&sources), vec![util, main]);
+}
+
+#[test]
+fn test_remove_orphaned_outputs_deletes_classes_of_deleted_sources() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let kept = write_source(temp.path(), "src/Kept.java", "public class Kept {}\n");
+    let gone = write_source(temp.path(), "src/Gone.java", "public class Gone {}\n");
+    for name in ["Kept.class", "Gone.class", "Gone$Inner.class"] {
+        write_source(&output_dir, name, "");
+    }
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    cache
+        .update_for_compiled_sources(
+            &BuildCache::snapshot(&[kept.clone(), gone.clone()]),
+            &output_dir,
+            &[],
+        )
+        .unwrap();
+    assert!(cache.remove_orphaned_outputs().unwrap().is_empty());
+
+    fs::remove_file(&gone).unwrap();
+    let mut removed = cache.remove_orphaned_outputs().unwrap();
+    removed.sort();
+
+    assert_eq!(
+        removed,
+        vec![
+            output_dir.join("Gone$Inner.class"),
+            output_dir.join("Gone.class")
+        ]
+    );
+    assert!(output_dir.join("Kept.class").exists());
+    assert!(
+        !cache
+            .entries
+            .contains_key(&gone.to_string_lossy().to_string())
+    );
+    assert!(
+        cache
+            .entries
+            .contains_key(&kept.to_string_lossy().to_string())
+    );
+
+    // The dropped entry is persisted
+    let reloaded = BuildCache::new(&config).unwrap();
+    assert!(
+        !reloaded
+            .entries
+            .contains_key(&gone.to_string_lossy().to_string())
+    );
+}
+
+#[test]
+fn test_needs_recompile_only_reports_touched_source() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let main = write_source(temp.path(), "src/Main.java", "class Main {}");
+    let util = write_source(temp.path(), "src/Util.java", "class Util {}");
+    write_source(&output_dir, "Main.class", "");
+    write_source(&output_dir, "Util.class", "");
+    let sources = [main.clone(), util.clone()];
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    cache
+        .update_for_compiled_sources(&BuildCache::snapshot(&sources), &output_dir, &[])
+        .unwrap();
+    assert!(cache.needs_recompile(&sources).is_empty());
+
+    fs::write(&util, "class Util { int v; }").unwrap();
+    assert_eq!(cache.needs_recompile(&sources), vec![util]);
+}
+
+#[test]
+fn test_same_second_edit_with_unchanged_mtime_is_stale() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let main = write_source(temp.path(), "src/Main.java", "class Main { int v = 1; }");
+    let modified = fs::metadata(&main).unwrap().modified().unwrap();
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    cache
+        .update_for_sources(std::slice::from_ref(&main))
+        .unwrap();
+
+    fs::write(&main, "class Main { int v = 2; }").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&main)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    assert_eq!(
+        cache
+            .get_files_to_compile(std::slice::from_ref(&main))
+            .unwrap(),
+        vec![main]
+    );
+}
+
+#[test]
+fn test_edit_during_compilation_stays_stale() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let main = write_source(temp.path(), "src/Main.java", "class Main {}");
+    write_source(&output_dir, "Main.class", "");
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    let snapshot = BuildCache::snapshot(std::slice::from_ref(&main));
+    // Saved while javac was still running
+    fs::write(&main, "class Main { int v; }").unwrap();
+    cache
+        .update_for_compiled_sources(&snapshot, &output_dir, &[])
+        .unwrap();
+
+    assert_eq!(
+        cache.needs_recompile(std::slice::from_ref(&main)),
+        vec![main]
+    );
+}
+
+#[test]
+fn test_update_for_compiled_sources_reports_changed_class_files() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let main = write_source(temp.path(), "src/Main.java", "class Main {}");
+    write_source(&output_dir, "Main.class", "first");
+    let sources = [main];
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    // Nothing was recorded before, so nothing can have changed
+    assert!(
+        !cache
+            .update_for_compiled_sources(&BuildCache::snapshot(&sources), &output_dir, &[])
+            .unwrap()
+    );
+    assert!(
+        !cache
+            .update_for_compiled_sources(&BuildCache::snapshot(&sources), &output_dir, &[])
+            .unwrap()
+    );
+
+    write_source(&output_dir, "Main.class", "second");
+    assert!(
+        cache
+            .update_for_compiled_sources(&BuildCache::snapshot(&sources), &output_dir, &[])
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_classpath_change_invalidates_every_source() {
+    let temp = tempdir().unwrap();
+    let config = create_config(&temp.path().join(".polytunnel/cache"));
+    let output_dir = temp.path().join("target/classes");
+    let main = write_source(temp.path(), "src/Main.java", "class Main {}");
+    write_source(&output_dir, "Main.class", "");
+    let sources = [main.clone()];
+    let classpath = [PathBuf::from("lib/a-1.0.jar")];
+
+    let mut cache = BuildCache::new(&config).unwrap();
+    cache
+        .update_for_compiled_sources(&BuildCache::snapshot(&sources), &output_dir, &classpath)
+        .unwrap();
+    assert!(
+        !cache
+            .invalidate_if_classpath_changed(&sources, &classpath)
+            .unwrap()
+    );
+    assert!(cache.needs_recompile(&sources).is_empty());
+
+    let upgraded = [PathBuf::from("lib/a-2.0.jar")];
+    assert!(
+        cache
+            .invalidate_if_classpath_changed(&sources, &upgraded)
+            .unwrap()
+    );
+    assert_eq!(cache.needs_recompile(&sources), vec![main]);
+}
@@ -10,6 +10,10 @@ fn test_build_cache_entry_serialization() {
         source_file: PathBuf::from("src/Main.java"),
         last_modified: 1705334400,
         output_file: PathBuf::from("target/classes/Main.class"),
+        output_files: Vec::new(),
+        classpath_digest: String::new(),
+        content_digest: String::new(),
+        api_digest: String::new(),
     };
 
     let json = serde_json::to_string(&entry).unwrap();
@@ -28,6 +32,10 @@ fn test_cache_entry_map_serialization() {
             source_file: PathBuf::from("src/Main.java"),
             last_modified: 1705334400,
             output_file: PathBuf::from("target/classes/Main.class"),
+            output_files: Vec::new(),
+            classpath_digest: String::new(),
+            content_digest: String::new(),
+            api_digest: String::new(),
         },
     );
 
@@ -0,0 +1,189 @@
+//! Tests for `javadoc` command construction, diagnostics parsing, and generation
+
+use polytunnel_build::{
+    BuildError, BuildOrchestrator, DiagnosticKind, JavadocDiagnostic, JavadocOptions, javadoc_args,
+    javadoc_link, parse_javadoc_diagnostics,
+};
+use polytunnel_core::{BuildConfig, ProjectConfig};
+use polytunnel_maven::Coordinate;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn test_javadoc_args_order() {
+    let args = javadoc_args(
+        &[PathBuf::from("src/main/java/com/example/App.java")],
+        &[PathBuf::from("lib/a.jar")],
+        Path::new("target/javadoc"),
+        "17",
+        "UTF-8",
+        &["https://javadoc.io/doc/com.google.guava/guava/33.0.0-jre/".to_string()],
+    );
+    let expected: Vec<OsString> = [
+        "-d",
+        "target/javadoc",
+        "--release",
+        "17",
+        "-encoding",
+        "UTF-8",
+        "-classpath",
+        "lib/a.jar",
+        "-link",
+        "https://javadoc.io/doc/com.google.guava/guava/33.0.0-jre/",
+        "src/main/java/com/example/App.java",
+    ]
+    .into_iter()
+    .map(OsString::from)
+    .collect();
+    assert_eq!(args, expected);
+}
+
+#[test]
+fn test_javadoc_args_skip_empty_classpath_and_encoding() {
+    let args = javadoc_args(
+        &[PathBuf::from("App.java")],
+        &[],
+        Path::new("out"),
+        "21",
+        "",
+        &[],
+    );
+    assert_eq!(args, ["-d", "out", "--release", "21", "App.java"]);
+}
+
+#[test]
+fn test_javadoc_link_points_at_javadoc_io() {
+    let coord = Coordinate::parse("org.slf4j:slf4j-api:2.0.9").unwrap();
+    assert_eq!(
+        javadoc_link(&coord),
+        "https://javadoc.io/doc/org.slf4j/slf4j-api/2.0.9/"
+    );
+}
+
+#[test]
+fn test_parse_javadoc_diagnostics() {
+    let output = "\
+Loading source files for package com.example...
+Constructing Javadoc information...
+/p/src/main/java/com/example/App.java:3: warning: no comment
+public class App {
+       ^
+/p/src/main/java/com/example/App.java:5: error: reference not found
+    /** {@link Missing} */
+               ^
+javadoc: error - No public or protected classes found to document.
+error: invalid flag: -foo
+1 error
+1 warning
+";
+    let diagnostics = parse_javadoc_diagnostics(output);
+    assert_eq!(
+        diagnostics,
+        [
+            JavadocDiagnostic {
+                kind: DiagnosticKind::Warning,
+                file: Some(PathBuf::from("/p/src/main/java/com/example/App.java")),
+                line: Some(3),
+                message: "no comment".to_string(),
+            },
+            JavadocDiagnostic {
+                kind: DiagnosticKind::Error,
+                file: Some(PathBuf::from("/p/src/main/java/com/example/App.java")),
+                line: Some(5),
+                message: "reference not found".to_string(),
+            },
+            JavadocDiagnostic {
+                kind: DiagnosticKind::Error,
+                file: None,
+                line: None,
+                message: "No public or protected classes found to document.".to_string(),
+            },
+            JavadocDiagnostic {
+                kind: DiagnosticKind::Error,
+                file: None,
+                line: None,
+                message: "invalid flag: -foo".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "/p/src/main/java/com/example/App.java:3: warning: no comment"
+    );
+}
+
+fn javadoc_available() -> bool {
+    Command::new("javadoc").arg("--version").output().is_ok()
+        && Command::new("javac").arg("-version").output().is_ok()
+}
+
+fn project(root: &Path, source: &str) -> ProjectConfig {
+    let path = root.join("src/main/java/com/example/App.java");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, source).unwrap();
+
+    let mut config = ProjectConfig::new("docs");
+    config.repositories.clear();
+    config.build = BuildConfig {
+        source_dirs: vec![root.join("src/main/java").to_string_lossy().to_string()],
+        javadoc_dir: root.join("target/javadoc").to_string_lossy().to_string(),
+        cache_dir: root.join(".polytunnel/cache").to_string_lossy().to_string(),
+        ..BuildConfig::default()
+    };
+    config
+}
+
+#[tokio::test]
+async fn test_javadoc_generates_html() {
+    if !javadoc_available() {
+        eprintln!("Skipping: javadoc not available");
+        return;
+    }
+    let temp = tempdir().unwrap();
+    let config = project(
+        temp.path(),
+        "package com.example;\n/** Entry point. */\npublic class App {\n    /** Creates an app. */\n    public App() {}\n}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(config).unwrap();
+    let result = orchestrator
+        .javadoc(&JavadocOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(result.documented_files, 1);
+    assert!(result.output_dir.join("index.html").is_file());
+    assert!(result.output_dir.join("com/example/App.html").is_file());
+}
+
+#[tokio::test]
+async fn test_javadoc_errors_are_reported() {
+    if !javadoc_available() {
+        eprintln!("Skipping: javadoc not available");
+        return;
+    }
+    let temp = tempdir().unwrap();
+    let config = project(
+        temp.path(),
+        "package com.example;\n/** See {@link DoesNotExist}. */\npublic class App {}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(config).unwrap();
+    let err = orchestrator
+        .javadoc(&JavadocOptions::default())
+        .await
+        .unwrap_err();
+
+    match err {
+        BuildError::JavadocFailed { message } => {
+            assert!(
+                message.contains("App.java:2: error: reference not found"),
+                "{message}"
+            );
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
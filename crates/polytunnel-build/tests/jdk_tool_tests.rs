@@ -0,0 +1,106 @@
+//! Tests for locating JDK tools other than javac
+
+use polytunnel_build::{BuildError, find_jdk_tool};
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use tempfile::tempdir;
+
+fn env_lock() -> MutexGuard<'static, ()> {
+    static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    ENV_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Restores `PATH` and `JAVA_HOME` on drop
+struct EnvGuard {
+    path: Option<OsString>,
+    java_home: Option<OsString>,
+}
+
+impl EnvGuard {
+    fn set(path: &Path, java_home: Option<&Path>) -> Self {
+        let guard = Self {
+            path: std::env::var_os("PATH"),
+            java_home: std::env::var_os("JAVA_HOME"),
+        };
+        // SAFETY: tests mutate process env only while holding env_lock().
+        unsafe {
+            std::env::set_var("PATH", path);
+            match java_home {
+                Some(home) => std::env::set_var("JAVA_HOME", home),
+                None => std::env::remove_var("JAVA_HOME"),
+            }
+        }
+        guard
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        // SAFETY: tests mutate process env only while holding env_lock().
+        unsafe {
+            for (key, value) in [("PATH", &self.path), ("JAVA_HOME", &self.java_home)] {
+                match value {
+                    Some(value) => std::env::set_var(key, value),
+                    None => std::env::remove_var(key),
+                }
+            }
+        }
+    }
+}
+
+fn create_tool(dir: &Path, name: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join(name), "#!/bin/sh\n").unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_finds_tool_next_to_javac() {
+    let temp = tempdir().unwrap();
+    let jdk_bin = temp.path().join("jdk/bin");
+    create_tool(&jdk_bin, "javac");
+    create_tool(&jdk_bin, "jar");
+
+    let _lock = env_lock();
+    let _env = EnvGuard::set(&jdk_bin, None);
+
+    assert_eq!(
+        find_jdk_tool("jar").unwrap(),
+        jdk_bin.canonicalize().unwrap().join("jar")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_falls_back_to_java_home() {
+    let temp = tempdir().unwrap();
+    let path_bin = temp.path().join("path-bin");
+    create_tool(&path_bin, "javac");
+    let java_home = temp.path().join("jdk");
+    create_tool(&java_home.join("bin"), "javadoc");
+
+    let _lock = env_lock();
+    let _env = EnvGuard::set(&path_bin, Some(&java_home));
+
+    assert_eq!(
+        find_jdk_tool("javadoc").unwrap(),
+        java_home.join("bin/javadoc")
+    );
+}
+
+#[test]
+fn test_missing_tool_is_reported_by_name() {
+    let temp = tempdir().unwrap();
+
+    let _lock = env_lock();
+    let _env = EnvGuard::set(temp.path(), None);
+
+    let err = find_jdk_tool("javadoc").unwrap_err();
+    assert!(matches!(&err, BuildError::JdkToolNotFound { tool } if tool == "javadoc"));
+    assert!(err.to_string().contains("`javadoc` not found"));
+}
@@ -0,0 +1,233 @@
+//! Tests for `--locked`/`--frozen` lockfile enforcement in ClasspathBuilder
+
+use polytunnel_build::{BuildError, ClasspathBuilder, LockMode};
+use polytunnel_core::{BuildConfig, Dependency, DependencyScope, ProjectConfig, ProjectInfo};
+use polytunnel_maven::{Coordinate, DependencyScope as MavenScope};
+use polytunnel_resolver::{Lockfile, ResolvedTree};
+use std::collections::HashMap;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn config() -> ProjectConfig {
+    ProjectConfig {
+        project: ProjectInfo {
+            name: "locked-app".to_string(),
+            java_version: "17".to_string(),
+        },
+        build: BuildConfig::default(),
+        dependencies: HashMap::new(),
+        repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
+    }
+}
+
+fn write_lockfile(path: &Path, packages: &[&str]) {
+    let packages: Vec<Coordinate> = packages
+        .iter()
+        .map(|c| Coordinate::parse(c).unwrap())
+        .collect();
+    Lockfile::from_tree(&ResolvedTree {
+        root_dependencies: packages.clone(),
+        all_dependencies: packages,
+        graph: Default::default(),
+        conflicts: Vec::new(),
+    })
+    .save(path)
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_locked_without_lockfile_fails() {
+    let dir = tempdir().unwrap();
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(dir.path().join("polytunnel.lock"), LockMode::Locked);
+
+    let error = builder
+        .build_classpath(dir.path().join("cache").to_str().unwrap(), false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BuildError::LockfileMissing { .. }));
+}
+
+#[tokio::test]
+async fn test_locked_reports_diff_when_resolution_drifts() {
+    let dir = tempdir().unwrap();
+    let lock_path = dir.path().join("polytunnel.lock");
+    write_lockfile(&lock_path, &["org.stale:lib:1.0.0"]);
+
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(&lock_path, LockMode::Locked);
+
+    let error = builder
+        .build_classpath(dir.path().join("cache").to_str().unwrap(), false)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("- org.stale:lib:1.0.0"));
+    match error {
+        BuildError::LockfileOutdated { diff } => assert_eq!(diff.removed.len(), 1),
+        other => panic!("expected LockfileOutdated, got {other:?}"),
+    }
+    // The lockfile is left untouched
+    let lock = Lockfile::load(&lock_path).unwrap().unwrap();
+    assert_eq!(lock.packages.len(), 1);
+}
+
+#[tokio::test]
+async fn test_locked_matching_lockfile_succeeds() {
+    let dir = tempdir().unwrap();
+    let lock_path = dir.path().join("polytunnel.lock");
+    write_lockfile(&lock_path, &[]);
+
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(&lock_path, LockMode::Locked);
+
+    let result = builder
+        .build_classpath(dir.path().join("cache").to_str().unwrap(), false)
+        .await
+        .unwrap();
+    assert!(result.compile_classpath.is_empty());
+}
+
+#[tokio::test]
+async fn test_frozen_uses_lockfile_and_cache_only() {
+    let dir = tempdir().unwrap();
+    let lock_path = dir.path().join("polytunnel.lock");
+    write_lockfile(&lock_path, &["org.cached:lib:1.0.0"]);
+
+    let cache = dir.path().join("cache");
+    let jar = cache.join("org/cached/lib/1.0.0/lib-1.0.0.jar");
+    std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+    std::fs::write(&jar, b"jar").unwrap();
+
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(&lock_path, LockMode::Frozen);
+
+    let result = builder
+        .build_classpath(cache.to_str().unwrap(), false)
+        .await
+        .unwrap();
+    assert_eq!(result.compile_classpath, vec![jar]);
+}
+
+#[tokio::test]
+async fn test_frozen_keeps_transitives_of_test_root_off_compile_classpath() {
+    let dir = tempdir().unwrap();
+    let lock_path = dir.path().join("polytunnel.lock");
+    let junit = Coordinate::parse("org.test:junit:1.0").unwrap();
+    let hamcrest = Coordinate::parse("org.test:hamcrest:1.0").unwrap();
+    let scopes = HashMap::from([
+        (junit.to_string(), MavenScope::Test),
+        (hamcrest.to_string(), MavenScope::Test),
+    ]);
+    Lockfile::from_tree(&ResolvedTree {
+        root_dependencies: vec![junit.clone()],
+        all_dependencies: vec![junit.clone(), hamcrest.clone()],
+        graph: Default::default(),
+        conflicts: Vec::new(),
+    })
+    .with_scopes(&scopes)
+    .save(&lock_path)
+    .unwrap();
+
+    let cache = dir.path().join("cache");
+    for coord in [&junit, &hamcrest] {
+        let jar = cache.join(coord.repo_path()).join(coord.jar_filename());
+        std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+        std::fs::write(&jar, b"jar").unwrap();
+    }
+
+    let mut config = config();
+    config.dependencies.insert(
+        "org.test:junit".to_string(),
+        Dependency::Detailed {
+            version: "1.0".to_string(),
+            scope: DependencyScope::Test,
+            optional: false,
+            exclusions: Vec::new(),
+            include_optional: false,
+        },
+    );
+    let mut builder = ClasspathBuilder::new(config);
+    builder.set_lock_mode(&lock_path, LockMode::Frozen);
+
+    let result = builder
+        .build_classpath(cache.to_str().unwrap(), false)
+        .await
+        .unwrap();
+    assert!(result.compile_classpath.is_empty());
+    assert!(result.runtime_classpath.is_empty());
+    assert_eq!(result.test_classpath.len(), 2);
+}
+
+#[tokio::test]
+async fn test_frozen_fails_on_uncached_artifact() {
+    let dir = tempdir().unwrap();
+    let lock_path = dir.path().join("polytunnel.lock");
+    write_lockfile(&lock_path, &["org.remote:lib:1.0.0"]);
+
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(&lock_path, LockMode::Frozen);
+
+    let error = builder
+        .build_classpath(dir.path().join("cache").to_str().unwrap(), false)
+        .await
+        .unwrap_err();
+
+    match error {
+        BuildError::FrozenArtifactMissing { coordinate } => {
+            assert_eq!(coordinate, "org.remote:lib:1.0.0")
+        }
+        other => panic!("expected FrozenArtifactMissing, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_artifact_returns_cached_jar() {
+    let dir = tempdir().unwrap();
+    let jar = dir
+        .path()
+        .join("org/junit/platform/junit-platform-console-standalone/1.10.2")
+        .join("junit-platform-console-standalone-1.10.2.jar");
+    std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+    std::fs::write(&jar, b"jar").unwrap();
+
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(dir.path().join("polytunnel.lock"), LockMode::Frozen);
+
+    let launcher = Coordinate::new(
+        "org.junit.platform",
+        "junit-platform-console-standalone",
+        "1.10.2",
+    );
+    let path = builder
+        .fetch_artifact(&launcher, dir.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+    assert_eq!(path, jar);
+}
+
+#[tokio::test]
+async fn test_frozen_fetch_artifact_fails_when_uncached() {
+    let dir = tempdir().unwrap();
+    let mut builder = ClasspathBuilder::new(config());
+    builder.set_lock_mode(dir.path().join("polytunnel.lock"), LockMode::Frozen);
+
+    let launcher = Coordinate::new(
+        "org.junit.platform",
+        "junit-platform-console-standalone",
+        "1.10.2",
+    );
+    let error = builder
+        .fetch_artifact(&launcher, dir.path().to_str().unwrap(), false)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, BuildError::FrozenArtifactMissing { .. }));
+}
@@ -0,0 +1,112 @@
+//! Tests for detecting the class `pt run` starts.
+
+use polytunnel_build::{BuildError, detect_main_class, find_main_classes};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn write_source(root: &Path, relative: &str, source: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, source).unwrap();
+}
+
+#[test]
+fn test_find_main_classes_qualifies_with_package() {
+    let dir = tempdir().unwrap();
+    write_source(
+        dir.path(),
+        "com/example/App.java",
+        "// entry point\npackage com.example;\n\npublic class App {\n    public static void main(String[] args) {}\n}\n",
+    );
+    write_source(
+        dir.path(),
+        "com/example/Util.java",
+        "package com.example;\npublic class Util { static int helper() { return 1; } }\n",
+    );
+    write_source(
+        dir.path(),
+        "Script.java",
+        "public class Script { static public void main(final String args[]) {} }\n",
+    );
+
+    assert_eq!(
+        find_main_classes(&[dir.path().to_path_buf()]),
+        ["Script", "com.example.App"]
+    );
+}
+
+#[test]
+fn test_find_main_classes_ignores_non_entry_points() {
+    let dir = tempdir().unwrap();
+    write_source(
+        dir.path(),
+        "a/Commented.java",
+        "package a; public class Commented { /* public static void main(String[] args) {} */ }",
+    );
+    write_source(
+        dir.path(),
+        "a/Literal.java",
+        "package a; public class Literal { String s = \"public static void main(String[] x)\"; }",
+    );
+    write_source(
+        dir.path(),
+        "a/Instance.java",
+        "package a; public class Instance { public void main(String[] args) {} }",
+    );
+    write_source(
+        dir.path(),
+        "a/WrongParams.java",
+        "package a; public class WrongParams { public static void main(int argc) {} }",
+    );
+
+    assert!(find_main_classes(&[dir.path().to_path_buf()]).is_empty());
+}
+
+#[test]
+fn test_detect_main_class_returns_single_candidate() {
+    let dir = tempdir().unwrap();
+    write_source(
+        dir.path(),
+        "com/example/App.java",
+        "package com.example; public class App { public static void main(String... args) {} }",
+    );
+
+    assert_eq!(
+        detect_main_class(&[dir.path().to_path_buf()]).unwrap(),
+        "com.example.App"
+    );
+}
+
+#[test]
+fn test_detect_main_class_errors_without_candidates() {
+    let missing = PathBuf::from("/nonexistent/src/main/java");
+
+    match detect_main_class(std::slice::from_ref(&missing)) {
+        Err(BuildError::MainClassNotFound { source_dirs }) => {
+            assert_eq!(source_dirs, missing.display().to_string())
+        }
+        other => panic!("expected MainClassNotFound, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_detect_main_class_lists_all_candidates() {
+    let dir = tempdir().unwrap();
+    for class in ["B", "A"] {
+        write_source(
+            dir.path(),
+            &format!("p/{class}.java"),
+            &format!(
+                "package p; public class {class} {{ public static void main(String[] a) {{}} }}"
+            ),
+        );
+    }
+
+    match detect_main_class(&[dir.path().to_path_buf()]) {
+        Err(BuildError::AmbiguousMainClass { candidates }) => {
+            assert_eq!(candidates, ["p.A", "p.B"])
+        }
+        other => panic!("expected AmbiguousMainClass, got {other:?}"),
+    }
+}
@@ -0,0 +1,99 @@
+//! Tests for `--offline` builds, which use only POMs and JARs already in the cache
+
+mod common;
+
+use common::{MockTransport, dependency};
+use polytunnel_build::{BuildError, ClasspathBuilder};
+use polytunnel_core::{Dependency, ProjectConfig};
+use polytunnel_maven::{ArtifactStore, Coordinate, MemoryArtifactStore, jar_key};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// `app` -> `lib`
+fn transport() -> MockTransport {
+    MockTransport::default()
+        .artifact("app", &dependency("lib", ""))
+        .artifact("lib", "")
+}
+
+fn config() -> ProjectConfig {
+    let mut config = ProjectConfig::new("offline-app");
+    config.dependencies.insert(
+        "com.example:app".to_string(),
+        Dependency::Simple("1.0".to_string()),
+    );
+    config
+}
+
+fn builder(transport: MockTransport, store: &Arc<MemoryArtifactStore>) -> ClasspathBuilder {
+    ClasspathBuilder::new(config())
+        .with_maven_client(transport.client())
+        .with_artifact_store(store.clone())
+}
+
+#[tokio::test]
+async fn test_offline_uncached_dependency_fails_without_network() {
+    let temp = tempdir().unwrap();
+    let transport = transport();
+    let mut builder = builder(transport.clone(), &Arc::new(MemoryArtifactStore::new()));
+    builder.set_offline(true);
+
+    let error = builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap_err();
+
+    match error {
+        BuildError::OfflineArtifactMissing { coordinate } => {
+            assert_eq!(coordinate, "com.example:app:1.0")
+        }
+        other => panic!("expected OfflineArtifactMissing, got {other:?}"),
+    }
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_offline_uses_cache_filled_by_online_build() {
+    let temp = tempdir().unwrap();
+    let store = Arc::new(MemoryArtifactStore::new());
+    let online = builder(transport(), &store)
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+
+    let transport = transport();
+    let mut offline = builder(transport.clone(), &store);
+    offline.set_offline(true);
+    let result = offline
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(result.compile_classpath, online.compile_classpath);
+    assert_eq!(result.compile_classpath.len(), 2);
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_offline_missing_jar_names_its_coordinate() {
+    let temp = tempdir().unwrap();
+    let store = Arc::new(MemoryArtifactStore::new());
+    builder(transport(), &store)
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+    let lib = Coordinate::new("com.example", "lib", "1.0");
+    store.remove(&jar_key(&lib)).unwrap();
+
+    let mut offline = builder(transport(), &store);
+    offline.set_offline(true);
+    let error = offline
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "com.example:lib:1.0 is not in the local cache and --offline forbids downloading it"
+    );
+}
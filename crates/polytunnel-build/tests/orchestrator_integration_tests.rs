@@ -1,4 +1,6 @@
-use polytunnel_build::{BuildError, BuildOptions, BuildOrchestrator};
+use polytunnel_build::{
+    BuildError, BuildOptions, BuildOrchestrator, BuildPhase, BuildScope, TestOptions,
+};
 use polytunnel_core::{BuildConfig, ProjectConfig, ProjectInfo};
 use std::collections::HashMap;
 use std::fs;
@@ -21,6 +23,9 @@ fn create_config(root: &Path) -> ProjectConfig {
             .to_string_lossy()
             .to_string(),
         cache_dir: root.join(".polytunnel/cache").to_string_lossy().to_string(),
+        compiler_daemon: false,
+        resolve_timeout_secs: None,
+        main_class: None,
         ..BuildConfig::default()
     };
 
@@ -32,6 +37,13 @@ fn create_config(root: &Path) -> ProjectConfig {
         build,
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     }
 }
 
@@ -77,7 +89,7 @@ public class MainTest {
     );
 
     let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
-    let compiled = orchestrator.compile_sources().unwrap();
+    let compiled = orchestrator.compile_sources(None).unwrap();
     orchestrator.compile_tests().unwrap();
 
     assert_eq!(compiled, 1);
@@ -117,14 +129,35 @@ public class Main {
     let result = orchestrator
         .build(&BuildOptions {
             clean: true,
+            no_incremental: false,
             skip_tests: true,
             verbose: false,
+            explain: false,
+            compiler_args: Vec::new(),
+            test_compiler_args: Vec::new(),
+            only: Vec::new(),
+            keep_going: false,
+            fail_on_warning: false,
+            scope: Default::default(),
+            offline: false,
+            test: Default::default(),
         })
         .await
         .unwrap();
 
     assert_eq!(result.compiled_files, 1);
     assert!(result.test_result.is_none());
+    assert!(result.test_compilation.is_none());
+    assert!(result.main_compilation.as_ref().is_some_and(|c| c.success));
+    let phases: Vec<_> = result.timings.iter().map(|t| t.phase).collect();
+    assert_eq!(
+        phases,
+        vec![
+            BuildPhase::Resolve,
+            BuildPhase::Download,
+            BuildPhase::CompileMain
+        ]
+    );
     assert!(!stale.exists());
     assert!(root.join("target/classes/com/example/Main.class").exists());
 }
@@ -165,13 +198,37 @@ public class MainTest {
     let result = orchestrator
         .build(&BuildOptions {
             clean: false,
+            no_incremental: false,
             skip_tests: false,
             verbose: false,
+            explain: false,
+            compiler_args: Vec::new(),
+            test_compiler_args: Vec::new(),
+            only: Vec::new(),
+            keep_going: false,
+            fail_on_warning: false,
+            scope: Default::default(),
+            offline: false,
+            test: Default::default(),
         })
         .await
         .unwrap();
 
     assert_eq!(result.compiled_files, 1);
+    assert!(result.main_compilation.as_ref().is_some_and(|c| c.success));
+    assert!(result.test_compilation.as_ref().is_some_and(|c| c.success));
+    let phases: Vec<_> = result.timings.iter().map(|t| t.phase).collect();
+    assert_eq!(
+        phases,
+        vec![
+            BuildPhase::Resolve,
+            BuildPhase::Download,
+            BuildPhase::CompileMain,
+            BuildPhase::CompileTest,
+            BuildPhase::RunTests
+        ]
+    );
+    assert!(result.timings.iter().all(|t| t.duration <= result.duration));
     let test_result = result.test_result.expect("test result should exist");
     assert_eq!(test_result.total, 0);
     assert_eq!(test_result.passed, 0);
@@ -182,6 +239,66 @@ public class MainTest {
     );
 }
 
+#[tokio::test]
+async fn test_orchestrator_compile_tests_compiles_missing_main_classes_first() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_compile_tests_compiles_missing_main_classes_first: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/App.java", "public class App {}\n");
+    write_java(
+        root,
+        "src/test/java/AppTest.java",
+        "public class AppTest { App app = new App(); }\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    orchestrator.compile_tests().unwrap();
+
+    assert!(root.join("target/classes/App.class").exists());
+    assert!(root.join("target/test-classes/AppTest.class").exists());
+}
+
+#[tokio::test]
+async fn test_orchestrator_compile_tests_reports_unbuildable_main_sources() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_compile_tests_reports_unbuildable_main_sources: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/App.java", "public class App {\n");
+    write_java(
+        root,
+        "src/test/java/AppTest.java",
+        "public class AppTest { App app = new App(); }\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let error = orchestrator.compile_tests().unwrap_err();
+
+    match &error {
+        BuildError::MainNotBuilt { error } => {
+            assert!(matches!(**error, BuildError::CompilationFailed { .. }));
+        }
+        other => panic!("expected MainNotBuilt, got {other:?}"),
+    }
+    assert!(
+        error
+            .to_string()
+            .starts_with("Main sources must be built before tests")
+    );
+    assert!(!root.join("target/test-classes/AppTest.class").exists());
+}
+
 #[tokio::test]
 async fn test_orchestrator_compile_sources_errors_for_missing_source_dir() {
     if !java_tools_available() {
@@ -197,7 +314,7 @@ async fn test_orchestrator_compile_sources_errors_for_missing_source_dir() {
     config.build.source_dirs = vec![root.join("missing-src").to_string_lossy().to_string()];
 
     let mut orchestrator = BuildOrchestrator::new(config).unwrap();
-    let err = orchestrator.compile_sources().unwrap_err();
+    let err = orchestrator.compile_sources(None).unwrap_err();
     assert!(matches!(err, BuildError::SourceDirNotFound { .. }));
 }
 
@@ -221,8 +338,577 @@ fn test_orchestrator_clean_removes_output_directories() {
     fs::write(test_output_dir.join("stale.class"), "").unwrap();
 
     let orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
-    orchestrator.clean().unwrap();
+    let removed = orchestrator.clean().unwrap();
 
+    assert_eq!(removed, vec![output_dir.clone(), test_output_dir.clone()]);
     assert!(!output_dir.exists());
     assert!(!test_output_dir.exists());
+    assert!(orchestrator.clean().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_without_test_sources_populates_test_result() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_without_test_sources_populates_test_result: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(
+        root,
+        "src/main/java/com/example/Main.java",
+        r#"package com.example;
+public class Main {}
+"#,
+    );
+
+    fs::create_dir_all(root.join("src/test/java")).unwrap();
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let result = orchestrator
+        .build(&BuildOptions {
+            clean: false,
+            no_incremental: false,
+            skip_tests: false,
+            verbose: false,
+            explain: false,
+            compiler_args: Vec::new(),
+            test_compiler_args: Vec::new(),
+            only: Vec::new(),
+            keep_going: false,
+            fail_on_warning: false,
+            scope: Default::default(),
+            offline: false,
+            test: TestOptions {
+                pattern: Some("Nothing*".to_string()),
+                fail_fast: true,
+                ..Default::default()
+            },
+        })
+        .await
+        .unwrap();
+
+    let test_result = result.test_result.expect("test result should exist");
+    assert_eq!(test_result.total, 0);
+    assert!(result.main_compilation.is_some());
+}
+
+#[tokio::test]
+async fn test_orchestrator_rebuild_deletes_classes_of_removed_sources() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_rebuild_deletes_classes_of_removed_sources: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(
+        root,
+        "src/main/java/com/example/Main.java",
+        r#"package com.example;
+public class Main {}
+"#,
+    );
+    let helper = write_java(
+        root,
+        "src/main/java/com/example/Helper.java",
+        r#"package com.example;
+public class Helper {
+    static class Inner {}
+    Runnable task = new Runnable() { public void run() {} };
+}
+"#,
+    );
+    let classes = root.join("target/classes/com/example");
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    orchestrator.compile_sources(None).unwrap();
+    assert!(classes.join("Helper.class").exists());
+    assert!(classes.join("Helper$Inner.class").exists());
+    assert!(classes.join("Helper$1.class").exists());
+
+    fs::remove_file(&helper).unwrap();
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    orchestrator.compile_sources(None).unwrap();
+
+    assert!(classes.join("Main.class").exists());
+    assert!(!classes.join("Helper.class").exists());
+    assert!(!classes.join("Helper$Inner.class").exists());
+    assert!(!classes.join("Helper$1.class").exists());
+}
+
+#[tokio::test]
+async fn test_orchestrator_compile_sources_only_compiles_selected_files() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_compile_sources_only_compiles_selected_files: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    let selected = write_java(
+        root,
+        "src/main/java/com/example/Foo.java",
+        "package com.example;\npublic class Foo {}\n",
+    );
+    write_java(
+        root,
+        "src/main/java/com/example/Bar.java",
+        "package com.example;\npublic class Bar {}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let compiled = orchestrator
+        .compile_sources(Some(&[selected.clone(), selected]))
+        .unwrap();
+
+    assert_eq!(compiled, 1);
+    assert!(root.join("target/classes/com/example/Foo.class").exists());
+    assert!(!root.join("target/classes/com/example/Bar.class").exists());
+}
+
+#[tokio::test]
+async fn test_orchestrator_recompiles_only_touched_source() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_recompiles_only_touched_source: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(
+        root,
+        "src/main/java/com/example/Main.java",
+        "package com.example;\npublic class Main { Helper helper = new Helper(); }\n",
+    );
+    let helper = write_java(
+        root,
+        "src/main/java/com/example/Helper.java",
+        "package com.example;\npublic class Helper {}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    assert_eq!(orchestrator.compile_sources(None).unwrap(), 2);
+    assert_eq!(orchestrator.compile_sources(None).unwrap(), 0);
+
+    // Same length and timestamp; only the contents tell the edit apart
+    let modified = fs::metadata(&helper).unwrap().modified().unwrap();
+    fs::write(
+        &helper,
+        "package com.example;\npublic class Helper { private int n; }\n",
+    )
+    .unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&helper)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    assert_eq!(orchestrator.compile_sources(None).unwrap(), 1);
+    assert!(root.join("target/classes/com/example/Main.class").exists());
+    assert!(
+        root.join("target/classes/com/example/Helper.class")
+            .exists()
+    );
+}
+
+#[tokio::test]
+async fn test_orchestrator_signature_change_recompiles_dependents() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_signature_change_recompiles_dependents: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(
+        root,
+        "src/main/java/com/example/Main.java",
+        "package com.example;\npublic class Main { int v = new Helper().answer(); }\n",
+    );
+    let helper = write_java(
+        root,
+        "src/main/java/com/example/Helper.java",
+        "package com.example;\npublic class Helper { public int answer() { return 42; } }\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    assert_eq!(orchestrator.compile_sources(None).unwrap(), 2);
+
+    // A new method body keeps the signature, so Main is left alone
+    fs::write(
+        &helper,
+        "package com.example;\npublic class Helper { public int answer() { return 7; } }\n",
+    )
+    .unwrap();
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    assert_eq!(orchestrator.compile_sources(None).unwrap(), 1);
+
+    // Removing the method must break Main instead of leaving its stale class behind
+    fs::write(&helper, "package com.example;\npublic class Helper {}\n").unwrap();
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    assert!(orchestrator.compile_sources(None).is_err());
+}
+
+#[test]
+fn test_orchestrator_compile_sources_only_rejects_missing_file() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_compile_sources_only_rejects_missing_file: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    fs::create_dir_all(root.join("src/main/java")).unwrap();
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let err = orchestrator
+        .compile_sources(Some(&[root.join("src/main/java/Missing.java")]))
+        .unwrap_err();
+
+    assert!(matches!(err, BuildError::SourceFileNotFound { .. }));
+    assert!(!root.join("target/classes").exists());
+}
+
+#[test]
+fn test_orchestrator_compile_sources_only_rejects_file_outside_source_dirs() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_compile_sources_only_rejects_file_outside_source_dirs: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    fs::create_dir_all(root.join("src/main/java")).unwrap();
+    let outside = write_java(root, "scratch/Tmp.java", "public class Tmp {}\n");
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let err = orchestrator.compile_sources(Some(&[outside])).unwrap_err();
+
+    assert!(matches!(
+        err,
+        BuildError::SourceFileOutsideSourceDirs { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_with_only_option_compiles_selection() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_with_only_option_compiles_selection: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    let selected = write_java(root, "src/main/java/Foo.java", "public class Foo {}\n");
+    write_java(root, "src/main/java/Bar.java", "public class Bar {}\n");
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let result = orchestrator
+        .build(&BuildOptions {
+            clean: false,
+            no_incremental: false,
+            skip_tests: true,
+            verbose: false,
+            explain: false,
+            test: TestOptions::default(),
+            compiler_args: Vec::new(),
+            test_compiler_args: Vec::new(),
+            only: vec![selected],
+            keep_going: false,
+            fail_on_warning: false,
+            scope: Default::default(),
+            offline: false,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result.compiled_files, 1);
+    assert!(root.join("target/classes/Foo.class").exists());
+    assert!(!root.join("target/classes/Bar.class").exists());
+}
+
+fn build_options(keep_going: bool) -> BuildOptions {
+    BuildOptions {
+        clean: false,
+        no_incremental: false,
+        skip_tests: false,
+        verbose: false,
+        explain: false,
+        test: TestOptions::default(),
+        compiler_args: Vec::new(),
+        test_compiler_args: Vec::new(),
+        only: Vec::new(),
+        keep_going,
+        fail_on_warning: false,
+        scope: Default::default(),
+        offline: false,
+    }
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_no_incremental_recompiles_unchanged_sources() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_no_incremental_recompiles_unchanged_sources: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/A.java", "public class A {}\n");
+    write_java(root, "src/main/java/B.java", "public class B {}\n");
+    let options = BuildOptions {
+        skip_tests: true,
+        ..build_options(false)
+    };
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    orchestrator.build(&options).await.unwrap();
+    let state = root.join(".polytunnel/cache/build-cache.json");
+    assert!(fs::read_to_string(&state).unwrap().contains("A.java"));
+
+    // Sources are untouched, so only the reset forces both files through javac
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let result = orchestrator
+        .build(&BuildOptions {
+            no_incremental: true,
+            ..options
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result.compiled_files, 2);
+    assert!(root.join("target/classes/A.class").exists());
+    assert!(root.join("target/classes/B.class").exists());
+    let state = fs::read_to_string(&state).unwrap();
+    assert!(state.contains("A.java") && state.contains("B.java"));
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_stops_at_first_failed_step_by_default() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_stops_at_first_failed_step_by_default: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/Broken.java", "public class Broken {\n");
+    write_java(
+        root,
+        "src/test/java/Standalone.java",
+        "public class Standalone {}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let error = orchestrator.build(&build_options(false)).await.unwrap_err();
+
+    assert!(matches!(error, BuildError::CompilationFailed { .. }));
+    assert!(!root.join("target/test-classes/Standalone.class").exists());
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_keep_going_compiles_remaining_steps() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_keep_going_compiles_remaining_steps: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/Broken.java", "public class Broken {\n");
+    write_java(
+        root,
+        "src/test/java/Standalone.java",
+        "public class Standalone {}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let error = orchestrator.build(&build_options(true)).await.unwrap_err();
+
+    match error {
+        BuildError::StepsFailed { failures } => {
+            let phases: Vec<_> = failures.iter().map(|(phase, _)| *phase).collect();
+            assert_eq!(phases, [BuildPhase::CompileMain]);
+            assert!(matches!(
+                failures[0].1,
+                BuildError::CompilationFailed { .. }
+            ));
+        }
+        other => panic!("expected StepsFailed, got {other:?}"),
+    }
+    assert!(root.join("target/test-classes/Standalone.class").exists());
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_keep_going_reports_every_failed_step() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_keep_going_reports_every_failed_step: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/Broken.java", "public class Broken {\n");
+    write_java(
+        root,
+        "src/test/java/BrokenTest.java",
+        "public class BrokenTest {\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let error = orchestrator.build(&build_options(true)).await.unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.starts_with("2 build step(s) failed:"), "{message}");
+    assert!(message.contains("\n[compile-main] Compilation failed:"));
+    assert!(message.contains("\n[compile-test] Compilation failed:"));
+    assert!(message.contains("Broken.java"));
+    assert!(message.contains("BrokenTest.java"));
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_compile_scope_skips_test_sources() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_compile_scope_skips_test_sources: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_java(root, "src/main/java/App.java", "public class App {}\n");
+    write_java(
+        root,
+        "src/test/java/AppTest.java",
+        "public class AppTest {}\n",
+    );
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let result = orchestrator
+        .build(&BuildOptions {
+            scope: BuildScope::Compile,
+            offline: false,
+            ..build_options(false)
+        })
+        .await
+        .unwrap();
+
+    assert!(result.test_compilation.is_none());
+    assert!(result.test_result.is_none());
+    assert!(root.join("target/classes/App.class").exists());
+    assert!(!root.join("target/test-classes/AppTest.class").exists());
+}
+
+/// `Caller` uses a deprecated method, which javac reports under `-Xlint:deprecation`
+fn write_deprecation_warning(root: &Path) {
+    write_java(
+        root,
+        "src/main/java/Old.java",
+        "public class Old {\n    @Deprecated\n    public static void old() {}\n}\n",
+    );
+    write_java(
+        root,
+        "src/main/java/Caller.java",
+        "public class Caller {\n    void call() {\n        Old.old();\n    }\n}\n",
+    );
+}
+
+fn warning_options(fail_on_warning: bool) -> BuildOptions {
+    BuildOptions {
+        skip_tests: true,
+        compiler_args: vec!["-Xlint:deprecation".to_string()],
+        fail_on_warning,
+        ..build_options(false)
+    }
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_fail_on_warning_reports_all_warnings() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_fail_on_warning_reports_all_warnings: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_deprecation_warning(root);
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let error = orchestrator
+        .build(&warning_options(true))
+        .await
+        .unwrap_err();
+
+    let BuildError::CompilerWarnings { warnings } = error else {
+        panic!("expected CompilerWarnings, got {error:?}");
+    };
+    // Newer JDKs may add an `[options]` warning about `-source`
+    let deprecations: Vec<_> = warnings
+        .iter()
+        .filter(|warning| warning.message.contains("[deprecation]"))
+        .collect();
+    assert_eq!(deprecations.len(), 1);
+    assert!(
+        deprecations[0]
+            .file
+            .as_ref()
+            .unwrap()
+            .ends_with("Caller.java")
+    );
+    // Unlike -Werror, the sources were still compiled
+    assert!(root.join("target/classes/Caller.class").exists());
+}
+
+#[tokio::test]
+async fn test_orchestrator_build_succeeds_with_warnings_by_default() {
+    if !java_tools_available() {
+        eprintln!(
+            "skipping test_orchestrator_build_succeeds_with_warnings_by_default: java/javac not found"
+        );
+        return;
+    }
+
+    let workspace = tempdir().unwrap();
+    let root = workspace.path();
+    write_deprecation_warning(root);
+
+    let mut orchestrator = BuildOrchestrator::new(create_config(root)).unwrap();
+    let result = orchestrator.build(&warning_options(false)).await.unwrap();
+
+    let compilation = result.main_compilation.unwrap();
+    assert!(
+        compilation
+            .warnings()
+            .any(|warning| warning.message.contains("[deprecation]"))
+    );
 }
@@ -0,0 +1,97 @@
+//! Tests for `pom`-packaged dependencies, which have no jar of their own.
+
+mod common;
+
+use common::{MockTransport, dependency, pom, url};
+use polytunnel_build::{ClasspathBuilder, LockMode};
+use polytunnel_core::{Dependency, ProjectConfig};
+use polytunnel_maven::{ArtifactStore, Coordinate, MemoryArtifactStore, jar_key};
+use polytunnel_resolver::{Lockfile, ResolvedTree};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// `aggregator` (`pom` packaging) -> `core`, `util`
+fn transport() -> MockTransport {
+    let dependencies = dependency("core", "") + &dependency("util", "");
+    MockTransport::default()
+        .route(
+            url("aggregator", "pom"),
+            200,
+            pom(
+                "aggregator",
+                &format!("<packaging>pom</packaging><dependencies>{dependencies}</dependencies>"),
+            ),
+        )
+        .artifact("core", "")
+        .artifact("util", "")
+}
+
+fn config() -> ProjectConfig {
+    let mut config = ProjectConfig::new("aggregated");
+    config.dependencies.insert(
+        "com.example:aggregator".to_string(),
+        Dependency::Simple("1.0".to_string()),
+    );
+    config
+}
+
+fn jar_names(classpath: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<_> = classpath
+        .iter()
+        .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_pom_packaged_dependency_contributes_transitive_jars_only() {
+    let temp = tempdir().unwrap();
+    let transport = transport();
+    let mut builder = ClasspathBuilder::new(config())
+        .with_maven_client(transport.client())
+        .with_artifact_store(Arc::new(MemoryArtifactStore::new()));
+
+    let result = builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        jar_names(&result.compile_classpath),
+        ["core-1.0.jar", "util-1.0.jar"]
+    );
+    let requests = transport.requests();
+    assert!(requests.contains(&url("aggregator", "pom")));
+    assert!(!requests.contains(&url("aggregator", "jar")));
+}
+
+#[tokio::test]
+async fn test_pom_packaged_lockfile_entry_is_not_downloaded() {
+    let temp = tempdir().unwrap();
+    let lock_path = temp.path().join("polytunnel.lock");
+    let aggregator = Coordinate::parse("com.example:aggregator:pom:1.0").unwrap();
+    let core = Coordinate::new("com.example", "core", "1.0");
+    Lockfile::from_tree(&ResolvedTree {
+        root_dependencies: vec![aggregator.clone()],
+        all_dependencies: vec![aggregator, core.clone()],
+        graph: Default::default(),
+        conflicts: Vec::new(),
+    })
+    .save(&lock_path)
+    .unwrap();
+
+    let store = Arc::new(MemoryArtifactStore::new());
+    store.write(&jar_key(&core), b"core").unwrap();
+    let mut builder = ClasspathBuilder::new(config()).with_artifact_store(store);
+    builder.set_lock_mode(&lock_path, LockMode::Frozen);
+
+    // Frozen builds fail on any artifact missing from the cache
+    let result = builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap();
+
+    assert_eq!(jar_names(&result.compile_classpath), ["core-1.0.jar"]);
+}
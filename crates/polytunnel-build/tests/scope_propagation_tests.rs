@@ -0,0 +1,107 @@
+//! Tests for propagating dependency scopes to transitive artifacts on the classpaths.
+
+mod common;
+
+use common::{MockTransport, dependency};
+use polytunnel_build::{ClasspathBuilder, ClasspathResult};
+use polytunnel_core::{Dependency, DependencyScope, ProjectConfig};
+use polytunnel_maven::{MavenClient, MemoryArtifactStore};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn client() -> MavenClient {
+    let compile = |artifact| dependency(artifact, "<scope>compile</scope>");
+    MockTransport::default()
+        // `app` needs a JDBC driver only when running
+        .artifact(
+            "app",
+            &(compile("util") + &dependency("jdbc", "<scope>runtime</scope>")),
+        )
+        .artifact("util", "")
+        .artifact("jdbc", &compile("jdbc-core"))
+        .artifact("jdbc-core", "")
+        .artifact("logback", &compile("slf4j"))
+        .artifact("slf4j", "")
+        .artifact("junit", &compile("hamcrest"))
+        .artifact("hamcrest", "")
+        .client()
+}
+
+fn config() -> ProjectConfig {
+    let mut config = ProjectConfig::new("scoped");
+    config.dependencies.extend([
+        (
+            "com.example:app".to_string(),
+            Dependency::Simple("1.0".to_string()),
+        ),
+        (
+            "com.example:logback".to_string(),
+            Dependency::Simple("1.0".to_string()).with_scope(DependencyScope::Runtime),
+        ),
+        (
+            "com.example:junit".to_string(),
+            Dependency::Simple("1.0".to_string()).with_scope(DependencyScope::Test),
+        ),
+    ]);
+    config
+}
+
+async fn classpaths() -> ClasspathResult {
+    let temp = tempdir().unwrap();
+    let mut builder = ClasspathBuilder::new(config())
+        .with_maven_client(client())
+        .with_artifact_store(Arc::new(MemoryArtifactStore::new()));
+    builder
+        .build_classpath(temp.path().to_str().unwrap(), false)
+        .await
+        .unwrap()
+}
+
+fn artifacts(classpath: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = classpath
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .trim_end_matches("-1.0.jar")
+                .to_string()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_runtime_transitives_stay_off_compile_classpath() {
+    let result = classpaths().await;
+    assert_eq!(artifacts(&result.compile_classpath), ["app", "util"]);
+}
+
+#[tokio::test]
+async fn test_test_classpath_includes_runtime_dependencies_and_their_transitives() {
+    let result = classpaths().await;
+    assert_eq!(
+        artifacts(&result.test_classpath),
+        [
+            "app",
+            "hamcrest",
+            "jdbc",
+            "jdbc-core",
+            "junit",
+            "logback",
+            "slf4j",
+            "util"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_runtime_classpath_excludes_test_transitives() {
+    let result = classpaths().await;
+    assert_eq!(
+        artifacts(&result.runtime_classpath),
+        ["app", "jdbc", "jdbc-core", "logback", "slf4j", "util"]
+    );
+}
@@ -1,4 +1,5 @@
 use polytunnel_build::{BuildError, TestFramework, TestRunner};
+use std::collections::BTreeMap;
 use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -217,3 +218,316 @@ async fn test_run_falls_back_for_unsupported_framework() {
     assert_eq!(result.failed, 0);
     assert_eq!(result.skipped, 0);
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_junit5_passes_tag_filters_to_launcher() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    let args_file = temp.path().join("args.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\nprintf '%s\\n' '1 tests successful'\nexit 0\n",
+            args_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    let runner = TestRunner::new(
+        TestFramework::JUnit5,
+        vec![PathBuf::from(
+            "/tmp/junit-platform-console-standalone-1.10.0.jar",
+        )],
+        temp.path().to_path_buf(),
+    )
+    .with_tags(vec!["slow".to_string()], vec!["integration".to_string()]);
+
+    runner.run(None, false, false).await.unwrap();
+
+    let args = fs::read_to_string(&args_file).unwrap();
+    let args: Vec<&str> = args.lines().collect();
+    let include = args.iter().position(|a| *a == "--include-tag").unwrap();
+    assert_eq!(args[include + 1], "slow");
+    let exclude = args.iter().position(|a| *a == "--exclude-tag").unwrap();
+    assert_eq!(args[exclude + 1], "integration");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_tags_are_ignored_for_non_junit5_frameworks() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    let args_file = temp.path().join("args.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" >> '{}'\nprintf '%s\\n' 'OK (1 test)'\nexit 0\n",
+            args_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    for framework in [TestFramework::JUnit4, TestFramework::TestNG] {
+        let runner = TestRunner::new(framework, vec![], temp.path().to_path_buf())
+            .with_tags(vec!["slow".to_string()], vec!["integration".to_string()]);
+
+        let warning = runner.ignored_tags_warning().unwrap();
+        assert!(warning.contains(framework.name()));
+        runner.run(None, false, false).await.unwrap();
+    }
+
+    let args = fs::read_to_string(&args_file).unwrap();
+    assert!(args.contains("org.junit.runner.JUnitCore"));
+    assert!(args.contains("org.testng.TestNG"));
+    for tag_arg in ["--include-tag", "--exclude-tag", "slow", "integration"] {
+        assert!(!args.lines().any(|arg| arg == tag_arg));
+    }
+}
+
+#[test]
+fn test_no_tag_warning_for_junit5_or_without_tags() {
+    let junit5 = TestRunner::new(TestFramework::JUnit5, vec![], PathBuf::from("classes"))
+        .with_tags(vec!["slow".to_string()], vec![]);
+    assert_eq!(junit5.ignored_tags_warning(), None);
+
+    let untagged = TestRunner::new(TestFramework::JUnit4, vec![], PathBuf::from("classes"));
+    assert_eq!(untagged.ignored_tags_warning(), None);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_junit5_uses_configured_launcher_and_passthrough_args() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    let args_file = temp.path().join("args.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\nprintf '%s\\n' '1 tests successful'\nexit 0\n",
+            args_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    let launcher = PathBuf::from("/cache/junit-platform-console-standalone-1.10.2.jar");
+    let runner = TestRunner::new(
+        TestFramework::JUnit5,
+        vec![PathBuf::from("/tmp/junit-jupiter-api-5.10.2.jar")],
+        temp.path().to_path_buf(),
+    )
+    .with_console_launcher(Some(launcher.clone()))
+    .with_launcher_args(vec![
+        "--details=verbose".to_string(),
+        "--disable-banner".to_string(),
+    ]);
+
+    runner.run(None, false, false).await.unwrap();
+
+    let args = fs::read_to_string(&args_file).unwrap();
+    let args: Vec<&str> = args.lines().collect();
+    assert_eq!(args[..2], ["-jar", launcher.to_str().unwrap()]);
+    let details = args.iter().position(|a| *a == "--details=verbose").unwrap();
+    assert_eq!(args[details + 1], "--disable-banner");
+    let class = args.iter().position(|a| *a == "-c").unwrap();
+    assert!(details < class);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_junit5_prefers_launcher_on_classpath() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    let args_file = temp.path().join("args.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\nprintf '%s\\n' '1 tests successful'\nexit 0\n",
+            args_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    let runner = TestRunner::new(
+        TestFramework::JUnit5,
+        vec![PathBuf::from(
+            "/tmp/junit-platform-console-standalone-1.10.0.jar",
+        )],
+        temp.path().to_path_buf(),
+    )
+    .with_console_launcher(Some(PathBuf::from(
+        "/cache/junit-platform-console-standalone-1.10.2.jar",
+    )));
+
+    runner.run(None, false, false).await.unwrap();
+
+    let args = fs::read_to_string(&args_file).unwrap();
+    assert_eq!(
+        args.lines().nth(1),
+        Some("/tmp/junit-platform-console-standalone-1.10.0.jar")
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_junit5_with_classes_skips_discovery() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    write_class_file(temp.path(), "com/example/Checks.class");
+    let args_file = temp.path().join("args.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\nprintf '%s\\n' '1 tests successful'\nexit 0\n",
+            args_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    let runner = TestRunner::new(
+        TestFramework::JUnit5,
+        vec![PathBuf::from(
+            "/tmp/junit-platform-console-standalone-1.10.0.jar",
+        )],
+        temp.path().to_path_buf(),
+    )
+    .with_classes(vec!["com.example.Checks".to_string()]);
+
+    runner.run(None, false, false).await.unwrap();
+
+    let args = fs::read_to_string(&args_file).unwrap();
+    let args: Vec<&str> = args.lines().collect();
+    let selectors: Vec<&str> = args
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| **a == "-c")
+        .map(|(i, _)| args[i + 1])
+        .collect();
+    assert_eq!(selectors, ["com.example.Checks"]);
+}
+
+#[tokio::test]
+async fn test_run_with_missing_class_suggests_same_simple_name() {
+    let temp = tempdir().unwrap();
+    write_class_file(temp.path(), "com/example/app/FooTest.class");
+
+    let runner = TestRunner::new(TestFramework::JUnit5, vec![], temp.path().to_path_buf())
+        .with_classes(vec!["com.example.FooTest".to_string()]);
+    let err = runner.run(None, false, false).await.unwrap_err();
+
+    match &err {
+        BuildError::TestClassNotFound { class, similar, .. } => {
+            assert_eq!(class, "com.example.FooTest");
+            assert_eq!(similar, &["com.example.app.FooTest".to_string()]);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(
+        err.to_string()
+            .contains("did you mean com.example.app.FooTest?")
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_junit5_sets_env_on_test_jvm() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    let env_file = temp.path().join("env.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$SPRING_PROFILES_ACTIVE\" \"$DB_URL\" > '{}'\nprintf '%s\\n' '1 tests successful'\nexit 0\n",
+            env_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    let runner = TestRunner::new(
+        TestFramework::JUnit5,
+        vec![PathBuf::from(
+            "/tmp/junit-platform-console-standalone-1.10.0.jar",
+        )],
+        temp.path().to_path_buf(),
+    )
+    .with_env(BTreeMap::from([
+        ("SPRING_PROFILES_ACTIVE".to_string(), "test".to_string()),
+        ("DB_URL".to_string(), "jdbc:h2:mem:test".to_string()),
+    ]));
+
+    runner.run(None, false, false).await.unwrap();
+
+    let env = fs::read_to_string(&env_file).unwrap();
+    assert_eq!(
+        env.lines().collect::<Vec<_>>(),
+        ["test", "jdbc:h2:mem:test"]
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_junit5_puts_jvm_args_before_jar() {
+    let temp = tempdir().unwrap();
+    let bin_dir = temp.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_class_file(temp.path(), "com/example/AppTest.class");
+    let args_file = temp.path().join("args.txt");
+
+    create_fake_java(
+        &bin_dir,
+        &format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\nprintf '%s\\n' '1 tests successful'\nexit 0\n",
+            args_file.display()
+        ),
+    );
+
+    let _lock = env_lock().lock().await;
+    let _path_guard = prepend_path(&bin_dir);
+
+    let launcher = PathBuf::from("/tmp/junit-platform-console-standalone-1.10.0.jar");
+    let runner = TestRunner::new(
+        TestFramework::JUnit5,
+        vec![launcher.clone()],
+        temp.path().to_path_buf(),
+    )
+    .with_jvm_args(vec!["-Dfoo=bar".to_string(), "-Xmx512m".to_string()]);
+
+    runner.run(None, false, false).await.unwrap();
+
+    let args = fs::read_to_string(&args_file).unwrap();
+    let args: Vec<&str> = args.lines().collect();
+    assert_eq!(
+        args[..4],
+        ["-Dfoo=bar", "-Xmx512m", "-jar", launcher.to_str().unwrap()]
+    );
+}
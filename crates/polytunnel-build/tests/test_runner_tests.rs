@@ -1,6 +1,6 @@
 //! Tests for TestRunner framework detection and pattern matching
 
-use polytunnel_build::{TestFramework, TestRunner};
+use polytunnel_build::{TestFailure, TestFramework, TestResult, TestRunner};
 use std::path::PathBuf;
 
 #[test]
@@ -49,3 +49,278 @@ fn test_matches_test_pattern() {
     assert!(runner.matches_test_pattern("TestApp"));
     assert!(!runner.matches_test_pattern("App"));
 }
+
+#[test]
+fn test_merge_partial_results() {
+    let mut merged = TestResult {
+        total: 3,
+        passed: 2,
+        failed: 1,
+        skipped: 0,
+        failures: vec![TestFailure {
+            class_name: "com.example.ATest".to_string(),
+            test_name: "fails".to_string(),
+            message: "expected 1".to_string(),
+            stacktrace: String::new(),
+            output: String::new(),
+        }],
+    };
+    let other = TestResult {
+        total: 4,
+        passed: 2,
+        failed: 1,
+        skipped: 1,
+        failures: vec![TestFailure {
+            class_name: "com.example.BTest".to_string(),
+            test_name: "alsoFails".to_string(),
+            message: "expected 2".to_string(),
+            stacktrace: String::new(),
+            output: String::new(),
+        }],
+    };
+
+    merged.merge(other);
+
+    assert_eq!(merged.total, 7);
+    assert_eq!(merged.passed, 4);
+    assert_eq!(merged.failed, 2);
+    assert_eq!(merged.skipped, 1);
+    let classes: Vec<_> = merged.failures.iter().map(|f| &f.class_name).collect();
+    assert_eq!(classes, ["com.example.ATest", "com.example.BTest"]);
+}
+
+#[test]
+fn test_partition_classes_round_robin() {
+    let classes: Vec<String> = ["ATest", "BTest", "CTest", "DTest", "ETest"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let groups = TestRunner::partition_classes(&classes, 2);
+    assert_eq!(
+        groups,
+        vec![vec!["ATest", "CTest", "ETest"], vec!["BTest", "DTest"]]
+    );
+
+    // Never more groups than classes, and never zero groups
+    assert_eq!(TestRunner::partition_classes(&classes, 10).len(), 5);
+    assert_eq!(TestRunner::partition_classes(&classes, 0).len(), 1);
+}
+
+const JUNIT_XML_REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="JUnit Jupiter" tests="3" skipped="0" failures="1" errors="1">
+<testcase name="passes()" classname="com.example.CalcTest" time="0.002">
+<system-out><![CDATA[
+unique-id: [engine:junit-jupiter]/[class:com.example.CalcTest]/[method:passes()]
+display-name: passes()
+]]></system-out>
+<system-out><![CDATA[noisy passing output
+]]></system-out>
+</testcase>
+<testcase name="fails()" classname="com.example.CalcTest" time="0.004">
+<failure message="expected: &lt;3&gt; but was: &lt;4&gt;" type="org.opentest4j.AssertionFailedError">org.opentest4j.AssertionFailedError: expected: &lt;3&gt; but was: &lt;4&gt;
+	at com.example.CalcTest.fails(CalcTest.java:12)</failure>
+<system-out><![CDATA[
+unique-id: [engine:junit-jupiter]/[class:com.example.CalcTest]/[method:fails()]
+display-name: fails()
+]]></system-out>
+<system-out><![CDATA[computing 1 + 2
+result = 4
+]]></system-out>
+<system-err><![CDATA[warning: rounding
+]]></system-err>
+</testcase>
+<testcase name="throws()" classname="com.example.IoTest" time="0.001">
+<error message="disk full" type="java.io.IOException">java.io.IOException: disk full</error>
+</testcase>
+</testsuite>
+"#;
+
+#[test]
+fn test_parse_junit_xml_failures_keeps_failing_tests_only() {
+    let failures = TestRunner::parse_junit_xml_failures(JUNIT_XML_REPORT);
+
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[0].class_name, "com.example.CalcTest");
+    assert_eq!(failures[0].test_name, "fails()");
+    assert_eq!(failures[0].message, "expected: <3> but was: <4>");
+    assert!(
+        failures[0]
+            .stacktrace
+            .contains("at com.example.CalcTest.fails")
+    );
+    assert_eq!(failures[1].test_name, "throws()");
+    assert_eq!(failures[1].message, "disk full");
+}
+
+#[test]
+fn test_parse_junit_xml_failures_attaches_captured_output() {
+    let failures = TestRunner::parse_junit_xml_failures(JUNIT_XML_REPORT);
+
+    assert_eq!(
+        failures[0].output,
+        "computing 1 + 2\nresult = 4\nwarning: rounding"
+    );
+    assert!(!failures[0].output.contains("unique-id"));
+    assert!(failures[1].output.is_empty());
+    assert!(
+        failures
+            .iter()
+            .all(|f| !f.output.contains("noisy passing output"))
+    );
+}
+
+#[test]
+fn test_read_xml_reports_collects_failures_from_report_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("TEST-junit-jupiter.xml"), JUNIT_XML_REPORT).unwrap();
+    std::fs::write(dir.path().join("launcher.log"), "not a report").unwrap();
+
+    let failures = TestRunner::read_xml_reports(dir.path()).unwrap();
+
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[0].class_name, "com.example.CalcTest");
+    assert_eq!(failures[0].test_name, "fails()");
+    assert_eq!(failures[0].message, "expected: <3> but was: <4>");
+    assert!(
+        failures[0]
+            .stacktrace
+            .starts_with("org.opentest4j.AssertionFailedError")
+    );
+    assert_eq!(failures[1].class_name, "com.example.IoTest");
+    assert_eq!(failures[1].stacktrace, "java.io.IOException: disk full");
+}
+
+#[test]
+fn test_parse_junit_xml_failures_without_failures() {
+    let xml =
+        r#"<testsuite tests="1"><testcase name="ok()" classname="com.example.ATest"/></testsuite>"#;
+    assert!(TestRunner::parse_junit_xml_failures(xml).is_empty());
+}
+
+#[test]
+fn test_has_console_launcher() {
+    assert!(TestRunner::has_console_launcher(&[PathBuf::from(
+        "/cache/junit-platform-console-standalone-1.10.2.jar"
+    )]));
+    assert!(!TestRunner::has_console_launcher(&[PathBuf::from(
+        "/cache/junit-jupiter-api-5.10.2.jar"
+    )]));
+}
+
+fn class_result(passed: usize, failed: usize) -> TestResult {
+    TestResult {
+        total: passed + failed,
+        passed,
+        failed,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_fail_fast_skips_classes_after_first_failure() {
+    let classes: Vec<String> = ["ATest", "BTest", "CTest"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let mut launched = Vec::new();
+
+    let result = TestRunner::run_classes_in_order(&classes, true, |class| {
+        launched.push(class.clone());
+        let result = if class == "ATest" {
+            class_result(1, 1)
+        } else {
+            class_result(3, 0)
+        };
+        async move { Ok(result) }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(launched, ["ATest"]);
+    assert_eq!(result.total, 4);
+    assert_eq!(result.passed, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.skipped, 2);
+}
+
+#[tokio::test]
+async fn test_without_fail_fast_every_class_runs() {
+    let classes: Vec<String> = ["ATest", "BTest"].iter().map(|s| s.to_string()).collect();
+
+    let result = TestRunner::run_classes_in_order(&classes, false, |class| async move {
+        Ok(if class == "ATest" {
+            class_result(1, 1)
+        } else {
+            class_result(3, 0)
+        })
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(result.passed, 4);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.skipped, 0);
+}
+
+#[test]
+fn test_parse_junit4_output_with_failures() {
+    let stdout = "JUnit version 4.13.2
+..E.E
+Time: 0.012
+There were 2 failures:
+1) adds(com.example.CalcTest)
+java.lang.AssertionError: expected:<3> but was:<4>
+\tat org.junit.Assert.fail(Assert.java:89)
+\tat com.example.CalcTest.adds(CalcTest.java:12)
+2) reads(com.example.IoTest)
+java.io.IOException
+\tat com.example.IoTest.reads(IoTest.java:8)
+
+FAILURES!!!
+Tests run: 4,  Failures: 2
+
+";
+    let result = TestRunner::parse_junit4_output(stdout);
+
+    assert_eq!(result.total, 4);
+    assert_eq!(result.passed, 2);
+    assert_eq!(result.failed, 2);
+    assert_eq!(result.failures.len(), 2);
+    assert_eq!(result.failures[0].class_name, "com.example.CalcTest");
+    assert_eq!(result.failures[0].test_name, "adds");
+    assert_eq!(result.failures[0].message, "expected:<3> but was:<4>");
+    assert!(
+        result.failures[0]
+            .stacktrace
+            .ends_with("(CalcTest.java:12)")
+    );
+    assert_eq!(result.failures[1].message, "java.io.IOException");
+}
+
+#[test]
+fn test_parse_junit4_output_ok() {
+    let result = TestRunner::parse_junit4_output(
+        "JUnit version 4.13.2\n...\nTime: 0.01\n\nOK (3 tests)\n\n",
+    );
+
+    assert_eq!(result.total, 3);
+    assert_eq!(result.passed, 3);
+    assert!(result.failures.is_empty());
+}
+
+#[test]
+fn test_parse_testng_output() {
+    let stdout = "
+===============================================
+Command line suite
+Total tests run: 5, Passes: 3, Failures: 1, Skips: 1
+===============================================
+";
+    let result = TestRunner::parse_testng_output(stdout);
+
+    assert_eq!(result.total, 5);
+    assert_eq!(result.passed, 3);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.skipped, 1);
+}
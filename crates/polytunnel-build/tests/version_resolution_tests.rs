@@ -0,0 +1,142 @@
+//! Tests for filling in versions of versionless root dependencies.
+
+mod common;
+
+use common::{BASE_URL, MockTransport};
+use polytunnel_build::{BuildError, ClasspathBuilder};
+use polytunnel_maven::{Coordinate, MavenClient};
+
+fn client() -> MavenClient {
+    let routes = vec![
+        (
+            format!("{BASE_URL}/org/test/test-bom/1.0.0/test-bom-1.0.0.pom"),
+            200,
+            r#"<project>
+  <groupId>org.test</groupId>
+  <artifactId>test-bom</artifactId>
+  <version>1.0.0</version>
+  <packaging>pom</packaging>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>org.test</groupId>
+        <artifactId>managed</artifactId>
+        <version>3.1.0</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+</project>"#,
+        ),
+        (
+            format!("{BASE_URL}/org/test/released/maven-metadata.xml"),
+            200,
+            r#"<metadata>
+  <groupId>org.test</groupId>
+  <artifactId>released</artifactId>
+  <versioning>
+    <latest>2.1.0-SNAPSHOT</latest>
+    <release>2.0.0</release>
+  </versioning>
+</metadata>"#,
+        ),
+    ];
+    routes
+        .into_iter()
+        .fold(
+            MockTransport::default(),
+            |transport, (url, status, body)| transport.route(url, status, body),
+        )
+        .client()
+}
+
+#[tokio::test]
+async fn test_versioned_coordinates_are_unchanged() {
+    let coords = vec![Coordinate::new("org.test", "pinned", "1.2.3")];
+
+    let resolved = ClasspathBuilder::resolve_missing_versions(&client(), coords.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(resolved, coords);
+}
+
+#[tokio::test]
+async fn test_missing_version_taken_from_bom() {
+    let coords = vec![
+        Coordinate::new("org.test", "test-bom", "1.0.0"),
+        Coordinate::new("org.test", "managed", ""),
+    ];
+
+    let resolved = ClasspathBuilder::resolve_missing_versions(&client(), coords)
+        .await
+        .unwrap();
+
+    assert_eq!(resolved[1].version, "3.1.0");
+}
+
+#[tokio::test]
+async fn test_missing_version_taken_from_metadata_release() {
+    let coords = vec![Coordinate::new("org.test", "released", "")];
+
+    let resolved = ClasspathBuilder::resolve_missing_versions(&client(), coords)
+        .await
+        .unwrap();
+
+    assert_eq!(resolved[0].version, "2.0.0");
+}
+
+#[tokio::test]
+async fn test_missing_version_without_source_fails() {
+    let coords = vec![Coordinate::new("org.test", "unknown", "")];
+
+    let err = ClasspathBuilder::resolve_missing_versions(&client(), coords)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, BuildError::MissingVersion { ref coordinate } if coordinate == "org.test:unknown")
+    );
+}
+
+#[tokio::test]
+async fn test_failed_metadata_lookup_names_the_coordinate() {
+    let client = MockTransport::default()
+        .route(
+            format!("{BASE_URL}/org/test/broken/maven-metadata.xml"),
+            500,
+            "boom",
+        )
+        .client()
+        .with_max_retries(0);
+    let coords = vec![Coordinate::new("org.test", "broken", "")];
+
+    let err = ClasspathBuilder::resolve_missing_versions(&client, coords)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, BuildError::VersionLookupFailed { ref coordinate, .. } if coordinate == "org.test:broken"),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_offline_metadata_lookup_is_not_a_missing_version() {
+    let client = client().with_offline(true);
+    let coords = vec![Coordinate::new("org.test", "released", "")];
+
+    let err = ClasspathBuilder::resolve_missing_versions(&client, coords)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, BuildError::OfflineArtifactMissing { ref coordinate } if coordinate == "org.test:released"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_parse_coordinate_without_version_is_empty() {
+    let coord = ClasspathBuilder::parse_coordinate("org.test:lib").unwrap();
+    assert!(coord.version.is_empty());
+}
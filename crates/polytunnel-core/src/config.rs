@@ -1,11 +1,29 @@
 //! Configuration types for polytunnel
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use crate::error::Result;
 
+/// File name of the project configuration
+pub const CONFIG_FILE_NAME: &str = "polytunnel.toml";
+
+/// Find the nearest directory at or above `start` containing `polytunnel.toml`
+///
+/// # Errors
+///
+/// * `CoreError::ProjectRootNotFound` - If no ancestor up to the filesystem root has one
+pub fn find_project_root(start: &Path) -> Result<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(CONFIG_FILE_NAME).is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| crate::error::CoreError::ProjectRootNotFound {
+            start: start.display().to_string(),
+        })
+}
+
 /// Project configuration (polytunnel.toml)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
@@ -15,13 +33,53 @@ pub struct ProjectConfig {
     #[serde(default)]
     pub build: BuildConfig,
     /// Dependency map: `"groupId:artifactId"` → version or detailed spec
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     pub dependencies: HashMap<String, Dependency>,
     /// Additional Maven repositories (besides Maven Central)
     #[serde(default)]
     pub repositories: Vec<Repository>,
+    /// HTTP settings applied to every repository request
+    #[serde(default, skip_serializing_if = "HttpConfig::is_empty")]
+    pub http: HttpConfig,
+    /// Test execution settings
+    #[serde(default, skip_serializing_if = "TestConfig::is_default")]
+    pub test: TestConfig,
+    /// `pt run` settings
+    #[serde(default, skip_serializing_if = "RunConfig::is_default")]
+    pub run: RunConfig,
+    /// `[dev-dependencies]`: test-scoped dependencies, merged into `dependencies` by [`ProjectConfig::load`]
+    #[serde(
+        default,
+        rename = "dev-dependencies",
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub dev_dependencies: HashMap<String, Dependency>,
+    /// `[runtime-dependencies]`: runtime-scoped dependencies, merged into `dependencies` by [`ProjectConfig::load`]
+    #[serde(
+        default,
+        rename = "runtime-dependencies",
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub runtime_dependencies: HashMap<String, Dependency>,
+    /// `[dependency-management]`: `"groupId:artifactId"` → version pinned wherever the
+    /// artifact appears transitively, without making it a direct dependency
+    #[serde(
+        default,
+        rename = "dependency-management",
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub dependency_management: HashMap<String, String>,
+    /// License policy checked by `pt deps --licenses`
+    #[serde(default, skip_serializing_if = "LicensesConfig::is_empty")]
+    pub licenses: LicensesConfig,
 }
 
+/// Names of the tables dependencies can be declared in
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "runtime-dependencies"];
+
 /// Basic project metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectInfo {
@@ -38,7 +96,7 @@ pub struct ProjectInfo {
 pub enum Dependency {
     /// Simple format: `"groupId:artifactId" = "1.4.14"`
     Simple(String),
-    /// Detailed format with explicit scope and optional flag
+    /// Detailed format with explicit scope, optional flag, and exclusions
     Detailed {
         /// Artifact version string
         version: String,
@@ -46,8 +104,14 @@ pub enum Dependency {
         #[serde(default)]
         scope: DependencyScope,
         /// Whether the dependency is optional
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
         optional: bool,
+        /// Transitives (`"groupId:artifactId"`) removed from this dependency's subtree
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        exclusions: Vec<String>,
+        /// Also resolve the optional dependencies declared in this dependency's POM
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        include_optional: bool,
     },
 }
 
@@ -66,13 +130,284 @@ pub enum DependencyScope {
     Provided,
 }
 
+/// Where repositories added for a single run go in the lookup order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepositoryPriority {
+    /// Try them before the configured repositories
+    #[default]
+    First,
+    /// Try them after the configured repositories
+    Last,
+}
+
 /// External Maven repository definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Repository {
     /// Human-readable repository name (e.g. `"central"`)
     pub name: String,
     /// Repository base URL (e.g. `"https://repo1.maven.org/maven2/"`)
     pub url: String,
+    /// Whether `-SNAPSHOT` artifacts are fetched from this repository (default: `true`)
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub snapshots_enabled: bool,
+    /// Whether release artifacts are fetched from this repository (default: `true`)
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub releases_enabled: bool,
+    /// User name for HTTP Basic authentication; `${env.NAME}` is read from the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Password or access token for HTTP Basic authentication; `${env.NAME}` is read from the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl std::fmt::Debug for Repository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print a literal password
+        f.debug_struct("Repository")
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("snapshots_enabled", &self.snapshots_enabled)
+            .field("releases_enabled", &self.releases_enabled)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl Repository {
+    /// Create a repository serving both snapshots and releases
+    pub fn new(name: &str, url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            url: url.to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
+        }
+    }
+
+    /// Maven Central, used when no repositories are configured
+    pub fn central() -> Self {
+        Self::new("central", "https://repo1.maven.org/maven2/")
+    }
+
+    /// Whether artifacts of the given version may be fetched from this repository
+    ///
+    /// Versions ending in `-SNAPSHOT` are governed by `snapshots_enabled`,
+    /// everything else by `releases_enabled`.
+    pub fn serves_version(&self, version: &str) -> bool {
+        if version.ends_with("-SNAPSHOT") {
+            self.snapshots_enabled
+        } else {
+            self.releases_enabled
+        }
+    }
+
+    /// User name and password for HTTP Basic authentication, if either is set
+    ///
+    /// `${env.NAME}` placeholders are replaced with the environment variable
+    /// `NAME`, so secrets need not be committed to `polytunnel.toml`. A
+    /// missing part is empty, e.g. for token-only repositories.
+    ///
+    /// # Errors
+    ///
+    /// * `CoreError::MissingEnvVar` - If a placeholder names an unset variable
+    pub fn credentials(&self) -> Result<Option<(String, String)>> {
+        if self.username.is_none() && self.password.is_none() {
+            return Ok(None);
+        }
+        let expand = |value: &Option<String>| match value {
+            Some(value) => expand_env(value),
+            None => Ok(String::new()),
+        };
+        Ok(Some((expand(&self.username)?, expand(&self.password)?)))
+    }
+}
+
+/// Replace every `${env.NAME}` in `value` with the environment variable `NAME`
+///
+/// # Errors
+///
+/// * `CoreError::MissingEnvVar` - If a referenced variable is not set
+pub fn expand_env(value: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${env.") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + "${env.".len()..start + len];
+        let var = std::env::var(name).map_err(|_| crate::error::CoreError::MissingEnvVar {
+            name: name.to_string(),
+        })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var);
+        rest = &rest[start + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Serialize a map with its keys in sorted order so output is stable
+fn serialize_sorted<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// HTTP client settings (`[http]` table)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// `User-Agent` override (default: `polytunnel/<version>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Extra headers sent with every request (e.g. a repository-mandated `X-Api-Key`)
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub headers: HashMap<String, String>,
+    /// Cap on the combined JAR download rate in bytes per second (default: unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_download_bytes_per_sec: Option<u64>,
+    /// Maximum number of POMs fetched at once while resolving (default: `16`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolve_concurrency: Option<usize>,
+    /// Maximum number of JARs downloaded at once (default: `6`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_concurrency: Option<usize>,
+}
+
+/// POMs fetched at once when `[http] resolve_concurrency` is not set
+pub const DEFAULT_RESOLVE_CONCURRENCY: usize = 16;
+
+/// JARs downloaded at once when `[http] download_concurrency` is not set
+pub const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 6;
+
+impl HttpConfig {
+    /// Whether no HTTP settings are configured
+    pub fn is_empty(&self) -> bool {
+        self.user_agent.is_none()
+            && self.headers.is_empty()
+            && self.max_download_bytes_per_sec.is_none()
+            && self.resolve_concurrency.is_none()
+            && self.download_concurrency.is_none()
+    }
+
+    /// Effective limit on concurrent POM fetches (at least 1)
+    pub fn resolve_concurrency(&self) -> usize {
+        self.resolve_concurrency
+            .unwrap_or(DEFAULT_RESOLVE_CONCURRENCY)
+            .max(1)
+    }
+
+    /// Effective limit on concurrent JAR downloads (at least 1)
+    pub fn download_concurrency(&self) -> usize {
+        self.download_concurrency
+            .unwrap_or(DEFAULT_DOWNLOAD_CONCURRENCY)
+            .max(1)
+    }
+}
+
+/// License policy (`[licenses]` table)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LicensesConfig {
+    /// License names (as declared in POM `<license><name>`) or URLs that dependencies may use
+    ///
+    /// Matching ignores case and surrounding whitespace. An empty list accepts
+    /// every declared license.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+}
+
+impl LicensesConfig {
+    /// Whether no license policy is configured
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty()
+    }
+
+    /// Whether a license with the given name or URL is allowed
+    pub fn allows(&self, name_or_url: &str) -> bool {
+        self.allowed.is_empty()
+            || self
+                .allowed
+                .iter()
+                .any(|allowed| allowed.trim().eq_ignore_ascii_case(name_or_url.trim()))
+    }
+}
+
+/// Test execution settings (`[test]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestConfig {
+    /// Number of test launcher processes run concurrently (default: `1`)
+    #[serde(default = "default_test_parallel")]
+    pub parallel: usize,
+    /// Version of `junit-platform-console-standalone` fetched when JUnit 5 tests
+    /// run without the launcher on the test classpath (default: `1.10.2`)
+    #[serde(default = "default_junit_console_version")]
+    pub junit_console_version: String,
+    /// `[test.env]`: environment variables set for the test JVM
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Arguments passed to the test JVM, e.g. `-Xmx512m` or `--add-opens ...`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jvm_args: Vec<String>,
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self {
+            parallel: default_test_parallel(),
+            junit_console_version: default_junit_console_version(),
+            env: BTreeMap::new(),
+            jvm_args: Vec::new(),
+        }
+    }
+}
+
+impl TestConfig {
+    /// Whether all settings have their default values
+    pub fn is_default(&self) -> bool {
+        self.parallel == default_test_parallel()
+            && self.junit_console_version == default_junit_console_version()
+            && self.env.is_empty()
+            && self.jvm_args.is_empty()
+    }
+}
+
+/// `pt run` settings (`[run]` table)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunConfig {
+    /// Arguments passed to the JVM before the main class, e.g. `-Xmx512m`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jvm_args: Vec<String>,
+}
+
+impl RunConfig {
+    /// Whether all settings have their default values
+    pub fn is_default(&self) -> bool {
+        self.jvm_args.is_empty()
+    }
+}
+
+fn default_test_parallel() -> usize {
+    1
+}
+
+fn default_junit_console_version() -> String {
+    "1.10.2".to_string()
 }
 
 /// Build configuration
@@ -94,6 +429,10 @@ pub struct BuildConfig {
     #[serde(default = "default_test_output_dir")]
     pub test_output_dir: String,
 
+    /// Output directory for `pt javadoc` HTML (default: `"target/javadoc"`)
+    #[serde(default = "default_javadoc_dir")]
+    pub javadoc_dir: String,
+
     /// Additional arguments passed to `javac` for main sources
     #[serde(default)]
     pub compiler_args: Vec<String>,
@@ -102,6 +441,12 @@ pub struct BuildConfig {
     #[serde(default)]
     pub test_compiler_args: Vec<String>,
 
+    /// Source file encoding passed to `javac` as `-encoding` (default: `"UTF-8"`)
+    ///
+    /// Ignored when the compiler arguments already contain `-encoding`.
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+
     /// Test framework hint (`"auto"`, `"junit5"`, `"junit4"`, `"testng"`)
     #[serde(default = "default_test_framework")]
     pub test_framework: String,
@@ -109,6 +454,22 @@ pub struct BuildConfig {
     /// Directory for caching downloaded JARs and build metadata (default: `".polytunnel/cache"`)
     #[serde(default = "default_cache_dir")]
     pub cache_dir: String,
+
+    /// Reuse one long-lived JVM for all `javac` invocations of a build (default: `false`)
+    #[serde(default)]
+    pub compiler_daemon: bool,
+
+    /// Store identical cached JARs once, hard-linked from each coordinate (default: `false`)
+    #[serde(default)]
+    pub cache_dedup: bool,
+
+    /// Overall deadline in seconds for dependency resolution (default: no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolve_timeout_secs: Option<u64>,
+
+    /// Fully qualified class run by `pt run` when none is given (default: detected from sources)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub main_class: Option<String>,
 }
 
 impl Default for BuildConfig {
@@ -118,14 +479,106 @@ impl Default for BuildConfig {
             test_source_dirs: default_test_source_dirs(),
             output_dir: default_output_dir(),
             test_output_dir: default_test_output_dir(),
+            javadoc_dir: default_javadoc_dir(),
             compiler_args: Vec::new(),
             test_compiler_args: Vec::new(),
+            encoding: default_encoding(),
             test_framework: default_test_framework(),
             cache_dir: default_cache_dir(),
+            compiler_daemon: false,
+            cache_dedup: false,
+            resolve_timeout_secs: None,
+            main_class: None,
         }
     }
 }
 
+/// Token in `[build]` directory settings replaced by the project root
+pub const PROJECT_BASEDIR_TOKEN: &str = "${project.basedir}";
+
+/// `[build]` directories resolved against the project root
+///
+/// Produced by [`BuildConfig::resolve_paths`]; [`ProjectConfig::load`] applies
+/// it so the build does not depend on the working directory `pt` runs from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPaths {
+    /// Directory containing `polytunnel.toml`
+    pub root: PathBuf,
+    /// Resolved `build.source_dirs`
+    pub source_dirs: Vec<PathBuf>,
+    /// Resolved `build.test_source_dirs`
+    pub test_source_dirs: Vec<PathBuf>,
+    /// Resolved `build.output_dir`
+    pub output_dir: PathBuf,
+    /// Resolved `build.test_output_dir`
+    pub test_output_dir: PathBuf,
+    /// Resolved `build.javadoc_dir`
+    pub javadoc_dir: PathBuf,
+    /// Resolved `build.cache_dir`
+    pub cache_dir: PathBuf,
+}
+
+impl ResolvedPaths {
+    /// Write the resolved directories back into `build`
+    pub fn apply(&self, build: &mut BuildConfig) {
+        let to_string = |path: &PathBuf| path.to_string_lossy().into_owned();
+        build.source_dirs = self.source_dirs.iter().map(to_string).collect();
+        build.test_source_dirs = self.test_source_dirs.iter().map(to_string).collect();
+        build.output_dir = to_string(&self.output_dir);
+        build.test_output_dir = to_string(&self.test_output_dir);
+        build.javadoc_dir = to_string(&self.javadoc_dir);
+        build.cache_dir = to_string(&self.cache_dir);
+    }
+}
+
+impl BuildConfig {
+    /// Resolve every directory setting against `root`
+    ///
+    /// `${project.basedir}` is replaced by `root`, relative paths are joined
+    /// onto it, and `.`/`..` components are normalized away. Absolute paths
+    /// are kept as they are (apart from normalization).
+    pub fn resolve_paths(&self, root: &Path) -> ResolvedPaths {
+        let resolve = |dir: &String| resolve_dir(root, dir);
+        ResolvedPaths {
+            root: root.to_path_buf(),
+            source_dirs: self.source_dirs.iter().map(resolve).collect(),
+            test_source_dirs: self.test_source_dirs.iter().map(resolve).collect(),
+            output_dir: resolve(&self.output_dir),
+            test_output_dir: resolve(&self.test_output_dir),
+            javadoc_dir: resolve(&self.javadoc_dir),
+            cache_dir: resolve(&self.cache_dir),
+        }
+    }
+}
+
+fn resolve_dir(root: &Path, dir: &str) -> PathBuf {
+    let expanded = dir.replace(PROJECT_BASEDIR_TOKEN, &root.to_string_lossy());
+    normalize_path(&root.join(expanded))
+}
+
+/// Lexically remove `.` and `..` components without touching the filesystem
+fn normalize_path(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn default_encoding() -> String {
+    "UTF-8".to_string()
+}
+
 fn default_source_dirs() -> Vec<String> {
     vec!["src/main/java".to_string()]
 }
@@ -142,6 +595,10 @@ fn default_test_output_dir() -> String {
     "target/test-classes".to_string()
 }
 
+fn default_javadoc_dir() -> String {
+    "target/javadoc".to_string()
+}
+
 fn default_test_framework() -> String {
     "auto".to_string()
 }
@@ -156,9 +613,47 @@ fn default_java_version() -> String {
 
 impl ProjectConfig {
     /// Load configuration from a file
+    ///
+    /// `[dev-dependencies]` and `[runtime-dependencies]` are merged into
+    /// `dependencies` (see [`ProjectConfig::merge_grouped_dependencies`]).
+    /// `[build]` directories are resolved against the directory containing
+    /// the file (see [`BuildConfig::resolve_paths`]).
     pub fn load(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        Ok(toml::from_str(&content)?)
+        let mut config: Self = toml::from_str(&content)?;
+        config.merge_grouped_dependencies()?;
+        let root = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => std::path::absolute(parent)?,
+            _ => std::env::current_dir()?,
+        };
+        config.build.resolve_paths(&root).apply(&mut config.build);
+        Ok(config)
+    }
+
+    /// Move `[dev-dependencies]` and `[runtime-dependencies]` into `dependencies`
+    ///
+    /// Entries take the scope of their table. Returns
+    /// `CoreError::DuplicateDependency` if a key appears in more than one table.
+    pub fn merge_grouped_dependencies(&mut self) -> Result<()> {
+        let grouped = [
+            (
+                std::mem::take(&mut self.dev_dependencies),
+                DependencyScope::Test,
+            ),
+            (
+                std::mem::take(&mut self.runtime_dependencies),
+                DependencyScope::Runtime,
+            ),
+        ];
+        for (table, scope) in grouped {
+            for (key, dep) in table {
+                if self.dependencies.contains_key(&key) {
+                    return Err(crate::error::CoreError::DuplicateDependency { coordinate: key });
+                }
+                self.dependencies.insert(key, dep.with_scope(scope));
+            }
+        }
+        Ok(())
     }
 
     /// Save configuration to a file
@@ -177,12 +672,112 @@ impl ProjectConfig {
             },
             build: BuildConfig::default(),
             dependencies: HashMap::new(),
-            repositories: vec![Repository {
-                name: "central".to_string(),
-                url: "https://repo1.maven.org/maven2/".to_string(),
-            }],
+            repositories: vec![Repository::central()],
+            http: HttpConfig::default(),
+            test: TestConfig::default(),
+            run: RunConfig::default(),
+            dev_dependencies: HashMap::new(),
+            runtime_dependencies: HashMap::new(),
+            dependency_management: HashMap::new(),
+            licenses: Default::default(),
         }
     }
+
+    /// Add repositories for this run only, e.g. from `pt build --repository`
+    ///
+    /// An empty `repositories` list means Maven Central, so Central is kept
+    /// alongside the added repositories in that case.
+    pub fn add_repositories(&mut self, added: Vec<Repository>, priority: RepositoryPriority) {
+        if added.is_empty() {
+            return;
+        }
+        if self.repositories.is_empty() {
+            self.repositories.push(Repository::central());
+        }
+        match priority {
+            RepositoryPriority::First => {
+                self.repositories.splice(0..0, added);
+            }
+            RepositoryPriority::Last => self.repositories.extend(added),
+        }
+    }
+
+    /// Rewrite dependencies in their simplest equivalent form
+    ///
+    /// Entries with no `optional` flag and no exclusions become plain version
+    /// strings when their scope is the one implied by their table (compile for
+    /// `[dependencies]`). Detailed entries in grouped tables take the table's scope.
+    pub fn normalize(&mut self) {
+        let tables = [
+            (&mut self.dependencies, None),
+            (&mut self.dev_dependencies, Some(DependencyScope::Test)),
+            (
+                &mut self.runtime_dependencies,
+                Some(DependencyScope::Runtime),
+            ),
+        ];
+        for (table, table_scope) in tables {
+            for dep in table.values_mut() {
+                if let Dependency::Detailed { scope, .. } = dep
+                    && let Some(table_scope) = table_scope
+                {
+                    *scope = table_scope;
+                }
+                if let Dependency::Detailed {
+                    version,
+                    scope,
+                    optional: false,
+                    exclusions,
+                    include_optional: false,
+                } = dep
+                    && exclusions.is_empty()
+                    && *scope == table_scope.unwrap_or(DependencyScope::Compile)
+                {
+                    *dep = Dependency::Simple(std::mem::take(version));
+                }
+            }
+        }
+    }
+
+    /// `groupId:artifactId` keys of dependencies declared with `include_optional = true`
+    pub fn optional_inclusions(&self) -> HashSet<String> {
+        self.dependencies
+            .iter()
+            .filter(|(_, dep)| dep.include_optional())
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// `[dependency-management]` versions keyed by `groupId:artifactId`
+    ///
+    /// Returns `CoreError::InvalidCoordinate` if a key is not `groupId:artifactId`.
+    pub fn managed_versions(&self) -> Result<HashMap<String, String>> {
+        self.dependency_management
+            .iter()
+            .map(|(key, version)| Ok((parse_remove_coordinate(key)?, version.clone())))
+            .collect()
+    }
+
+    /// Per-dependency exclusions keyed by the dependency's `groupId:artifactId`
+    ///
+    /// Dependencies without exclusions are omitted. Returns
+    /// `CoreError::InvalidCoordinate` if an exclusion is not `groupId:artifactId`.
+    pub fn dependency_exclusions(&self) -> Result<HashMap<String, Vec<String>>> {
+        let mut result = HashMap::new();
+        for (key, dep) in &self.dependencies {
+            if dep.exclusions().is_empty() {
+                continue;
+            }
+            let exclusions = dep
+                .exclusions()
+                .iter()
+                .map(|e| parse_remove_coordinate(e))
+                .collect::<Result<Vec<_>>>()?;
+            let ga = key.splitn(3, ':').take(2).collect::<Vec<_>>().join(":");
+            result.insert(ga, exclusions);
+        }
+        Ok(result)
+    }
 }
 
 impl Dependency {
@@ -201,6 +796,53 @@ impl Dependency {
             Dependency::Detailed { scope, .. } => *scope,
         }
     }
+
+    /// This dependency with its scope replaced, in detailed form unless the scope is compile
+    pub fn with_scope(self, scope: DependencyScope) -> Self {
+        match self {
+            Dependency::Simple(version) if scope == DependencyScope::Compile => {
+                Dependency::Simple(version)
+            }
+            Dependency::Simple(version) => Dependency::Detailed {
+                version,
+                scope,
+                optional: false,
+                exclusions: Vec::new(),
+                include_optional: false,
+            },
+            Dependency::Detailed {
+                version,
+                optional,
+                exclusions,
+                include_optional,
+                ..
+            } => Dependency::Detailed {
+                version,
+                scope,
+                optional,
+                exclusions,
+                include_optional,
+            },
+        }
+    }
+
+    /// Whether this dependency's optional transitives should be resolved
+    pub fn include_optional(&self) -> bool {
+        match self {
+            Dependency::Simple(_) => false,
+            Dependency::Detailed {
+                include_optional, ..
+            } => *include_optional,
+        }
+    }
+
+    /// Get the per-dependency exclusions (empty for simple format)
+    pub fn exclusions(&self) -> &[String] {
+        match self {
+            Dependency::Simple(_) => &[],
+            Dependency::Detailed { exclusions, .. } => exclusions,
+        }
+    }
 }
 
 /// Validate Maven coordinate string and return `(ga_key, version)` pair.
@@ -208,7 +850,7 @@ impl Dependency {
 /// Accepts `"groupId:artifactId:version"` format only.
 /// Returns `CoreError::InvalidCoordinate` on failure.
 pub fn parse_add_coordinate(input: &str) -> Result<(String, String)> {
-    let parts: Vec<&str> = input.split(':').collect();
+    let parts: Vec<&str> = input.split(':').map(str::trim).collect();
     if parts.len() != 3 {
         return Err(crate::error::CoreError::InvalidCoordinate {
             message: format!("expected format 'groupId:artifactId:version', got '{input}'"),
@@ -224,55 +866,211 @@ pub fn parse_add_coordinate(input: &str) -> Result<(String, String)> {
             message: format!("groupId, artifactId, and version must not be empty: '{input}'"),
         });
     }
+    check_coordinate_characters(input, &parts)?;
 
     let ga_key = format!("{group_id}:{artifact_id}");
     Ok((ga_key, version.to_string()))
 }
 
+/// How `pt add` writes the version of a new dependency
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionPolicy {
+    /// Pin the version as given (`1.2.3`)
+    #[default]
+    Exact,
+    /// Accept updates up to the next major version (`[1.2.3,2.0.0)`)
+    ///
+    /// For `0.x` versions the next minor version is the limit
+    /// (`[0.4.1,0.5.0)`), since those may break on any minor release.
+    Compatible,
+}
+
+impl VersionPolicy {
+    /// Version string to write for `version` under this policy
+    ///
+    /// Returns `CoreError::InvalidCoordinate` if a compatible range cannot be
+    /// derived because `version` is already a range or does not start with a
+    /// number.
+    pub fn format(self, version: &str) -> Result<String> {
+        match self {
+            VersionPolicy::Exact => Ok(version.to_string()),
+            VersionPolicy::Compatible => compatible_range(version),
+        }
+    }
+}
+
+fn compatible_range(version: &str) -> Result<String> {
+    let invalid = || crate::error::CoreError::InvalidCoordinate {
+        message: format!("cannot derive a compatible range from version '{version}'"),
+    };
+    if version.starts_with(['[', '(']) {
+        return Err(invalid());
+    }
+    let mut numbers = version.split(['.', '-']).map(|part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<u64>().ok()
+    });
+    let major = numbers.next().flatten().ok_or_else(invalid)?;
+    let upper = if major == 0 {
+        let minor = numbers.next().flatten().unwrap_or(0);
+        format!("0.{}.0", minor + 1)
+    } else {
+        format!("{}.0.0", major + 1)
+    };
+    Ok(format!("[{version},{upper})"))
+}
+
 /// Add a dependency to a TOML config file, preserving formatting and comments.
 ///
 /// If the `[dependencies]` table does not exist it is created.
-/// Returns `CoreError::DuplicateDependency` when the same `ga_key` is already present.
+/// Returns `CoreError::DuplicateDependency` when the same `ga_key` is already
+/// present in any dependency table.
 pub fn add_dependency_to_file(
     path: &Path,
     ga_key: &str,
     version: &str,
     scope: Option<DependencyScope>,
 ) -> Result<()> {
+    let dependency = [(ga_key.to_string(), version.to_string())];
+    single(add_dependencies_to_file(path, &dependency, scope)?)
+}
+
+/// Add several dependencies with the same scope in a single write.
+///
+/// Each entry is a `(ga_key, version)` pair. The outer `Result` fails if the
+/// file cannot be read, parsed, or written; the returned list holds one
+/// result per entry, `CoreError::DuplicateDependency` for a `ga_key` already
+/// present in any dependency table or earlier in `dependencies`. The other
+/// entries are still written.
+pub fn add_dependencies_to_file(
+    path: &Path,
+    dependencies: &[(String, String)],
+    scope: Option<DependencyScope>,
+) -> Result<Vec<Result<()>>> {
+    // Insert dependency: simple string for Compile scope, inline table otherwise
+    let entries = dependencies.iter().map(|(ga_key, version)| {
+        let value = match scope {
+            None | Some(DependencyScope::Compile) => toml_edit::value(version),
+            Some(s) => {
+                let mut inline = toml_edit::InlineTable::new();
+                inline.insert("version", version.into());
+                inline.insert("scope", scope_to_toml_str(s).into());
+                toml_edit::value(inline)
+            }
+        };
+        (ga_key.as_str(), value)
+    });
+    insert_dependencies(path, "dependencies", entries)
+}
+
+/// Add a test dependency to the `[dev-dependencies]` table, preserving formatting and comments.
+///
+/// Returns `CoreError::DuplicateDependency` when the same `ga_key` is already
+/// present in any dependency table.
+pub fn add_dev_dependency_to_file(path: &Path, ga_key: &str, version: &str) -> Result<()> {
+    let dependency = [(ga_key.to_string(), version.to_string())];
+    single(add_dev_dependencies_to_file(path, &dependency)?)
+}
+
+/// Add several test dependencies to `[dev-dependencies]` in a single write.
+///
+/// Results are reported per entry as for [`add_dependencies_to_file`].
+pub fn add_dev_dependencies_to_file(
+    path: &Path,
+    dependencies: &[(String, String)],
+) -> Result<Vec<Result<()>>> {
+    let entries = dependencies
+        .iter()
+        .map(|(ga_key, version)| (ga_key.as_str(), toml_edit::value(version)));
+    insert_dependencies(path, "dev-dependencies", entries)
+}
+
+fn single(mut results: Vec<Result<()>>) -> Result<()> {
+    results.pop().expect("one result per dependency")
+}
+
+fn insert_dependencies<'a>(
+    path: &Path,
+    table: &str,
+    entries: impl IntoIterator<Item = (&'a str, toml_edit::Item)>,
+) -> Result<Vec<Result<()>>> {
     let content = std::fs::read_to_string(path)?;
     let mut doc: toml_edit::DocumentMut = content.parse::<toml_edit::DocumentMut>()?;
 
-    // Ensure [dependencies] table exists
-    if !doc.contains_table("dependencies") {
-        doc["dependencies"] = toml_edit::Item::Table(toml_edit::Table::new());
-    }
+    let mut results = Vec::new();
+    for (ga_key, value) in entries {
+        // Check for duplicate
+        let duplicate = DEPENDENCY_TABLES.iter().any(|name| {
+            doc.get(name)
+                .and_then(|t| t.as_table_like())
+                .is_some_and(|t| t.contains_key(ga_key))
+        });
+        if duplicate {
+            results.push(Err(crate::error::CoreError::DuplicateDependency {
+                coordinate: ga_key.to_string(),
+            }));
+            continue;
+        }
 
-    let deps = doc["dependencies"]
-        .as_table_mut()
-        .expect("dependencies should be a table");
+        // Ensure the table exists
+        if !doc.contains_table(table) {
+            doc[table] = toml_edit::Item::Table(toml_edit::Table::new());
+        }
 
-    // Check for duplicate
-    if deps.contains_key(ga_key) {
-        return Err(crate::error::CoreError::DuplicateDependency {
-            coordinate: ga_key.to_string(),
-        });
+        let deps = doc[table]
+            .as_table_mut()
+            .expect("dependencies should be a table");
+        deps[ga_key] = value;
+        results.push(Ok(()));
     }
 
-    // Insert dependency: simple string for Compile scope, inline table otherwise
-    match scope {
-        None | Some(DependencyScope::Compile) => {
-            deps[ga_key] = toml_edit::value(version);
-        }
-        Some(s) => {
-            let mut inline = toml_edit::InlineTable::new();
-            inline.insert("version", version.into());
-            inline.insert("scope", scope_to_toml_str(s).into());
-            deps[ga_key] = toml_edit::value(inline);
+    if results.iter().any(Result::is_ok) {
+        std::fs::write(path, doc.to_string())?;
+    }
+    Ok(results)
+}
+
+/// Canonical formatting of a `polytunnel.toml` document.
+///
+/// Dependencies are normalized (see [`ProjectConfig::normalize`]) and sorted by
+/// key, detailed dependencies are written as inline tables, and the leading
+/// comment block is kept. Repositories keep their order since it decides
+/// lookup priority. Other comments are not preserved.
+pub fn format_config(content: &str) -> Result<String> {
+    let mut config: ProjectConfig = toml::from_str(content)?;
+    config.normalize();
+
+    let mut doc: toml_edit::DocumentMut = toml::to_string_pretty(&config)?.parse()?;
+    for table in DEPENDENCY_TABLES {
+        let Some(deps) = doc.get_mut(table).and_then(|d| d.as_table_mut()) else {
+            continue;
+        };
+        let keys: Vec<String> = deps.iter().map(|(k, _)| k.to_string()).collect();
+        for key in keys {
+            // Re-insert so the key gets default `key = value` spacing
+            let item = match deps.remove(&key) {
+                Some(toml_edit::Item::Table(table)) => toml_edit::value(table.into_inline_table()),
+                Some(item) => item,
+                None => continue,
+            };
+            deps.insert(&key, item);
         }
+        deps.sort_values();
     }
 
-    std::fs::write(path, doc.to_string())?;
-    Ok(())
+    let header: String = content
+        .lines()
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let header = header.trim();
+
+    let body = doc.to_string();
+    if header.is_empty() {
+        Ok(body)
+    } else {
+        Ok(format!("{header}\n\n{body}"))
+    }
 }
 
 /// Validate a remove coordinate and return the `ga_key`.
@@ -280,7 +1078,7 @@ pub fn add_dependency_to_file(
 /// Accepts `"groupId:artifactId"` format only (no version).
 /// Returns `CoreError::InvalidCoordinate` on failure.
 pub fn parse_remove_coordinate(input: &str) -> Result<String> {
-    let parts: Vec<&str> = input.split(':').collect();
+    let parts: Vec<&str> = input.split(':').map(str::trim).collect();
     if parts.len() != 2 {
         return Err(crate::error::CoreError::InvalidCoordinate {
             message: format!("expected format 'groupId:artifactId', got '{input}'"),
@@ -295,12 +1093,28 @@ pub fn parse_remove_coordinate(input: &str) -> Result<String> {
             message: format!("groupId and artifactId must not be empty: '{input}'"),
         });
     }
+    check_coordinate_characters(input, &parts)?;
+
+    Ok(format!("{group_id}:{artifact_id}"))
+}
 
-    Ok(input.to_string())
+/// Reject whitespace and path separators, which would break repository URLs
+fn check_coordinate_characters(input: &str, segments: &[&str]) -> Result<()> {
+    let illegal = segments
+        .iter()
+        .flat_map(|segment| segment.chars())
+        .find(|c| c.is_whitespace() || matches!(c, '/' | '\\'));
+    match illegal {
+        Some(c) => Err(crate::error::CoreError::InvalidCoordinate {
+            message: format!("illegal character {c:?} in '{input}'"),
+        }),
+        None => Ok(()),
+    }
 }
 
 /// Remove a dependency from a TOML config file, preserving formatting and comments.
 ///
+/// The dependency is removed from whichever dependency table declares it.
 /// Creates a backup (`.bak`) before writing. On success the backup is removed.
 /// On write failure the original file is restored from the backup.
 /// Returns `CoreError::DependencyNotFound` when the `ga_key` is not present.
@@ -308,20 +1122,21 @@ pub fn remove_dependency_from_file(path: &Path, ga_key: &str) -> Result<()> {
     let content = std::fs::read_to_string(path)?;
     let mut doc: toml_edit::DocumentMut = content.parse::<toml_edit::DocumentMut>()?;
 
-    let deps = doc
-        .get_mut("dependencies")
-        .and_then(|d| d.as_table_mut())
+    let table = DEPENDENCY_TABLES
+        .into_iter()
+        .find(|name| {
+            doc.get(name)
+                .and_then(|d| d.as_table())
+                .is_some_and(|deps| deps.contains_key(ga_key))
+        })
         .ok_or_else(|| crate::error::CoreError::DependencyNotFound {
             coordinate: ga_key.to_string(),
         })?;
 
-    if !deps.contains_key(ga_key) {
-        return Err(crate::error::CoreError::DependencyNotFound {
-            coordinate: ga_key.to_string(),
-        });
-    }
-
-    deps.remove(ga_key);
+    doc[table]
+        .as_table_mut()
+        .expect("dependencies should be a table")
+        .remove(ga_key);
 
     let backup_path = unique_backup_path(path);
     std::fs::copy(path, &backup_path)?;
@@ -29,6 +29,13 @@ pub enum CoreError {
         path: String,
     },
 
+    /// No `polytunnel.toml` in the start directory or any of its ancestors
+    #[error("polytunnel.toml not found in `{start}` or any parent directory. Run `pt init` first.")]
+    ProjectRootNotFound {
+        /// Directory the search started from
+        start: String,
+    },
+
     /// Invalid Maven coordinate format
     #[error("Invalid coordinate: {message}")]
     InvalidCoordinate {
@@ -59,6 +66,13 @@ pub enum CoreError {
         rollback_error: String,
     },
 
+    /// A `${env.NAME}` placeholder names an environment variable that is not set
+    #[error("Environment variable {name} is not set")]
+    MissingEnvVar {
+        /// Name of the variable
+        name: String,
+    },
+
     /// TOML edit error
     #[error("TOML edit error: {0}")]
     TomlEdit(#[from] toml_edit::TomlError),
@@ -1,6 +1,6 @@
 //! Tests for parse_add_coordinate and add_dependency_to_file
 
-use polytunnel_core::parse_add_coordinate;
+use polytunnel_core::{VersionPolicy, parse_add_coordinate};
 
 #[test]
 fn test_parse_valid_coordinate() {
@@ -22,6 +22,21 @@ fn test_parse_coordinate_empty_parts() {
     assert!(parse_add_coordinate("group:artifact:").is_err());
 }
 
+#[test]
+fn test_parse_coordinate_trims_whitespace() {
+    let (ga, ver) = parse_add_coordinate("org.foo: bar :1.0 ").unwrap();
+    assert_eq!(ga, "org.foo:bar");
+    assert_eq!(ver, "1.0");
+    assert!(parse_add_coordinate("group: :1.0").is_err());
+}
+
+#[test]
+fn test_parse_coordinate_illegal_characters() {
+    assert!(parse_add_coordinate("org.foo:bar/baz:1.0").is_err());
+    assert!(parse_add_coordinate("org.foo:bar\\baz:1.0").is_err());
+    assert!(parse_add_coordinate("org.foo:my lib:1.0").is_err());
+}
+
 #[test]
 fn test_parse_coordinate_single_part() {
     assert!(parse_add_coordinate("just-a-name").is_err());
@@ -35,7 +50,9 @@ fn test_parse_coordinate_too_many_parts() {
 
 // --- add_dependency_to_file tests ---
 
-use polytunnel_core::{DependencyScope, add_dependency_to_file};
+use polytunnel_core::{
+    CoreError, DependencyScope, add_dependencies_to_file, add_dependency_to_file,
+};
 use std::fs;
 
 #[test]
@@ -89,3 +106,60 @@ fn test_add_dependency_duplicate_error() {
     let result = add_dependency_to_file(&path, "com.example:lib", "2.0.0", None);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_add_dependencies_writes_all_but_duplicates() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &path,
+        "[project]\nname = \"demo\"\njava_version = \"17\"\n\n[dependencies]\n\"com.example:lib\" = \"1.0.0\"\n",
+    )
+    .unwrap();
+    let dependencies = [
+        ("com.example:lib", "2.0.0"),
+        ("com.example:a", "1.0"),
+        ("com.example:b", "1.0"),
+        ("com.example:a", "2.0"),
+    ]
+    .map(|(ga, version)| (ga.to_string(), version.to_string()));
+
+    let results = add_dependencies_to_file(&path, &dependencies, None).unwrap();
+
+    assert!(matches!(
+        &results[..],
+        [
+            Err(CoreError::DuplicateDependency { .. }),
+            Ok(()),
+            Ok(()),
+            Err(CoreError::DuplicateDependency { .. }),
+        ]
+    ));
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("\"com.example:lib\" = \"1.0.0\""));
+    assert!(content.contains("\"com.example:a\" = \"1.0\""));
+    assert!(content.contains("\"com.example:b\" = \"1.0\""));
+}
+
+#[test]
+fn test_version_policy_exact_keeps_version() {
+    assert_eq!(VersionPolicy::Exact.format("1.2.3").unwrap(), "1.2.3");
+    assert_eq!(VersionPolicy::default(), VersionPolicy::Exact);
+}
+
+#[test]
+fn test_version_policy_compatible_writes_range_to_next_major() {
+    let compatible = VersionPolicy::Compatible;
+    assert_eq!(compatible.format("1.2.3").unwrap(), "[1.2.3,2.0.0)");
+    assert_eq!(
+        compatible.format("33.0.0-jre").unwrap(),
+        "[33.0.0-jre,34.0.0)"
+    );
+    assert_eq!(compatible.format("0.4.1").unwrap(), "[0.4.1,0.5.0)");
+}
+
+#[test]
+fn test_version_policy_compatible_rejects_non_numeric_versions() {
+    assert!(VersionPolicy::Compatible.format("[1.0,2.0)").is_err());
+    assert!(VersionPolicy::Compatible.format("RELEASE").is_err());
+}
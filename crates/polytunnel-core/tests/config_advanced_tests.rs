@@ -4,6 +4,7 @@
 
 use polytunnel_core::{
     BuildConfig, Dependency, DependencyScope, ProjectConfig, ProjectInfo, Repository,
+    RepositoryPriority,
 };
 use std::collections::HashMap;
 
@@ -17,6 +18,13 @@ fn test_project_config_default_creation() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     assert!(!config.project.name.is_empty());
@@ -52,6 +60,8 @@ fn test_dependency_detailed_variant_creation() {
         version: "2.0.0".to_string(),
         scope: DependencyScope::Test,
         optional: false,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
@@ -59,10 +69,13 @@ fn test_dependency_detailed_variant_creation() {
             version,
             scope,
             optional,
+            exclusions,
+            ..
         } => {
             assert_eq!(version, "2.0.0");
             assert_eq!(scope, DependencyScope::Test);
             assert!(!optional);
+            assert!(exclusions.is_empty());
         }
         _ => panic!("Wrong variant"),
     }
@@ -73,6 +86,10 @@ fn test_repository_structure_fields() {
     let repo = Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     assert!(!repo.name.is_empty());
@@ -163,6 +180,13 @@ fn test_project_config_dependencies_field() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     config.dependencies.insert(
@@ -183,11 +207,22 @@ fn test_project_config_repositories_field() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     config.repositories.push(Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     });
 
     assert_eq!(config.repositories.len(), 1);
@@ -203,6 +238,13 @@ fn test_config_modification_preserves_other_fields() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     let original_name = config.project.name.clone();
@@ -227,10 +269,18 @@ fn test_repository_collection_iteration() {
         Repository {
             name: "repo1".to_string(),
             url: "url1".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         Repository {
             name: "repo2".to_string(),
             url: "url2".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
@@ -281,6 +331,10 @@ fn test_repository_lookup_by_name() {
     let repos = [Repository {
         name: "central".to_string(),
         url: "https://repo1".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     }];
 
     let found = repos.iter().find(|r| r.name == "central");
@@ -297,6 +351,13 @@ fn test_config_partial_initialization() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     assert_eq!(config.dependencies.len(), 0);
@@ -356,6 +417,10 @@ fn test_repository_url_format_validation() {
     let repo = Repository {
         name: "test".to_string(),
         url: "https://example.com/repo/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     assert!(repo.url.starts_with("https://"));
@@ -389,6 +454,8 @@ fn test_detailed_dependency_scope_field_access() {
         version: "1.0.0".to_string(),
         scope: DependencyScope::Compile,
         optional: true,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
@@ -403,10 +470,18 @@ fn test_repository_list_filtering() {
         Repository {
             name: "central".to_string(),
             url: "https://repo1".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         Repository {
             name: "custom".to_string(),
             url: "https://repo2".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
@@ -465,8 +540,108 @@ fn test_repository_cloneable() {
     let repo1 = Repository {
         name: "test".to_string(),
         url: "https://test.com".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     let repo2 = repo1.clone();
     assert_eq!(repo1.name, repo2.name);
 }
+
+fn repository_names(config: &ProjectConfig) -> Vec<&str> {
+    config
+        .repositories
+        .iter()
+        .map(|repo| repo.name.as_str())
+        .collect()
+}
+
+#[test]
+fn test_add_repositories_first_goes_before_configured() {
+    let mut config = ProjectConfig::new("app");
+    config.add_repositories(
+        vec![
+            Repository::new("staging", "https://staging.example.test/"),
+            Repository::new("mirror", "https://mirror.example.test/"),
+        ],
+        RepositoryPriority::First,
+    );
+
+    assert_eq!(repository_names(&config), ["staging", "mirror", "central"]);
+}
+
+#[test]
+fn test_add_repositories_last_goes_after_configured() {
+    let mut config = ProjectConfig::new("app");
+    config.add_repositories(
+        vec![Repository::new("staging", "https://staging.example.test/")],
+        RepositoryPriority::Last,
+    );
+
+    assert_eq!(repository_names(&config), ["central", "staging"]);
+}
+
+#[test]
+fn test_add_repositories_keeps_implicit_central() {
+    let mut config = ProjectConfig::new("app");
+    config.repositories.clear();
+    config.add_repositories(
+        vec![Repository::new("staging", "https://staging.example.test/")],
+        RepositoryPriority::First,
+    );
+
+    assert_eq!(repository_names(&config), ["staging", "central"]);
+    assert_eq!(config.repositories[1].url, Repository::central().url);
+}
+
+#[test]
+fn test_add_no_repositories_leaves_list_unchanged() {
+    let mut config = ProjectConfig::new("app");
+    config.repositories.clear();
+    config.add_repositories(Vec::new(), RepositoryPriority::First);
+
+    assert!(config.repositories.is_empty());
+}
+
+#[test]
+fn test_repository_credentials_expand_environment_variables() {
+    // SAFETY: no other test reads or writes these variables.
+    unsafe {
+        std::env::set_var("POLYTUNNEL_TEST_REPO_USER", "deploy");
+        std::env::set_var("POLYTUNNEL_TEST_REPO_TOKEN", "s3cret");
+    }
+    let config: ProjectConfig = toml::from_str(
+        r#"
+[project]
+name = "app"
+
+[[repositories]]
+name = "private"
+url = "https://nexus.example.test/releases"
+username = "${env.POLYTUNNEL_TEST_REPO_USER}"
+password = "${env.POLYTUNNEL_TEST_REPO_TOKEN}"
+"#,
+    )
+    .unwrap();
+    let repository = &config.repositories[0];
+
+    assert_eq!(
+        repository.credentials().unwrap(),
+        Some(("deploy".to_string(), "s3cret".to_string()))
+    );
+    assert!(!format!("{repository:?}").contains("POLYTUNNEL_TEST_REPO_TOKEN"));
+    assert_eq!(Repository::central().credentials().unwrap(), None);
+}
+
+#[test]
+fn test_repository_credentials_with_unset_variable_fail() {
+    let repository = Repository {
+        password: Some("token-${env.POLYTUNNEL_TEST_UNSET_VARIABLE}".to_string()),
+        ..Repository::new("private", "https://nexus.example.test/releases")
+    };
+
+    let err = repository.credentials().unwrap_err();
+    assert!(err.to_string().contains("POLYTUNNEL_TEST_UNSET_VARIABLE"));
+}
@@ -62,6 +62,13 @@ fn test_project_config_creation() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     assert_eq!(config.project.name, "my-app");
@@ -73,6 +80,10 @@ fn test_project_config_with_repositories() {
     let repos = vec![Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     }];
 
     let config = ProjectConfig {
@@ -83,6 +94,13 @@ fn test_project_config_with_repositories() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: repos,
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     assert_eq!(config.repositories.len(), 1);
@@ -116,6 +134,10 @@ fn test_repository_name_and_url() {
     let repo = Repository {
         name: "custom-repo".to_string(),
         url: "https://custom.example.com/repo/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     assert_eq!(repo.name, "custom-repo");
@@ -143,6 +165,13 @@ fn test_project_config_java_version_variations() {
             build: BuildConfig::default(),
             dependencies: HashMap::new(),
             repositories: vec![],
+            http: Default::default(),
+            test: Default::default(),
+            run: Default::default(),
+            dev_dependencies: Default::default(),
+            runtime_dependencies: Default::default(),
+            dependency_management: Default::default(),
+            licenses: Default::default(),
         };
 
         assert_eq!(config.project.java_version, version);
@@ -161,10 +190,18 @@ fn test_multiple_repositories_in_config() {
         Repository {
             name: "central".to_string(),
             url: "https://repo1.maven.org/maven2/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         Repository {
             name: "custom".to_string(),
             url: "https://custom.example.com/repo/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
@@ -176,6 +213,13 @@ fn test_multiple_repositories_in_config() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: repos,
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     assert_eq!(config.repositories.len(), 2);
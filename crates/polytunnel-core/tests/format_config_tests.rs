@@ -0,0 +1,104 @@
+//! Tests for canonical polytunnel.toml formatting
+
+use polytunnel_core::{Dependency, DependencyScope, ProjectConfig, format_config};
+
+const MESSY: &str = r#"# Team config - edit with care
+
+[project]
+java_version = "17"
+name = "app"
+
+[dependencies]
+"org.z:z" = { version = "1.0", scope = "compile", optional = false }
+"org.a:a" = { version = "2.0", scope = "test" }
+"org.m:m" = "3.0"
+"org.e:e" = { version = "1.0", exclusions = ["commons-logging:commons-logging"] }
+
+[[repositories]]
+name = "snapshots"
+url = "https://nexus.example.com/snapshots/"
+
+[[repositories]]
+name = "central"
+url = "https://repo1.maven.org/maven2/"
+"#;
+
+#[test]
+fn test_format_config_is_idempotent() {
+    let once = format_config(MESSY).unwrap();
+    let twice = format_config(&once).unwrap();
+
+    assert_eq!(once, twice);
+}
+
+#[test]
+fn test_format_config_sorts_and_normalizes_dependencies() {
+    let formatted = format_config(MESSY).unwrap();
+
+    let deps: Vec<&str> = formatted
+        .lines()
+        .skip_while(|l| *l != "[dependencies]")
+        .skip(1)
+        .take_while(|l| !l.is_empty())
+        .collect();
+    assert_eq!(
+        deps,
+        [
+            r#""org.a:a" = { version = "2.0", scope = "test" }"#,
+            r#""org.e:e" = { version = "1.0", scope = "compile", exclusions = ["commons-logging:commons-logging"] }"#,
+            r#""org.m:m" = "3.0""#,
+            r#""org.z:z" = "1.0""#,
+        ]
+    );
+}
+
+#[test]
+fn test_format_config_keeps_header_comment_and_repository_order() {
+    let formatted = format_config(MESSY).unwrap();
+
+    assert!(formatted.starts_with("# Team config - edit with care\n\n[project]"));
+    let snapshots = formatted.find("name = \"snapshots\"").unwrap();
+    let central = formatted.find("name = \"central\"").unwrap();
+    assert!(snapshots < central);
+}
+
+#[test]
+fn test_format_config_preserves_meaning() {
+    let original: ProjectConfig = toml::from_str(MESSY).unwrap();
+    let formatted: ProjectConfig = toml::from_str(&format_config(MESSY).unwrap()).unwrap();
+
+    assert_eq!(formatted.dependencies.len(), original.dependencies.len());
+    for (key, dep) in &original.dependencies {
+        let other = &formatted.dependencies[key];
+        assert_eq!(dep.version(), other.version());
+        assert_eq!(dep.scope(), other.scope());
+        assert_eq!(dep.exclusions(), other.exclusions());
+    }
+}
+
+#[test]
+fn test_format_config_rejects_invalid_config() {
+    assert!(format_config("[project]\nname = 1\n").is_err());
+}
+
+#[test]
+fn test_normalize_keeps_non_default_detailed_dependencies() {
+    let mut config = ProjectConfig::new("app");
+    config.dependencies.insert(
+        "org.opt:opt".to_string(),
+        Dependency::Detailed {
+            version: "1.0".to_string(),
+            scope: DependencyScope::Compile,
+            optional: true,
+            exclusions: vec![],
+            include_optional: false,
+        },
+    );
+
+    config.normalize();
+
+    assert!(matches!(
+        config.dependencies["org.opt:opt"],
+        Dependency::Detailed { optional: true, .. }
+    ));
+}
@@ -0,0 +1,127 @@
+//! Tests for `[dev-dependencies]` and `[runtime-dependencies]` tables
+
+use polytunnel_core::{
+    CoreError, DependencyScope, ProjectConfig, add_dev_dependency_to_file, format_config,
+    remove_dependency_from_file,
+};
+use std::fs;
+use tempfile::tempdir;
+
+const GROUPED: &str = r#"[project]
+name = "app"
+
+[dependencies]
+"com.google.guava:guava" = "33.0.0-jre"
+
+[dev-dependencies]
+"org.junit.jupiter:junit-jupiter" = "5.10.1"
+"org.mockito:mockito-core" = { version = "5.8.0", exclusions = ["net.bytebuddy:byte-buddy-agent"] }
+
+[runtime-dependencies]
+"ch.qos.logback:logback-classic" = "1.4.14"
+"#;
+
+#[test]
+fn test_load_merges_grouped_tables_with_scopes() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.toml");
+    fs::write(&path, GROUPED).unwrap();
+
+    let config = ProjectConfig::load(&path).unwrap();
+
+    let scope = |key: &str| config.dependencies[key].scope();
+    assert_eq!(scope("com.google.guava:guava"), DependencyScope::Compile);
+    assert_eq!(
+        scope("org.junit.jupiter:junit-jupiter"),
+        DependencyScope::Test
+    );
+    assert_eq!(scope("org.mockito:mockito-core"), DependencyScope::Test);
+    assert_eq!(
+        scope("ch.qos.logback:logback-classic"),
+        DependencyScope::Runtime
+    );
+    assert_eq!(
+        config.dependencies["org.mockito:mockito-core"].exclusions(),
+        ["net.bytebuddy:byte-buddy-agent"]
+    );
+    assert!(config.dev_dependencies.is_empty());
+    assert!(config.runtime_dependencies.is_empty());
+}
+
+#[test]
+fn test_load_rejects_dependency_declared_in_two_tables() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &path,
+        r#"[project]
+name = "app"
+
+[dependencies]
+"org.junit.jupiter:junit-jupiter" = "5.10.1"
+
+[dev-dependencies]
+"org.junit.jupiter:junit-jupiter" = "5.10.1"
+"#,
+    )
+    .unwrap();
+
+    let error = ProjectConfig::load(&path).unwrap_err();
+    assert!(matches!(error, CoreError::DuplicateDependency { .. }));
+}
+
+#[test]
+fn test_add_dev_dependency_writes_dev_table() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.toml");
+    fs::write(&path, "[project]\nname = \"app\"\n").unwrap();
+
+    add_dev_dependency_to_file(&path, "org.assertj:assertj-core", "3.25.1").unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("[dev-dependencies]\n\"org.assertj:assertj-core\" = \"3.25.1\""));
+    let config = ProjectConfig::load(&path).unwrap();
+    assert_eq!(
+        config.dependencies["org.assertj:assertj-core"].scope(),
+        DependencyScope::Test
+    );
+}
+
+#[test]
+fn test_add_dev_dependency_rejects_existing_compile_dependency() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.toml");
+    fs::write(&path, GROUPED).unwrap();
+
+    let error = add_dev_dependency_to_file(&path, "com.google.guava:guava", "1.0").unwrap_err();
+    assert!(matches!(error, CoreError::DuplicateDependency { .. }));
+}
+
+#[test]
+fn test_remove_finds_dependency_in_grouped_table() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.toml");
+    fs::write(&path, GROUPED).unwrap();
+
+    remove_dependency_from_file(&path, "ch.qos.logback:logback-classic").unwrap();
+
+    let config = ProjectConfig::load(&path).unwrap();
+    assert!(
+        !config
+            .dependencies
+            .contains_key("ch.qos.logback:logback-classic")
+    );
+    assert_eq!(config.dependencies.len(), 3);
+}
+
+#[test]
+fn test_format_config_keeps_grouped_tables() {
+    let formatted = format_config(GROUPED).unwrap();
+
+    assert!(formatted.contains("[dev-dependencies]\n\"org.junit.jupiter:junit-jupiter\" = \"5.10.1\"\n\"org.mockito:mockito-core\" = { version = \"5.8.0\", scope = \"test\", exclusions = [\"net.bytebuddy:byte-buddy-agent\"] }"));
+    assert!(
+        formatted
+            .contains("[runtime-dependencies]\n\"ch.qos.logback:logback-classic\" = \"1.4.14\"")
+    );
+    assert_eq!(format_config(&formatted).unwrap(), formatted);
+}
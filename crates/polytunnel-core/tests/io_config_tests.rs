@@ -30,6 +30,10 @@ fn test_project_config_load_and_save_roundtrip() -> Result<(), Box<dyn std::erro
     config.repositories.push(polytunnel_core::Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     });
     config.dependencies.insert(
         "org.junit.jupiter:junit-jupiter".to_string(),
@@ -37,6 +41,8 @@ fn test_project_config_load_and_save_roundtrip() -> Result<(), Box<dyn std::erro
             version: "5.10.2".to_string(),
             scope: DependencyScope::Test,
             optional: false,
+            exclusions: vec![],
+            include_optional: false,
         },
     );
 
@@ -44,7 +50,13 @@ fn test_project_config_load_and_save_roundtrip() -> Result<(), Box<dyn std::erro
 
     let loaded = ProjectConfig::load(&config_path)?;
     assert_eq!(loaded.project.name, config.project.name);
-    assert_eq!(loaded.build.source_dirs, config.build.source_dirs);
+    let expected_source_dirs: Vec<String> = config
+        .build
+        .source_dirs
+        .iter()
+        .map(|dir| project_dir.join(dir).to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(loaded.build.source_dirs, expected_source_dirs);
     assert_eq!(
         loaded.dependencies.len(),
         1,
@@ -69,6 +81,8 @@ fn test_dependency_scope_helpers() {
         version: "2.0".to_string(),
         scope: DependencyScope::Runtime,
         optional: true,
+        exclusions: vec![],
+        include_optional: false,
     };
     assert_eq!(dep.version(), "2.0");
     assert_eq!(dep.scope(), DependencyScope::Runtime);
@@ -0,0 +1,73 @@
+//! Tests for discovering the project root from a subdirectory
+
+use polytunnel_core::{CONFIG_FILE_NAME, CoreError, find_project_root};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_find_project_root_in_start_directory() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(CONFIG_FILE_NAME),
+        "[project]\nname = \"app\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(find_project_root(dir.path()).unwrap(), dir.path());
+}
+
+#[test]
+fn test_find_project_root_walks_up_from_subdirectory() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(CONFIG_FILE_NAME),
+        "[project]\nname = \"app\"\n",
+    )
+    .unwrap();
+    let nested = dir.path().join("src/main/java/com/example");
+    fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(find_project_root(&nested).unwrap(), dir.path());
+}
+
+#[test]
+fn test_find_project_root_prefers_nearest_config() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(CONFIG_FILE_NAME),
+        "[project]\nname = \"outer\"\n",
+    )
+    .unwrap();
+    let inner = dir.path().join("modules/inner");
+    fs::create_dir_all(inner.join("src")).unwrap();
+    fs::write(
+        inner.join(CONFIG_FILE_NAME),
+        "[project]\nname = \"inner\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(find_project_root(&inner.join("src")).unwrap(), inner);
+}
+
+#[test]
+fn test_find_project_root_ignores_directory_named_like_config() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join(CONFIG_FILE_NAME),
+        "[project]\nname = \"app\"\n",
+    )
+    .unwrap();
+    let nested = dir.path().join("sub");
+    fs::create_dir_all(nested.join(CONFIG_FILE_NAME)).unwrap();
+
+    assert_eq!(find_project_root(&nested).unwrap(), dir.path());
+}
+
+#[test]
+fn test_find_project_root_errors_when_missing() {
+    let dir = tempdir().unwrap();
+
+    let error = find_project_root(dir.path()).unwrap_err();
+    assert!(matches!(error, CoreError::ProjectRootNotFound { .. }));
+    assert!(error.to_string().contains("any parent directory"));
+}
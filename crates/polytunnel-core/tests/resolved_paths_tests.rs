@@ -0,0 +1,78 @@
+//! Tests for resolving `[build]` directories against the project root
+
+use polytunnel_core::{BuildConfig, ProjectConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+#[test]
+fn test_resolve_paths_joins_relative_dirs_onto_root() {
+    let root = Path::new("/work/app");
+    let paths = BuildConfig::default().resolve_paths(root);
+
+    assert_eq!(paths.root, root);
+    assert_eq!(paths.source_dirs, vec![root.join("src/main/java")]);
+    assert_eq!(paths.test_source_dirs, vec![root.join("src/test/java")]);
+    assert_eq!(paths.output_dir, root.join("target/classes"));
+    assert_eq!(paths.test_output_dir, root.join("target/test-classes"));
+    assert_eq!(paths.cache_dir, root.join(".polytunnel/cache"));
+}
+
+#[test]
+fn test_resolve_paths_expands_project_basedir_token() {
+    let build = BuildConfig {
+        output_dir: "${project.basedir}/out/classes".to_string(),
+        ..BuildConfig::default()
+    };
+
+    let paths = build.resolve_paths(Path::new("/work/app"));
+
+    assert_eq!(paths.output_dir, PathBuf::from("/work/app/out/classes"));
+}
+
+#[test]
+fn test_resolve_paths_keeps_absolute_dirs_and_normalizes_dots() {
+    let build = BuildConfig {
+        source_dirs: vec!["./src/../generated".to_string()],
+        cache_dir: "/var/cache/polytunnel".to_string(),
+        ..BuildConfig::default()
+    };
+
+    let paths = build.resolve_paths(Path::new("/work/app"));
+
+    assert_eq!(
+        paths.source_dirs,
+        vec![PathBuf::from("/work/app/generated")]
+    );
+    assert_eq!(paths.cache_dir, PathBuf::from("/var/cache/polytunnel"));
+}
+
+#[test]
+fn test_load_resolves_build_dirs_against_config_directory() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &config_path,
+        r#"[project]
+name = "app"
+
+[build]
+source_dirs = ["src/main/java", "${project.basedir}/gen"]
+output_dir = "build/classes"
+"#,
+    )
+    .unwrap();
+
+    let config = ProjectConfig::load(&config_path).unwrap();
+
+    let expected = |rel: &str| dir.path().join(rel).to_string_lossy().into_owned();
+    assert_eq!(
+        config.build.source_dirs,
+        vec![expected("src/main/java"), expected("gen")]
+    );
+    assert_eq!(config.build.output_dir, expected("build/classes"));
+    assert_eq!(
+        config.build.test_output_dir,
+        expected("target/test-classes")
+    );
+}
@@ -3,7 +3,8 @@
 //! Coverage: Validates the accuracy of TOML serialization/deserialization for all project and build configuration structures.
 
 use polytunnel_core::{
-    BuildConfig, Dependency, DependencyScope, ProjectConfig, ProjectInfo, Repository,
+    BuildConfig, DEFAULT_DOWNLOAD_CONCURRENCY, DEFAULT_RESOLVE_CONCURRENCY, Dependency,
+    DependencyScope, ProjectConfig, ProjectInfo, Repository,
 };
 use std::collections::HashMap;
 
@@ -25,10 +26,16 @@ fn test_build_config_serialization() {
         test_source_dirs: vec!["src/test/java".to_string()],
         output_dir: "target/classes".to_string(),
         test_output_dir: "target/test-classes".to_string(),
+        javadoc_dir: "target/javadoc".to_string(),
         compiler_args: vec![],
         test_compiler_args: vec![],
+        encoding: "UTF-8".to_string(),
         test_framework: "auto".to_string(),
         cache_dir: ".polytunnel/cache".to_string(),
+        compiler_daemon: false,
+        cache_dedup: false,
+        resolve_timeout_secs: None,
+        main_class: None,
     };
 
     assert_eq!(config.source_dirs[0], "src/main/java");
@@ -40,6 +47,10 @@ fn test_repository_serialization() {
     let repo = Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     assert_eq!(repo.name, "central");
@@ -61,6 +72,8 @@ fn test_dependency_detailed_serialization() {
         version: "2.0.0".to_string(),
         scope: DependencyScope::Test,
         optional: false,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
@@ -68,10 +81,13 @@ fn test_dependency_detailed_serialization() {
             version,
             scope,
             optional,
+            exclusions,
+            ..
         } => {
             assert_eq!(version, "2.0.0");
             assert_eq!(scope, DependencyScope::Test);
             assert!(!optional);
+            assert!(exclusions.is_empty());
         }
         _ => panic!("Wrong variant"),
     }
@@ -95,13 +111,26 @@ fn test_full_config_structure() {
             test_source_dirs: vec!["src/test/java".to_string()],
             output_dir: "target/classes".to_string(),
             test_output_dir: "target/test-classes".to_string(),
+            javadoc_dir: "target/javadoc".to_string(),
             compiler_args: vec![],
             test_compiler_args: vec![],
+            encoding: "UTF-8".to_string(),
             test_framework: "auto".to_string(),
             cache_dir: ".polytunnel/cache".to_string(),
+            compiler_daemon: false,
+            cache_dedup: false,
+            resolve_timeout_secs: None,
+            main_class: None,
         },
         dependencies: deps,
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     assert_eq!(config.project.name, "app");
@@ -114,10 +143,18 @@ fn test_multiple_repositories() {
         Repository {
             name: "central".to_string(),
             url: "https://repo1.maven.org/maven2/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         Repository {
             name: "custom".to_string(),
             url: "https://custom.example.com".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
@@ -159,6 +196,13 @@ fn test_config_clone_and_modify() {
         build: BuildConfig::default(),
         dependencies: HashMap::new(),
         repositories: vec![],
+        http: Default::default(),
+        test: Default::default(),
+        run: Default::default(),
+        dev_dependencies: Default::default(),
+        runtime_dependencies: Default::default(),
+        dependency_management: Default::default(),
+        licenses: Default::default(),
     };
 
     let mut config2 = config.clone();
@@ -192,6 +236,10 @@ fn test_repository_clone() {
     let repo = Repository {
         name: "central".to_string(),
         url: "https://repo.example.com".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     let repo2 = repo.clone();
@@ -205,10 +253,16 @@ fn test_build_config_custom_paths() {
         test_source_dirs: vec!["custom/test".to_string()],
         output_dir: "build/main".to_string(),
         test_output_dir: "build/test".to_string(),
+        javadoc_dir: "build/javadoc".to_string(),
         compiler_args: vec![],
         test_compiler_args: vec![],
+        encoding: "UTF-8".to_string(),
         test_framework: "junit5".to_string(),
         cache_dir: "build/cache".to_string(),
+        compiler_daemon: false,
+        cache_dedup: false,
+        resolve_timeout_secs: None,
+        main_class: None,
     };
 
     assert_eq!(config.source_dirs[0], "custom/src");
@@ -221,6 +275,8 @@ fn test_dependency_optional_flag() {
         version: "1.0.0".to_string(),
         scope: DependencyScope::Compile,
         optional: true,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
@@ -264,10 +320,16 @@ fn test_build_cache_directory_path() {
         test_source_dirs: vec![],
         output_dir: "target/classes".to_string(),
         test_output_dir: "target/test-classes".to_string(),
+        javadoc_dir: "target/javadoc".to_string(),
         compiler_args: vec![],
         test_compiler_args: vec![],
+        encoding: "UTF-8".to_string(),
         test_framework: "auto".to_string(),
         cache_dir: ".polytunnel/cache".to_string(),
+        compiler_daemon: false,
+        cache_dedup: false,
+        resolve_timeout_secs: None,
+        main_class: None,
     };
 
     assert!(config.cache_dir.starts_with(".polytunnel"));
@@ -278,6 +340,10 @@ fn test_repository_url_validation() {
     let repo = Repository {
         name: "test".to_string(),
         url: "https://example.com/repo/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     assert!(repo.url.starts_with("https://"));
@@ -305,3 +371,312 @@ fn test_project_info_equality() {
 
     assert_eq!(info1.name, info2.name);
 }
+
+#[test]
+fn test_http_config_deserialization() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[http]
+user_agent = "corp-agent/1.0"
+headers = { "X-Api-Key" = "secret" }
+max_download_bytes_per_sec = 1048576
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.http.user_agent.as_deref(), Some("corp-agent/1.0"));
+    assert_eq!(config.http.headers.get("X-Api-Key").unwrap(), "secret");
+    assert_eq!(config.http.max_download_bytes_per_sec, Some(1048576));
+}
+
+#[test]
+fn test_http_concurrency_limits() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[http]
+resolve_concurrency = 4
+download_concurrency = 0
+"#,
+    )
+    .unwrap();
+
+    assert!(!config.http.is_empty());
+    assert_eq!(config.http.resolve_concurrency(), 4);
+    // 0 would never download anything
+    assert_eq!(config.http.download_concurrency(), 1);
+
+    let defaults = ProjectConfig::new("app").http;
+    assert_eq!(defaults.resolve_concurrency(), DEFAULT_RESOLVE_CONCURRENCY);
+    assert_eq!(
+        defaults.download_concurrency(),
+        DEFAULT_DOWNLOAD_CONCURRENCY
+    );
+}
+
+#[test]
+fn test_empty_http_config_is_not_serialized() {
+    let config = ProjectConfig::new("app");
+    let serialized = toml::to_string(&config).unwrap();
+
+    assert!(config.http.is_empty());
+    assert!(!serialized.contains("[http]"));
+}
+
+#[test]
+fn test_dependency_exclusions_deserialization() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[dependencies]
+"org.apache.httpcomponents:httpclient" = { version = "4.5.14", exclusions = ["commons-logging:commons-logging"] }
+"com.google.guava:guava" = "33.0.0-jre"
+"#,
+    )
+    .unwrap();
+
+    let dep = &config.dependencies["org.apache.httpcomponents:httpclient"];
+    assert_eq!(dep.exclusions(), ["commons-logging:commons-logging"]);
+    assert!(
+        config.dependencies["com.google.guava:guava"]
+            .exclusions()
+            .is_empty()
+    );
+
+    let exclusions = config.dependency_exclusions().unwrap();
+    assert_eq!(exclusions.len(), 1);
+    assert_eq!(
+        exclusions["org.apache.httpcomponents:httpclient"],
+        vec!["commons-logging:commons-logging".to_string()]
+    );
+}
+
+#[test]
+fn test_dependency_exclusions_reject_malformed_entry() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[dependencies]
+"org.example:lib" = { version = "1.0", exclusions = ["commons-logging"] }
+"#,
+    )
+    .unwrap();
+
+    assert!(config.dependency_exclusions().is_err());
+}
+
+#[test]
+fn test_empty_exclusions_are_not_serialized() {
+    let dep = Dependency::Detailed {
+        version: "1.0".to_string(),
+        scope: DependencyScope::Test,
+        optional: false,
+        exclusions: vec![],
+        include_optional: false,
+    };
+
+    let serialized = toml::to_string(&dep).unwrap();
+    assert!(!serialized.contains("exclusions"));
+}
+
+#[test]
+fn test_resolve_timeout_secs_is_optional() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[build]
+resolve_timeout_secs = 120
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.build.resolve_timeout_secs, Some(120));
+
+    let default = ProjectConfig::new("app");
+    assert_eq!(default.build.resolve_timeout_secs, None);
+    assert!(
+        !toml::to_string(&default)
+            .unwrap()
+            .contains("resolve_timeout_secs")
+    );
+}
+
+#[test]
+fn test_test_config_parallel() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[test]
+parallel = 4
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.test.parallel, 4);
+
+    let default = ProjectConfig::new("app");
+    assert_eq!(default.test.parallel, 1);
+    assert!(!toml::to_string(&default).unwrap().contains("[test]"));
+}
+
+#[test]
+fn test_test_config_junit_console_version() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[test]
+junit_console_version = "1.11.0"
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.test.junit_console_version, "1.11.0");
+    assert_eq!(config.test.parallel, 1);
+    assert!(toml::to_string(&config).unwrap().contains("[test]"));
+
+    let default = ProjectConfig::new("app");
+    assert_eq!(default.test.junit_console_version, "1.10.2");
+}
+
+#[test]
+fn test_test_config_env() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[test.env]
+SPRING_PROFILES_ACTIVE = "test"
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.test.env["SPRING_PROFILES_ACTIVE"], "test");
+    assert!(!config.test.is_default());
+    assert!(toml::to_string(&config).unwrap().contains("[test.env]"));
+
+    let default = ProjectConfig::new("app");
+    assert!(default.test.env.is_empty());
+}
+
+#[test]
+fn test_jvm_args_config() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[test]
+jvm_args = ["-Xmx512m"]
+
+[run]
+jvm_args = ["-Dfoo=bar"]
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.test.jvm_args, ["-Xmx512m"]);
+    assert_eq!(config.run.jvm_args, ["-Dfoo=bar"]);
+    assert!(toml::to_string(&config).unwrap().contains("[run]"));
+
+    let default = ProjectConfig::new("app");
+    assert!(default.run.is_default());
+    assert!(!toml::to_string(&default).unwrap().contains("[run]"));
+}
+
+#[test]
+fn test_licenses_config() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[licenses]
+allowed = ["Apache License, Version 2.0", "MIT"]
+"#,
+    )
+    .unwrap();
+
+    assert!(config.licenses.allows("apache license, version 2.0"));
+    assert!(config.licenses.allows(" MIT "));
+    assert!(!config.licenses.allows("GPL-3.0"));
+
+    let default = ProjectConfig::new("app");
+    assert!(default.licenses.allows("GPL-3.0"));
+    assert!(!toml::to_string(&default).unwrap().contains("[licenses]"));
+}
+
+#[test]
+fn test_include_optional_flag() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[dependencies]
+"org.example:lib" = { version = "1.0", include_optional = true }
+"org.example:plain" = { version = "1.0", scope = "test" }
+"#,
+    )
+    .unwrap();
+
+    assert!(config.dependencies["org.example:lib"].include_optional());
+    assert!(!config.dependencies["org.example:plain"].include_optional());
+    assert_eq!(
+        config.optional_inclusions(),
+        ["org.example:lib".to_string()].into_iter().collect()
+    );
+    assert!(
+        !toml::to_string(&config.dependencies["org.example:plain"])
+            .unwrap()
+            .contains("include_optional")
+    );
+}
+
+#[test]
+fn test_dependency_management_table() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[dependency-management]
+"com.fasterxml.jackson.core:jackson-databind" = "2.17.0"
+"#,
+    )
+    .unwrap();
+
+    let managed = config.managed_versions().unwrap();
+    assert_eq!(
+        managed
+            .get("com.fasterxml.jackson.core:jackson-databind")
+            .map(String::as_str),
+        Some("2.17.0")
+    );
+    assert!(config.dependencies.is_empty());
+    assert!(
+        toml::to_string(&config)
+            .unwrap()
+            .contains("[dependency-management]")
+    );
+
+    let default = ProjectConfig::new("app");
+    assert!(
+        !toml::to_string(&default)
+            .unwrap()
+            .contains("dependency-management")
+    );
+}
+
+#[test]
+fn test_dependency_management_rejects_invalid_key() {
+    let config: ProjectConfig = toml::from_str(
+        r#"[project]
+name = "app"
+
+[dependency-management]
+"com.example:lib:1.0" = "2.0"
+"#,
+    )
+    .unwrap();
+
+    assert!(config.managed_versions().is_err());
+}
@@ -20,3 +20,14 @@ pub enum IdeError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl From<IdeError> for polytunnel_build::BuildError {
+    /// Unwrap build errors and fold the rest into the matching build variant
+    fn from(error: IdeError) -> Self {
+        match error {
+            IdeError::Build(e) => e,
+            IdeError::Core(e) => polytunnel_build::BuildError::Core(e),
+            IdeError::Io(e) => polytunnel_build::BuildError::Io(e),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! VS Code project file generation
 
-use crate::error::{IdeError, Result};
+use crate::error::Result;
 use colored::*;
 use polytunnel_build::BuildOrchestrator;
 use polytunnel_core::ProjectConfig;
@@ -16,12 +16,9 @@ pub async fn generate(config: &ProjectConfig, root_path: &Path) -> Result<()> {
     let name = config.project.name.clone();
 
     // Create orchestrator and resolve dependencies
-    let mut orchestrator = BuildOrchestrator::new(config.clone()).map_err(IdeError::Build)?;
+    let mut orchestrator = BuildOrchestrator::new(config.clone())?;
     print_status("Resolving", "dependencies...", Color::Cyan);
-    orchestrator
-        .resolve_dependencies(false)
-        .await
-        .map_err(IdeError::Build)?;
+    orchestrator.resolve_dependencies(false).await?;
 
     let classpath_result = orchestrator.get_resolved_classpath();
 
@@ -121,7 +118,7 @@ pub async fn generate(config: &ProjectConfig, root_path: &Path) -> Result<()> {
 fn update_gitignore(root_path: &Path) -> Result<()> {
     let gitignore_path = root_path.join(".gitignore");
     let mut current_content = if gitignore_path.exists() {
-        std::fs::read_to_string(&gitignore_path).map_err(IdeError::Io)?
+        std::fs::read_to_string(&gitignore_path)?
     } else {
         String::new()
     };
@@ -143,7 +140,7 @@ fn update_gitignore(root_path: &Path) -> Result<()> {
     }
 
     if updated {
-        std::fs::write(gitignore_path, current_content).map_err(IdeError::Io)?;
+        std::fs::write(gitignore_path, current_content)?;
         print_status("Updated", ".gitignore", Color::Green);
     }
 
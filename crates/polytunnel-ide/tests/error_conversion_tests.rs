@@ -0,0 +1,41 @@
+use polytunnel_build::BuildError;
+use polytunnel_core::CoreError;
+use polytunnel_ide::IdeError;
+use std::io::ErrorKind;
+
+fn propagate(error: IdeError) -> polytunnel_build::Result<()> {
+    Err(error)?;
+    Ok(())
+}
+
+#[test]
+fn test_ide_build_error_is_unwrapped() {
+    let build = BuildError::Io(std::io::Error::new(ErrorKind::BrokenPipe, "broken"));
+    let error = propagate(IdeError::Build(build)).unwrap_err();
+    match error {
+        BuildError::Io(e) => assert_eq!(e.kind(), ErrorKind::BrokenPipe),
+        other => panic!("expected Io, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_ide_core_error_propagates_as_core_variant() {
+    let core = CoreError::ConfigNotFound {
+        path: "polytunnel.toml".to_string(),
+    };
+    let error = propagate(IdeError::Core(core)).unwrap_err();
+    assert!(matches!(
+        error,
+        BuildError::Core(CoreError::ConfigNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_ide_io_error_propagates_as_io_variant() {
+    let io = std::io::Error::new(ErrorKind::PermissionDenied, "denied");
+    let error = propagate(IdeError::Io(io)).unwrap_err();
+    match error {
+        BuildError::Io(e) => assert_eq!(e.kind(), ErrorKind::PermissionDenied),
+        other => panic!("expected Io, got {other:?}"),
+    }
+}
@@ -0,0 +1,121 @@
+//! Minimal ZIP reader
+//!
+//! Reads the central directory of a ZIP (or JAR) archive and inflates its
+//! file entries. Only `stored` and `deflate` entries of non-ZIP64 archives
+//! are supported, which covers JARs and repository snapshot downloads.
+
+use crate::error::{MavenError, Result};
+use flate2::read::DeflateDecoder;
+use std::io::Read;
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+/// End of central directory record without its trailing comment
+const EOCD_LEN: usize = 22;
+
+/// A file stored in a ZIP archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipEntry {
+    /// Path inside the archive, `/`-separated
+    pub name: String,
+    /// Uncompressed contents
+    pub contents: Vec<u8>,
+}
+
+/// File entries of the ZIP archive in `bytes`, in central directory order
+///
+/// Directory entries are skipped.
+///
+/// # Errors
+///
+/// * `MavenError::InvalidArchive` - If `bytes` is not a readable ZIP archive
+pub fn read_zip(bytes: &[u8]) -> Result<Vec<ZipEntry>> {
+    let eocd = (0..=bytes.len().saturating_sub(EOCD_LEN))
+        .rev()
+        .find(|&offset| read_u32(bytes, offset) == Some(END_OF_CENTRAL_DIRECTORY))
+        .ok_or_else(|| invalid("end of central directory not found"))?;
+    let count = field_u16(bytes, eocd + 10)? as usize;
+    let mut offset = field_u32(bytes, eocd + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if read_u32(bytes, offset) != Some(CENTRAL_DIRECTORY_HEADER) {
+            return Err(invalid("corrupt central directory"));
+        }
+        let method = field_u16(bytes, offset + 10)?;
+        let compressed_size = field_u32(bytes, offset + 20)?;
+        let size = field_u32(bytes, offset + 24)?;
+        let name_len = field_u16(bytes, offset + 28)? as usize;
+        let extra_len = field_u16(bytes, offset + 30)? as usize;
+        let comment_len = field_u16(bytes, offset + 32)? as usize;
+        let local_offset = field_u32(bytes, offset + 42)?;
+        let name = bytes
+            .get(offset + 46..offset + 46 + name_len)
+            .ok_or_else(|| invalid("truncated entry name"))?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        offset += 46 + name_len + extra_len + comment_len;
+
+        if [compressed_size, size, local_offset].contains(&u32::MAX) {
+            return Err(invalid(&format!("{name}: ZIP64 entries are not supported")));
+        }
+        if name.ends_with('/') {
+            continue;
+        }
+
+        let data = local_data(bytes, local_offset as usize, compressed_size as usize)
+            .ok_or_else(|| invalid(&format!("{name}: truncated entry")))?;
+        let contents = match method {
+            0 => data.to_vec(),
+            8 => {
+                let mut contents = Vec::with_capacity(size as usize);
+                DeflateDecoder::new(data)
+                    .read_to_end(&mut contents)
+                    .map_err(|error| invalid(&format!("{name}: {error}")))?;
+                contents
+            }
+            other => {
+                return Err(invalid(&format!(
+                    "{name}: unsupported compression method {other}"
+                )));
+            }
+        };
+        entries.push(ZipEntry { name, contents });
+    }
+    Ok(entries)
+}
+
+/// Compressed data following the local file header at `offset`
+fn local_data(bytes: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    if read_u32(bytes, offset)? != LOCAL_FILE_HEADER {
+        return None;
+    }
+    let name_len = read_u16(bytes, offset + 26)? as usize;
+    let extra_len = read_u16(bytes, offset + 28)? as usize;
+    let start = offset + 30 + name_len + extra_len;
+    bytes.get(start..start + len)
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let field = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([field[0], field[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let field = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+}
+
+fn field_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    read_u16(bytes, offset).ok_or_else(|| invalid("truncated header"))
+}
+
+fn field_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    read_u32(bytes, offset).ok_or_else(|| invalid("truncated header"))
+}
+
+fn invalid(message: &str) -> MavenError {
+    MavenError::InvalidArchive {
+        message: message.to_string(),
+    }
+}
@@ -2,16 +2,35 @@
 
 use crate::coordinate::Coordinate;
 use crate::error::{MavenError, Result};
-use crate::pom::Pom;
+use crate::metadata::MavenMetadata;
+use crate::pom::{License, Pom};
+use crate::store::{ArtifactStore, FileSystemStore, jar_key, partial_key, pom_key};
+use crate::throttle::RateLimiter;
+use crate::version::{MavenVersion, is_snapshot_version};
+use base64::prelude::{BASE64_STANDARD, Engine};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use polytunnel_core::Repository;
 use reqwest::Client;
 use serde::de::DeserializeOwned;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::fmt;
 use std::future::Future;
+use std::io::Read;
 use std::path::Path;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2";
 const MAVEN_SEARCH_URL: &str = "https://search.maven.org/solrsearch/select";
+/// Maximum number of `<parent>` POMs followed by [`MavenClient::fetch_licenses`]
+pub const MAX_PARENT_DEPTH: usize = 10;
+const DEFAULT_USER_AGENT: &str = concat!("polytunnel/", env!("CARGO_PKG_VERSION"));
+/// Retries of a GET that failed to connect or got a 5xx response
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Wait before the first retry; each further retry waits twice as long
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
 
 /// Boxed future returned by [`MavenTransport::get`]
 pub type HttpTransportFuture = Pin<Box<dyn Future<Output = Result<HttpResponse>> + Send>>;
@@ -23,12 +42,119 @@ pub struct HttpResponse {
     pub status: u16,
     /// Response body bytes
     pub body: Vec<u8>,
+    /// Response headers
+    pub headers: HashMap<String, String>,
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Bodies can be whole jars; print their size only
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("body", &format_args!("<{} bytes>", self.body.len()))
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+impl HttpResponse {
+    /// Look up a response header, ignoring ASCII case in the name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether an interrupted transfer of this response can be resumed with a `Range` request
+    ///
+    /// True for `206 Partial Content` and for responses advertising
+    /// `Accept-Ranges: bytes`, unless the body is content-encoded.
+    pub fn accepts_ranges(&self) -> bool {
+        let encoded = self
+            .header("Content-Encoding")
+            .is_some_and(|e| !e.trim().eq_ignore_ascii_case("identity"));
+        let advertised = self.header("Accept-Ranges").is_some_and(|ranges| {
+            ranges
+                .split(',')
+                .any(|unit| unit.trim().eq_ignore_ascii_case("bytes"))
+        });
+        !encoded && (self.status == 206 || advertised)
+    }
+
+    /// Undo a gzip/deflate `Content-Encoding` so `body` holds the raw payload
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::ContentDecoding` - If the body is not valid for its encoding
+    pub fn decode_content(mut self) -> Result<Self> {
+        let Some(encoding) = self
+            .header("Content-Encoding")
+            .map(|e| e.trim().to_ascii_lowercase())
+        else {
+            return Ok(self);
+        };
+
+        let mut decoded = Vec::new();
+        let result = match encoding.as_str() {
+            "gzip" | "x-gzip" => GzDecoder::new(self.body.as_slice()).read_to_end(&mut decoded),
+            // `deflate` is zlib-wrapped per RFC 9110, but some servers send raw deflate
+            "deflate" => ZlibDecoder::new(self.body.as_slice())
+                .read_to_end(&mut decoded)
+                .or_else(|_| {
+                    decoded.clear();
+                    DeflateDecoder::new(self.body.as_slice()).read_to_end(&mut decoded)
+                }),
+            "identity" => return Ok(self),
+            _ => {
+                return Err(MavenError::ContentDecoding {
+                    encoding,
+                    message: "unsupported content encoding".to_string(),
+                });
+            }
+        };
+        result.map_err(|error| MavenError::ContentDecoding {
+            encoding,
+            message: error.to_string(),
+        })?;
+
+        self.body = decoded;
+        self.headers
+            .retain(|key, _| !key.eq_ignore_ascii_case("Content-Encoding"));
+        Ok(self)
+    }
 }
 
 /// Pluggable transport for testability and integration boundaries.
 pub trait MavenTransport: Send + Sync {
     /// Perform an HTTP GET request and return the raw response
     fn get(&self, url: String) -> HttpTransportFuture;
+
+    /// Perform an HTTP GET request with additional request headers
+    ///
+    /// The default implementation ignores the headers and delegates to [`MavenTransport::get`].
+    fn get_with_headers(&self, url: String, headers: Vec<(String, String)>) -> HttpTransportFuture {
+        let _ = headers;
+        self.get(url)
+    }
+
+    /// Perform an HTTP GET request whose body is read no faster than `limiter` allows
+    ///
+    /// The default implementation reads the whole body with
+    /// [`MavenTransport::get_with_headers`] and then waits out the limiter.
+    fn get_throttled(
+        &self,
+        url: String,
+        headers: Vec<(String, String)>,
+        limiter: Arc<RateLimiter>,
+    ) -> HttpTransportFuture {
+        let response = self.get_with_headers(url, headers);
+        Box::pin(async move {
+            let response = response.await?;
+            limiter.consume(response.body.len()).await;
+            Ok(response)
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -42,27 +168,212 @@ impl ReqwestTransport {
             client: Client::new(),
         }
     }
+
+    fn fetch(
+        &self,
+        url: String,
+        headers: Vec<(String, String)>,
+        limiter: Option<Arc<RateLimiter>>,
+    ) -> HttpTransportFuture {
+        let client = self.client.clone();
+        Box::pin(async move {
+            // Compressed bodies are decoded by `MavenClient`
+            let mut request = client.get(&url).header("Accept-Encoding", "gzip, deflate");
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            let mut response = request.send().await?;
+            let mut result = HttpResponse {
+                status: response.status().as_u16(),
+                body: Vec::new(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect(),
+            };
+            // Read chunk by chunk so a dropped connection keeps what arrived
+            loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        if let Some(limiter) = &limiter {
+                            limiter.consume(chunk.len()).await;
+                        }
+                        result.body.extend_from_slice(&chunk);
+                    }
+                    Ok(None) => break,
+                    Err(error)
+                        if (200..=299).contains(&result.status) && !result.body.is_empty() =>
+                    {
+                        return Err(MavenError::TransferInterrupted {
+                            url,
+                            partial: Box::new(result),
+                            message: error.to_string(),
+                        });
+                    }
+                    Err(error) => return Err(error.into()),
+                }
+            }
+
+            Ok(result)
+        })
+    }
 }
 
 impl MavenTransport for ReqwestTransport {
     fn get(&self, url: String) -> HttpTransportFuture {
-        let client = self.client.clone();
-        Box::pin(async move {
-            let response = client.get(&url).send().await?;
-            let status = response.status().as_u16();
-            let body = response.bytes().await?.to_vec();
+        self.fetch(url, Vec::new(), None)
+    }
 
-            Ok(HttpResponse { status, body })
+    fn get_with_headers(&self, url: String, headers: Vec<(String, String)>) -> HttpTransportFuture {
+        self.fetch(url, headers, None)
+    }
+
+    fn get_throttled(
+        &self,
+        url: String,
+        headers: Vec<(String, String)>,
+        limiter: Arc<RateLimiter>,
+    ) -> HttpTransportFuture {
+        self.fetch(url, headers, Some(limiter))
+    }
+}
+
+/// Directory of an [`ArtifactStore`] holding revalidated responses, one per URL
+const VALIDATED_DIR: &str = ".validated";
+
+/// Body and validators of a response that can be revalidated with a conditional GET
+#[derive(Clone)]
+struct ValidatedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Vec<u8>,
+}
+
+impl ValidatedResponse {
+    fn from_response(response: &HttpResponse) -> Option<Self> {
+        let etag = response.header("ETag").map(str::to_string);
+        let last_modified = response.header("Last-Modified").map(str::to_string);
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        Some(Self {
+            etag,
+            last_modified,
+            body: response.body.clone(),
         })
     }
+
+    /// Store key of the body fetched from `url`; the validators sit next to it
+    /// with `.etag` and `.lastmod` appended
+    fn key(url: &str) -> String {
+        let file_name = url.rsplit('/').next().unwrap_or_default();
+        format!(
+            "{}/{}/{}",
+            VALIDATED_DIR,
+            sha1_hex(url.as_bytes()),
+            file_name
+        )
+    }
+
+    fn load(store: &dyn ArtifactStore, url: &str) -> Option<Self> {
+        let key = Self::key(url);
+        let text = |key: String| {
+            store
+                .read(&key)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+        };
+        let etag = text(format!("{}.etag", key));
+        let last_modified = text(format!("{}.lastmod", key));
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        Some(Self {
+            etag,
+            last_modified,
+            body: store.read(&key).ok()?,
+        })
+    }
+
+    /// Validators are written after the body, so an interrupted save never
+    /// pairs them with a body they do not describe
+    fn save(&self, store: &dyn ArtifactStore, url: &str) -> Result<()> {
+        let key = Self::key(url);
+        let sidecars = [
+            (format!("{}.etag", key), &self.etag),
+            (format!("{}.lastmod", key), &self.last_modified),
+        ];
+        for (sidecar, _) in &sidecars {
+            store.remove(sidecar)?;
+        }
+        store.write(&key, &self.body)?;
+        for (sidecar, value) in &sidecars {
+            if let Some(value) = value {
+                store.write(sidecar, value.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn conditional_headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match".to_string(), etag.clone()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since".to_string(), last_modified.clone()));
+        }
+        headers
+    }
 }
 
 /// Maven Central HTTP client
 #[derive(Clone)]
 pub struct MavenClient {
     http: Arc<dyn MavenTransport>,
-    base_url: String,
+    repositories: Vec<Repository>,
     search_url: String,
+    user_agent: String,
+    headers: HashMap<String, String>,
+    /// Validated metadata responses keyed by URL, shared between clones
+    ///
+    /// Backed by the [`Self::with_pom_cache`] store, so they outlive the client.
+    validated: Arc<Mutex<HashMap<String, ValidatedResponse>>>,
+    /// Parsed snapshot `maven-metadata.xml` keyed by URL, `None` where a
+    /// repository has none; shared between clones
+    snapshot_metadata: Arc<Mutex<HashMap<String, Option<crate::metadata::MavenMetadata>>>>,
+    /// JAR download rate limit, shared between clones
+    limiter: Option<Arc<RateLimiter>>,
+    /// Check downloaded jars against their published `.sha1` files
+    verify_checksums: bool,
+    /// Store fetched POMs and revalidated metadata are kept in; POMs are read from it when offline
+    pom_cache: Option<Arc<dyn ArtifactStore>>,
+    /// Fail instead of performing network I/O
+    offline: bool,
+    /// Retries of a GET that failed to connect or got a 5xx response
+    max_retries: u32,
+    /// Wait before the first retry, doubled for each further one
+    retry_base_delay: Duration,
+}
+
+impl fmt::Debug for MavenClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MavenClient")
+            .field("repositories", &self.repositories)
+            .field("user_agent", &self.user_agent)
+            .field(
+                "max_download_bytes_per_sec",
+                &self.limiter.as_ref().map(|limiter| limiter.bytes_per_sec()),
+            )
+            .field("verify_checksums", &self.verify_checksums)
+            .field("offline", &self.offline)
+            .field("max_retries", &self.max_retries)
+            .field("retry_base_delay", &self.retry_base_delay)
+            .finish_non_exhaustive()
+    }
 }
 
 /// Search result from Maven Central
@@ -78,6 +389,9 @@ pub struct SearchResponseBody {
     /// Total number of matching artifacts
     #[serde(rename = "numFound")]
     pub num_found: u32,
+    /// Offset of the first returned document among all matches
+    #[serde(default)]
+    pub start: u32,
     /// Matching artifact documents
     pub docs: Vec<SearchDoc>,
 }
@@ -99,6 +413,21 @@ pub struct SearchDoc {
     pub version: Option<String>,
 }
 
+impl SearchDoc {
+    /// Coordinate of this result
+    ///
+    /// Uses the document's own version (`core=gav` queries), falling back to
+    /// the latest published version; the version is empty if neither is set.
+    pub fn to_coordinate(&self) -> Coordinate {
+        let version = self
+            .version
+            .as_deref()
+            .or(self.latest_version.as_deref())
+            .unwrap_or_default();
+        Coordinate::new(&self.g, &self.a, version)
+    }
+}
+
 impl MavenClient {
     /// Create a client that connects to Maven Central using the default reqwest transport
     pub fn new() -> Self {
@@ -114,43 +443,413 @@ impl MavenClient {
     pub fn with_transport(base_url: &str, transport: Arc<dyn MavenTransport>) -> Self {
         Self {
             http: transport,
-            base_url: base_url.to_string(),
+            repositories: vec![Repository::new("default", base_url)],
             search_url: MAVEN_SEARCH_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            headers: HashMap::new(),
+            validated: Arc::default(),
+            snapshot_metadata: Arc::default(),
+            limiter: None,
+            verify_checksums: true,
+            pom_cache: None,
+            offline: false,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
         }
     }
 
+    /// Fetch artifacts from the given repositories, tried in order
+    ///
+    /// An empty list keeps the current repositories.
+    pub fn with_repositories(mut self, repositories: &[Repository]) -> Self {
+        if !repositories.is_empty() {
+            self.repositories = repositories.to_vec();
+        }
+        self
+    }
+
+    /// Repositories artifacts are fetched from, in lookup order
+    pub fn repositories(&self) -> &[Repository] {
+        &self.repositories
+    }
+
+    /// Repositories eligible to serve the given coordinate
+    ///
+    /// Snapshot versions skip repositories with `snapshots_enabled = false`,
+    /// release versions skip those with `releases_enabled = false`.
+    pub fn repositories_for(&self, coord: &Coordinate) -> Vec<&Repository> {
+        self.repositories
+            .iter()
+            .filter(|repository| repository.serves_version(&coord.version))
+            .collect()
+    }
+
     /// Override the search URL (default: `https://search.maven.org/solrsearch/select`)
     pub fn with_search_url(mut self, search_url: &str) -> Self {
         self.search_url = search_url.to_string();
         self
     }
 
-    async fn read_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
-        let response = self.http.get(url.to_string()).await?;
+    /// Override the `User-Agent` header (default: `polytunnel/<version>`)
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Add extra headers sent with every request
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Cap the combined rate of JAR downloads at `bytes_per_sec`
+    ///
+    /// The limit is shared by every clone of the returned client. POMs,
+    /// metadata, and checksums are not throttled.
+    pub fn with_max_download_speed(mut self, bytes_per_sec: u64) -> Self {
+        self.limiter = Some(Arc::new(RateLimiter::new(bytes_per_sec)));
+        self
+    }
+
+    /// Check downloaded jars against their published `.sha1` files (default: on)
+    ///
+    /// When off, the checksum files are not requested and whatever the first
+    /// repository serves is accepted.
+    pub fn with_checksum_verification(mut self, verify: bool) -> Self {
+        self.verify_checksums = verify;
+        self
+    }
+
+    /// Keep every POM fetched by [`Self::fetch_pom`] in `store` under its [`pom_key`]
+    ///
+    /// Offline clients read POMs from this store instead. Revalidated
+    /// `maven-metadata.xml` responses and their `ETag`/`Last-Modified`
+    /// validators are kept there too, so later clients send conditional GETs.
+    pub fn with_pom_cache(mut self, store: Arc<dyn ArtifactStore>) -> Self {
+        self.pom_cache = Some(store);
+        self
+    }
+
+    /// Never perform network I/O (`--offline`)
+    ///
+    /// POMs come from the [`Self::with_pom_cache`] store; every other request
+    /// fails with `MavenError::Offline`.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Whether this client refuses network I/O
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Retry a GET that failed to connect or got a 5xx response up to `max_retries` times
+    ///
+    /// Defaults to [`DEFAULT_MAX_RETRIES`]; `0` fails on the first error. Other
+    /// statuses, such as `404`, are never retried.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait `delay` before the first retry and twice as long before each further one
+    ///
+    /// Defaults to [`DEFAULT_RETRY_BASE_DELAY`].
+    pub fn with_retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = delay;
+        self
+    }
+
+    /// Retries of a failed GET (see [`Self::with_max_retries`])
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Wait before the first retry (see [`Self::with_retry_base_delay`])
+    pub fn retry_base_delay(&self) -> Duration {
+        self.retry_base_delay
+    }
+
+    /// Apply the `[http]` settings from `polytunnel.toml`
+    pub fn with_http_config(self, config: &polytunnel_core::HttpConfig) -> Self {
+        let mut client = match &config.user_agent {
+            Some(user_agent) => self.with_user_agent(user_agent),
+            None => self,
+        };
+        if let Some(bytes_per_sec) = config.max_download_bytes_per_sec {
+            client = client.with_max_download_speed(bytes_per_sec);
+        }
+        client.with_headers(config.headers.clone())
+    }
+
+    /// Headers sent with every request, `User-Agent` first
+    pub fn request_headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![("User-Agent".to_string(), self.user_agent.clone())];
+        let mut extra: Vec<_> = self.headers.clone().into_iter().collect();
+        extra.sort();
+        headers.extend(extra);
+        headers
+    }
+
+    /// [`Self::request_headers`] plus `Authorization` if `url` belongs to a repository with credentials
+    fn headers_for(&self, url: &str) -> Result<Vec<(String, String)>> {
+        let mut headers = self.request_headers();
+        let repository = self
+            .repositories
+            .iter()
+            .find(|repository| url.starts_with(&repository_url(repository, "")));
+        if let Some((username, password)) = repository
+            .map(Repository::credentials)
+            .transpose()?
+            .flatten()
+        {
+            let token = BASE64_STANDARD.encode(format!("{username}:{password}"));
+            headers.push(("Authorization".to_string(), format!("Basic {token}")));
+        }
+        Ok(headers)
+    }
+
+    /// Fail with `MavenError::Offline` if requesting `url` is not allowed
+    fn ensure_online(&self, url: &str) -> Result<()> {
+        if self.offline {
+            return Err(MavenError::Offline {
+                resource: url.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Send the request built by `request`, retrying transient failures with exponential backoff
+    ///
+    /// Connection errors, timeouts, and 5xx responses are transient; the last
+    /// attempt's result is returned once [`Self::max_retries`] are used up.
+    async fn send_with_retries(
+        &self,
+        request: impl Fn() -> HttpTransportFuture,
+    ) -> Result<HttpResponse> {
+        let mut attempt = 0;
+        loop {
+            let result = request().await;
+            let transient = match &result {
+                Ok(response) => (500..=599).contains(&response.status),
+                Err(MavenError::Http(error)) => error.is_connect() || error.is_timeout(),
+                Err(_) => false,
+            };
+            if !transient || attempt >= self.max_retries {
+                return result;
+            }
+            let delay = self
+                .retry_base_delay
+                .saturating_mul(2u32.saturating_pow(attempt));
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        self.ensure_online(url)?;
+        let headers = self.headers_for(url)?;
+        self.send_with_retries(|| self.http.get_with_headers(url.to_string(), headers.clone()))
+            .await?
+            .decode_content()
+    }
+
+    /// GET `url`, revalidating a previously seen response with `If-None-Match`/`If-Modified-Since`
+    ///
+    /// A `304 Not Modified` reply is answered from the remembered body.
+    async fn read_revalidated(&self, url: &str) -> Result<HttpResponse> {
+        self.ensure_online(url)?;
+        let mut headers = self.headers_for(url)?;
+        let cached = self.validated_response(url);
+        if let Some(cached) = &cached {
+            headers.extend(cached.conditional_headers());
+        }
+
+        let response = self
+            .send_with_retries(|| self.http.get_with_headers(url.to_string(), headers.clone()))
+            .await?
+            .decode_content()?;
+
+        if response.status == 304
+            && let Some(cached) = cached
+        {
+            return Ok(HttpResponse {
+                status: 200,
+                body: cached.body,
+                headers: response.headers,
+            });
+        }
         if !(200..=299).contains(&response.status) {
             return Err(MavenError::HttpStatus {
                 status: response.status,
                 url: url.to_string(),
             });
         }
+        if let Some(entry) = ValidatedResponse::from_response(&response) {
+            if let Some(store) = &self.pom_cache {
+                entry.save(store.as_ref(), url)?;
+            }
+            self.validated
+                .lock()
+                .unwrap()
+                .insert(url.to_string(), entry);
+        }
+        Ok(response)
+    }
 
+    /// Response remembered for `url` by this client or, failing that, in the POM cache
+    fn validated_response(&self, url: &str) -> Option<ValidatedResponse> {
+        let mut validated = self.validated.lock().unwrap();
+        if let Some(cached) = validated.get(url) {
+            return Some(cached.clone());
+        }
+        let cached = ValidatedResponse::load(self.pom_cache.as_deref()?, url)?;
+        validated.insert(url.to_string(), cached.clone());
+        Some(cached)
+    }
+
+    async fn read_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self.read_ok(url).await?;
         serde_json::from_slice(&response.body).map_err(|error| MavenError::JsonParse {
             message: error.to_string(),
         })
     }
 
-    async fn read_text(&self, url: &str) -> Result<String> {
-        let response = self.http.get(url.to_string()).await?;
+    async fn read_ok(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.get(url).await?;
         if !(200..=299).contains(&response.status) {
             return Err(MavenError::HttpStatus {
                 status: response.status,
                 url: url.to_string(),
             });
         }
+        Ok(response)
+    }
 
-        String::from_utf8(response.body).map_err(|error| MavenError::InvalidUtf8 {
-            message: error.to_string(),
-        })
+    /// GET `path` from each repository in turn, returning the first successful response
+    async fn read_from(&self, repositories: &[&Repository], path: &str) -> Result<HttpResponse> {
+        self.read_from_with(repositories, path, false).await
+    }
+
+    async fn read_from_with(
+        &self,
+        repositories: &[&Repository],
+        path: &str,
+        revalidate: bool,
+    ) -> Result<HttpResponse> {
+        let mut last_error = None;
+        for repository in repositories {
+            let url = repository_url(repository, path);
+            let result = if revalidate {
+                self.read_revalidated(&url).await
+            } else {
+                self.read_ok(&url).await
+            };
+            match result {
+                Ok(response) => return Ok(response),
+                Err(error @ MavenError::HttpStatus { .. }) => last_error = Some(error),
+                Err(error) => return Err(error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| MavenError::ArtifactNotFound {
+            coordinate: path.to_string(),
+        }))
+    }
+
+    async fn read_artifact_file(
+        &self,
+        coord: &Coordinate,
+        file_name: &str,
+    ) -> Result<HttpResponse> {
+        let repositories = self.repositories_for(coord);
+        if repositories.is_empty() {
+            return Err(MavenError::ArtifactNotFound {
+                coordinate: coord.to_string(),
+            });
+        }
+        let mut last_error = None;
+        for repository in repositories {
+            let path = self.artifact_path(repository, coord, file_name).await?;
+            match self.read_from(&[repository], &path).await {
+                Ok(response) => return Ok(response),
+                Err(error @ MavenError::HttpStatus { .. }) => last_error = Some(error),
+                Err(error) => return Err(error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| MavenError::ArtifactNotFound {
+            coordinate: coord.to_string(),
+        }))
+    }
+
+    /// Repository path of `file_name` for `coord`, resolving `-SNAPSHOT` to the latest build
+    ///
+    /// For a snapshot version the version-level `maven-metadata.xml` on
+    /// `repository` names the timestamped file. It is fetched once per client
+    /// with a conditional GET. Some repositories publish no such metadata but
+    /// serve the literal `-SNAPSHOT` file; that path is used instead, with a
+    /// warning.
+    async fn artifact_path(
+        &self,
+        repository: &Repository,
+        coord: &Coordinate,
+        file_name: &str,
+    ) -> Result<String> {
+        let literal = format!("{}/{}", coord.repo_path(), file_name);
+        if !is_snapshot_version(&coord.version) {
+            return Ok(literal);
+        }
+
+        let metadata_url = repository_url(
+            repository,
+            &format!("{}/maven-metadata.xml", coord.repo_path()),
+        );
+        let known = self
+            .snapshot_metadata
+            .lock()
+            .unwrap()
+            .get(&metadata_url)
+            .cloned();
+        let metadata = match known {
+            Some(metadata) => metadata,
+            None => {
+                let metadata = match self.read_revalidated(&metadata_url).await {
+                    Ok(response) => Some(crate::metadata::parse_metadata(&decode_text(response)?)?),
+                    Err(MavenError::HttpStatus { .. }) => {
+                        eprintln!(
+                            "Warning: no snapshot metadata for {} at {}; requesting its files directly",
+                            coord, repository.url
+                        );
+                        None
+                    }
+                    Err(error) => return Err(error),
+                };
+                self.snapshot_metadata
+                    .lock()
+                    .unwrap()
+                    .insert(metadata_url, metadata.clone());
+                metadata
+            }
+        };
+        let Some(metadata) = metadata else {
+            return Ok(literal);
+        };
+
+        let extension = file_name.rsplit('.').next().unwrap_or_default();
+        let classifier = coord
+            .classifier
+            .as_deref()
+            .filter(|_| file_name == coord.jar_filename());
+        Ok(
+            match metadata.snapshot_version(&coord.version, extension, classifier) {
+                Some(timestamped) => format!(
+                    "{}/{}",
+                    coord.repo_path(),
+                    file_name.replacen(&coord.version, &timestamped, 1)
+                ),
+                None => literal,
+            },
+        )
     }
 
     /// Search artifacts by query
@@ -166,74 +865,451 @@ impl MavenClient {
         Ok(response.response.docs)
     }
 
-    /// Fetch POM file content
-    pub async fn fetch_pom_content(&self, coord: &Coordinate) -> Result<String> {
+    /// Search artifacts by query, returning `rows` results starting at offset `start`
+    ///
+    /// The returned body's `num_found` is the total number of matches across all pages.
+    pub async fn search_paged(
+        &self,
+        query: &str,
+        start: u32,
+        rows: u32,
+    ) -> Result<SearchResponseBody> {
         let url = format!(
-            "{}/{}/{}",
-            self.base_url,
-            coord.repo_path(),
-            coord.pom_filename()
+            "{}?q={}&start={}&rows={}&wt=json",
+            self.search_url,
+            urlencoding::encode(query),
+            start,
+            rows
         );
-        self.read_text(&url).await
+        let response: SearchResponse = self.read_json(&url).await?;
+
+        Ok(response.response)
     }
 
-    /// Fetch and parse POM
+    /// GET an arbitrary `url` with this client's headers, returning the response body
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::HttpStatus` - If the server does not answer with a 2xx status
+    pub async fn fetch_url(&self, url: &str) -> Result<Vec<u8>> {
+        Ok(self.read_ok(url).await?.body)
+    }
+
+    /// Fetch POM file content
+    ///
+    /// Repositories are tried in order, skipping those that don't serve the
+    /// coordinate's version type.
+    pub async fn fetch_pom_content(&self, coord: &Coordinate) -> Result<String> {
+        let response = self
+            .read_artifact_file(coord, &coord.pom_filename())
+            .await?;
+        decode_text(response)
+    }
+
+    /// Fetch and parse POM, verifying it declares the requested coordinate
+    ///
+    /// The response status and `Content-Type` are checked before parsing, so
+    /// error pages are reported as such rather than as malformed XML.
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::PomNotFound` - If the repositories answer 404 or 410
+    /// * `MavenError::ServerError` - If a repository answers with a 5xx status
+    /// * `MavenError::UnexpectedContentType` - If the response is not XML (e.g. an HTML page)
+    /// * `MavenError::CoordinateMismatch` - If the repository served a POM for another artifact
+    /// * `MavenError::Offline` - If the client is offline and the POM is not cached
     pub async fn fetch_pom(&self, coord: &Coordinate) -> Result<Pom> {
-        let content = self.fetch_pom_content(coord).await?;
-        crate::pom::parse_pom(&content)
+        if self.offline {
+            let offline = || MavenError::Offline {
+                resource: coord.to_string(),
+            };
+            let bytes = match &self.pom_cache {
+                Some(cache) => cache.read(&pom_key(coord)).map_err(|_| offline())?,
+                None => return Err(offline()),
+            };
+            let xml = String::from_utf8(bytes).map_err(|error| MavenError::InvalidUtf8 {
+                message: error.to_string(),
+            })?;
+            let pom = crate::pom::parse_pom(&xml)?;
+            pom.verify_coordinate(coord)?;
+            return Ok(pom);
+        }
+
+        let response = match self.read_artifact_file(coord, &coord.pom_filename()).await {
+            Ok(response) => response,
+            Err(MavenError::HttpStatus {
+                status: 404 | 410,
+                url,
+            }) => return Err(MavenError::PomNotFound { path: url }),
+            Err(MavenError::HttpStatus { status, url }) if status >= 500 => {
+                return Err(MavenError::ServerError { status, url });
+            }
+            Err(error) => return Err(error),
+        };
+        if let Some(content_type) = response.header("Content-Type")
+            && !is_xml_content_type(content_type)
+        {
+            return Err(MavenError::UnexpectedContentType {
+                what: format!("POM of {}", coord),
+                content_type: content_type.to_string(),
+            });
+        }
+        let xml = decode_text(response)?;
+        let pom = crate::pom::parse_pom(&xml)?;
+        pom.verify_coordinate(coord)?;
+        if let Some(cache) = &self.pom_cache {
+            cache.write(&pom_key(coord), xml.as_bytes())?;
+        }
+        Ok(pom)
     }
 
-    /// Get list of available versions for an artifact
+    /// Fetch the licenses declared for an artifact
+    ///
+    /// When the POM declares none, the `<parent>` chain is followed (up to
+    /// [`MAX_PARENT_DEPTH`] levels) since licenses are commonly inherited.
+    /// Returns an empty list when no POM in the chain declares a license.
+    pub async fn fetch_licenses(&self, coord: &Coordinate) -> Result<Vec<License>> {
+        let mut current = coord.clone();
+        for _ in 0..=MAX_PARENT_DEPTH {
+            let pom = crate::pom::parse_pom(&self.fetch_pom_content(&current).await?)?;
+            if !pom.licenses.is_empty() {
+                return Ok(pom.licenses);
+            }
+            match pom.parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// List every published version of an artifact, newest first
+    ///
+    /// Versions come from the `<versioning><versions>` list of
+    /// `maven-metadata.xml` and are ordered as [`MavenVersion`]s.
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::HttpStatus` - If no repository serves the metadata
+    /// * `MavenError::XmlParse` - If the metadata is malformed
     pub async fn list_versions(&self, group_id: &str, artifact_id: &str) -> Result<Vec<String>> {
-        let query = format!("g:\"{}\" AND a:\"{}\"", group_id, artifact_id);
-        let url = format!(
-            "{}?q={}&core=gav&rows=100&wt=json",
-            self.search_url,
-            urlencoding::encode(&query)
-        );
-        let response: SearchResponse = self.read_json(&url).await?;
+        let metadata = self.fetch_metadata(group_id, artifact_id).await?;
+        let mut versions = metadata.versions;
+        versions.sort_by_cached_key(|version| std::cmp::Reverse(MavenVersion::parse(version)));
+        versions.dedup();
+        Ok(versions)
+    }
 
-        let versions: Vec<String> = response
-            .response
-            .docs
-            .into_iter()
-            .filter_map(|d| d.version)
-            .collect();
+    /// Get the artifact-level `maven-metadata.xml` URL on the first repository
+    pub fn metadata_url(&self, group_id: &str, artifact_id: &str) -> String {
+        repository_url(&self.repositories[0], &metadata_path(group_id, artifact_id))
+    }
 
-        Ok(versions)
+    /// Fetch and parse the artifact-level `maven-metadata.xml`
+    ///
+    /// Repositories are tried in order until one serves the file. Repeated
+    /// fetches send the `ETag`/`Last-Modified` of the previous response and
+    /// reuse its body when the server answers `304 Not Modified`.
+    pub async fn fetch_metadata(&self, group_id: &str, artifact_id: &str) -> Result<MavenMetadata> {
+        let repositories: Vec<_> = self.repositories.iter().collect();
+        let response = self
+            .read_from_with(&repositories, &metadata_path(group_id, artifact_id), true)
+            .await?;
+        crate::metadata::parse_metadata(&decode_text(response)?)
     }
 
-    /// Get JAR download URL
+    /// Get JAR download URL on the first repository serving the coordinate
     pub fn jar_url(&self, coord: &Coordinate) -> String {
-        format!(
-            "{}/{}/{}",
-            self.base_url,
-            coord.repo_path(),
-            coord.jar_filename()
+        let repository = self
+            .repositories_for(coord)
+            .into_iter()
+            .next()
+            .unwrap_or(&self.repositories[0]);
+        repository_url(
+            repository,
+            &format!("{}/{}", coord.repo_path(), coord.jar_filename()),
         )
     }
 
     /// Download JAR to a path
+    ///
+    /// Each jar is checked against the `.sha1` file published next to it. On a
+    /// mismatch the jar is discarded and the next eligible repository is tried;
+    /// `dest` is only written once a jar verifies (or its repository publishes
+    /// no checksum).
+    ///
+    /// If the connection drops mid-transfer and the server accepts byte ranges,
+    /// the received bytes are kept in `<dest>.part` and the next call resumes
+    /// from there with a `Range` request. The completed jar is renamed into place.
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::ChecksumMismatch` - If every repository served a corrupt jar
+    /// * `MavenError::HttpStatus` - If no repository serves the jar
+    /// * `MavenError::TransferInterrupted` - If the connection dropped mid-transfer
     pub async fn download_jar(&self, coord: &Coordinate, dest: &Path, verbose: bool) -> Result<()> {
-        let url = self.jar_url(coord);
+        let file_name = dest
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| coord.jar_filename());
+        let dir = match dest.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let partial = FileSystemStore::new(dir);
+        let part_key = partial_key(&file_name);
+
+        let body = self
+            .fetch_verified_jar(coord, &partial, &part_key, verbose)
+            .await?;
+        let part_path = partial.path_for(&part_key);
+        tokio::fs::write(&part_path, body).await?;
+        tokio::fs::rename(part_path, dest).await?;
+        Ok(())
+    }
+
+    /// Download a JAR into `store` under its [`jar_key`], returning its size in bytes
+    ///
+    /// Verification, repository fallback, and resuming interrupted transfers
+    /// work as in [`MavenClient::download_jar`]; the partial download is kept
+    /// in `store` under [`partial_key`].
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::ChecksumMismatch` - If every repository served a corrupt jar
+    /// * `MavenError::HttpStatus` - If no repository serves the jar
+    /// * `MavenError::TransferInterrupted` - If the connection dropped mid-transfer
+    pub async fn download_jar_to_store(
+        &self,
+        coord: &Coordinate,
+        store: &dyn ArtifactStore,
+        verbose: bool,
+    ) -> Result<u64> {
+        let key = jar_key(coord);
+        let part_key = partial_key(&key);
+        let body = self
+            .fetch_verified_jar(coord, store, &part_key, verbose)
+            .await?;
+        store.write(&key, &body)?;
+        store.remove(&part_key)?;
+        Ok(body.len() as u64)
+    }
 
+    /// Fetch and verify a jar, resuming from and saving to `partial` under `part_key`
+    async fn fetch_verified_jar(
+        &self,
+        coord: &Coordinate,
+        partial: &dyn ArtifactStore,
+        part_key: &str,
+        verbose: bool,
+    ) -> Result<Vec<u8>> {
         if verbose {
             eprintln!("   Downloading {}", coord);
         }
 
-        let response = self.http.get(url.clone()).await?;
-        if !(200..=299).contains(&response.status) {
-            return Err(MavenError::HttpStatus {
-                status: response.status,
-                url,
+        let repositories = self.repositories_for(coord);
+        let mut last_error = None;
+        for repository in repositories {
+            let path = self
+                .artifact_path(repository, coord, &coord.jar_filename())
+                .await?;
+            let url = repository_url(repository, &path);
+            let body = match self.read_resumable(&url, partial, part_key, verbose).await {
+                Ok(body) => body,
+                Err(error @ MavenError::HttpStatus { .. }) => {
+                    last_error = Some(error);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+
+            match self.verify_sha1(&url, &body).await {
+                Ok(()) => return Ok(body),
+                Err(error @ MavenError::ChecksumMismatch { .. }) => {
+                    if verbose {
+                        eprintln!("   {}; trying next repository", error);
+                    }
+                    // A resumed download may have been corrupted by its first part
+                    partial.remove(part_key)?;
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| MavenError::ArtifactNotFound {
+            coordinate: coord.to_string(),
+        }))
+    }
+
+    /// GET `url`, resuming from the partial download under `part_key` if there is one
+    ///
+    /// An interrupted transfer is saved under `part_key` when the server
+    /// accepts byte ranges. A server that ignores the `Range` header (`200`)
+    /// or rejects it (`416`) gets the whole file instead.
+    async fn read_resumable(
+        &self,
+        url: &str,
+        partial: &dyn ArtifactStore,
+        part_key: &str,
+        verbose: bool,
+    ) -> Result<Vec<u8>> {
+        self.ensure_online(url)?;
+        let mut existing = match partial.read(part_key) {
+            Ok(bytes) => bytes,
+            Err(MavenError::ArtifactNotFound { .. }) => Vec::new(),
+            Err(error) => return Err(error),
+        };
+
+        loop {
+            let mut headers = self.headers_for(url)?;
+            if !existing.is_empty() {
+                if verbose {
+                    eprintln!("   Resuming {} from byte {}", url, existing.len());
+                }
+                headers.push(("Range".to_string(), format!("bytes={}-", existing.len())));
+            }
+            let request = || match &self.limiter {
+                Some(limiter) => {
+                    self.http
+                        .get_throttled(url.to_string(), headers.clone(), Arc::clone(limiter))
+                }
+                None => self.http.get_with_headers(url.to_string(), headers.clone()),
+            };
+            let (response, interrupted) = match self.send_with_retries(request).await {
+                Ok(response) => (response, None),
+                Err(MavenError::TransferInterrupted {
+                    partial, message, ..
+                }) => (*partial, Some(message)),
+                Err(error) => return Err(error),
+            };
+
+            if response.status == 416 && !existing.is_empty() {
+                partial.remove(part_key)?;
+                existing.clear();
+                continue;
+            }
+            if !(200..=299).contains(&response.status) {
+                return Err(MavenError::HttpStatus {
+                    status: response.status,
+                    url: url.to_string(),
+                });
+            }
+
+            let resumed = !existing.is_empty() && resumes_at(&response, existing.len());
+            let Some(message) = interrupted else {
+                if !resumed {
+                    return Ok(response.decode_content()?.body);
+                }
+                existing.extend_from_slice(&response.body);
+                return Ok(existing);
+            };
+
+            let mut received = if resumed { existing } else { Vec::new() };
+            received.extend_from_slice(&response.body);
+            if response.accepts_ranges() {
+                partial.write(part_key, &received)?;
+            } else {
+                partial.remove(part_key)?;
+            }
+            return Err(MavenError::TransferInterrupted {
+                url: url.to_string(),
+                partial: Box::new(HttpResponse {
+                    body: received,
+                    ..response
+                }),
+                message,
             });
         }
+    }
+
+    /// Check `body` against `<url>.sha1`; a missing checksum file is not an error
+    async fn verify_sha1(&self, url: &str, body: &[u8]) -> Result<()> {
+        if !self.verify_checksums {
+            return Ok(());
+        }
+        let checksum_url = format!("{}.sha1", url);
+        let response = match self.read_ok(&checksum_url).await {
+            Ok(response) => response,
+            Err(MavenError::HttpStatus { .. }) => return Ok(()),
+            Err(error) => return Err(error),
+        };
 
-        tokio::fs::write(dest, response.body).await?;
+        // Checksum files may be `<hex>` or `<hex>  <filename>`
+        let expected = String::from_utf8_lossy(&response.body)
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let actual = sha1_hex(body);
+        if expected != actual {
+            return Err(MavenError::ChecksumMismatch {
+                url: url.to_string(),
+                expected,
+                actual,
+            });
+        }
         Ok(())
     }
 }
 
+fn repository_url(repository: &Repository, path: &str) -> String {
+    format!("{}/{}", repository.url.trim_end_matches('/'), path)
+}
+
+/// Whether `response` is a `206` continuing a download at byte `offset`
+fn resumes_at(response: &HttpResponse, offset: usize) -> bool {
+    if response.status != 206 || !response.accepts_ranges() {
+        return false;
+    }
+    // `Content-Range: bytes <start>-<end>/<total>`
+    response
+        .header("Content-Range")
+        .and_then(|range| range.trim().strip_prefix("bytes "))
+        .and_then(|range| range.split('-').next())
+        .and_then(|start| start.trim().parse::<usize>().ok())
+        == Some(offset)
+}
+
+fn metadata_path(group_id: &str, artifact_id: &str) -> String {
+    format!(
+        "{}/{}/maven-metadata.xml",
+        group_id.replace('.', "/"),
+        artifact_id
+    )
+}
+
+pub(crate) fn sha1_hex(bytes: &[u8]) -> String {
+    Sha1::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Whether a `Content-Type` can carry an XML document
+///
+/// Besides XML media types, `text/plain` and `application/octet-stream` are
+/// accepted since some repositories serve every file with one of them.
+fn is_xml_content_type(content_type: &str) -> bool {
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    media_type.is_empty()
+        || media_type.ends_with("/xml")
+        || (media_type.ends_with("+xml") && media_type != "application/xhtml+xml")
+        || media_type == "text/plain"
+        || media_type == "application/octet-stream"
+}
+
+fn decode_text(response: HttpResponse) -> Result<String> {
+    String::from_utf8(response.body).map_err(|error| MavenError::InvalidUtf8 {
+        message: error.to_string(),
+    })
+}
+
 impl Default for MavenClient {
     fn default() -> Self {
         Self::new()
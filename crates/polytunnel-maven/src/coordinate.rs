@@ -24,39 +24,115 @@ pub(crate) fn default_packaging() -> String {
     "jar".to_string()
 }
 
+/// Packagings whose main artifact is a plain `.jar` on disk
+const JAR_PACKAGINGS: &[&str] = &[
+    "jar",
+    "bundle",
+    "maven-plugin",
+    "eclipse-plugin",
+    "ejb",
+    "test-jar",
+];
+
+/// Map a packaging type to the file extension of its main artifact
+///
+/// Jar-like packagings such as `bundle` or `maven-plugin` map to `"jar"`,
+/// `pom` maps to `None` (nothing to download besides the POM itself), and
+/// any other packaging (e.g. `war`) is used as the extension verbatim.
+pub fn packaging_extension(packaging: &str) -> Option<&str> {
+    if JAR_PACKAGINGS.contains(&packaging) {
+        Some("jar")
+    } else if packaging == "pom" {
+        None
+    } else {
+        Some(packaging)
+    }
+}
+
 impl Coordinate {
     /// Create a new coordinate with JAR packaging and no classifier
+    ///
+    /// Surrounding whitespace is trimmed from each segment; use
+    /// [`Coordinate::validate`] to reject empty or malformed segments.
     pub fn new(group_id: &str, artifact_id: &str, version: &str) -> Self {
         Self {
-            group_id: group_id.to_string(),
-            artifact_id: artifact_id.to_string(),
-            version: version.to_string(),
+            group_id: group_id.trim().to_string(),
+            artifact_id: artifact_id.trim().to_string(),
+            version: version.trim().to_string(),
             classifier: None,
             packaging: default_packaging(),
         }
     }
 
     /// Parse from "groupId:artifactId:version" format
+    ///
+    /// Also accepts `groupId:artifactId:packaging:version` and
+    /// `groupId:artifactId:packaging:classifier:version`. Segments are
+    /// trimmed, must not be empty, and must not contain whitespace or path
+    /// separators.
     pub fn parse(s: &str) -> Result<Self, CoordinateError> {
-        let parts: Vec<&str> = s.split(':').collect();
-        match parts.len() {
-            3 => Ok(Self::new(parts[0], parts[1], parts[2])),
-            4 => Ok(Self {
+        let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+        let coord = match parts.len() {
+            3 => Self::new(parts[0], parts[1], parts[2]),
+            4 => Self {
                 group_id: parts[0].to_string(),
                 artifact_id: parts[1].to_string(),
                 packaging: parts[2].to_string(),
                 version: parts[3].to_string(),
                 classifier: None,
-            }),
-            5 => Ok(Self {
+            },
+            5 => Self {
                 group_id: parts[0].to_string(),
                 artifact_id: parts[1].to_string(),
                 packaging: parts[2].to_string(),
                 classifier: Some(parts[3].to_string()),
                 version: parts[4].to_string(),
-            }),
-            _ => Err(CoordinateError::InvalidFormat(s.to_string())),
+            },
+            _ => return Err(CoordinateError::InvalidFormat(s.to_string())),
+        };
+        coord.validate()?;
+        if coord.version.is_empty() {
+            return Err(CoordinateError::EmptySegment {
+                segment: "version",
+                input: s.to_string(),
+            });
+        }
+        Ok(coord)
+    }
+
+    /// Check that no segment would produce a broken repository path
+    ///
+    /// `group_id`, `artifact_id`, `packaging`, and a present classifier must be
+    /// non-empty; the version may be empty (it is filled in during resolution).
+    /// No segment may contain whitespace, `/`, or `\`.
+    pub fn validate(&self) -> Result<(), CoordinateError> {
+        let segments = [
+            ("groupId", Some(self.group_id.as_str()), true),
+            ("artifactId", Some(self.artifact_id.as_str()), true),
+            ("version", Some(self.version.as_str()), false),
+            ("packaging", Some(self.packaging.as_str()), true),
+            ("classifier", self.classifier.as_deref(), true),
+        ];
+        for (segment, value, required) in segments {
+            let Some(value) = value else { continue };
+            if required && value.is_empty() {
+                return Err(CoordinateError::EmptySegment {
+                    segment,
+                    input: self.to_string(),
+                });
+            }
+            if let Some(character) = value
+                .chars()
+                .find(|c| c.is_whitespace() || matches!(c, '/' | '\\'))
+            {
+                return Err(CoordinateError::InvalidCharacter {
+                    segment,
+                    character,
+                    input: self.to_string(),
+                });
+            }
         }
+        Ok(())
     }
 
     /// Convert group_id to path format (org.slf4j -> org/slf4j)
@@ -64,6 +140,16 @@ impl Coordinate {
         self.group_id.replace('.', "/")
     }
 
+    /// File extension of the main artifact, or `None` for `pom` packaging
+    pub fn extension(&self) -> Option<&str> {
+        packaging_extension(&self.packaging)
+    }
+
+    /// Whether the main artifact is a `.jar` on disk
+    pub fn is_jar_packaging(&self) -> bool {
+        self.extension() == Some("jar")
+    }
+
     /// Get the JAR filename
     pub fn jar_filename(&self) -> String {
         match &self.classifier {
@@ -90,7 +176,11 @@ impl Coordinate {
 
 impl fmt::Display for Coordinate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}:{}", self.group_id, self.artifact_id, self.version)
+        write!(f, "{}:{}:{}", self.group_id, self.artifact_id, self.version)?;
+        if let Some(classifier) = &self.classifier {
+            write!(f, ":{}", classifier)?;
+        }
+        Ok(())
     }
 }
 
@@ -100,4 +190,22 @@ pub enum CoordinateError {
     /// The coordinate string did not match the expected `groupId:artifactId:version` format
     #[error("Invalid coordinate format: {0}")]
     InvalidFormat(String),
+    /// A required segment was empty after trimming whitespace
+    #[error("Invalid coordinate '{input}': {segment} must not be empty")]
+    EmptySegment {
+        /// Name of the empty segment (e.g. `"artifactId"`)
+        segment: &'static str,
+        /// The coordinate as given
+        input: String,
+    },
+    /// A segment contained whitespace or a path separator
+    #[error("Invalid coordinate '{input}': {segment} contains illegal character {character:?}")]
+    InvalidCharacter {
+        /// Name of the offending segment (e.g. `"artifactId"`)
+        segment: &'static str,
+        /// The illegal character
+        character: char,
+        /// The coordinate as given
+        input: String,
+    },
 }
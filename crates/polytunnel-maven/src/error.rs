@@ -18,6 +18,17 @@ pub enum MavenError {
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
+    /// Connection dropped while the response body was being received
+    #[error("Transfer of {url} interrupted after {} bytes: {message}", partial.body.len())]
+    TransferInterrupted {
+        /// URL being downloaded
+        url: String,
+        /// Status, headers, and the body bytes received before the interruption
+        partial: Box<crate::HttpResponse>,
+        /// Description of the underlying transport error
+        message: String,
+    },
+
     /// HTTP status error
     #[error("HTTP status {status} for {url}")]
     HttpStatus {
@@ -27,6 +38,33 @@ pub enum MavenError {
         url: String,
     },
 
+    /// Repository answered with a 5xx status
+    #[error("Server error {status} for {url}")]
+    ServerError {
+        /// HTTP status code received
+        status: u16,
+        /// URL that returned the error status
+        url: String,
+    },
+
+    /// Response has a `Content-Type` that cannot hold the expected document
+    #[error("Unexpected content type {content_type} for {what}")]
+    UnexpectedContentType {
+        /// What was being fetched (e.g. the POM of a coordinate)
+        what: String,
+        /// `Content-Type` header of the response
+        content_type: String,
+    },
+
+    /// Response body could not be decompressed
+    #[error("Failed to decode {encoding} response: {message}")]
+    ContentDecoding {
+        /// `Content-Encoding` of the response
+        encoding: String,
+        /// Description of the decoding error
+        message: String,
+    },
+
     /// Failed to parse JSON response
     #[error("JSON parse error: {message}")]
     JsonParse {
@@ -62,6 +100,33 @@ pub enum MavenError {
         coordinate: String,
     },
 
+    /// Downloaded POM declares a different coordinate than was requested
+    #[error("POM coordinate mismatch: requested {requested}, but POM declares {found}")]
+    CoordinateMismatch {
+        /// Coordinate that was requested
+        requested: String,
+        /// Coordinate declared by the downloaded POM
+        found: String,
+    },
+
+    /// Downloaded artifact does not match the repository's published checksum
+    #[error("Checksum mismatch for {url}: expected SHA-1 {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// URL the artifact was downloaded from
+        url: String,
+        /// SHA-1 published next to the artifact
+        expected: String,
+        /// SHA-1 of the downloaded bytes
+        actual: String,
+    },
+
+    /// Downloaded archive could not be read
+    #[error("Invalid archive: {message}")]
+    InvalidArchive {
+        /// Description of the problem
+        message: String,
+    },
+
     /// POM file not found
     #[error("POM not found: {path}")]
     PomNotFound {
@@ -69,6 +134,13 @@ pub enum MavenError {
         path: String,
     },
 
+    /// Offline mode needs something that is not in the local cache
+    #[error("{resource} is not in the local cache and offline mode forbids downloading it")]
+    Offline {
+        /// Coordinate (for POMs) or URL that would have been fetched
+        resource: String,
+    },
+
     /// Core configuration error
     #[error("Configuration error: {0}")]
     Config(#[from] polytunnel_core::CoreError),
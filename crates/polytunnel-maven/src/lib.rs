@@ -4,14 +4,30 @@
 
 #![warn(missing_docs)]
 
+mod archive;
 mod client;
 mod coordinate;
 mod error;
+mod metadata;
 mod pom;
+mod store;
+mod throttle;
+mod version;
 
+pub use archive::{ZipEntry, read_zip};
 pub use client::{
-    HttpResponse, HttpTransportFuture, MavenClient, MavenTransport, SearchDoc, SearchResponse,
+    DEFAULT_MAX_RETRIES, DEFAULT_RETRY_BASE_DELAY, HttpResponse, HttpTransportFuture,
+    MAX_PARENT_DEPTH, MavenClient, MavenTransport, SearchDoc, SearchResponse, SearchResponseBody,
 };
-pub use coordinate::{Coordinate, CoordinateError};
+pub use coordinate::{Coordinate, CoordinateError, packaging_extension};
 pub use error::{MavenError, Result};
-pub use pom::{DependencyScope, Exclusion, Pom, PomDependency, parse_pom};
+pub use metadata::{MavenMetadata, SnapshotVersion, parse_metadata};
+pub use pom::{DependencyScope, Exclusion, License, Pom, PomDependency, parse_pom};
+pub use store::{
+    ArtifactStore, CONTENT_DIR, FileSystemStore, MemoryArtifactStore, jar_key, partial_key, pom_key,
+};
+pub use throttle::RateLimiter;
+pub use version::{
+    MavenVersion, RangeBound, VersionRange, compare_versions, is_meta_version, is_snapshot_version,
+    is_version_range,
+};
@@ -0,0 +1,162 @@
+//! `maven-metadata.xml` parser
+
+use crate::error::{MavenError, Result};
+use crate::version::MavenVersion;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+
+/// Parsed `maven-metadata.xml`
+///
+/// Artifact-level metadata lists versions; version-level metadata of a
+/// `-SNAPSHOT` version describes its timestamped builds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MavenMetadata {
+    /// Group ID declared in the metadata
+    pub group_id: String,
+    /// Artifact ID declared in the metadata
+    pub artifact_id: String,
+    /// Most recently deployed version, including snapshots (`<latest>`)
+    pub latest: Option<String>,
+    /// Most recently deployed non-snapshot version (`<release>`)
+    pub release: Option<String>,
+    /// All published versions in repository order (`<versions>`)
+    pub versions: Vec<String>,
+    /// Last update timestamp (`<lastUpdated>`, `yyyyMMddHHmmss`)
+    pub last_updated: Option<String>,
+    /// Timestamp of the latest snapshot build (`<snapshot><timestamp>`, `yyyyMMdd.HHmmss`)
+    pub snapshot_timestamp: Option<String>,
+    /// Build number of the latest snapshot build (`<snapshot><buildNumber>`)
+    pub snapshot_build_number: Option<String>,
+    /// Timestamped version of each deployed file (`<snapshotVersions>`)
+    pub snapshot_versions: Vec<SnapshotVersion>,
+}
+
+/// Timestamped version of one file of a snapshot build (`<snapshotVersion>`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotVersion {
+    /// Classifier of the file, if any
+    pub classifier: Option<String>,
+    /// File extension (`jar`, `pom`, ...)
+    pub extension: String,
+    /// Version used in the file name, e.g. `1.0.0-20240101.120000-3`
+    pub value: String,
+}
+
+impl MavenMetadata {
+    /// Concrete version named by the `LATEST` or `RELEASE` metaversion
+    ///
+    /// Uses `<latest>`/`<release>`, falling back to the newest listed version
+    /// (ignoring snapshots for `RELEASE`). `None` for any other `spec` or if
+    /// nothing was published.
+    pub fn meta_version(&self, spec: &str) -> Option<String> {
+        let (declared, releases_only) = match spec.trim() {
+            "LATEST" => (&self.latest, false),
+            "RELEASE" => (&self.release, true),
+            _ => return None,
+        };
+        declared.clone().or_else(|| {
+            self.versions
+                .iter()
+                .map(|version| MavenVersion::parse(version))
+                .filter(|version| !releases_only || !version.is_snapshot())
+                .max()
+                .map(|version| version.as_str().to_string())
+        })
+    }
+
+    /// Timestamped version replacing the `-SNAPSHOT` `version` in a file name
+    ///
+    /// Uses the `<snapshotVersion>` matching `extension` and `classifier`,
+    /// falling back to `<snapshot>` timestamp and build number. `None` if the
+    /// metadata describes no remote build (e.g. a local install).
+    pub fn snapshot_version(
+        &self,
+        version: &str,
+        extension: &str,
+        classifier: Option<&str>,
+    ) -> Option<String> {
+        if let Some(entry) = self
+            .snapshot_versions
+            .iter()
+            .find(|entry| entry.extension == extension && entry.classifier.as_deref() == classifier)
+        {
+            return Some(entry.value.clone());
+        }
+        let base = version.strip_suffix("SNAPSHOT")?;
+        let timestamp = self.snapshot_timestamp.as_ref()?;
+        let build_number = self.snapshot_build_number.as_ref()?;
+        Some(format!("{}{}-{}", base, timestamp, build_number))
+    }
+}
+
+/// Parse `maven-metadata.xml` content into a [`MavenMetadata`] struct.
+pub fn parse_metadata(xml: &str) -> Result<MavenMetadata> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut metadata = MavenMetadata::default();
+    let mut current_path: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if name == "snapshotVersion" {
+                    metadata.snapshot_versions.push(SnapshotVersion::default());
+                }
+                current_path.push(name);
+            }
+            Ok(Event::End(_)) => {
+                current_path.pop();
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().unwrap_or_default().to_string();
+                let path: Vec<&str> = current_path.iter().map(String::as_str).collect();
+
+                match path.as_slice() {
+                    ["metadata", "groupId"] => metadata.group_id = text,
+                    ["metadata", "artifactId"] => metadata.artifact_id = text,
+                    ["metadata", "versioning", "latest"] => metadata.latest = Some(text),
+                    ["metadata", "versioning", "release"] => metadata.release = Some(text),
+                    ["metadata", "versioning", "lastUpdated"] => metadata.last_updated = Some(text),
+                    ["metadata", "versioning", "versions", "version"] => {
+                        metadata.versions.push(text)
+                    }
+                    ["metadata", "versioning", "snapshot", "timestamp"] => {
+                        metadata.snapshot_timestamp = Some(text)
+                    }
+                    ["metadata", "versioning", "snapshot", "buildNumber"] => {
+                        metadata.snapshot_build_number = Some(text)
+                    }
+                    [
+                        "metadata",
+                        "versioning",
+                        "snapshotVersions",
+                        "snapshotVersion",
+                        field,
+                    ] => {
+                        if let Some(entry) = metadata.snapshot_versions.last_mut() {
+                            match *field {
+                                "classifier" => entry.classifier = Some(text),
+                                "extension" => entry.extension = text,
+                                "value" => entry.value = text,
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(MavenError::XmlParse {
+                    message: format!("XML parse error: {}", e),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(metadata)
+}
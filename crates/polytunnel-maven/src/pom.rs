@@ -22,6 +22,28 @@ pub struct Pom {
     pub dependency_management: Vec<PomDependency>,
     /// POM properties (key/value pairs, including `project.*` aliases)
     pub properties: std::collections::HashMap<String, String>,
+    /// Licenses declared in `<licenses>` (empty when inherited from the parent)
+    #[serde(default)]
+    pub licenses: Vec<License>,
+}
+
+/// License entry in a POM file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct License {
+    /// License name (e.g. `"Apache License, Version 2.0"`)
+    pub name: Option<String>,
+    /// License text URL
+    pub url: Option<String>,
+}
+
+impl License {
+    /// Name for display, falling back to the URL
+    pub fn display_name(&self) -> &str {
+        self.name
+            .as_deref()
+            .or(self.url.as_deref())
+            .unwrap_or("<unnamed>")
+    }
 }
 
 /// Dependency entry in a POM file
@@ -33,6 +55,9 @@ pub struct PomDependency {
     pub artifact_id: String,
     /// Dependency version (may be absent when managed by `<dependencyManagement>`)
     pub version: Option<String>,
+    /// Artifact classifier (e.g. `"natives-linux"`)
+    #[serde(default)]
+    pub classifier: Option<String>,
     /// Dependency scope (default: `Compile`)
     #[serde(default)]
     pub scope: DependencyScope,
@@ -44,24 +69,83 @@ pub struct PomDependency {
 }
 
 /// Maven dependency scope
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+///
+/// Variants are ordered from widest to narrowest visibility, so the `min` of
+/// two scopes is the one that puts an artifact on more classpaths.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum DependencyScope {
     /// Available during compilation, testing, and runtime (default)
     #[default]
     Compile,
+    /// Provided by the runtime environment; not packaged
+    Provided,
     /// Available at runtime and test time only
     Runtime,
     /// Available during test compilation and execution only
     Test,
-    /// Provided by the runtime environment; not packaged
-    Provided,
     /// Resolved from the local filesystem via `<systemPath>`
     System,
     /// Used in `<dependencyManagement>` to import a BOM
     Import,
 }
 
+impl DependencyScope {
+    /// Whether artifacts in this scope are on the main compile classpath
+    pub fn on_compile_classpath(self) -> bool {
+        matches!(self, Self::Compile | Self::Provided | Self::System)
+    }
+
+    /// Whether artifacts in this scope are on the test compile/run classpath
+    pub fn on_test_classpath(self) -> bool {
+        matches!(
+            self,
+            Self::Compile | Self::Provided | Self::Runtime | Self::Test | Self::System
+        )
+    }
+
+    /// Whether artifacts in this scope are on the runtime classpath
+    pub fn on_runtime_classpath(self) -> bool {
+        matches!(self, Self::Compile | Self::Runtime)
+    }
+
+    /// Whether a dependency declared with this scope is inherited by dependents
+    ///
+    /// Per Maven, only `compile` and `runtime` dependencies are transitive;
+    /// `provided`, `test`, and `system` apply only to the declaring project.
+    pub fn is_transitive(self) -> bool {
+        matches!(self, Self::Compile | Self::Runtime)
+    }
+
+    /// Effective scope of a dependency declared in `declared` scope by an artifact in this scope
+    ///
+    /// Follows Maven's scope table: a `runtime` dependency of a `compile`
+    /// artifact is `runtime`, and everything below a `provided` or `test`
+    /// artifact takes that scope. `None` if `declared` is not transitive.
+    pub fn transitive_scope(self, declared: Self) -> Option<Self> {
+        if !declared.is_transitive() {
+            return None;
+        }
+        Some(match self {
+            Self::Compile => declared,
+            other => other,
+        })
+    }
+}
+
+impl From<polytunnel_core::DependencyScope> for DependencyScope {
+    fn from(scope: polytunnel_core::DependencyScope) -> Self {
+        match scope {
+            polytunnel_core::DependencyScope::Compile => Self::Compile,
+            polytunnel_core::DependencyScope::Runtime => Self::Runtime,
+            polytunnel_core::DependencyScope::Test => Self::Test,
+            polytunnel_core::DependencyScope::Provided => Self::Provided,
+        }
+    }
+}
+
 /// Transitive dependency exclusion rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Exclusion {
@@ -130,6 +214,7 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
         dependencies: Vec::new(),
         dependency_management: Vec::new(),
         properties: std::collections::HashMap::new(),
+        licenses: Vec::new(),
     };
 
     // ... existing parsing loop ...
@@ -140,6 +225,7 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
     let mut group_id = String::new();
     let mut artifact_id = String::new();
     let mut version = String::new();
+    let mut classifier = String::new();
     let mut scope = DependencyScope::Compile;
     let mut optional = false;
 
@@ -148,6 +234,7 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
     let mut in_properties = false;
     let mut in_dependency_management = false;
     let mut in_exclusion = false;
+    let mut current_license: Option<License> = None;
     let mut prop_name = String::new();
     let mut excl_group_id = String::new();
     let mut excl_artifact_id = String::new();
@@ -165,6 +252,7 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
                         group_id.clear();
                         artifact_id.clear();
                         version.clear();
+                        classifier.clear();
                         scope = DependencyScope::Compile;
                         optional = false;
                         current_exclusions.clear();
@@ -174,6 +262,9 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
                         excl_group_id.clear();
                         excl_artifact_id.clear();
                     }
+                    "license" if current_path.len() == 3 && current_path[1] == "licenses" => {
+                        current_license = Some(License::default());
+                    }
                     "parent" => in_parent = true,
                     "properties" => in_properties = true,
                     "dependencyManagement" => in_dependency_management = true,
@@ -197,6 +288,11 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
                             } else {
                                 Some(version.clone())
                             },
+                            classifier: if classifier.is_empty() {
+                                None
+                            } else {
+                                Some(classifier.clone())
+                            },
                             scope,
                             optional,
                             exclusions: std::mem::take(&mut current_exclusions),
@@ -220,6 +316,11 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
                         }
                         in_exclusion = false;
                     }
+                    "license" if current_path.len() == 3 => {
+                        if let Some(license) = current_license.take() {
+                            pom.licenses.push(license);
+                        }
+                    }
                     "parent" => {
                         pom.parent = Some(Coordinate::new(&group_id, &artifact_id, &version));
                         in_parent = false;
@@ -284,9 +385,22 @@ pub fn parse_pom(xml: &str) -> Result<Pom> {
                                 _ => DependencyScope::Compile,
                             };
                         }
+                        "classifier" if in_dependency => {
+                            classifier = current_text.clone();
+                        }
                         "optional" if in_dependency => {
                             optional = current_text == "true";
                         }
+                        "name" if current_license.is_some() && current_path.len() == 4 => {
+                            if let Some(license) = current_license.as_mut() {
+                                license.name = Some(current_text.clone());
+                            }
+                        }
+                        "url" if current_license.is_some() && current_path.len() == 4 => {
+                            if let Some(license) = current_license.as_mut() {
+                                license.url = Some(current_text.clone());
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -382,6 +496,53 @@ fn resolve_single_pass(
 }
 
 impl Pom {
+    /// Check that this POM declares the requested coordinate
+    ///
+    /// `groupId` and `version` fall back to the `<parent>` values when the POM
+    /// inherits them. Elements that are absent or still contain unresolved
+    /// `${...}` placeholders are not compared.
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::CoordinateMismatch` - If any declared element differs
+    pub fn verify_coordinate(&self, requested: &Coordinate) -> Result<()> {
+        let parent = self.parent.as_ref();
+        let group_id = Some(self.coordinate.group_id.as_str())
+            .filter(|g| !g.is_empty())
+            .or_else(|| parent.map(|p| p.group_id.as_str()))
+            .unwrap_or_default();
+        let version = Some(self.coordinate.version.as_str())
+            .filter(|v| !v.is_empty())
+            .or_else(|| parent.map(|p| p.version.as_str()))
+            .unwrap_or_default();
+        let version = self.resolve_property(version);
+
+        let differs = |declared: &str, expected: &str| {
+            !declared.is_empty() && !declared.contains("${") && declared != expected
+        };
+
+        if differs(group_id, &requested.group_id)
+            || differs(&self.coordinate.artifact_id, &requested.artifact_id)
+            || differs(&version, &requested.version)
+        {
+            return Err(MavenError::CoordinateMismatch {
+                requested: requested.to_string(),
+                found: format!("{}:{}:{}", group_id, self.coordinate.artifact_id, version),
+            });
+        }
+        Ok(())
+    }
+
+    /// File extension of the main artifact, or `None` for `pom` packaging
+    pub fn artifact_extension(&self) -> Option<&str> {
+        crate::coordinate::packaging_extension(&self.packaging)
+    }
+
+    /// Whether the main artifact is a `.jar` on disk (`jar`, `bundle`, `maven-plugin`, ...)
+    pub fn is_jar_packaging(&self) -> bool {
+        self.artifact_extension() == Some("jar")
+    }
+
     /// Resolve property placeholders like ${project.version}
     pub fn resolve_property(&self, value: &str) -> String {
         resolve_value(value, &self.properties)
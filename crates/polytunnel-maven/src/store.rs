@@ -0,0 +1,218 @@
+//! Pluggable storage for downloaded artifacts
+
+use crate::client::sha1_hex;
+use crate::coordinate::Coordinate;
+use crate::error::{MavenError, Result};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Storage backend for downloaded JARs and POMs
+///
+/// Artifacts are addressed by their repository-relative key, e.g.
+/// `org/slf4j/slf4j-api/2.0.9/slf4j-api-2.0.9.jar` (see [`jar_key`] and
+/// [`pom_key`]). [`FileSystemStore`] is the default backend.
+pub trait ArtifactStore: Debug + Send + Sync {
+    /// Whether an artifact is stored under `key`
+    fn contains(&self, key: &str) -> bool;
+
+    /// Read the artifact stored under `key`
+    ///
+    /// # Errors
+    ///
+    /// * `MavenError::ArtifactNotFound` - If nothing is stored under `key`
+    fn read(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Store `bytes` under `key`, replacing any previous content
+    fn write(&self, key: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Remove the artifact stored under `key`; removing a missing key is not an error
+    fn remove(&self, key: &str) -> Result<()>;
+
+    /// Path the artifact under `key` is (or would be) available at
+    ///
+    /// This is what ends up on the `javac`/`java` classpath.
+    fn path_for(&self, key: &str) -> PathBuf;
+}
+
+/// Store key of a coordinate's JAR
+pub fn jar_key(coord: &Coordinate) -> String {
+    format!("{}/{}", coord.repo_path(), coord.jar_filename())
+}
+
+/// Store key under which an interrupted download of `key` is kept until it is resumed
+pub fn partial_key(key: &str) -> String {
+    format!("{}.part", key)
+}
+
+/// Store key of a coordinate's POM
+pub fn pom_key(coord: &Coordinate) -> String {
+    format!("{}/{}", coord.repo_path(), coord.pom_filename())
+}
+
+/// Directory below a [`FileSystemStore`] root holding deduplicated JAR content
+pub const CONTENT_DIR: &str = ".content";
+
+/// Artifacts stored as files below a cache directory
+#[derive(Debug, Clone)]
+pub struct FileSystemStore {
+    root: PathBuf,
+    dedup: bool,
+}
+
+impl FileSystemStore {
+    /// Create a store rooted at `root` (typically `build.cache_dir`)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            dedup: false,
+        }
+    }
+
+    /// Store identical JARs once (`build.cache_dedup`)
+    ///
+    /// JAR bytes are written to `<root>/.content/sha1/<hash>` and hard-linked
+    /// from their coordinate path, so coordinates republishing the same bytes
+    /// share one copy. Falls back to a plain copy where hard links fail.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Cache directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the deduplicated copy of `bytes`
+    pub fn content_path(&self, bytes: &[u8]) -> PathBuf {
+        self.root
+            .join(CONTENT_DIR)
+            .join("sha1")
+            .join(sha1_hex(bytes))
+    }
+
+    fn write_deduplicated(&self, path: &Path, bytes: &[u8]) -> Result<()> {
+        let content = self.content_path(bytes);
+        if !content.is_file() {
+            if let Some(parent) = content.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            // Write then rename so a concurrent reader never links a partial file
+            let tmp = content.with_extension(format!("{}.tmp", std::process::id()));
+            std::fs::write(&tmp, bytes)?;
+            std::fs::rename(&tmp, &content)?;
+        }
+
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        if std::fs::hard_link(&content, path).is_err() {
+            std::fs::write(path, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+impl ArtifactStore for FileSystemStore {
+    fn contains(&self, key: &str) -> bool {
+        self.path_for(key).is_file()
+    }
+
+    fn read(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.path_for(key);
+        if !path.is_file() {
+            return Err(MavenError::ArtifactNotFound {
+                coordinate: key.to_string(),
+            });
+        }
+        Ok(std::fs::read(path)?)
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.path_for(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if self.dedup && key.ends_with(".jar") {
+            return self.write_deduplicated(&path, bytes);
+        }
+        // A file written with dedup on may be a hard link to shared content;
+        // writing through it would change every artifact linked to it
+        match std::fs::remove_file(&path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error.into()),
+            _ => {}
+        }
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        match std::fs::remove_file(self.path_for(key)) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+/// Artifacts kept in memory, for tests that should not touch the disk
+///
+/// [`ArtifactStore::path_for`] returns the key itself as a relative path;
+/// nothing is ever written there.
+#[derive(Debug, Default)]
+pub struct MemoryArtifactStore {
+    artifacts: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryArtifactStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keys of all stored artifacts, sorted
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<_> = self.artifacts.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+impl ArtifactStore for MemoryArtifactStore {
+    fn contains(&self, key: &str) -> bool {
+        self.artifacts.lock().unwrap().contains_key(key)
+    }
+
+    fn read(&self, key: &str) -> Result<Vec<u8>> {
+        self.artifacts
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| MavenError::ArtifactNotFound {
+                coordinate: key.to_string(),
+            })
+    }
+
+    fn write(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        self.artifacts
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.artifacts.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        PathBuf::from(key)
+    }
+}
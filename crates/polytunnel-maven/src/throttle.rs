@@ -0,0 +1,66 @@
+//! Download rate limiting
+//!
+//! A token bucket shared by every clone of a [`crate::MavenClient`], so the
+//! cap applies to the combined rate of concurrent downloads.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting the rate at which response bytes are consumed
+///
+/// The bucket holds up to one second's worth of bytes and starts full. Reading
+/// more than is available puts the bucket in debt, and the reader sleeps until
+/// the debt would have been refilled.
+#[derive(Debug)]
+pub struct RateLimiter {
+    bytes_per_sec: u64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Limit reads to `bytes_per_sec` (at least 1)
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        Self {
+            bytes_per_sec,
+            bucket: Mutex::new(Bucket {
+                tokens: bytes_per_sec as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Configured rate in bytes per second
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Account for `bytes` just read, waiting as long as the cap requires
+    pub async fn consume(&self, bytes: usize) {
+        let wait = self.reserve(bytes);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take `bytes` from the bucket, returning how long the caller must wait
+    fn reserve(&self, bytes: usize) -> Duration {
+        let rate = self.bytes_per_sec as f64;
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let refill = now.duration_since(bucket.refilled_at).as_secs_f64() * rate;
+        bucket.tokens = (bucket.tokens + refill).min(rate) - bytes as f64;
+        bucket.refilled_at = now;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! Maven versions, their ordering, and version ranges
+
+use std::cmp::Ordering;
+
+/// Qualifiers in ascending order; the empty qualifier is a plain release
+const QUALIFIERS: &[&str] = &["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// One component of a parsed version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Item {
+    /// Numeric component, digits without leading zeros
+    Number(String),
+    /// Qualifier component, lowercased with aliases expanded
+    Qualifier(String),
+}
+
+/// A parsed Maven version
+///
+/// Ordering is a simplified form of Maven's `ComparableVersion`:
+///
+/// - Versions are split on `.`, `-`, and digit/letter transitions.
+/// - Numeric parts compare numerically, so `1.9 < 1.10`.
+/// - Qualifiers rank `alpha < beta < milestone < rc < snapshot < release < sp`.
+/// - Unknown qualifiers sort after these, alphabetically.
+/// - A numeric part ranks above any qualifier.
+/// - Missing trailing parts count as `0` or as a plain release.
+///
+/// So `1.0-alpha1 < 1.0-rc1 < 1.0-SNAPSHOT < 1.0 = 1.0.0 < 1.0-sp1 < 1.0.1`.
+/// Equality follows the same rules; the original text is kept for display.
+#[derive(Debug, Clone)]
+pub struct MavenVersion {
+    original: String,
+    items: Vec<Item>,
+}
+
+impl MavenVersion {
+    /// Parse `version`; any string is accepted, as in Maven
+    pub fn parse(version: &str) -> Self {
+        Self {
+            original: version.trim().to_string(),
+            items: parse(version),
+        }
+    }
+
+    /// The version as written
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// Leading `.`-separated numbers, e.g. `[33, 0, 0]` for `33.0.0-jre`
+    pub fn numbers(&self) -> Vec<u64> {
+        self.numeric_prefix()
+            .split('.')
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    }
+
+    /// Everything after the leading numbers, e.g. `jre` for `33.0.0-jre`
+    pub fn qualifier(&self) -> Option<&str> {
+        let rest = &self.original[self.numeric_prefix().len()..];
+        let rest = rest.trim_start_matches(['.', '-', '_']);
+        (!rest.is_empty()).then_some(rest)
+    }
+
+    /// The leading numbers without the qualifier, e.g. `1.2.3` for `1.2.3-SNAPSHOT`
+    pub fn base_version(&self) -> String {
+        let numbers: Vec<String> = self.numbers().iter().map(u64::to_string).collect();
+        numbers.join(".")
+    }
+
+    /// Whether this is a `-SNAPSHOT` version
+    pub fn is_snapshot(&self) -> bool {
+        self.original.ends_with("-SNAPSHOT")
+    }
+
+    /// Whether this sorts before its release: alpha, beta, milestone, rc, or snapshot
+    pub fn is_prerelease(&self) -> bool {
+        self.items.iter().any(|item| match item {
+            Item::Qualifier(qualifier) => qualifier_rank(qualifier) < qualifier_rank(""),
+            Item::Number(_) => false,
+        })
+    }
+
+    fn numeric_prefix(&self) -> &str {
+        let end = self
+            .original
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(self.original.len());
+        &self.original[..end]
+    }
+}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.items, &other.items);
+        for i in 0..a.len().max(b.len()) {
+            let ordering = match (a.get(i), b.get(i)) {
+                (Some(x), Some(y)) => compare_items(x, y),
+                (Some(x), None) => compare_to_null(x),
+                (None, Some(y)) => compare_to_null(y).reverse(),
+                (None, None) => Ordering::Equal,
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl std::hash::Hash for MavenVersion {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.items.hash(state);
+    }
+}
+
+impl std::fmt::Display for MavenVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+impl From<&str> for MavenVersion {
+    fn from(version: &str) -> Self {
+        Self::parse(version)
+    }
+}
+
+/// Compare two version strings the way Maven orders them
+///
+/// Shorthand for comparing [`MavenVersion`]s.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    MavenVersion::parse(a).cmp(&MavenVersion::parse(b))
+}
+
+/// Whether `version` is a `-SNAPSHOT` version
+pub fn is_snapshot_version(version: &str) -> bool {
+    MavenVersion::parse(version).is_snapshot()
+}
+
+/// Whether `spec` is a version range (`[...]` or `(...)`) rather than a version
+pub fn is_version_range(spec: &str) -> bool {
+    spec.trim().starts_with(['[', '('])
+}
+
+/// Whether `spec` is the `LATEST` or `RELEASE` metaversion
+pub fn is_meta_version(spec: &str) -> bool {
+    matches!(spec.trim(), "LATEST" | "RELEASE")
+}
+
+/// One end of a [`VersionRange`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeBound {
+    /// Boundary version
+    pub version: MavenVersion,
+    /// Whether `version` itself is in the range (`[`/`]` rather than `(`/`)`)
+    pub inclusive: bool,
+}
+
+/// A single Maven version range such as `[1.2.3,2.0.0)`, `[1.5,)`, or `[1.0]`
+///
+/// Unions of several ranges (`(,1.0],[1.2,)`) are not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    /// Lower bound, `None` when unbounded
+    pub lower: Option<RangeBound>,
+    /// Upper bound, `None` when unbounded
+    pub upper: Option<RangeBound>,
+}
+
+impl VersionRange {
+    /// Parse a range, returning `None` if `spec` is not a supported range
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        let lower_inclusive = match spec.chars().next()? {
+            '[' => true,
+            '(' => false,
+            _ => return None,
+        };
+        let upper_inclusive = match spec.chars().last()? {
+            ']' => true,
+            ')' => false,
+            _ => return None,
+        };
+        let inner = spec.get(1..spec.len() - 1)?;
+        let bound = |version: &str, inclusive: bool| {
+            let version = version.trim();
+            (!version.is_empty()).then(|| RangeBound {
+                version: MavenVersion::parse(version),
+                inclusive,
+            })
+        };
+
+        match inner.split(',').collect::<Vec<_>>().as_slice() {
+            // `[1.0]` is exactly 1.0
+            [version] if lower_inclusive && upper_inclusive => {
+                let exact = bound(version, true)?;
+                Some(Self {
+                    lower: Some(exact.clone()),
+                    upper: Some(exact),
+                })
+            }
+            [lower, upper] => Some(Self {
+                lower: bound(lower, lower_inclusive),
+                upper: bound(upper, upper_inclusive),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether `version` falls inside the range
+    pub fn contains(&self, version: &MavenVersion) -> bool {
+        let above_lower =
+            self.lower
+                .as_ref()
+                .is_none_or(|bound| match version.cmp(&bound.version) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => bound.inclusive,
+                    Ordering::Less => false,
+                });
+        let below_upper =
+            self.upper
+                .as_ref()
+                .is_none_or(|bound| match version.cmp(&bound.version) {
+                    Ordering::Less => true,
+                    Ordering::Equal => bound.inclusive,
+                    Ordering::Greater => false,
+                });
+        above_lower && below_upper
+    }
+
+    /// Newest of `versions` inside the range
+    ///
+    /// `-SNAPSHOT` versions are skipped unless a bound is itself a snapshot.
+    pub fn select<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a MavenVersion>,
+    ) -> Option<&'a MavenVersion> {
+        let allow_snapshots = [&self.lower, &self.upper]
+            .into_iter()
+            .flatten()
+            .any(|bound| bound.version.is_snapshot());
+        versions
+            .into_iter()
+            .filter(|version| allow_snapshots || !version.is_snapshot())
+            .filter(|version| self.contains(version))
+            .max()
+    }
+}
+
+impl std::fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let (Some(lower), Some(upper)) = (&self.lower, &self.upper)
+            && lower == upper
+            && lower.inclusive
+        {
+            return write!(f, "[{}]", lower.version);
+        }
+        let (open, lower) = match &self.lower {
+            Some(bound) => (
+                if bound.inclusive { '[' } else { '(' },
+                bound.version.as_str(),
+            ),
+            None => ('(', ""),
+        };
+        let (close, upper) = match &self.upper {
+            Some(bound) => (
+                if bound.inclusive { ']' } else { ')' },
+                bound.version.as_str(),
+            ),
+            None => (')', ""),
+        };
+        write!(f, "{open}{lower},{upper}{close}")
+    }
+}
+
+fn parse(version: &str) -> Vec<Item> {
+    let version = version.trim().to_lowercase();
+    let mut items = Vec::new();
+    let mut current = String::new();
+
+    for c in version.chars() {
+        if c == '.' || c == '-' || c == '_' {
+            push_item(&mut items, &mut current, false);
+            continue;
+        }
+        if let Some(last) = current.chars().last()
+            && last.is_ascii_digit() != c.is_ascii_digit()
+        {
+            // `a1`/`b1`/`m1` are short for alpha/beta/milestone
+            let followed_by_digit = c.is_ascii_digit();
+            push_item(&mut items, &mut current, followed_by_digit);
+        }
+        current.push(c);
+    }
+    push_item(&mut items, &mut current, false);
+
+    // Trailing `0`s and release qualifiers do not change the version
+    while items.last().is_some_and(is_null) {
+        items.pop();
+    }
+    items
+}
+
+fn push_item(items: &mut Vec<Item>, current: &mut String, followed_by_digit: bool) {
+    if current.is_empty() {
+        return;
+    }
+    let token = std::mem::take(current);
+    let item = if token.chars().all(|c| c.is_ascii_digit()) {
+        let digits = token.trim_start_matches('0');
+        Item::Number(digits.to_string())
+    } else {
+        let qualifier = match token.as_str() {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "cr" => "rc",
+            "ga" | "final" | "release" => "",
+            other => other,
+        };
+        // `1.0-alpha` is `1-alpha`: zeros before a qualifier are dropped
+        while items
+            .last()
+            .is_some_and(|last| *last == Item::Number(String::new()))
+        {
+            items.pop();
+        }
+        Item::Qualifier(qualifier.to_string())
+    };
+    items.push(item);
+}
+
+fn is_null(item: &Item) -> bool {
+    match item {
+        Item::Number(digits) => digits.is_empty(),
+        Item::Qualifier(qualifier) => qualifier.is_empty(),
+    }
+}
+
+fn compare_items(a: &Item, b: &Item) -> Ordering {
+    match (a, b) {
+        (Item::Number(x), Item::Number(y)) => x.len().cmp(&y.len()).then_with(|| x.cmp(y)),
+        (Item::Number(_), Item::Qualifier(_)) => Ordering::Greater,
+        (Item::Qualifier(_), Item::Number(_)) => Ordering::Less,
+        (Item::Qualifier(x), Item::Qualifier(y)) => qualifier_rank(x).cmp(&qualifier_rank(y)),
+    }
+}
+
+/// Compare an item against a missing one (`0` / plain release)
+fn compare_to_null(item: &Item) -> Ordering {
+    match item {
+        Item::Number(digits) if digits.is_empty() => Ordering::Equal,
+        Item::Number(_) => Ordering::Greater,
+        Item::Qualifier(qualifier) => qualifier_rank(qualifier).cmp(&qualifier_rank("")),
+    }
+}
+
+fn qualifier_rank(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|known| *known == qualifier) {
+        Some(rank) => (rank, ""),
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
@@ -0,0 +1,104 @@
+//! Tests for reading ZIP archives.
+
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+use polytunnel_maven::{MavenError, ZipEntry, read_zip};
+use std::io::Write;
+
+/// A ZIP archive of `(name, contents, deflate)` entries; CRCs are left zero
+fn zip(entries: &[(&str, &[u8], bool)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, contents, deflate) in entries {
+        let (method, data): (u16, Vec<u8>) = if *deflate {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(contents).unwrap();
+            (8, encoder.finish().unwrap())
+        } else {
+            (0, contents.to_vec())
+        };
+        let offset = out.len() as u32;
+
+        out.extend(0x0403_4b50u32.to_le_bytes());
+        out.extend([20, 0, 0, 0]);
+        out.extend(method.to_le_bytes());
+        out.extend([0; 8]);
+        out.extend((data.len() as u32).to_le_bytes());
+        out.extend((contents.len() as u32).to_le_bytes());
+        out.extend((name.len() as u16).to_le_bytes());
+        out.extend([0, 0]);
+        out.extend(name.as_bytes());
+        out.extend(&data);
+
+        central.extend(0x0201_4b50u32.to_le_bytes());
+        central.extend([20, 0, 20, 0, 0, 0]);
+        central.extend(method.to_le_bytes());
+        central.extend([0; 8]);
+        central.extend((data.len() as u32).to_le_bytes());
+        central.extend((contents.len() as u32).to_le_bytes());
+        central.extend((name.len() as u16).to_le_bytes());
+        central.extend([0; 12]);
+        central.extend(offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+    let central_offset = out.len() as u32;
+    out.extend(&central);
+    out.extend(0x0605_4b50u32.to_le_bytes());
+    out.extend([0; 4]);
+    out.extend((entries.len() as u16).to_le_bytes());
+    out.extend((entries.len() as u16).to_le_bytes());
+    out.extend((central.len() as u32).to_le_bytes());
+    out.extend(central_offset.to_le_bytes());
+    out.extend([0, 0]);
+    out
+}
+
+#[test]
+fn test_read_zip_stored_and_deflated_entries() {
+    let archive = zip(&[
+        ("template/", b"", false),
+        ("template/README.md", b"# {{name}}\n", false),
+        (
+            "template/polytunnel.toml",
+            b"[project]\nname = \"{{name}}\"\n",
+            true,
+        ),
+    ]);
+
+    let entries = read_zip(&archive).unwrap();
+    assert_eq!(
+        entries,
+        [
+            ZipEntry {
+                name: "template/README.md".to_string(),
+                contents: b"# {{name}}\n".to_vec(),
+            },
+            ZipEntry {
+                name: "template/polytunnel.toml".to_string(),
+                contents: b"[project]\nname = \"{{name}}\"\n".to_vec(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_read_empty_zip() {
+    assert!(read_zip(&zip(&[])).unwrap().is_empty());
+}
+
+#[test]
+fn test_read_zip_rejects_non_archives() {
+    let err = read_zip(b"<html>Not Found</html>").unwrap_err();
+    assert!(matches!(err, MavenError::InvalidArchive { .. }));
+}
+
+#[test]
+fn test_read_zip_rejects_truncated_archive() {
+    let archive = zip(&[("a.txt", b"hello world", false)]);
+    // Only the end of central directory record remains
+    let truncated = &archive[archive.len() - 22..];
+    assert!(matches!(
+        read_zip(truncated),
+        Err(MavenError::InvalidArchive { .. })
+    ));
+}
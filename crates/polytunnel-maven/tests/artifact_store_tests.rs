@@ -0,0 +1,172 @@
+//! Tests for the pluggable artifact store backends
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{
+    ArtifactStore, CONTENT_DIR, Coordinate, FileSystemStore, MavenClient, MavenError,
+    MemoryArtifactStore, jar_key, partial_key, pom_key,
+};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+const BASE: &str = "https://repo.example.test/maven2";
+const JAR: &[u8] = b"jar bytes";
+
+fn coord() -> Coordinate {
+    Coordinate::new("org.test", "lib", "1.0")
+}
+
+#[test]
+fn test_store_keys_follow_repository_layout() {
+    assert_eq!(jar_key(&coord()), "org/test/lib/1.0/lib-1.0.jar");
+    assert_eq!(pom_key(&coord()), "org/test/lib/1.0/lib-1.0.pom");
+}
+
+#[test]
+fn test_filesystem_store_roundtrip() {
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path());
+    let key = jar_key(&coord());
+
+    assert!(!store.contains(&key));
+    store.write(&key, JAR).unwrap();
+
+    assert!(store.contains(&key));
+    assert_eq!(store.read(&key).unwrap(), JAR);
+    assert_eq!(store.path_for(&key), dir.path().join(&key));
+    assert!(dir.path().join(&key).is_file());
+}
+
+#[test]
+fn test_filesystem_store_read_missing_is_not_found() {
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path());
+
+    let error = store.read("org/test/lib/1.0/lib-1.0.jar").unwrap_err();
+    assert!(matches!(error, MavenError::ArtifactNotFound { .. }));
+}
+
+#[test]
+fn test_memory_store_roundtrip() {
+    let store = MemoryArtifactStore::new();
+    let key = jar_key(&coord());
+
+    assert!(!store.contains(&key));
+    assert!(matches!(
+        store.read(&key),
+        Err(MavenError::ArtifactNotFound { .. })
+    ));
+
+    store.write(&key, JAR).unwrap();
+    assert!(store.contains(&key));
+    assert_eq!(store.read(&key).unwrap(), JAR);
+    assert_eq!(store.keys(), vec![key]);
+}
+
+#[tokio::test]
+async fn test_download_jar_to_store_writes_under_jar_key() {
+    let transport =
+        MockTransport::default().route(format!("{BASE}/org/test/lib/1.0/lib-1.0.jar"), 200, JAR);
+    let client = MavenClient::with_transport(BASE, Arc::new(transport));
+    let store = MemoryArtifactStore::new();
+
+    let bytes = client
+        .download_jar_to_store(&coord(), &store, false)
+        .await
+        .unwrap();
+
+    assert_eq!(bytes, JAR.len() as u64);
+    assert_eq!(store.read(&jar_key(&coord())).unwrap(), JAR);
+}
+
+#[tokio::test]
+async fn test_download_jar_to_store_leaves_store_untouched_on_failure() {
+    let client = MavenClient::with_transport(BASE, Arc::new(MockTransport::default()));
+    let store = MemoryArtifactStore::new();
+
+    let result = client.download_jar_to_store(&coord(), &store, false).await;
+
+    assert!(result.is_err());
+    assert!(store.keys().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dedup_store_shares_storage_for_identical_jars() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path()).with_dedup(true);
+    let first = jar_key(&Coordinate::new("org.test", "lib", "1.0"));
+    let second = jar_key(&Coordinate::new("org.relocated", "lib", "1.0"));
+
+    store.write(&first, JAR).unwrap();
+    store.write(&second, JAR).unwrap();
+
+    let first_meta = std::fs::metadata(store.path_for(&first)).unwrap();
+    let second_meta = std::fs::metadata(store.path_for(&second)).unwrap();
+    assert_eq!(first_meta.ino(), second_meta.ino());
+    assert_eq!(first_meta.nlink(), 3, "content plus two coordinate links");
+    assert_eq!(store.read(&second).unwrap(), JAR);
+
+    let content = store.content_path(JAR);
+    assert!(content.starts_with(dir.path().join(CONTENT_DIR)));
+    assert_eq!(std::fs::metadata(content).unwrap().ino(), first_meta.ino());
+}
+
+#[test]
+fn test_dedup_store_keeps_different_jars_apart() {
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path()).with_dedup(true);
+    let first = jar_key(&Coordinate::new("org.test", "lib", "1.0"));
+    let second = jar_key(&Coordinate::new("org.test", "lib", "2.0"));
+
+    store.write(&first, b"version one").unwrap();
+    store.write(&second, b"version two").unwrap();
+    // Rewriting a key must not modify the content shared with other keys
+    store.write(&first, b"version two").unwrap();
+
+    assert_eq!(store.read(&first).unwrap(), b"version two");
+    assert_eq!(store.read(&second).unwrap(), b"version two");
+    assert!(store.content_path(b"version one").is_file());
+}
+
+#[test]
+fn test_store_without_dedup_writes_plain_files() {
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path());
+
+    store.write(&jar_key(&coord()), JAR).unwrap();
+
+    assert!(!dir.path().join(CONTENT_DIR).exists());
+    assert_eq!(store.read(&jar_key(&coord())).unwrap(), JAR);
+}
+
+#[test]
+fn test_store_without_dedup_does_not_write_through_shared_links() {
+    let dir = tempdir().unwrap();
+    let first = jar_key(&Coordinate::new("org.test", "lib", "1.0"));
+    let second = jar_key(&Coordinate::new("org.relocated", "lib", "1.0"));
+    let dedup = FileSystemStore::new(dir.path()).with_dedup(true);
+    dedup.write(&first, JAR).unwrap();
+    dedup.write(&second, JAR).unwrap();
+
+    // `build.cache_dedup` turned off later
+    let store = FileSystemStore::new(dir.path());
+    store.write(&first, b"rewritten").unwrap();
+
+    assert_eq!(store.read(&first).unwrap(), b"rewritten");
+    assert_eq!(store.read(&second).unwrap(), JAR);
+    assert_eq!(std::fs::read(store.content_path(JAR)).unwrap(), JAR);
+}
+
+#[test]
+fn test_dedup_store_does_not_deduplicate_partial_downloads() {
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path()).with_dedup(true);
+
+    store.write(&partial_key(&jar_key(&coord())), JAR).unwrap();
+
+    assert!(!dir.path().join(CONTENT_DIR).exists());
+}
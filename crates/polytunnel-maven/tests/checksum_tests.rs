@@ -0,0 +1,136 @@
+//! Tests for verifying downloaded jars against published SHA-1 checksums
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_core::Repository;
+use polytunnel_maven::{Coordinate, MavenClient, MavenError};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+const MIRROR: &str = "https://mirror.example.test/maven2";
+const CENTRAL: &str = "https://central.example.test/maven2";
+const JAR_PATH: &str = "org/test/lib/1.0/lib-1.0.jar";
+const GOOD_JAR: &[u8] = b"good jar bytes";
+const BAD_JAR: &[u8] = b"corrupted jar bytes";
+const GOOD_SHA1: &str = "c0947d9208d142f2b0d05701f3ec9e658a2197d8";
+
+fn url(base: &str, suffix: &str) -> String {
+    format!("{base}/{JAR_PATH}{suffix}")
+}
+
+fn client(transport: &MockTransport) -> MavenClient {
+    MavenClient::with_transport(MIRROR, Arc::new(transport.clone())).with_repositories(&[
+        Repository::new("mirror", MIRROR),
+        Repository::new("central", CENTRAL),
+    ])
+}
+
+fn coord() -> Coordinate {
+    Coordinate::new("org.test", "lib", "1.0")
+}
+
+#[tokio::test]
+async fn test_checksum_mismatch_falls_through_to_next_repository() {
+    let transport = MockTransport::default()
+        .route(url(MIRROR, ""), 200, BAD_JAR)
+        .route(url(MIRROR, ".sha1"), 200, GOOD_SHA1.as_bytes())
+        .route(url(CENTRAL, ""), 200, GOOD_JAR)
+        .route(url(CENTRAL, ".sha1"), 200, GOOD_SHA1.as_bytes());
+    let cache = tempdir().unwrap();
+    let dest = cache.path().join("lib-1.0.jar");
+
+    client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), GOOD_JAR);
+    assert_eq!(
+        transport.requests(),
+        vec![
+            url(MIRROR, ""),
+            url(MIRROR, ".sha1"),
+            url(CENTRAL, ""),
+            url(CENTRAL, ".sha1"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_checksum_mismatch_everywhere_is_error() {
+    let transport = MockTransport::default()
+        .route(url(MIRROR, ""), 200, BAD_JAR)
+        .route(url(MIRROR, ".sha1"), 200, GOOD_SHA1.as_bytes())
+        .route(url(CENTRAL, ""), 200, BAD_JAR)
+        .route(url(CENTRAL, ".sha1"), 200, GOOD_SHA1.as_bytes());
+    let cache = tempdir().unwrap();
+    let dest = cache.path().join("lib-1.0.jar");
+
+    let error = client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap_err();
+
+    match error {
+        MavenError::ChecksumMismatch {
+            url: failed,
+            expected,
+            ..
+        } => {
+            assert_eq!(failed, url(CENTRAL, ""));
+            assert_eq!(expected, GOOD_SHA1);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(!dest.exists());
+}
+
+#[tokio::test]
+async fn test_checksum_file_with_filename_suffix_is_accepted() {
+    let checksum = format!("{}  lib-1.0.jar\n", GOOD_SHA1.to_uppercase());
+    let transport = MockTransport::default()
+        .route(url(MIRROR, ""), 200, GOOD_JAR)
+        .route(url(MIRROR, ".sha1"), 200, checksum.as_bytes());
+    let cache = tempdir().unwrap();
+    let dest = cache.path().join("lib-1.0.jar");
+
+    client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), GOOD_JAR);
+}
+
+#[tokio::test]
+async fn test_missing_checksum_file_skips_verification() {
+    let transport = MockTransport::default().route(url(MIRROR, ""), 200, GOOD_JAR);
+    let cache = tempdir().unwrap();
+    let dest = cache.path().join("lib-1.0.jar");
+
+    client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), GOOD_JAR);
+}
+
+#[tokio::test]
+async fn test_disabled_verification_accepts_jar_without_fetching_checksum() {
+    let transport = MockTransport::default()
+        .route(url(MIRROR, ""), 200, BAD_JAR)
+        .route(url(MIRROR, ".sha1"), 200, GOOD_SHA1.as_bytes());
+    let cache = tempdir().unwrap();
+    let dest = cache.path().join("lib-1.0.jar");
+
+    client(&transport)
+        .with_checksum_verification(false)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), BAD_JAR);
+    assert_eq!(transport.requests(), [url(MIRROR, "")]);
+}
@@ -0,0 +1,136 @@
+//! Mock repository shared by the maven integration tests
+#![allow(dead_code)]
+
+use polytunnel_maven::{HttpResponse, HttpTransportFuture, MavenTransport};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Headers of one request, in the order they were sent
+pub type RequestHeaders = Vec<(String, String)>;
+
+/// Serves routed responses by URL, 404 for anything else, and records every request
+///
+/// A URL given several responses with [`MockTransport::then`] answers them in
+/// order, then keeps repeating the last one. A response carrying an `ETag` is answered with 304
+/// when the request sends that tag back in `If-None-Match`.
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    routes: Arc<Mutex<HashMap<String, VecDeque<HttpResponse>>>>,
+    requests: Arc<Mutex<Vec<(String, RequestHeaders)>>>,
+}
+
+impl MockTransport {
+    /// Transport answering each `(url, status, body)` route
+    pub fn new(routes: Vec<(String, u16, String)>) -> Self {
+        routes
+            .into_iter()
+            .fold(Self::default(), |transport, (url, status, body)| {
+                transport.route(url, status, body)
+            })
+    }
+
+    /// Answer `url` with `status` and `body`, replacing any earlier route
+    pub fn route(self, url: impl Into<String>, status: u16, body: impl Into<Vec<u8>>) -> Self {
+        self.route_with_headers(url, status, body, &[])
+    }
+
+    /// Answer `url` with `status`, `body` and the response `headers`, replacing any earlier route
+    pub fn route_with_headers(
+        self,
+        url: impl Into<String>,
+        status: u16,
+        body: impl Into<Vec<u8>>,
+        headers: &[(&str, &str)],
+    ) -> Self {
+        let response = HttpResponse {
+            status,
+            body: body.into(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        };
+        self.routes
+            .lock()
+            .unwrap()
+            .insert(url.into(), VecDeque::from([response]));
+        self
+    }
+
+    /// Answer `url` with `status` and `body` once its earlier responses are used up
+    pub fn then(self, url: impl Into<String>, status: u16, body: impl Into<Vec<u8>>) -> Self {
+        self.routes
+            .lock()
+            .unwrap()
+            .entry(url.into())
+            .or_default()
+            .push_back(HttpResponse {
+                status,
+                body: body.into(),
+                headers: Default::default(),
+            });
+        self
+    }
+
+    /// URLs requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|(url, _)| url.clone()).collect()
+    }
+
+    /// Headers sent with each request so far, in order
+    pub fn request_headers(&self) -> Vec<RequestHeaders> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .map(|(_, headers)| headers.clone())
+            .collect()
+    }
+}
+
+impl MavenTransport for MockTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        self.get_with_headers(url, Vec::new())
+    }
+
+    fn get_with_headers(&self, url: String, headers: Vec<(String, String)>) -> HttpTransportFuture {
+        let response = {
+            let mut routes = self.routes.lock().unwrap();
+            match routes.get_mut(&url) {
+                Some(responses) if responses.len() > 1 => responses.pop_front(),
+                Some(responses) => responses.front().cloned(),
+                None => None,
+            }
+        };
+        let response = match response {
+            Some(response) => {
+                let etag = response.headers.get("ETag").map(String::as_str);
+                if etag.is_some() && header(&headers, "If-None-Match") == etag {
+                    HttpResponse {
+                        status: 304,
+                        body: Vec::new(),
+                        headers: Default::default(),
+                    }
+                } else {
+                    response
+                }
+            }
+            None => HttpResponse {
+                status: 404,
+                body: b"not found".to_vec(),
+                headers: Default::default(),
+            },
+        };
+        self.requests.lock().unwrap().push((url, headers));
+
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// Value of the `name` header, ignoring case
+pub fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
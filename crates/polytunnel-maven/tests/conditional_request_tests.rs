@@ -0,0 +1,110 @@
+//! Tests for revalidating `maven-metadata.xml` with conditional GETs
+
+mod common;
+
+use common::{MockTransport, header};
+use polytunnel_maven::{FileSystemStore, MavenClient};
+use std::sync::Arc;
+
+const BASE_URL: &str = "https://repo.example.test/maven2";
+const METADATA: &str = r#"<metadata>
+  <groupId>org.test</groupId>
+  <artifactId>lib</artifactId>
+  <versioning>
+    <latest>1.1.0-SNAPSHOT</latest>
+    <versions><version>1.0.0</version><version>1.1.0-SNAPSHOT</version></versions>
+  </versioning>
+</metadata>"#;
+
+/// Repository serving `METADATA` with the given response headers
+fn metadata_transport(response_headers: &[(&str, &str)]) -> MockTransport {
+    MockTransport::default().route_with_headers(
+        format!("{BASE_URL}/org/test/lib/maven-metadata.xml"),
+        200,
+        METADATA,
+        response_headers,
+    )
+}
+
+#[tokio::test]
+async fn test_metadata_revalidated_with_etag_uses_cached_body_on_304() {
+    let transport = metadata_transport(&[
+        ("ETag", "\"abc123\""),
+        ("Last-Modified", "Wed, 14 Oct 2026 10:00:00 GMT"),
+    ]);
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(transport.clone()));
+
+    let first = client.fetch_metadata("org.test", "lib").await.unwrap();
+    let second = client.fetch_metadata("org.test", "lib").await.unwrap();
+
+    assert_eq!(first.versions, second.versions);
+    assert_eq!(second.latest.as_deref(), Some("1.1.0-SNAPSHOT"));
+
+    let requests = transport.request_headers();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(header(&requests[0], "If-None-Match"), None);
+    assert_eq!(header(&requests[1], "If-None-Match"), Some("\"abc123\""));
+    assert_eq!(
+        header(&requests[1], "If-Modified-Since"),
+        Some("Wed, 14 Oct 2026 10:00:00 GMT")
+    );
+}
+
+#[tokio::test]
+async fn test_metadata_without_validators_is_fetched_unconditionally() {
+    let transport = metadata_transport(&[]);
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(transport.clone()));
+
+    client.fetch_metadata("org.test", "lib").await.unwrap();
+    client.fetch_metadata("org.test", "lib").await.unwrap();
+
+    let requests = transport.request_headers();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(header(&requests[1], "If-None-Match"), None);
+    assert_eq!(header(&requests[1], "If-Modified-Since"), None);
+}
+
+#[tokio::test]
+async fn test_cloned_clients_share_validated_responses() {
+    let transport = metadata_transport(&[("ETag", "\"v1\"")]);
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(transport.clone()));
+
+    client.fetch_metadata("org.test", "lib").await.unwrap();
+    let metadata = client
+        .clone()
+        .fetch_metadata("org.test", "lib")
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.versions.len(), 2);
+    assert_eq!(
+        header(&transport.request_headers()[1], "If-None-Match"),
+        Some("\"v1\"")
+    );
+}
+
+#[tokio::test]
+async fn test_fresh_client_revalidates_from_persisted_validators() {
+    let cache = tempfile::tempdir().unwrap();
+    let store = Arc::new(FileSystemStore::new(cache.path()));
+    let transport = metadata_transport(&[
+        ("ETag", "\"v1\""),
+        ("Last-Modified", "Wed, 14 Oct 2026 10:00:00 GMT"),
+    ]);
+    let client = || {
+        MavenClient::with_transport(BASE_URL, Arc::new(transport.clone()))
+            .with_pom_cache(store.clone())
+    };
+
+    client().fetch_metadata("org.test", "lib").await.unwrap();
+    let metadata = client().fetch_metadata("org.test", "lib").await.unwrap();
+
+    assert_eq!(metadata.versions.len(), 2);
+    let requests = transport.request_headers();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(header(&requests[1], "If-None-Match"), Some("\"v1\""));
+    assert_eq!(
+        header(&requests[1], "If-Modified-Since"),
+        Some("Wed, 14 Oct 2026 10:00:00 GMT")
+    );
+}
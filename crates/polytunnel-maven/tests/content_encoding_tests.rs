@@ -0,0 +1,96 @@
+//! Tests for transparent gzip/deflate decoding of repository responses
+
+mod common;
+
+use common::MockTransport;
+use flate2::Compression;
+use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+use polytunnel_maven::{Coordinate, HttpResponse, MavenClient, MavenError};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+const BASE_URL: &str = "https://repo.example.test";
+const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <groupId>org.test</groupId>
+  <artifactId>lib</artifactId>
+  <version>1.0.0</version>
+</project>
+"#;
+
+/// Client whose repository serves the POM as `body` with `Content-Encoding: encoding`
+fn client(body: Vec<u8>, encoding: &str) -> MavenClient {
+    let transport = MockTransport::default().route_with_headers(
+        format!("{BASE_URL}/org/test/lib/1.0.0/lib-1.0.0.pom"),
+        200,
+        body,
+        &[("Content-Encoding", encoding)],
+    );
+    MavenClient::with_transport(BASE_URL, Arc::new(transport))
+}
+
+fn coord() -> Coordinate {
+    Coordinate::parse("org.test:lib:1.0.0").unwrap()
+}
+
+#[tokio::test]
+async fn test_gzip_encoded_pom_is_decoded() {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(POM.as_bytes()).unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let pom = client(gzipped, "gzip").fetch_pom(&coord()).await.unwrap();
+
+    assert_eq!(pom.coordinate.artifact_id, "lib");
+}
+
+#[tokio::test]
+async fn test_deflate_encoded_pom_is_decoded() {
+    let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+    zlib.write_all(POM.as_bytes()).unwrap();
+    let mut raw = DeflateEncoder::new(Vec::new(), Compression::default());
+    raw.write_all(POM.as_bytes()).unwrap();
+
+    for body in [zlib.finish().unwrap(), raw.finish().unwrap()] {
+        let content = client(body, "deflate")
+            .fetch_pom_content(&coord())
+            .await
+            .unwrap();
+        assert_eq!(content, POM);
+    }
+}
+
+#[tokio::test]
+async fn test_identity_encoding_is_passed_through() {
+    let content = client(POM.as_bytes().to_vec(), "identity")
+        .fetch_pom_content(&coord())
+        .await
+        .unwrap();
+
+    assert_eq!(content, POM);
+}
+
+#[tokio::test]
+async fn test_corrupt_gzip_body_is_a_decoding_error() {
+    let error = client(b"not gzip".to_vec(), "gzip")
+        .fetch_pom(&coord())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, MavenError::ContentDecoding { ref encoding, .. } if encoding == "gzip")
+    );
+}
+
+#[test]
+fn test_header_lookup_ignores_case() {
+    let response = HttpResponse {
+        status: 200,
+        body: Vec::new(),
+        headers: HashMap::from([("content-encoding".to_string(), "gzip".to_string())]),
+    };
+
+    assert_eq!(response.header("Content-Encoding"), Some("gzip"));
+    assert_eq!(response.header("ETag"), None);
+}
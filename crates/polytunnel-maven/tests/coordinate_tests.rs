@@ -1,6 +1,6 @@
 //! Tests for coordinate module
 
-use polytunnel_maven::Coordinate;
+use polytunnel_maven::{Coordinate, packaging_extension};
 
 #[test]
 fn test_parse_gav() {
@@ -65,3 +65,92 @@ fn test_display() {
     let coord = Coordinate::new("org.slf4j", "slf4j-api", "2.0.9");
     assert_eq!(format!("{}", coord), "org.slf4j:slf4j-api:2.0.9");
 }
+
+#[test]
+fn test_packaging_extension_maps_jar_like_packagings() {
+    for packaging in ["jar", "bundle", "maven-plugin", "eclipse-plugin"] {
+        assert_eq!(packaging_extension(packaging), Some("jar"), "{packaging}");
+    }
+    assert_eq!(packaging_extension("pom"), None);
+    assert_eq!(packaging_extension("war"), Some("war"));
+}
+
+#[test]
+fn test_bundle_coordinate_is_jar_packaging() {
+    let coord =
+        Coordinate::parse("org.apache.felix:org.apache.felix.framework:bundle:7.0.5").unwrap();
+    assert!(coord.is_jar_packaging());
+    assert_eq!(coord.extension(), Some("jar"));
+    assert_eq!(coord.jar_filename(), "org.apache.felix.framework-7.0.5.jar");
+}
+
+#[test]
+fn test_display_includes_classifier() {
+    let mut coord = Coordinate::new("org.lwjgl", "lwjgl", "3.3.3");
+    coord.classifier = Some("natives-linux".to_string());
+    assert_eq!(coord.to_string(), "org.lwjgl:lwjgl:3.3.3:natives-linux");
+    assert_ne!(
+        coord.to_string(),
+        Coordinate::new("org.lwjgl", "lwjgl", "3.3.3").to_string()
+    );
+}
+
+#[test]
+fn test_parse_trims_segment_whitespace() {
+    let coord = Coordinate::parse(" org.foo: bar :1.0 ").unwrap();
+    assert_eq!(coord.group_id, "org.foo");
+    assert_eq!(coord.artifact_id, "bar");
+    assert_eq!(coord.version, "1.0");
+    assert_eq!(coord.repo_path(), "org/foo/bar/1.0");
+}
+
+#[test]
+fn test_new_trims_segment_whitespace() {
+    let coord = Coordinate::new("org.foo ", " bar", " 1.0");
+    assert_eq!(coord.to_string(), "org.foo:bar:1.0");
+}
+
+#[test]
+fn test_parse_rejects_empty_segments() {
+    use polytunnel_maven::CoordinateError;
+
+    for (input, expected) in [
+        (":bar:1.0", "groupId"),
+        ("org.foo: :1.0", "artifactId"),
+        ("org.foo:bar:", "version"),
+        ("org.foo:bar: :1.0", "packaging"),
+    ] {
+        match Coordinate::parse(input) {
+            Err(CoordinateError::EmptySegment { segment, .. }) => {
+                assert_eq!(segment, expected, "{input}")
+            }
+            other => panic!("{input}: expected EmptySegment, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_parse_rejects_illegal_characters() {
+    use polytunnel_maven::CoordinateError;
+
+    for (input, expected) in [
+        ("org.foo:bar/baz:1.0", '/'),
+        ("org.foo:bar\\baz:1.0", '\\'),
+        ("org foo:bar:1.0", ' '),
+        ("org.foo:bar:1.0\t2", '\t'),
+    ] {
+        match Coordinate::parse(input) {
+            Err(CoordinateError::InvalidCharacter { character, .. }) => {
+                assert_eq!(character, expected, "{input}")
+            }
+            other => panic!("{input}: expected InvalidCharacter, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_validate_allows_empty_version() {
+    let coord = Coordinate::new("org.foo", "bar", "");
+    assert!(coord.validate().is_ok());
+    assert!(Coordinate::new("org.foo", "", "1.0").validate().is_err());
+}
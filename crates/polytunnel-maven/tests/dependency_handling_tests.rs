@@ -57,6 +57,8 @@ fn test_dependency_detailed_variant_fields() {
         version: "1.0.0".to_string(),
         scope: polytunnel_core::DependencyScope::Compile,
         optional: false,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
@@ -64,10 +66,13 @@ fn test_dependency_detailed_variant_fields() {
             version,
             scope,
             optional,
+            exclusions,
+            ..
         } => {
             assert_eq!(version, "1.0.0");
             assert_eq!(scope, polytunnel_core::DependencyScope::Compile);
             assert!(!optional);
+            assert!(exclusions.is_empty());
         }
         _ => panic!("Wrong variant"),
     }
@@ -212,6 +217,8 @@ fn test_dependency_optional_field_true() {
         version: "1.0.0".to_string(),
         scope: polytunnel_core::DependencyScope::Compile,
         optional: true,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
@@ -226,6 +233,8 @@ fn test_dependency_optional_field_false() {
         version: "1.0.0".to_string(),
         scope: polytunnel_core::DependencyScope::Compile,
         optional: false,
+        exclusions: vec![],
+        include_optional: false,
     };
 
     match dep {
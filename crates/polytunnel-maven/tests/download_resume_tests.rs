@@ -0,0 +1,291 @@
+//! Tests for resuming interrupted jar downloads with HTTP range requests
+
+use polytunnel_maven::{
+    ArtifactStore, Coordinate, HttpResponse, HttpTransportFuture, MavenClient, MavenError,
+    MavenTransport, MemoryArtifactStore, jar_key, partial_key,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tempfile::tempdir;
+
+/// Scripted reply to one request
+enum Reply {
+    Complete(u16, &'static [u8], &'static [(&'static str, &'static str)]),
+    Interrupted(u16, &'static [u8], &'static [(&'static str, &'static str)]),
+}
+
+#[derive(Clone, Default)]
+struct MockTransport {
+    replies: Arc<Mutex<HashMap<String, VecDeque<Reply>>>>,
+    ranges: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl MockTransport {
+    fn reply(self, url: &str, reply: Reply) -> Self {
+        self.replies
+            .lock()
+            .unwrap()
+            .entry(url.to_string())
+            .or_default()
+            .push_back(reply);
+        self
+    }
+
+    /// `Range` header of each jar request, in order
+    fn ranges(&self) -> Vec<Option<String>> {
+        self.ranges.lock().unwrap().clone()
+    }
+}
+
+fn response(status: u16, body: &[u8], headers: &[(&str, &str)]) -> HttpResponse {
+    HttpResponse {
+        status,
+        body: body.to_vec(),
+        headers: headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    }
+}
+
+impl MavenTransport for MockTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        self.get_with_headers(url, Vec::new())
+    }
+
+    fn get_with_headers(&self, url: String, headers: Vec<(String, String)>) -> HttpTransportFuture {
+        if url.ends_with(".jar") {
+            let range = headers
+                .iter()
+                .find(|(name, _)| name == "Range")
+                .map(|(_, value)| value.clone());
+            self.ranges.lock().unwrap().push(range);
+        }
+        let reply = self
+            .replies
+            .lock()
+            .unwrap()
+            .get_mut(&url)
+            .and_then(VecDeque::pop_front);
+        Box::pin(async move {
+            match reply {
+                Some(Reply::Complete(status, body, headers)) => Ok(response(status, body, headers)),
+                Some(Reply::Interrupted(status, body, headers)) => {
+                    Err(MavenError::TransferInterrupted {
+                        url,
+                        partial: Box::new(response(status, body, headers)),
+                        message: "connection reset".to_string(),
+                    })
+                }
+                None => Ok(response(404, b"not found", &[])),
+            }
+        })
+    }
+}
+
+const BASE: &str = "https://repo.example.test/maven2";
+const JAR_URL: &str = "https://repo.example.test/maven2/org/test/big/1.0/big-1.0.jar";
+const SHA1_URL: &str = "https://repo.example.test/maven2/org/test/big/1.0/big-1.0.jar.sha1";
+const JAR: &[u8] = b"hello world";
+const JAR_SHA1: &[u8] = b"2aae6c35c94fcfb415dbe95f408b9ce91ee846ed";
+const RANGES: &[(&str, &str)] = &[("Accept-Ranges", "bytes")];
+
+fn client(transport: &MockTransport) -> MavenClient {
+    MavenClient::with_transport(BASE, Arc::new(transport.clone()))
+}
+
+fn coord() -> Coordinate {
+    Coordinate::new("org.test", "big", "1.0")
+}
+
+#[tokio::test]
+async fn test_download_resumes_partial_file_with_range_request() {
+    let transport = MockTransport::default()
+        .reply(
+            JAR_URL,
+            Reply::Complete(206, b"world", &[("Content-Range", "bytes 6-10/11")]),
+        )
+        .reply(SHA1_URL, Reply::Complete(200, JAR_SHA1, &[]));
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+    std::fs::write(dir.path().join("big-1.0.jar.part"), b"hello ").unwrap();
+
+    client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), JAR);
+    assert!(!dir.path().join("big-1.0.jar.part").exists());
+    assert_eq!(transport.ranges(), [Some("bytes=6-".to_string())]);
+}
+
+#[tokio::test]
+async fn test_download_restarts_when_server_ignores_range() {
+    let transport = MockTransport::default()
+        .reply(JAR_URL, Reply::Complete(200, JAR, &[]))
+        .reply(SHA1_URL, Reply::Complete(200, JAR_SHA1, &[]));
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+    std::fs::write(dir.path().join("big-1.0.jar.part"), b"hello ").unwrap();
+
+    client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), JAR);
+    assert!(!dir.path().join("big-1.0.jar.part").exists());
+}
+
+#[tokio::test]
+async fn test_download_restarts_when_range_is_not_satisfiable() {
+    let transport = MockTransport::default()
+        .reply(JAR_URL, Reply::Complete(416, b"", &[]))
+        .reply(JAR_URL, Reply::Complete(200, JAR, &[]))
+        .reply(SHA1_URL, Reply::Complete(200, JAR_SHA1, &[]));
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+    std::fs::write(dir.path().join("big-1.0.jar.part"), b"stale partial bytes").unwrap();
+
+    client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&dest).unwrap(), JAR);
+    assert_eq!(transport.ranges(), [Some("bytes=19-".to_string()), None]);
+}
+
+#[tokio::test]
+async fn test_interrupted_download_is_kept_and_resumed() {
+    let transport = MockTransport::default()
+        .reply(JAR_URL, Reply::Interrupted(200, b"hello ", RANGES))
+        .reply(
+            JAR_URL,
+            Reply::Complete(206, b"world", &[("Content-Range", "bytes 6-10/11")]),
+        )
+        .reply(SHA1_URL, Reply::Complete(200, JAR_SHA1, &[]));
+    let client = client(&transport);
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+
+    let error = client
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, MavenError::TransferInterrupted { .. }));
+    assert_eq!(
+        std::fs::read(dir.path().join("big-1.0.jar.part")).unwrap(),
+        b"hello "
+    );
+    assert!(!dest.exists());
+
+    client.download_jar(&coord(), &dest, false).await.unwrap();
+    assert_eq!(std::fs::read(&dest).unwrap(), JAR);
+    assert_eq!(transport.ranges(), [None, Some("bytes=6-".to_string())]);
+}
+
+#[tokio::test]
+async fn test_interrupted_resume_extends_partial_file() {
+    let transport = MockTransport::default().reply(
+        JAR_URL,
+        Reply::Interrupted(206, b"wor", &[("Content-Range", "bytes 6-10/11")]),
+    );
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+    std::fs::write(dir.path().join("big-1.0.jar.part"), b"hello ").unwrap();
+
+    let error = client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("interrupted after 9 bytes"));
+    assert_eq!(
+        std::fs::read(dir.path().join("big-1.0.jar.part")).unwrap(),
+        b"hello wor"
+    );
+}
+
+#[tokio::test]
+async fn test_interrupted_download_without_range_support_is_discarded() {
+    let transport =
+        MockTransport::default().reply(JAR_URL, Reply::Interrupted(200, b"hello ", &[]));
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+
+    let error = client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MavenError::TransferInterrupted { .. }));
+    assert!(!dir.path().join("big-1.0.jar.part").exists());
+}
+
+#[tokio::test]
+async fn test_download_to_store_resumes_and_removes_partial_key() {
+    let transport = MockTransport::default()
+        .reply(JAR_URL, Reply::Interrupted(200, b"hello ", RANGES))
+        .reply(
+            JAR_URL,
+            Reply::Complete(206, b"world", &[("Content-Range", "bytes 6-10/11")]),
+        )
+        .reply(SHA1_URL, Reply::Complete(200, JAR_SHA1, &[]));
+    let client = client(&transport);
+    let store = MemoryArtifactStore::new();
+    let key = jar_key(&coord());
+
+    client
+        .download_jar_to_store(&coord(), &store, false)
+        .await
+        .unwrap_err();
+    assert_eq!(store.keys(), [partial_key(&key)]);
+
+    let size = client
+        .download_jar_to_store(&coord(), &store, false)
+        .await
+        .unwrap();
+    assert_eq!(size, JAR.len() as u64);
+    assert_eq!(store.read(&key).unwrap(), JAR);
+    assert_eq!(store.keys(), [key]);
+}
+
+#[tokio::test]
+async fn test_resumed_download_failing_checksum_discards_partial() {
+    let transport = MockTransport::default()
+        .reply(
+            JAR_URL,
+            Reply::Complete(206, b"world", &[("Content-Range", "bytes 6-10/11")]),
+        )
+        .reply(SHA1_URL, Reply::Complete(200, JAR_SHA1, &[]));
+    let dir = tempdir().unwrap();
+    let dest = dir.path().join("big-1.0.jar");
+    std::fs::write(dir.path().join("big-1.0.jar.part"), b"HELLO ").unwrap();
+
+    let error = client(&transport)
+        .download_jar(&coord(), &dest, false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MavenError::ChecksumMismatch { .. }));
+    assert!(!dir.path().join("big-1.0.jar.part").exists());
+    assert!(!dest.exists());
+}
+
+#[test]
+fn test_accepts_ranges_requires_unencoded_byte_ranges() {
+    assert!(response(200, b"", &[("accept-ranges", "bytes")]).accepts_ranges());
+    assert!(response(206, b"", &[]).accepts_ranges());
+    assert!(!response(200, b"", &[]).accepts_ranges());
+    assert!(!response(200, b"", &[("Accept-Ranges", "none")]).accepts_ranges());
+    assert!(
+        !response(
+            200,
+            b"",
+            &[("Accept-Ranges", "bytes"), ("Content-Encoding", "gzip")]
+        )
+        .accepts_ranges()
+    );
+}
@@ -0,0 +1,101 @@
+//! Tests for looking up artifact licenses through the parent POM chain
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{Coordinate, MavenClient};
+use std::sync::Arc;
+
+const BASE_URL: &str = "https://repo.example.test/maven2";
+
+const PARENT_POM: &str = r#"<project>
+    <groupId>org.test</groupId>
+    <artifactId>parent</artifactId>
+    <version>1</version>
+    <licenses><license><name>MIT</name><url>https://opensource.org/licenses/MIT</url></license></licenses>
+</project>"#;
+
+const CHILD_POM: &str = r#"<project>
+    <parent><groupId>org.test</groupId><artifactId>parent</artifactId><version>1</version></parent>
+    <artifactId>child</artifactId>
+    <version>2.0</version>
+</project>"#;
+
+const OWN_LICENSE_POM: &str = r#"<project>
+    <parent><groupId>org.test</groupId><artifactId>parent</artifactId><version>1</version></parent>
+    <artifactId>own</artifactId>
+    <version>1.0</version>
+    <licenses><license><name>Apache-2.0</name></license></licenses>
+</project>"#;
+
+const UNLICENSED_POM: &str = r#"<project>
+    <groupId>org.test</groupId>
+    <artifactId>bare</artifactId>
+    <version>1.0</version>
+</project>"#;
+
+fn client() -> MavenClient {
+    let transport = MockTransport::default()
+        .route(
+            format!("{BASE_URL}/org/test/parent/1/parent-1.pom"),
+            200,
+            PARENT_POM,
+        )
+        .route(
+            format!("{BASE_URL}/org/test/child/2.0/child-2.0.pom"),
+            200,
+            CHILD_POM,
+        )
+        .route(
+            format!("{BASE_URL}/org/test/own/1.0/own-1.0.pom"),
+            200,
+            OWN_LICENSE_POM,
+        )
+        .route(
+            format!("{BASE_URL}/org/test/bare/1.0/bare-1.0.pom"),
+            200,
+            UNLICENSED_POM,
+        );
+    MavenClient::with_transport(BASE_URL, Arc::new(transport))
+}
+
+#[tokio::test]
+async fn test_fetch_licenses_inherits_from_parent() {
+    let licenses = client()
+        .fetch_licenses(&Coordinate::new("org.test", "child", "2.0"))
+        .await
+        .unwrap();
+
+    assert_eq!(licenses.len(), 1);
+    assert_eq!(licenses[0].name.as_deref(), Some("MIT"));
+}
+
+#[tokio::test]
+async fn test_fetch_licenses_prefers_own_declaration() {
+    let licenses = client()
+        .fetch_licenses(&Coordinate::new("org.test", "own", "1.0"))
+        .await
+        .unwrap();
+
+    assert_eq!(licenses.len(), 1);
+    assert_eq!(licenses[0].display_name(), "Apache-2.0");
+}
+
+#[tokio::test]
+async fn test_fetch_licenses_empty_when_undeclared() {
+    let licenses = client()
+        .fetch_licenses(&Coordinate::new("org.test", "bare", "1.0"))
+        .await
+        .unwrap();
+
+    assert!(licenses.is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_licenses_missing_pom_is_error() {
+    let result = client()
+        .fetch_licenses(&Coordinate::new("org.test", "missing", "1.0"))
+        .await;
+
+    assert!(result.is_err());
+}
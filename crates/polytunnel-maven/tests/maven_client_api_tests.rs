@@ -299,6 +299,10 @@ fn test_maven_repository_structure() {
     let repo = Repository {
         name: "central".to_string(),
         url: "https://repo1.maven.org/maven2/".to_string(),
+        snapshots_enabled: true,
+        releases_enabled: true,
+        username: None,
+        password: None,
     };
 
     assert!(!repo.name.is_empty());
@@ -311,10 +315,18 @@ fn test_multiple_maven_repositories() {
         Repository {
             name: "central".to_string(),
             url: "https://repo1.maven.org/maven2/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
         Repository {
             name: "google".to_string(),
             url: "https://maven.google.com/".to_string(),
+            snapshots_enabled: true,
+            releases_enabled: true,
+            username: None,
+            password: None,
         },
     ];
 
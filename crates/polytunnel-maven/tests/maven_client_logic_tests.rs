@@ -1,45 +1,14 @@
 //! Tests for `MavenClient` backed by a deterministic in-memory transport.
 
-use polytunnel_maven::{
-    Coordinate, HttpResponse, HttpTransportFuture, MavenClient, MavenTransport,
-};
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{Coordinate, MavenClient, MavenError};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tempfile::NamedTempFile;
 
-#[derive(Clone)]
-struct MockTransport {
-    routes: HashMap<String, (u16, Vec<u8>)>,
-}
-
-impl MockTransport {
-    fn new(routes: Vec<(String, u16, String)>) -> Self {
-        let routes = routes
-            .into_iter()
-            .map(|(path, status, body)| (path, (status, body.into_bytes())))
-            .collect();
-
-        Self { routes }
-    }
-}
-
-impl MavenTransport for MockTransport {
-    fn get(&self, url: String) -> HttpTransportFuture {
-        let response = self
-            .routes
-            .get(&url)
-            .cloned()
-            .unwrap_or((404, b"not found".to_vec()));
-
-        Box::pin(async move {
-            Ok(HttpResponse {
-                status: response.0,
-                body: response.1,
-            })
-        })
-    }
-}
-
 fn routes(base_url: &str) -> Vec<(String, u16, String)> {
     let query = urlencoding::encode("g:\"org.test\" AND a:\"lib\"");
     let search_url = format!("{base_url}/solrsearch/select");
@@ -71,6 +40,21 @@ fn routes(base_url: &str) -> Vec<(String, u16, String)> {
             200,
             "dummy jar content".to_string(),
         ),
+        (
+            format!("{base_url}/org/test/lib/maven-metadata.xml"),
+            200,
+            r#"
+<metadata>
+  <groupId>org.test</groupId>
+  <artifactId>lib</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions><version>1.0.0</version></versions>
+  </versioning>
+</metadata>
+"#
+            .to_string(),
+        ),
         (
             search_query,
             200,
@@ -103,6 +87,34 @@ async fn test_fetch_pom_success() {
     assert_eq!(pom.coordinate.version, "1.0.0");
 }
 
+#[tokio::test]
+async fn test_fetch_pom_rejects_mismatched_coordinate() {
+    let base_url = "https://repo.example.test";
+    let client = MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(vec![(
+            format!("{base_url}/org/test/lib/2.0.0/lib-2.0.0.pom"),
+            200,
+            r#"<project>
+  <groupId>org.other</groupId>
+  <artifactId>lib</artifactId>
+  <version>2.0.0</version>
+</project>"#
+                .to_string(),
+        )])),
+    );
+    let coord = Coordinate::parse("org.test:lib:2.0.0").unwrap();
+
+    let err = client.fetch_pom(&coord).await.unwrap_err();
+    match err {
+        polytunnel_maven::MavenError::CoordinateMismatch { requested, found } => {
+            assert_eq!(requested, "org.test:lib:2.0.0");
+            assert_eq!(found, "org.other:lib:2.0.0");
+        }
+        other => panic!("expected CoordinateMismatch, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn test_fetch_pom_not_found() {
     let base_url = "https://repo.example.test";
@@ -113,6 +125,19 @@ async fn test_fetch_pom_not_found() {
     assert!(client.fetch_pom(&coord).await.is_err());
 }
 
+#[tokio::test]
+async fn test_fetch_metadata_release() {
+    let base_url = "https://repo.example.test";
+    let client =
+        MavenClient::with_transport(base_url, Arc::new(MockTransport::new(routes(base_url))));
+
+    let metadata = client.fetch_metadata("org.test", "lib").await.unwrap();
+    assert_eq!(metadata.release.as_deref(), Some("1.0.0"));
+    assert_eq!(metadata.versions, vec!["1.0.0"]);
+
+    assert!(client.fetch_metadata("org.test", "unknown").await.is_err());
+}
+
 #[tokio::test]
 async fn test_download_jar() {
     let base_url = "https://repo.example.test";
@@ -227,7 +252,8 @@ async fn test_download_jar_bad_status() {
         500,
         "server error".to_string(),
     ));
-    let client = MavenClient::with_transport(base_url, Arc::new(MockTransport::new(routes)));
+    let client = MavenClient::with_transport(base_url, Arc::new(MockTransport::new(routes)))
+        .with_retry_base_delay(Duration::ZERO);
     let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
     let tmpdir = tempfile::tempdir().unwrap();
     let destination = tmpdir.path().join("artifact.jar");
@@ -239,3 +265,190 @@ async fn test_download_jar_bad_status() {
             .is_err()
     );
 }
+
+#[tokio::test]
+async fn test_default_user_agent_is_forwarded() {
+    let transport = MockTransport::default();
+    let client =
+        MavenClient::with_transport("https://repo.example.test", Arc::new(transport.clone()));
+
+    let _ = client.fetch_metadata("org.test", "lib").await;
+
+    let headers = transport.request_headers().pop().unwrap();
+    assert_eq!(
+        headers,
+        vec![(
+            "User-Agent".to_string(),
+            format!("polytunnel/{}", env!("CARGO_PKG_VERSION"))
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_configured_headers_are_forwarded() {
+    let transport = MockTransport::default();
+    let http = polytunnel_core::HttpConfig {
+        user_agent: Some("corp-agent/1.0".to_string()),
+        headers: HashMap::from([("X-Api-Key".to_string(), "secret".to_string())]),
+        ..Default::default()
+    };
+    let client =
+        MavenClient::with_transport("https://repo.example.test", Arc::new(transport.clone()))
+            .with_http_config(&http);
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+
+    let _ = client.fetch_pom(&coord).await;
+
+    let headers = transport.request_headers().pop().unwrap();
+    assert!(headers.contains(&("User-Agent".to_string(), "corp-agent/1.0".to_string())));
+    assert!(headers.contains(&("X-Api-Key".to_string(), "secret".to_string())));
+}
+
+#[tokio::test]
+async fn test_search_paged_reports_total_and_offset() {
+    let base_url = "https://repo.example.test";
+    let search_url = format!("{base_url}/solrsearch/select");
+    let client = MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(vec![(
+            format!("{search_url}?q=guava&start=2&rows=2&wt=json"),
+            200,
+            r#"{"responseHeader":{"status":0},"response":{"numFound":5,"start":2,"docs":[
+                {"id":"com.google.guava:guava","g":"com.google.guava","a":"guava","latestVersion":"33.0.0-jre"},
+                {"id":"com.google.guava:guava-testlib","g":"com.google.guava","a":"guava-testlib","latestVersion":"33.0.0-jre"}
+            ]}}"#
+                .to_string(),
+        )])),
+    )
+    .with_search_url(&search_url);
+
+    let page = client
+        .search_paged("guava", 2, 2)
+        .await
+        .expect("paged search should succeed");
+
+    assert_eq!(page.num_found, 5);
+    assert_eq!(page.start, 2);
+    let coords: Vec<String> = page
+        .docs
+        .iter()
+        .map(|doc| doc.to_coordinate().to_string())
+        .collect();
+    assert_eq!(
+        coords,
+        [
+            "com.google.guava:guava:33.0.0-jre",
+            "com.google.guava:guava-testlib:33.0.0-jre"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_search_doc_to_coordinate_prefers_document_version() {
+    let base_url = "https://repo.example.test";
+    let search_url = format!("{base_url}/solrsearch/select");
+    let client =
+        MavenClient::with_transport(base_url, Arc::new(MockTransport::new(routes(base_url))))
+            .with_search_url(&search_url);
+
+    let results = client
+        .search("g:\"org.test\" AND a:\"lib\"", 1)
+        .await
+        .expect("search should succeed");
+
+    assert_eq!(
+        results[0].to_coordinate(),
+        Coordinate::new("org.test", "lib", "1.0.0")
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_url_returns_body_or_status_error() {
+    let base_url = "https://repo.example.test";
+    let client = MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(vec![(
+            "https://templates.example.test/app.zip".to_string(),
+            200,
+            "PK".to_string(),
+        )])),
+    );
+
+    let body = client
+        .fetch_url("https://templates.example.test/app.zip")
+        .await
+        .unwrap();
+    assert_eq!(body, b"PK");
+
+    let err = client
+        .fetch_url("https://templates.example.test/missing.zip")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MavenError::HttpStatus { status: 404, .. }));
+}
+
+/// Client whose repository answers the `org.test:lib:1.0.0` POM with `status`, `content_type`, and `body`
+fn pom_client(status: u16, content_type: &str, body: &str) -> MavenClient {
+    let base_url = "https://repo.example.test";
+    let transport = MockTransport::default().route_with_headers(
+        format!("{base_url}/org/test/lib/1.0.0/lib-1.0.0.pom"),
+        status,
+        body,
+        &[("Content-Type", content_type)],
+    );
+    MavenClient::with_transport(base_url, Arc::new(transport))
+}
+
+async fn fetch_pom_with(status: u16, content_type: &str, body: &str) -> MavenError {
+    let client = pom_client(status, content_type, body).with_retry_base_delay(Duration::ZERO);
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    client.fetch_pom(&coord).await.unwrap_err()
+}
+
+#[tokio::test]
+async fn test_fetch_pom_404_is_pom_not_found() {
+    let err = fetch_pom_with(404, "text/html", "<html>Not Found</html>").await;
+    assert!(
+        matches!(&err, MavenError::PomNotFound { path } if path.ends_with("/org/test/lib/1.0.0/lib-1.0.0.pom")),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_pom_500_is_server_error() {
+    let err = fetch_pom_with(500, "text/plain", "oops").await;
+    assert!(
+        matches!(err, MavenError::ServerError { status: 500, .. }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_pom_200_html_is_rejected_by_content_type() {
+    let err = fetch_pom_with(200, "text/html; charset=utf-8", "<p>Please log in</p>").await;
+    match err {
+        MavenError::UnexpectedContentType { what, content_type } => {
+            assert_eq!(what, "POM of org.test:lib:1.0.0");
+            assert_eq!(content_type, "text/html; charset=utf-8");
+        }
+        other => panic!("expected UnexpectedContentType, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_pom_accepts_xml_content_types() {
+    for content_type in [
+        "text/xml",
+        "application/xml; charset=UTF-8",
+        "application/x-maven-pom+xml",
+        "text/plain",
+    ] {
+        let client = pom_client(
+            200,
+            content_type,
+            "<project><groupId>org.test</groupId><artifactId>lib</artifactId><version>1.0.0</version></project>",
+        );
+        let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+        assert!(client.fetch_pom(&coord).await.is_ok(), "{content_type}");
+    }
+}
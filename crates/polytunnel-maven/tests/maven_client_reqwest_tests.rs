@@ -53,7 +53,7 @@ async fn test_reqwest_transport_non_2xx_maps_to_http_status() {
         spawn_single_response_server(expected_path, 500, b"server error".to_vec(), "text/plain")
             .await;
 
-    let client = MavenClient::with_base_url(&base_url);
+    let client = MavenClient::with_base_url(&base_url).with_max_retries(0);
     let err = client.fetch_pom_content(&coord).await.unwrap_err();
 
     match err {
@@ -98,7 +98,9 @@ async fn test_search_non_2xx_maps_to_http_status() {
             .await;
     let search_url = format!("{base_url}/solrsearch/select");
 
-    let client = MavenClient::with_base_url(&base_url).with_search_url(&search_url);
+    let client = MavenClient::with_base_url(&base_url)
+        .with_search_url(&search_url)
+        .with_max_retries(0);
     let err = client.search(query, 1).await.unwrap_err();
 
     match err {
@@ -0,0 +1,130 @@
+//! Tests for `maven-metadata.xml` parsing
+
+use polytunnel_maven::parse_metadata;
+
+const METADATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>org.slf4j</groupId>
+  <artifactId>slf4j-api</artifactId>
+  <versioning>
+    <latest>2.1.0-alpha1</latest>
+    <release>2.0.9</release>
+    <versions>
+      <version>1.7.36</version>
+      <version>2.0.9</version>
+      <version>2.1.0-alpha1</version>
+    </versions>
+    <lastUpdated>20231001120000</lastUpdated>
+  </versioning>
+</metadata>
+"#;
+
+#[test]
+fn test_parse_metadata_versioning() {
+    let metadata = parse_metadata(METADATA).unwrap();
+
+    assert_eq!(metadata.group_id, "org.slf4j");
+    assert_eq!(metadata.artifact_id, "slf4j-api");
+    assert_eq!(metadata.latest.as_deref(), Some("2.1.0-alpha1"));
+    assert_eq!(metadata.release.as_deref(), Some("2.0.9"));
+    assert_eq!(metadata.versions, vec!["1.7.36", "2.0.9", "2.1.0-alpha1"]);
+    assert_eq!(metadata.last_updated.as_deref(), Some("20231001120000"));
+}
+
+#[test]
+fn test_meta_version_uses_latest_and_release() {
+    let metadata = parse_metadata(METADATA).unwrap();
+
+    assert_eq!(
+        metadata.meta_version("LATEST").as_deref(),
+        Some("2.1.0-alpha1")
+    );
+    assert_eq!(metadata.meta_version("RELEASE").as_deref(), Some("2.0.9"));
+    assert_eq!(metadata.meta_version("2.0.9"), None);
+}
+
+#[test]
+fn test_meta_version_falls_back_to_version_list() {
+    let xml = r#"<metadata>
+  <versioning>
+    <versions>
+      <version>1.10</version>
+      <version>1.9</version>
+      <version>2.0-SNAPSHOT</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+    let metadata = parse_metadata(xml).unwrap();
+
+    assert_eq!(
+        metadata.meta_version("LATEST").as_deref(),
+        Some("2.0-SNAPSHOT")
+    );
+    assert_eq!(metadata.meta_version("RELEASE").as_deref(), Some("1.10"));
+}
+
+#[test]
+fn test_parse_metadata_without_release() {
+    let xml = r#"<metadata>
+  <groupId>org.test</groupId>
+  <artifactId>lib</artifactId>
+  <versioning>
+    <versions><version>1.0.0-SNAPSHOT</version></versions>
+  </versioning>
+</metadata>"#;
+
+    let metadata = parse_metadata(xml).unwrap();
+    assert!(metadata.release.is_none());
+    assert!(metadata.latest.is_none());
+    assert_eq!(metadata.versions, vec!["1.0.0-SNAPSHOT"]);
+}
+
+#[test]
+fn test_parse_metadata_invalid_xml() {
+    assert!(parse_metadata("<metadata><groupId>x</artifactId></metadata>").is_err());
+}
+
+#[test]
+fn test_parse_snapshot_metadata() {
+    let xml = r#"<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>lib</artifactId>
+  <version>1.0.0-SNAPSHOT</version>
+  <versioning>
+    <snapshot><timestamp>20240102.030405</timestamp><buildNumber>7</buildNumber></snapshot>
+    <snapshotVersions>
+      <snapshotVersion><classifier>sources</classifier><extension>jar</extension><value>1.0.0-20240102.030405-6</value></snapshotVersion>
+      <snapshotVersion><extension>jar</extension><value>1.0.0-20240102.030405-7</value></snapshotVersion>
+    </snapshotVersions>
+  </versioning>
+</metadata>"#;
+
+    let metadata = parse_metadata(xml).unwrap();
+
+    assert_eq!(metadata.snapshot_versions.len(), 2);
+    assert_eq!(
+        metadata.snapshot_version("1.0.0-SNAPSHOT", "jar", None),
+        Some("1.0.0-20240102.030405-7".to_string())
+    );
+    assert_eq!(
+        metadata.snapshot_version("1.0.0-SNAPSHOT", "jar", Some("sources")),
+        Some("1.0.0-20240102.030405-6".to_string())
+    );
+    // No `<snapshotVersion>` for the POM: built from `<snapshot>`
+    assert_eq!(
+        metadata.snapshot_version("1.0.0-SNAPSHOT", "pom", None),
+        Some("1.0.0-20240102.030405-7".to_string())
+    );
+}
+
+#[test]
+fn test_local_snapshot_metadata_has_no_snapshot_version() {
+    let xml = r#"<metadata><version>1.0.0-SNAPSHOT</version><versioning><snapshot><localCopy>true</localCopy></snapshot></versioning></metadata>"#;
+
+    let metadata = parse_metadata(xml).unwrap();
+
+    assert_eq!(
+        metadata.snapshot_version("1.0.0-SNAPSHOT", "jar", None),
+        None
+    );
+}
@@ -1,6 +1,6 @@
 //! Tests for POM parser
 
-use polytunnel_maven::{DependencyScope, parse_pom};
+use polytunnel_maven::{Coordinate, DependencyScope, MavenError, parse_pom};
 use std::collections::HashMap;
 
 #[test]
@@ -95,6 +95,37 @@ fn test_parse_pom_with_optional() {
     assert!(pom.dependencies[0].optional);
 }
 
+#[test]
+fn test_parse_pom_with_classifier() {
+    let xml = r#"
+    <project>
+        <groupId>org.example</groupId>
+        <artifactId>my-lib</artifactId>
+        <version>1.0.0</version>
+        <dependencies>
+            <dependency>
+                <groupId>org.lwjgl</groupId>
+                <artifactId>lwjgl</artifactId>
+                <version>3.3.3</version>
+            </dependency>
+            <dependency>
+                <groupId>org.lwjgl</groupId>
+                <artifactId>lwjgl</artifactId>
+                <version>3.3.3</version>
+                <classifier>natives-linux</classifier>
+            </dependency>
+        </dependencies>
+    </project>
+    "#;
+
+    let pom = parse_pom(xml).unwrap();
+    assert_eq!(pom.dependencies[0].classifier, None);
+    assert_eq!(
+        pom.dependencies[1].classifier.as_deref(),
+        Some("natives-linux")
+    );
+}
+
 #[test]
 fn test_parse_html_like_response() {
     let xml = "<!DOCTYPE html><html><body>error</body></html>";
@@ -282,6 +313,7 @@ fn test_pom_property_helpers() {
         group_id: "com.example".to_string(),
         artifact_id: "managed".to_string(),
         version: Some("${base.version}".to_string()),
+        classifier: None,
         scope: DependencyScope::Compile,
         optional: false,
         exclusions: Vec::new(),
@@ -292,6 +324,7 @@ fn test_pom_property_helpers() {
         group_id: "com.example".to_string(),
         artifact_id: "managed".to_string(),
         version: None,
+        classifier: None,
         scope: DependencyScope::Compile,
         optional: true,
         exclusions: Vec::new(),
@@ -307,3 +340,109 @@ fn test_pom_property_helpers() {
         Some("1.0.0")
     );
 }
+
+#[test]
+fn test_pom_jar_packaging_equivalences() {
+    let pom_with = |packaging: &str| {
+        parse_pom(&format!(
+            "<project><groupId>g</groupId><artifactId>a</artifactId><version>1</version><packaging>{packaging}</packaging></project>"
+        ))
+        .unwrap()
+    };
+
+    assert!(pom_with("bundle").is_jar_packaging());
+    assert!(pom_with("maven-plugin").is_jar_packaging());
+    assert!(!pom_with("pom").is_jar_packaging());
+    assert_eq!(pom_with("pom").artifact_extension(), None);
+    assert_eq!(pom_with("war").artifact_extension(), Some("war"));
+}
+
+#[test]
+fn test_verify_coordinate_allows_inherited_group_and_version() {
+    let pom = parse_pom(
+        r#"<project>
+  <parent>
+    <groupId>org.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>3.1.0</version>
+  </parent>
+  <artifactId>child</artifactId>
+</project>"#,
+    )
+    .unwrap();
+
+    assert!(
+        pom.verify_coordinate(&Coordinate::new("org.example", "child", "3.1.0"))
+            .is_ok()
+    );
+    assert!(matches!(
+        pom.verify_coordinate(&Coordinate::new("org.example", "child", "3.0.0")),
+        Err(MavenError::CoordinateMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_verify_coordinate_rejects_wrong_artifact() {
+    let pom = parse_pom(
+        "<project><groupId>g</groupId><artifactId>other</artifactId><version>1</version></project>",
+    )
+    .unwrap();
+
+    let err = pom
+        .verify_coordinate(&Coordinate::new("g", "wanted", "1"))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "POM coordinate mismatch: requested g:wanted:1, but POM declares g:other:1"
+    );
+}
+
+#[test]
+fn test_parse_licenses() {
+    let xml = r#"
+    <project>
+        <groupId>org.example</groupId>
+        <artifactId>my-lib</artifactId>
+        <version>1.0.0</version>
+        <name>My Library</name>
+        <licenses>
+            <license>
+                <name>Apache License, Version 2.0</name>
+                <url>https://www.apache.org/licenses/LICENSE-2.0.txt</url>
+            </license>
+            <license>
+                <name>MIT</name>
+            </license>
+        </licenses>
+    </project>
+    "#;
+
+    let pom = parse_pom(xml).unwrap();
+    assert_eq!(pom.licenses.len(), 2);
+    assert_eq!(
+        pom.licenses[0].name.as_deref(),
+        Some("Apache License, Version 2.0")
+    );
+    assert_eq!(
+        pom.licenses[0].url.as_deref(),
+        Some("https://www.apache.org/licenses/LICENSE-2.0.txt")
+    );
+    assert_eq!(pom.licenses[1].display_name(), "MIT");
+    assert_eq!(pom.licenses[1].url, None);
+}
+
+#[test]
+fn test_parse_pom_without_licenses() {
+    let xml = r#"
+    <project>
+        <groupId>org.example</groupId>
+        <artifactId>my-lib</artifactId>
+        <version>1.0.0</version>
+        <name>My Library</name>
+        <url>https://example.com</url>
+    </project>
+    "#;
+
+    let pom = parse_pom(xml).unwrap();
+    assert!(pom.licenses.is_empty());
+}
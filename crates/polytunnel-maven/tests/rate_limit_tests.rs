@@ -0,0 +1,72 @@
+//! Tests for capping the jar download rate
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_core::HttpConfig;
+use polytunnel_maven::{Coordinate, MavenClient, MemoryArtifactStore, RateLimiter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const BASE_URL: &str = "https://repo.example.test";
+const JAR_SIZE: usize = 6 * 1024;
+
+/// Serves a `JAR_SIZE`-byte jar for `com.example:lib:1.0` and 404 for anything else
+fn transport() -> Arc<MockTransport> {
+    Arc::new(MockTransport::default().route(
+        format!("{BASE_URL}/com/example/lib/1.0/lib-1.0.jar"),
+        200,
+        vec![0; JAR_SIZE],
+    ))
+}
+
+async fn timed_download(client: MavenClient) -> Duration {
+    let coord = Coordinate::parse("com.example:lib:1.0").unwrap();
+    let store = MemoryArtifactStore::new();
+    let start = Instant::now();
+    let bytes = client
+        .download_jar_to_store(&coord, &store, false)
+        .await
+        .unwrap();
+    assert_eq!(bytes, JAR_SIZE as u64);
+    start.elapsed()
+}
+
+#[tokio::test]
+async fn test_capped_download_takes_at_least_the_minimum_time() {
+    // The bucket starts with one second's worth (4 KiB); the remaining 2 KiB take 0.5s
+    let client =
+        MavenClient::with_transport(BASE_URL, transport()).with_max_download_speed(4 * 1024);
+
+    assert!(timed_download(client).await >= Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_http_config_applies_download_cap() {
+    let http = HttpConfig {
+        max_download_bytes_per_sec: Some(4 * 1024),
+        ..Default::default()
+    };
+    let client = MavenClient::with_transport(BASE_URL, transport()).with_http_config(&http);
+
+    assert!(timed_download(client).await >= Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_uncapped_download_is_not_delayed() {
+    let client = MavenClient::with_transport(BASE_URL, transport());
+
+    assert!(timed_download(client).await < Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_rate_limiter_burst_then_wait() {
+    let limiter = RateLimiter::new(1000);
+
+    let start = Instant::now();
+    limiter.consume(1000).await;
+    assert!(start.elapsed() < Duration::from_millis(200));
+
+    limiter.consume(250).await;
+    assert!(start.elapsed() >= Duration::from_millis(250));
+}
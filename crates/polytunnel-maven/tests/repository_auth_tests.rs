@@ -0,0 +1,58 @@
+//! Tests for HTTP Basic authentication against private repositories
+
+mod common;
+
+use common::{MockTransport, header};
+use polytunnel_core::Repository;
+use polytunnel_maven::{Coordinate, MavenClient};
+use std::sync::Arc;
+
+const PRIVATE: &str = "https://nexus.example.test/releases/";
+const PUBLIC: &str = "https://central.example.test/maven2";
+
+#[tokio::test]
+async fn test_authorization_is_sent_only_to_repository_with_credentials() {
+    let transport = MockTransport::default();
+    let client = MavenClient::with_transport(PUBLIC, Arc::new(transport.clone()))
+        .with_repositories(&[
+            Repository {
+                username: Some("deploy".to_string()),
+                password: Some("s3cret".to_string()),
+                ..Repository::new("private", PRIVATE)
+            },
+            Repository::new("central", PUBLIC),
+        ]);
+
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    assert!(client.fetch_pom(&coord).await.is_err());
+
+    assert_eq!(
+        transport.requests(),
+        [
+            format!("{PRIVATE}org/test/lib/1.0.0/lib-1.0.0.pom"),
+            format!("{PUBLIC}/org/test/lib/1.0.0/lib-1.0.0.pom"),
+        ]
+    );
+    let headers = transport.request_headers();
+    assert_eq!(
+        header(&headers[0], "Authorization"),
+        Some("Basic ZGVwbG95OnMzY3JldA==")
+    );
+    assert_eq!(header(&headers[1], "Authorization"), None);
+}
+
+#[tokio::test]
+async fn test_unset_credential_variable_fails_the_request() {
+    let transport = MockTransport::default();
+    let client = MavenClient::with_transport(PRIVATE, Arc::new(transport.clone()))
+        .with_repositories(&[Repository {
+            password: Some("${env.POLYTUNNEL_TEST_UNSET_TOKEN}".to_string()),
+            ..Repository::new("private", PRIVATE)
+        }]);
+
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    let err = client.fetch_pom(&coord).await.unwrap_err();
+
+    assert!(err.to_string().contains("POLYTUNNEL_TEST_UNSET_TOKEN"));
+    assert!(transport.requests().is_empty());
+}
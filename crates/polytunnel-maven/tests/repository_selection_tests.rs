@@ -0,0 +1,153 @@
+//! Tests for picking repositories by snapshot/release version type
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_core::Repository;
+use polytunnel_maven::{Coordinate, MavenClient};
+use std::sync::Arc;
+
+const INTERNAL: &str = "https://nexus.example.test/snapshots";
+const CENTRAL: &str = "https://central.example.test/maven2/";
+
+fn repositories() -> Vec<Repository> {
+    vec![
+        Repository {
+            name: "internal-snapshots".to_string(),
+            url: INTERNAL.to_string(),
+            snapshots_enabled: true,
+            releases_enabled: false,
+            username: None,
+            password: None,
+        },
+        Repository {
+            name: "central".to_string(),
+            url: CENTRAL.to_string(),
+            snapshots_enabled: false,
+            releases_enabled: true,
+            username: None,
+            password: None,
+        },
+    ]
+}
+
+fn pom(version: &str) -> String {
+    format!(
+        "<project><groupId>org.test</groupId><artifactId>lib</artifactId><version>{version}</version></project>"
+    )
+}
+
+#[test]
+fn test_repositories_for_selects_by_version_type() {
+    let client = MavenClient::new().with_repositories(&repositories());
+
+    let release = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    let snapshot = Coordinate::parse("org.test:lib:1.1.0-SNAPSHOT").unwrap();
+
+    let names = |coord| {
+        client
+            .repositories_for(coord)
+            .iter()
+            .map(|r| r.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&release), ["central"]);
+    assert_eq!(names(&snapshot), ["internal-snapshots"]);
+}
+
+#[test]
+fn test_with_empty_repositories_keeps_default() {
+    let client = MavenClient::new().with_repositories(&[]);
+    assert_eq!(client.repositories().len(), 1);
+    assert!(client.repositories()[0].snapshots_enabled);
+    assert!(client.repositories()[0].releases_enabled);
+}
+
+#[tokio::test]
+async fn test_release_pom_skips_snapshot_only_repository() {
+    let transport = MockTransport::new(vec![
+        (
+            format!("{INTERNAL}/org/test/lib/1.0.0/lib-1.0.0.pom"),
+            200,
+            pom("1.0.0"),
+        ),
+        (
+            "https://central.example.test/maven2/org/test/lib/1.0.0/lib-1.0.0.pom".to_string(),
+            200,
+            pom("1.0.0"),
+        ),
+    ]);
+    let client = MavenClient::with_transport(INTERNAL, Arc::new(transport.clone()))
+        .with_repositories(&repositories());
+
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    client.fetch_pom(&coord).await.unwrap();
+
+    assert_eq!(
+        transport.requests(),
+        ["https://central.example.test/maven2/org/test/lib/1.0.0/lib-1.0.0.pom"]
+    );
+}
+
+#[tokio::test]
+async fn test_snapshot_pom_never_requested_from_release_repository() {
+    let transport = MockTransport::new(vec![(
+        "https://central.example.test/maven2/org/test/lib/1.1.0-SNAPSHOT/lib-1.1.0-SNAPSHOT.pom"
+            .to_string(),
+        200,
+        pom("1.1.0-SNAPSHOT"),
+    )]);
+    let client = MavenClient::with_transport(INTERNAL, Arc::new(transport.clone()))
+        .with_repositories(&repositories());
+
+    let coord = Coordinate::parse("org.test:lib:1.1.0-SNAPSHOT").unwrap();
+    assert!(client.fetch_pom(&coord).await.is_err());
+
+    // Version-level metadata is looked up first to find the timestamped build
+    assert_eq!(
+        transport.requests(),
+        [
+            format!("{INTERNAL}/org/test/lib/1.1.0-SNAPSHOT/maven-metadata.xml"),
+            format!("{INTERNAL}/org/test/lib/1.1.0-SNAPSHOT/lib-1.1.0-SNAPSHOT.pom"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_missing_artifact_falls_through_to_next_repository() {
+    let transport = MockTransport::new(vec![(
+        "https://central.example.test/maven2/org/test/lib/1.0.0/lib-1.0.0.pom".to_string(),
+        200,
+        pom("1.0.0"),
+    )]);
+    let client = MavenClient::with_transport(INTERNAL, Arc::new(transport.clone()))
+        .with_repositories(&[
+            Repository::new("internal", INTERNAL),
+            Repository::new("central", CENTRAL),
+        ]);
+
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    let pom = client.fetch_pom(&coord).await.unwrap();
+
+    assert_eq!(pom.coordinate.artifact_id, "lib");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_no_eligible_repository_is_artifact_not_found() {
+    let transport = MockTransport::default();
+    let client = MavenClient::with_transport(INTERNAL, Arc::new(transport.clone()))
+        .with_repositories(&[Repository {
+            releases_enabled: false,
+            ..Repository::new("snapshots", INTERNAL)
+        }]);
+
+    let coord = Coordinate::parse("org.test:lib:1.0.0").unwrap();
+    let error = client.fetch_pom(&coord).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        polytunnel_maven::MavenError::ArtifactNotFound { .. }
+    ));
+    assert!(transport.requests().is_empty());
+}
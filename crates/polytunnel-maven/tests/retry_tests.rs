@@ -0,0 +1,94 @@
+//! Tests for retrying transient failures with exponential backoff
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{
+    Coordinate, DEFAULT_MAX_RETRIES, DEFAULT_RETRY_BASE_DELAY, MavenClient, MavenError,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const BASE_URL: &str = "https://repo.example.test";
+const POM: &str = "<project><groupId>org.test</groupId><artifactId>lib</artifactId><version>1.0.0</version></project>";
+
+/// Repository answering the POM with `statuses` in order, repeating the last one
+fn pom_transport(statuses: &[u16]) -> MockTransport {
+    let url = format!("{BASE_URL}/org/test/lib/1.0.0/lib-1.0.0.pom");
+    statuses
+        .iter()
+        .fold(MockTransport::default(), |transport, &status| {
+            let body = if status == 200 { POM } else { "unavailable" };
+            transport.then(url.clone(), status, body)
+        })
+}
+
+fn client(transport: &MockTransport) -> MavenClient {
+    MavenClient::with_transport(BASE_URL, Arc::new(transport.clone()))
+        .with_retry_base_delay(Duration::from_millis(10))
+}
+
+fn coord() -> Coordinate {
+    Coordinate::parse("org.test:lib:1.0.0").unwrap()
+}
+
+#[tokio::test]
+async fn test_service_unavailable_is_retried_until_success() {
+    let transport = pom_transport(&[503, 503, 200]);
+    let start = Instant::now();
+
+    let pom = client(&transport).fetch_pom(&coord()).await;
+
+    assert!(pom.is_ok(), "{pom:?}");
+    assert_eq!(transport.requests().len(), 3);
+    // 10ms, then 20ms
+    assert!(start.elapsed() >= Duration::from_millis(30));
+}
+
+#[tokio::test]
+async fn test_not_found_is_not_retried() {
+    let transport = pom_transport(&[404]);
+
+    let err = client(&transport).fetch_pom(&coord()).await.unwrap_err();
+
+    assert!(matches!(err, MavenError::PomNotFound { .. }), "{err:?}");
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_server_error_is_returned_once_retries_are_used_up() {
+    let transport = pom_transport(&[500]);
+
+    let err = client(&transport)
+        .with_max_retries(2)
+        .fetch_pom(&coord())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, MavenError::ServerError { status: 500, .. }),
+        "{err:?}"
+    );
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_zero_retries_fails_on_first_server_error() {
+    let transport = pom_transport(&[503, 200]);
+
+    let result = client(&transport)
+        .with_max_retries(0)
+        .fetch_pom(&coord())
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn test_default_retry_settings() {
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(pom_transport(&[200])));
+
+    assert_eq!(client.max_retries(), DEFAULT_MAX_RETRIES);
+    assert_eq!(client.retry_base_delay(), DEFAULT_RETRY_BASE_DELAY);
+}
@@ -0,0 +1,83 @@
+//! Tests for DependencyScope classpath membership against Maven's scope table
+
+use polytunnel_maven::DependencyScope;
+
+#[test]
+fn test_scope_classpath_matrix() {
+    // (scope, compile, test, runtime, transitive)
+    let matrix = [
+        (DependencyScope::Compile, true, true, true, true),
+        (DependencyScope::Provided, true, true, false, false),
+        (DependencyScope::Runtime, false, true, true, true),
+        (DependencyScope::Test, false, true, false, false),
+        (DependencyScope::System, true, true, false, false),
+        (DependencyScope::Import, false, false, false, false),
+    ];
+
+    for (scope, compile, test, runtime, transitive) in matrix {
+        assert_eq!(scope.on_compile_classpath(), compile, "{scope:?} compile");
+        assert_eq!(scope.on_test_classpath(), test, "{scope:?} test");
+        assert_eq!(scope.on_runtime_classpath(), runtime, "{scope:?} runtime");
+        assert_eq!(scope.is_transitive(), transitive, "{scope:?} transitive");
+    }
+}
+
+#[test]
+fn test_scope_ordering_widest_first() {
+    assert!(DependencyScope::Compile < DependencyScope::Runtime);
+    assert!(DependencyScope::Runtime < DependencyScope::Test);
+    assert_eq!(
+        DependencyScope::Test.min(DependencyScope::Compile),
+        DependencyScope::Compile
+    );
+}
+
+#[test]
+fn test_scope_from_config_scope() {
+    use polytunnel_core::DependencyScope as ConfigScope;
+
+    assert_eq!(
+        DependencyScope::from(ConfigScope::Compile),
+        DependencyScope::Compile
+    );
+    assert_eq!(
+        DependencyScope::from(ConfigScope::Runtime),
+        DependencyScope::Runtime
+    );
+    assert_eq!(
+        DependencyScope::from(ConfigScope::Test),
+        DependencyScope::Test
+    );
+    assert_eq!(
+        DependencyScope::from(ConfigScope::Provided),
+        DependencyScope::Provided
+    );
+}
+
+#[test]
+fn test_transitive_scope_follows_maven_table() {
+    use DependencyScope::*;
+
+    // (parent, declared, effective)
+    let table = [
+        (Compile, Compile, Some(Compile)),
+        (Compile, Runtime, Some(Runtime)),
+        (Provided, Compile, Some(Provided)),
+        (Provided, Runtime, Some(Provided)),
+        (Runtime, Compile, Some(Runtime)),
+        (Runtime, Runtime, Some(Runtime)),
+        (Test, Compile, Some(Test)),
+        (Test, Runtime, Some(Test)),
+        (Compile, Provided, None),
+        (Compile, Test, None),
+        (Runtime, System, None),
+    ];
+
+    for (parent, declared, effective) in table {
+        assert_eq!(
+            parent.transitive_scope(declared),
+            effective,
+            "{parent:?} -> {declared:?}"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Tests for resolving `-SNAPSHOT` artifacts to timestamped builds
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{
+    ArtifactStore, Coordinate, MavenClient, MavenError, MemoryArtifactStore, jar_key,
+};
+use std::sync::Arc;
+
+const BASE_URL: &str = "https://repo.example.test";
+const DIR: &str = "https://repo.example.test/com/example/lib/1.0.0-SNAPSHOT";
+
+const POM: &str = "<project><groupId>com.example</groupId><artifactId>lib</artifactId><version>1.0.0-SNAPSHOT</version></project>";
+
+const METADATA: &str = r#"<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>lib</artifactId>
+  <version>1.0.0-SNAPSHOT</version>
+  <versioning>
+    <snapshot><timestamp>20240102.030405</timestamp><buildNumber>7</buildNumber></snapshot>
+    <snapshotVersions>
+      <snapshotVersion><extension>jar</extension><value>1.0.0-20240102.030405-7</value></snapshotVersion>
+      <snapshotVersion><extension>pom</extension><value>1.0.0-20240102.030405-7</value></snapshotVersion>
+    </snapshotVersions>
+  </versioning>
+</metadata>"#;
+
+fn snapshot() -> Coordinate {
+    Coordinate::parse("com.example:lib:1.0.0-SNAPSHOT").unwrap()
+}
+
+fn url(file: &str) -> String {
+    format!("{DIR}/{file}")
+}
+
+#[tokio::test]
+async fn test_snapshot_resolves_to_timestamped_build() {
+    let jar = url("lib-1.0.0-20240102.030405-7.jar");
+    let pom = url("lib-1.0.0-20240102.030405-7.pom");
+    let metadata = url("maven-metadata.xml");
+    let transport = MockTransport::default()
+        .route(&metadata, 200, METADATA)
+        .route(&pom, 200, POM)
+        .route(&jar, 200, "timestamped")
+        .route(url("lib-1.0.0-SNAPSHOT.jar"), 200, "literal");
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(transport.clone()));
+    let store = MemoryArtifactStore::new();
+
+    let pom = client.fetch_pom(&snapshot()).await.unwrap();
+    assert_eq!(pom.coordinate, snapshot());
+    client
+        .download_jar_to_store(&snapshot(), &store, false)
+        .await
+        .unwrap();
+    assert_eq!(store.read(&jar_key(&snapshot())).unwrap(), b"timestamped");
+    // The POM, the JAR, and its checksum share one metadata lookup
+    let metadata_fetches = transport
+        .requests()
+        .iter()
+        .filter(|url| **url == metadata)
+        .count();
+    assert_eq!(metadata_fetches, 1);
+}
+
+#[tokio::test]
+async fn test_snapshot_without_metadata_falls_back_to_literal_file() {
+    let transport = MockTransport::default()
+        .route(url("lib-1.0.0-SNAPSHOT.pom"), 200, POM)
+        .route(url("lib-1.0.0-SNAPSHOT.jar"), 200, "literal");
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(transport));
+    let store = MemoryArtifactStore::new();
+
+    client.fetch_pom(&snapshot()).await.unwrap();
+    let bytes = client
+        .download_jar_to_store(&snapshot(), &store, false)
+        .await
+        .unwrap();
+    assert_eq!(bytes, 7);
+    assert_eq!(store.read(&jar_key(&snapshot())).unwrap(), b"literal");
+}
+
+#[tokio::test]
+async fn test_snapshot_missing_everywhere_fails() {
+    let client = MavenClient::with_transport(BASE_URL, Arc::new(MockTransport::default()));
+    let store = MemoryArtifactStore::new();
+
+    let err = client
+        .download_jar_to_store(&snapshot(), &store, false)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, MavenError::HttpStatus { status: 404, url } if url.ends_with("lib-1.0.0-SNAPSHOT.jar"))
+    );
+}
@@ -0,0 +1,248 @@
+//! Tests for Maven version ordering, ranges, and version listing
+
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{
+    MavenClient, MavenVersion, RangeBound, VersionRange, compare_versions, is_snapshot_version,
+    is_version_range,
+};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+const BASE: &str = "https://repo.example.test/maven2";
+
+#[test]
+fn test_compare_versions_numeric_parts() {
+    assert_eq!(compare_versions("2.0.10", "2.0.9"), Ordering::Greater);
+    assert_eq!(compare_versions("1.10", "1.9.9"), Ordering::Greater);
+    assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+    assert_eq!(compare_versions("1.01", "1.1"), Ordering::Equal);
+}
+
+#[test]
+fn test_compare_versions_qualifier_order() {
+    let ordered = [
+        "1.0-alpha1",
+        "1.0-beta2",
+        "1.0-M3",
+        "1.0-RC1",
+        "1.0-SNAPSHOT",
+        "1.0",
+        "1.0-sp1",
+        "1.0.1",
+    ];
+    for pair in ordered.windows(2) {
+        assert_eq!(
+            compare_versions(pair[0], pair[1]),
+            Ordering::Less,
+            "{} < {}",
+            pair[0],
+            pair[1]
+        );
+    }
+}
+
+#[test]
+fn test_compare_versions_aliases_and_case() {
+    assert_eq!(compare_versions("1.0-a1", "1.0-alpha-1"), Ordering::Equal);
+    assert_eq!(compare_versions("1.0-CR1", "1.0-rc1"), Ordering::Equal);
+    assert_eq!(compare_versions("1.0.Final", "1.0"), Ordering::Equal);
+    assert_eq!(compare_versions("1-alpha", "1.0.0-alpha"), Ordering::Equal);
+}
+
+#[test]
+fn test_compare_versions_unknown_qualifiers_sort_after_releases() {
+    assert_eq!(compare_versions("33.0.0-jre", "33.0.0"), Ordering::Greater);
+    assert_eq!(
+        compare_versions("33.0.0-android", "33.0.0-jre"),
+        Ordering::Less
+    );
+    assert_eq!(compare_versions("33.0.0-jre", "33.0.1"), Ordering::Less);
+}
+
+#[test]
+fn test_is_snapshot_version() {
+    assert!(is_snapshot_version("1.0-SNAPSHOT"));
+    assert!(!is_snapshot_version("1.0"));
+}
+
+fn v(version: &str) -> MavenVersion {
+    MavenVersion::parse(version)
+}
+
+#[test]
+fn test_maven_version_total_order() {
+    let ascending = [
+        "1.0-alpha1",
+        "1.0-alpha2",
+        "1.0-beta1",
+        "1.0-M1",
+        "1.0-rc1",
+        "1.0-SNAPSHOT",
+        "1.0",
+        "1.0-sp1",
+        "1.0.1",
+        "1.1",
+        "1.9",
+        "1.10",
+        "2.0-rc1",
+        "2.0",
+        "10.0",
+    ];
+    for pair in ascending.windows(2) {
+        assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+    }
+
+    let mut shuffled: Vec<MavenVersion> = ascending.iter().rev().map(|s| v(s)).collect();
+    shuffled.sort();
+    let sorted: Vec<&str> = shuffled.iter().map(MavenVersion::as_str).collect();
+    assert_eq!(sorted, ascending);
+}
+
+#[test]
+fn test_maven_version_equality_ignores_spelling() {
+    assert_eq!(v("1.0"), v("1.0.0"));
+    assert_eq!(v("1.0-ga"), v("1"));
+    assert_eq!(v("1.0-final"), v("1.0-RELEASE"));
+    assert_eq!(v("1.0-CR1"), v("1.0-rc1"));
+    assert_ne!(v("1.0-jre"), v("1.0-android"));
+    assert_eq!(v("1.0.0").to_string(), "1.0.0");
+
+    let unique: std::collections::HashSet<_> = [v("2.0"), v("2.0.0"), v("2")].into();
+    assert_eq!(unique.len(), 1);
+}
+
+#[test]
+fn test_maven_version_components() {
+    let guava = v("33.0.0-jre");
+    assert_eq!(guava.numbers(), [33, 0, 0]);
+    assert_eq!(guava.qualifier(), Some("jre"));
+    assert_eq!(guava.base_version(), "33.0.0");
+
+    let snapshot = v("1.2.3-SNAPSHOT");
+    assert!(snapshot.is_snapshot());
+    assert!(snapshot.is_prerelease());
+    assert_eq!(snapshot.base_version(), "1.2.3");
+
+    let plain = v("2.1");
+    assert_eq!(plain.qualifier(), None);
+    assert!(!plain.is_snapshot());
+    assert!(!plain.is_prerelease());
+
+    assert!(v("1.0-rc1").is_prerelease());
+    assert!(v("1.0.Beta2").is_prerelease());
+    assert!(!v("1.0-sp1").is_prerelease());
+    assert!(!v("1.0-jre").is_prerelease());
+    assert_eq!(v("1.0.Final").qualifier(), Some("Final"));
+}
+
+#[test]
+fn test_version_range_bounds() {
+    let range = VersionRange::parse("[1.2.3,2.0.0)").unwrap();
+    assert!(range.contains(&v("1.2.3")));
+    assert!(range.contains(&v("1.10")));
+    assert!(!range.contains(&v("1.2.2")));
+    assert!(!range.contains(&v("2.0.0")));
+    assert!(!range.contains(&v("2.0")));
+
+    let open_ended = VersionRange::parse("(1.0,)").unwrap();
+    assert!(!open_ended.contains(&v("1.0")));
+    assert!(open_ended.contains(&v("99")));
+
+    let exact = VersionRange::parse("[1.5]").unwrap();
+    assert!(exact.contains(&v("1.5.0")));
+    assert!(!exact.contains(&v("1.5.1")));
+}
+
+#[test]
+fn test_version_range_parse_bounds() {
+    let bound = |version: &str, inclusive| {
+        Some(RangeBound {
+            version: v(version),
+            inclusive,
+        })
+    };
+
+    let half_open = VersionRange::parse("[1.0,2.0)").unwrap();
+    assert_eq!(half_open.lower, bound("1.0", true));
+    assert_eq!(half_open.upper, bound("2.0", false));
+
+    let up_to = VersionRange::parse("(,1.0]").unwrap();
+    assert_eq!(up_to.lower, None);
+    assert_eq!(up_to.upper, bound("1.0", true));
+
+    let exact = VersionRange::parse("[1.0]").unwrap();
+    assert_eq!(exact.lower, bound("1.0", true));
+    assert_eq!(exact.upper, bound("1.0", true));
+}
+
+#[test]
+fn test_version_range_parse_rejects_non_ranges() {
+    assert!(is_version_range("[1.0,2.0)"));
+    assert!(!is_version_range("1.0"));
+    assert_eq!(VersionRange::parse("1.0"), None);
+    assert_eq!(VersionRange::parse("[1.0,2.0"), None);
+    assert_eq!(VersionRange::parse("(1.0)"), None);
+    assert_eq!(VersionRange::parse("(,1.0],[1.2,)"), None);
+}
+
+#[test]
+fn test_version_range_display_round_trips() {
+    for spec in ["[1.2.3,2.0.0)", "(,1.0]", "[1.5,)", "[1.5]"] {
+        assert_eq!(VersionRange::parse(spec).unwrap().to_string(), spec);
+    }
+}
+
+#[test]
+fn test_version_range_select_newest_release_in_range() {
+    let range = VersionRange::parse("[1.2.3,2.0.0)").unwrap();
+    let versions: Vec<_> = ["1.2.0", "1.2.3", "1.9", "1.10", "1.11-SNAPSHOT", "2.0.0"]
+        .into_iter()
+        .map(v)
+        .collect();
+    assert_eq!(
+        range.select(&versions).map(MavenVersion::as_str),
+        Some("1.10")
+    );
+    assert_eq!(range.select(&[v("2.1"), v("1.0")]), None);
+}
+
+#[tokio::test]
+async fn test_list_versions_reads_metadata_newest_first() {
+    let metadata = r#"<metadata>
+  <groupId>org.test</groupId>
+  <artifactId>lib</artifactId>
+  <versioning>
+    <latest>2.0-SNAPSHOT</latest>
+    <release>1.10</release>
+    <versions>
+      <version>1.2</version>
+      <version>1.10</version>
+      <version>1.10-RC1</version>
+      <version>2.0-SNAPSHOT</version>
+      <version>1.9</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+    let transport = MockTransport::default().route(
+        format!("{BASE}/org/test/lib/maven-metadata.xml"),
+        200,
+        metadata,
+    );
+    let client = MavenClient::with_transport(BASE, Arc::new(transport));
+
+    let versions = client.list_versions("org.test", "lib").await.unwrap();
+
+    assert_eq!(
+        versions,
+        vec!["2.0-SNAPSHOT", "1.10", "1.10-RC1", "1.9", "1.2"]
+    );
+}
+
+#[tokio::test]
+async fn test_list_versions_missing_metadata_is_an_error() {
+    let client = MavenClient::with_transport(BASE, Arc::new(MockTransport::default()));
+
+    assert!(client.list_versions("org.test", "missing").await.is_err());
+}
@@ -0,0 +1,82 @@
+//! Resolver configuration
+
+use crate::error::Result;
+use polytunnel_core::{DEFAULT_RESOLVE_CONCURRENCY, ProjectConfig};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// How to handle an artifact requested in more than one version
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the version closest to a root (shallowest, then first declared)
+    /// and record the others as mediated, like Maven
+    #[default]
+    Nearest,
+    /// Keep the highest version requested anywhere in the tree
+    Newest,
+    /// Fail with `ResolverError::VersionConflict`
+    ///
+    /// Versions forced by a root declaration or by dependency management are
+    /// not conflicts.
+    Fail,
+}
+
+/// Settings for a [`crate::Resolver`]
+///
+/// `Default` resolves like Maven: nearest version wins, no exclusions, no
+/// deadline, and `[http] resolve_concurrency`'s default number of POM fetches.
+#[derive(Debug, Clone)]
+pub struct ResolverConfig {
+    /// Per-root exclusions keyed by the root's `groupId:artifactId`
+    ///
+    /// Values are `groupId:artifactId` patterns (either part may be `*`)
+    /// removed anywhere below that root.
+    pub exclusions: HashMap<String, Vec<String>>,
+    /// Roots (`groupId:artifactId`) whose optional dependencies are resolved
+    pub optional_roots: HashSet<String>,
+    /// Versions forced for `groupId:artifactId` wherever it appears transitively
+    pub managed_versions: HashMap<String, String>,
+    /// Overall deadline for a single [`crate::Resolver::resolve`] call
+    pub timeout: Option<Duration>,
+    /// Follow optional and provided dependencies at every depth
+    pub full: bool,
+    /// Maximum number of POMs fetched at once (at least 1)
+    pub concurrency: usize,
+    /// How to handle an artifact requested in more than one version
+    pub conflict_policy: ConflictPolicy,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self {
+            exclusions: HashMap::new(),
+            optional_roots: HashSet::new(),
+            managed_versions: HashMap::new(),
+            timeout: None,
+            full: false,
+            concurrency: DEFAULT_RESOLVE_CONCURRENCY,
+            conflict_policy: ConflictPolicy::default(),
+        }
+    }
+}
+
+impl ResolverConfig {
+    /// Settings declared in `polytunnel.toml`
+    ///
+    /// Takes exclusions, optional inclusions, managed versions,
+    /// `build.resolve_timeout_secs`, and `http.resolve_concurrency`.
+    ///
+    /// # Errors
+    ///
+    /// * `ResolverError::Config` - If an exclusion or managed version is malformed
+    pub fn from_project(config: &ProjectConfig) -> Result<Self> {
+        Ok(Self {
+            exclusions: config.dependency_exclusions()?,
+            optional_roots: config.optional_inclusions(),
+            managed_versions: config.managed_versions()?,
+            timeout: config.build.resolve_timeout_secs.map(Duration::from_secs),
+            concurrency: config.http.resolve_concurrency(),
+            ..Self::default()
+        })
+    }
+}
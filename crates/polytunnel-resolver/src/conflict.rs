@@ -0,0 +1,70 @@
+//! Version conflicts settled during resolution
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One request for a version of a conflicting artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionRequest {
+    /// Version asked for
+    pub version: String,
+    /// Coordinates from a root down to the requesting artifact
+    ///
+    /// Empty for a root declared in `polytunnel.toml`.
+    pub path: Vec<String>,
+}
+
+/// An artifact requested in more than one version, and the version kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionConflict {
+    /// `groupId:artifactId`, with the classifier for classified artifacts
+    pub artifact: String,
+    /// Version in the resolved tree
+    pub selected: String,
+    /// Other requested versions, in the order first requested
+    pub rejected: Vec<String>,
+    /// Every request for the artifact, in breadth-first declaration order
+    pub requests: Vec<VersionRequest>,
+}
+
+impl VersionConflict {
+    /// Requests for `version`
+    pub fn requests_for<'a>(
+        &'a self,
+        version: &'a str,
+    ) -> impl Iterator<Item = &'a VersionRequest> {
+        self.requests
+            .iter()
+            .filter(move |request| request.version == version)
+    }
+}
+
+/// `group:artifact: using 2.0 (1.0 requested by a:b:1.0 -> c:d:1.0)`
+impl fmt::Display for VersionConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rejected: Vec<String> = self
+            .rejected
+            .iter()
+            .map(|version| {
+                let requesters: Vec<String> = self
+                    .requests_for(version)
+                    .map(|request| {
+                        if request.path.is_empty() {
+                            "the project".to_string()
+                        } else {
+                            request.path.join(" -> ")
+                        }
+                    })
+                    .collect();
+                format!("{} requested by {}", version, requesters.join(", "))
+            })
+            .collect();
+        write!(
+            f,
+            "{}: using {} ({})",
+            self.artifact,
+            self.selected,
+            rejected.join("; ")
+        )
+    }
+}
@@ -0,0 +1,88 @@
+//! Structured comparison of resolved dependency trees
+
+use crate::graph::artifact_key;
+use crate::resolve::ResolvedTree;
+use polytunnel_maven::Coordinate;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A dependency whose resolved version changed between two trees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionChange {
+    /// Coordinate in the previous tree
+    pub from: Coordinate,
+    /// Coordinate in the new tree
+    pub to: Coordinate,
+}
+
+/// Difference between two [`ResolvedTree`]s, keyed by `groupId:artifactId[:classifier]`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeDiff {
+    /// Dependencies present only in the new tree
+    pub added: Vec<Coordinate>,
+    /// Dependencies present only in the previous tree
+    pub removed: Vec<Coordinate>,
+    /// Dependencies present in both trees with different versions
+    pub changed: Vec<VersionChange>,
+}
+
+impl TreeDiff {
+    /// Whether the two trees resolved to the same dependency set
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// One line per entry: `+ added`, `- removed`, `~ from -> version`
+impl fmt::Display for TreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        for coord in &self.added {
+            lines.push(format!("+ {}", coord));
+        }
+        for coord in &self.removed {
+            lines.push(format!("- {}", coord));
+        }
+        for change in &self.changed {
+            lines.push(format!("~ {} -> {}", change.from, change.to.version));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl ResolvedTree {
+    /// Compare this (previous) tree against `other` (new) tree
+    ///
+    /// Classifier variants are compared separately, as the resolver keeps them
+    /// apart. Entries in each list are sorted by `groupId:artifactId[:classifier]`
+    /// so the output is stable across runs.
+    pub fn diff(&self, other: &ResolvedTree) -> TreeDiff {
+        let before = index_by_artifact(&self.all_dependencies);
+        let after = index_by_artifact(&other.all_dependencies);
+
+        let mut diff = TreeDiff::default();
+
+        for (ga, old) in &before {
+            match after.get(ga) {
+                None => diff.removed.push((*old).clone()),
+                Some(new) if new.version != old.version => diff.changed.push(VersionChange {
+                    from: (*old).clone(),
+                    to: (*new).clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        for (ga, new) in &after {
+            if !before.contains_key(ga) {
+                diff.added.push((*new).clone());
+            }
+        }
+
+        diff
+    }
+}
+
+fn index_by_artifact(coords: &[Coordinate]) -> BTreeMap<String, &Coordinate> {
+    coords.iter().map(|c| (artifact_key(c), c)).collect()
+}
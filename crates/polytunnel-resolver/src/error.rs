@@ -18,6 +18,17 @@ pub enum ResolverError {
     #[error("Maven error: {0}")]
     Maven(#[from] polytunnel_maven::MavenError),
 
+    /// Fetching an artifact failed during transitive resolution
+    #[error("failed to fetch {coordinate}{}: {source}", format_chain(chain))]
+    FetchFailed {
+        /// Coordinate of the artifact that could not be fetched
+        coordinate: String,
+        /// Coordinates from the requested root down to the failing artifact
+        chain: Vec<String>,
+        /// Underlying Maven error
+        source: Box<polytunnel_maven::MavenError>,
+    },
+
     /// Circular dependency detected
     #[error("Circular dependency detected: {path}")]
     CircularDependency {
@@ -41,7 +52,57 @@ pub enum ResolverError {
         versions: Vec<String>,
     },
 
+    /// A root dependency declares a version range that cannot be parsed
+    #[error("Invalid version range '{range}' for {artifact}")]
+    InvalidVersionRange {
+        /// Artifact identifier (`groupId:artifactId`)
+        artifact: String,
+        /// Declared range
+        range: String,
+    },
+
+    /// No published version falls inside a version range or matches `LATEST`/`RELEASE`
+    #[error("No version of {artifact} matches {range}")]
+    NoVersionInRange {
+        /// Artifact identifier (`groupId:artifactId`)
+        artifact: String,
+        /// Declared range or metaversion
+        range: String,
+    },
+
+    /// Resolution exceeded the configured overall deadline
+    #[error("Dependency resolution timed out after {seconds}s ({resolved} artifacts resolved)")]
+    Timeout {
+        /// Configured deadline in seconds
+        seconds: u64,
+        /// Artifacts whose POMs had been resolved before the deadline
+        resolved: usize,
+    },
+
     /// Core configuration error
     #[error("Configuration error: {0}")]
     Config(#[from] polytunnel_core::CoreError),
 }
+
+impl ResolverError {
+    /// What offline mode refused to download, if that is why resolution failed
+    pub fn offline_resource(&self) -> Option<&str> {
+        let source = match self {
+            Self::Maven(source) => source,
+            Self::FetchFailed { source, .. } => source.as_ref(),
+            _ => return None,
+        };
+        match source {
+            polytunnel_maven::MavenError::Offline { resource } => Some(resource),
+            _ => None,
+        }
+    }
+}
+
+fn format_chain(chain: &[String]) -> String {
+    if chain.len() <= 1 {
+        String::new()
+    } else {
+        format!(" (via {})", chain.join(" -> "))
+    }
+}
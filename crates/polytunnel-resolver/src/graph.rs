@@ -1,10 +1,13 @@
 //! Dependency graph representation
 
-use polytunnel_maven::Coordinate;
-use std::collections::HashMap;
+use polytunnel_maven::{Coordinate, DependencyScope};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use thiserror::Error;
 
 /// Node in the dependency graph
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DependencyNode {
     /// Maven coordinate of this artifact
     pub coordinate: Coordinate,
@@ -12,11 +15,66 @@ pub struct DependencyNode {
     pub dependencies: Vec<Coordinate>,
     /// Resolution depth (0 = root dependency)
     pub depth: usize,
+    /// Scope this artifact was reached with (roots are `Compile`)
+    pub scope: DependencyScope,
+}
+
+impl DependencyNode {
+    /// Resolution depth (0 = root dependency)
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Scope this artifact was reached with
+    pub fn scope(&self) -> DependencyScope {
+        self.scope
+    }
+}
+
+/// Summary of a resolved dependency graph, from [`DependencyGraph::stats`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphStats {
+    /// Number of resolved artifacts
+    pub total: usize,
+    /// Artifacts requested directly (depth 0)
+    pub direct: usize,
+    /// Artifacts only reached through other artifacts
+    pub transitive: usize,
+    /// Longest shortest path from a direct dependency to any artifact
+    pub max_depth: usize,
+    /// Artifacts (`groupId:artifactId[:classifier]`) requested in more than one version
+    pub conflicts: usize,
+    /// Total size of the artifacts' JARs on disk, if known
+    ///
+    /// The graph does not know where JARs are cached, so [`DependencyGraph::stats`]
+    /// leaves this `None` for the caller to fill in.
+    pub jar_bytes: Option<u64>,
+}
+
+/// Dependency cycle found by [`DependencyGraph::topological_order`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("dependency cycle: {}", format_cycle(cycle))]
+pub struct CycleError {
+    /// Coordinates along the cycle, each depending on the next; the last
+    /// depends on the first
+    pub cycle: Vec<Coordinate>,
+}
+
+fn format_cycle(cycle: &[Coordinate]) -> String {
+    cycle
+        .iter()
+        .chain(cycle.first())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" -> ")
 }
 
 /// Dependency graph for resolution
-#[derive(Debug, Default)]
+///
+/// Serializes as a map from coordinate to node, sorted by coordinate.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DependencyGraph {
+    #[serde(default, serialize_with = "serialize_sorted")]
     nodes: HashMap<String, DependencyNode>,
 }
 
@@ -28,8 +86,19 @@ impl DependencyGraph {
         }
     }
 
-    /// Add a node to the graph
+    /// Add a node to the graph with `Compile` scope
     pub fn add_node(&mut self, coord: Coordinate, deps: Vec<Coordinate>, depth: usize) {
+        self.add_node_with_scope(coord, deps, depth, DependencyScope::Compile);
+    }
+
+    /// Add a node to the graph with an explicit scope
+    pub fn add_node_with_scope(
+        &mut self,
+        coord: Coordinate,
+        deps: Vec<Coordinate>,
+        depth: usize,
+        scope: DependencyScope,
+    ) {
         let key = coord.to_string();
         self.nodes.insert(
             key,
@@ -37,6 +106,7 @@ impl DependencyGraph {
                 coordinate: coord,
                 dependencies: deps,
                 depth,
+                scope,
             },
         );
     }
@@ -55,4 +125,289 @@ impl DependencyGraph {
     pub fn contains(&self, key: &str) -> bool {
         self.nodes.contains_key(key)
     }
+
+    /// Direct dependencies of `coord` that are present in the graph, in declaration order
+    pub fn children(&self, coord: &Coordinate) -> impl Iterator<Item = &DependencyNode> {
+        self.nodes
+            .get(&coord.to_string())
+            .into_iter()
+            .flat_map(|node| node.dependencies.iter())
+            .filter_map(|child| self.nodes.get(&child.to_string()))
+    }
+
+    /// Shortest dependency path from a depth-0 node down to `target`
+    ///
+    /// The path starts at the root and ends with `target` itself, so a root
+    /// yields a one-element path. Among equally short paths the one through
+    /// the smallest root coordinate wins, then declaration order. `None` if
+    /// `target` is not reachable from any root.
+    pub fn shortest_path_to(&self, target: &Coordinate) -> Option<Vec<Coordinate>> {
+        let mut roots: Vec<&DependencyNode> =
+            self.nodes.values().filter(|n| n.depth == 0).collect();
+        roots.sort_by_key(|node| node.coordinate.to_string());
+
+        let mut parents: HashMap<String, Option<&Coordinate>> = HashMap::new();
+        let mut queue: VecDeque<&Coordinate> = VecDeque::new();
+        for root in roots {
+            parents.insert(root.coordinate.to_string(), None);
+            queue.push_back(&root.coordinate);
+        }
+
+        let target_key = target.to_string();
+        while let Some(coord) = queue.pop_front() {
+            if coord.to_string() == target_key {
+                let mut path = vec![coord.clone()];
+                while let Some(Some(parent)) = parents.get(&path[path.len() - 1].to_string()) {
+                    path.push((*parent).clone());
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for child in self.children(coord) {
+                if let Entry::Vacant(entry) = parents.entry(child.coordinate.to_string()) {
+                    entry.insert(Some(coord));
+                    queue.push_back(&child.coordinate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Counts, depth, and version conflicts of the resolved graph
+    ///
+    /// Depth is measured along the shortest path from any depth-0 node. A
+    /// conflict is an artifact whose resolved version differs from a version
+    /// requested by some dependent, or that is requested in several versions.
+    pub fn stats(&self) -> GraphStats {
+        let roots: Vec<&DependencyNode> = self.nodes.values().filter(|n| n.depth == 0).collect();
+
+        let mut depths: HashMap<String, usize> = HashMap::new();
+        for root in &roots {
+            for (node, depth) in self.bfs_from(&root.coordinate) {
+                depths
+                    .entry(node.coordinate.to_string())
+                    .and_modify(|d| *d = (*d).min(depth))
+                    .or_insert(depth);
+            }
+        }
+
+        GraphStats {
+            total: self.nodes.len(),
+            direct: roots.len(),
+            transitive: self.nodes.len() - roots.len(),
+            max_depth: depths.values().copied().max().unwrap_or(0),
+            conflicts: self.conflicts().len(),
+            jar_bytes: None,
+        }
+    }
+
+    /// Artifacts (`groupId:artifactId[:classifier]`) requested in more than one version
+    ///
+    /// Each maps to every version requested for it, including the resolved one.
+    pub fn conflicts(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for coord in self.requested() {
+            versions
+                .entry(artifact_key(coord))
+                .or_default()
+                .insert(coord.version.clone());
+        }
+        versions.retain(|_, versions| versions.len() > 1);
+        versions
+    }
+
+    /// Coordinates that are, or transitively depend on, a conflicting artifact
+    ///
+    /// Covers both resolved nodes and the versions dependents asked for, so a
+    /// tree limited to these keys shows every path to each [`Self::conflicts`]
+    /// entry and nothing else.
+    pub fn conflict_paths(&self) -> HashSet<String> {
+        let conflicts = self.conflicts();
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+        for node in self.nodes.values() {
+            for child in &node.dependencies {
+                dependents
+                    .entry(child.to_string())
+                    .or_default()
+                    .push(node.coordinate.to_string());
+            }
+        }
+
+        let mut queue: Vec<String> = self
+            .requested()
+            .filter(|coord| conflicts.contains_key(&artifact_key(coord)))
+            .map(|coord| coord.to_string())
+            .collect();
+        let mut paths = HashSet::new();
+        while let Some(key) = queue.pop() {
+            if let Some(parents) = dependents.get(&key)
+                && !paths.contains(&key)
+            {
+                queue.extend(parents.iter().cloned());
+            }
+            paths.insert(key);
+        }
+        paths
+    }
+
+    /// Every resolved coordinate and every coordinate a node depends on
+    fn requested(&self) -> impl Iterator<Item = &Coordinate> {
+        self.nodes
+            .values()
+            .flat_map(|node| std::iter::once(&node.coordinate).chain(&node.dependencies))
+    }
+
+    /// Effective scope of every node reachable from `roots`, keyed by coordinate
+    ///
+    /// Roots take their given scope; below them scopes combine per
+    /// [`DependencyScope::transitive_scope`], using each node's recorded scope
+    /// as the declared one. An artifact reached along several paths keeps the
+    /// widest scope, so a runtime dependency needed by a compile root stays on
+    /// the runtime classpath even if a test root also pulls it in.
+    pub fn effective_scopes(
+        &self,
+        roots: &[(Coordinate, DependencyScope)],
+    ) -> HashMap<String, DependencyScope> {
+        let mut scopes: HashMap<String, DependencyScope> = HashMap::new();
+        let mut queue: VecDeque<(&Coordinate, DependencyScope)> = VecDeque::new();
+        for (coord, scope) in roots {
+            let key = coord.to_string();
+            if scopes.get(&key).is_none_or(|existing| scope < existing) {
+                scopes.insert(key, *scope);
+                queue.push_back((coord, *scope));
+            }
+        }
+
+        while let Some((coord, scope)) = queue.pop_front() {
+            for child in self.children(coord) {
+                let effective = scope.transitive_scope(child.scope).unwrap_or(child.scope);
+                let key = child.coordinate.to_string();
+                if scopes
+                    .get(&key)
+                    .is_none_or(|existing| effective < *existing)
+                {
+                    scopes.insert(key, effective);
+                    queue.push_back((&child.coordinate, effective));
+                }
+            }
+        }
+        scopes
+    }
+
+    /// Every node ordered so that each comes after all of its dependencies
+    ///
+    /// Uses Kahn's algorithm over edges between nodes in the graph; among
+    /// nodes that are ready at the same time, the smallest coordinate string
+    /// goes first, so the order is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// * `CycleError` - If some nodes depend on each other in a cycle
+    pub fn topological_order(&self) -> std::result::Result<Vec<Coordinate>, CycleError> {
+        let mut pending: HashMap<&str, usize> = HashMap::new();
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (key, node) in &self.nodes {
+            let deps: BTreeSet<&str> = node
+                .dependencies
+                .iter()
+                .filter_map(|dep| self.nodes.get_key_value(&dep.to_string()))
+                .map(|(dep_key, _)| dep_key.as_str())
+                .collect();
+            pending.insert(key, deps.len());
+            for dep in deps {
+                dependents.entry(dep).or_default().push(key);
+            }
+        }
+
+        let mut ready: BTreeSet<&str> = pending
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(key, _)| *key)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(key) = ready.pop_first() {
+            pending.remove(key);
+            order.push(self.nodes[key].coordinate.clone());
+            for dependent in dependents.get(key).into_iter().flatten() {
+                let count = pending.get_mut(dependent).expect("dependent is pending");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+
+        match pending.keys().min() {
+            None => Ok(order),
+            Some(start) => Err(self.find_cycle(start, &pending)),
+        }
+    }
+
+    /// Cycle through the nodes left over by [`Self::topological_order`]
+    ///
+    /// Every leftover node still depends on another leftover node, so
+    /// following those edges from `start` must revisit a node.
+    fn find_cycle(&self, start: &str, pending: &HashMap<&str, usize>) -> CycleError {
+        let mut path: Vec<&str> = Vec::new();
+        let mut current = start;
+        loop {
+            if let Some(position) = path.iter().position(|key| *key == current) {
+                let cycle = path[position..]
+                    .iter()
+                    .map(|key| self.nodes[*key].coordinate.clone())
+                    .collect();
+                return CycleError { cycle };
+            }
+            path.push(current);
+            current = self.nodes[current]
+                .dependencies
+                .iter()
+                .filter_map(|dep| pending.get_key_value(dep.to_string().as_str()))
+                .map(|(key, _)| *key)
+                .min()
+                .expect("leftover node depends on another leftover node");
+        }
+    }
+
+    /// Breadth-first traversal starting at `start`
+    ///
+    /// Yields `(node, depth)` pairs where depth is relative to `start` (which
+    /// is yielded first at depth 0). Each node is visited once, at the
+    /// shallowest depth it is reachable from.
+    pub fn bfs_from(&self, start: &Coordinate) -> impl Iterator<Item = (&DependencyNode, usize)> {
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        if let Some(node) = self.nodes.get(&start.to_string()) {
+            visited.insert(start.to_string());
+            queue.push_back((node, 0));
+        }
+
+        std::iter::from_fn(move || {
+            let (node, depth) = queue.pop_front()?;
+            for child in self.children(&node.coordinate) {
+                if visited.insert(child.coordinate.to_string()) {
+                    queue.push_back((child, depth + 1));
+                }
+            }
+            Some((node, depth))
+        })
+    }
+}
+
+fn serialize_sorted<S: Serializer>(
+    nodes: &HashMap<String, DependencyNode>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    nodes
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+/// `groupId:artifactId[:classifier]`; classifier variants are separate artifacts
+pub(crate) fn artifact_key(coord: &Coordinate) -> String {
+    match &coord.classifier {
+        Some(classifier) => format!("{}:{}:{}", coord.group_id, coord.artifact_id, classifier),
+        None => format!("{}:{}", coord.group_id, coord.artifact_id),
+    }
 }
@@ -2,10 +2,20 @@
 
 #![warn(missing_docs)]
 
+mod config;
+mod conflict;
+mod diff;
 mod error;
 mod graph;
+mod lockfile;
 mod resolve;
+mod trace;
 
+pub use config::{ConflictPolicy, ResolverConfig};
+pub use conflict::{VersionConflict, VersionRequest};
+pub use diff::{TreeDiff, VersionChange};
 pub use error::{ResolverError, Result};
-pub use graph::{DependencyGraph, DependencyNode};
+pub use graph::{CycleError, DependencyGraph, DependencyNode, GraphStats};
+pub use lockfile::{LOCKFILE_VERSION, Lockfile};
 pub use resolve::{ResolvedTree, Resolver};
+pub use trace::{MediationReason, ResolutionEvent, ResolutionTrace, SkipReason};
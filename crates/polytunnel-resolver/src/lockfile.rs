@@ -0,0 +1,79 @@
+//! Lockfile (`polytunnel.lock`) recording the resolved dependency tree
+
+use crate::error::Result;
+use crate::resolve::ResolvedTree;
+use polytunnel_maven::{Coordinate, DependencyScope};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Current lockfile format version
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Lockfile contents written next to `polytunnel.toml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Lockfile format version
+    pub version: u32,
+    /// Directly declared dependencies
+    #[serde(default)]
+    pub root: Vec<Coordinate>,
+    /// All resolved dependencies including transitives
+    #[serde(default, rename = "package")]
+    pub packages: Vec<Coordinate>,
+    /// Effective scope of each package, keyed by coordinate
+    ///
+    /// Lets `--frozen` builds split transitives between classpaths without
+    /// the resolution graph. Lockfiles written before scopes were recorded
+    /// have none.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scopes: BTreeMap<String, DependencyScope>,
+}
+
+impl Lockfile {
+    /// Record a resolved tree
+    pub fn from_tree(tree: &ResolvedTree) -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            root: tree.root_dependencies.clone(),
+            packages: tree.all_dependencies.clone(),
+            scopes: BTreeMap::new(),
+        }
+    }
+
+    /// Record the effective scope of each package
+    pub fn with_scopes(mut self, scopes: &HashMap<String, DependencyScope>) -> Self {
+        self.scopes = scopes
+            .iter()
+            .map(|(coord, scope)| (coord.clone(), *scope))
+            .collect();
+        self
+    }
+
+    /// Reconstruct the recorded tree
+    pub fn to_tree(&self) -> ResolvedTree {
+        ResolvedTree {
+            root_dependencies: self.root.clone(),
+            all_dependencies: self.packages.clone(),
+            graph: Default::default(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Load a lockfile, returning `None` when the file does not exist
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        let lockfile = toml::from_str(&content).map_err(polytunnel_core::CoreError::from)?;
+        Ok(Some(lockfile))
+    }
+
+    /// Write the lockfile to disk
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).map_err(polytunnel_core::CoreError::from)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
@@ -1,84 +1,375 @@
 //! Dependency resolution algorithm
 
+use crate::config::{ConflictPolicy, ResolverConfig};
+use crate::conflict::{VersionConflict, VersionRequest};
 use crate::error::{ResolverError, Result};
 use crate::graph::DependencyGraph;
+use crate::trace::{MediationReason, ResolutionEvent, ResolutionTrace, SkipReason};
 use futures::future::{BoxFuture, FutureExt, try_join_all};
-use polytunnel_maven::{Coordinate, MavenClient};
+use polytunnel_maven::{
+    Coordinate, MavenClient, MavenVersion, VersionRange, is_meta_version, is_version_range,
+};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
 
 /// Resolved dependency tree
-#[derive(Debug)]
+///
+/// Serializes (e.g. to JSON) with its graph, so one resolution can be shared
+/// with later jobs and turned back into a resolver with
+/// [`Resolver::from_resolved_tree`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ResolvedTree {
     /// The root-level coordinates that were directly requested
     pub root_dependencies: Vec<Coordinate>,
     /// All resolved coordinates including transitives (deduplicated by GA)
     pub all_dependencies: Vec<Coordinate>,
+    /// Selected versions, declared scopes, and edges of the resolution
+    ///
+    /// Empty for trees read back from a [`crate::Lockfile`].
+    #[serde(default)]
+    pub graph: DependencyGraph,
+    /// Artifacts requested in more than one version, by artifact
+    ///
+    /// Empty for trees read back from a [`crate::Lockfile`].
+    #[serde(default)]
+    pub conflicts: Vec<VersionConflict>,
+}
+
+/// Dependencies to follow, with the scope they were declared in and the
+/// `groupId:artifactId` patterns their `<exclusions>` remove below them
+type TransitiveDeps = Vec<(Coordinate, polytunnel_maven::DependencyScope, Vec<String>)>;
+
+/// Effective POMs by coordinate, kept across the passes of one resolution
+type PomCache = Arc<Mutex<HashMap<String, polytunnel_maven::Pom>>>;
+
+/// Shared state threaded through concurrent recursive resolution
+#[derive(Clone)]
+struct ResolveContext {
+    client: MavenClient,
+    /// Effective POMs fetched so far, so later passes do not fetch them again
+    poms: PomCache,
+    /// Forced versions by `groupId:artifactId`: root versions, then managed versions
+    overrides: Arc<HashMap<String, String>>,
+    /// `groupId:artifactId` of the root dependencies (their overrides are root versions)
+    root_keys: Arc<HashSet<String>>,
+    /// Selected coordinate by visited key
+    visited: Arc<Mutex<HashMap<String, Coordinate>>>,
+    graph: Arc<Mutex<DependencyGraph>>,
+    trace: Arc<Mutex<ResolutionTrace>>,
+    /// `groupId:artifactId` patterns excluded from the current subtree
+    exclusions: Arc<HashSet<String>>,
+    /// Whether the artifact being resolved keeps its optional dependencies
+    include_optional: bool,
+    /// Follow optional and provided dependencies at every depth
+    full: bool,
+    conflict_policy: ConflictPolicy,
+    /// Versions chosen by mediation in an earlier pass, by visited key
+    mediated: Arc<HashMap<String, String>>,
+    /// Every version requested in this pass, by visited key
+    requests: Arc<Mutex<Requests>>,
+    /// Permits bounding the number of POM fetches in flight
+    fetch_permits: Arc<Semaphore>,
 }
 
+/// Requests for each artifact by visited key
+type Requests = HashMap<String, Vec<Request>>;
+
+/// A version of an artifact asked for by a root or a POM
+#[derive(Debug, Clone)]
+struct Request {
+    /// Index of the root and then of the dependency at each level
+    ///
+    /// Ordering positions by length and then lexicographically is
+    /// breadth-first declaration order.
+    position: Vec<usize>,
+    /// Version asked for
+    version: String,
+    /// Coordinates from the root down to the requester
+    path: Vec<String>,
+    /// Whether a root declaration or dependency management replaced the version
+    forced: bool,
+}
+
+impl Request {
+    /// Breadth-first declaration order
+    fn cmp_position(&self, other: &Self) -> std::cmp::Ordering {
+        (self.position.len(), &self.position).cmp(&(other.position.len(), &other.position))
+    }
+}
+
+/// Outcome of one resolution pass
+struct Pass {
+    /// Resolved coordinates, possibly repeated
+    dependencies: Vec<Coordinate>,
+    /// Every request, by visited key
+    requests: Requests,
+    /// Version selected for each visited key
+    selected: HashMap<String, String>,
+}
+
+/// Passes after which mediation stops re-resolving with its chosen versions
+const MAX_MEDIATION_PASSES: usize = 8;
+
 /// Dependency resolver
 pub struct Resolver {
     client: MavenClient,
+    config: ResolverConfig,
     /// Resolved dependency graph, populated after calling [`Resolver::resolve`]
     pub graph: DependencyGraph,
+    /// Decisions taken by the last [`Resolver::resolve`] call
+    pub trace: ResolutionTrace,
 }
 
 impl Resolver {
     /// Create a resolver connected to Maven Central
     pub fn new() -> Self {
-        Self {
-            client: MavenClient::new(),
-            graph: DependencyGraph::new(),
-        }
+        Self::with_client(MavenClient::new())
     }
 
     /// Create a resolver with a pre-configured [`MavenClient`]
     pub fn with_client(client: MavenClient) -> Self {
+        Self::with_config(client, ResolverConfig::default())
+    }
+
+    /// Create a resolver with a pre-configured [`MavenClient`] and settings
+    pub fn with_config(client: MavenClient, config: ResolverConfig) -> Self {
         Self {
             client,
+            config,
             graph: DependencyGraph::new(),
+            trace: ResolutionTrace::new(),
+        }
+    }
+
+    /// Settings used by [`Resolver::resolve`]
+    pub fn config(&self) -> &ResolverConfig {
+        &self.config
+    }
+
+    /// Recreate the state of the resolver that produced `tree`
+    ///
+    /// The graph is restored from `tree` without fetching anything, so graph
+    /// queries (stats, scopes, traversal) work on a resolution done
+    /// elsewhere. The trace of the original resolution is not kept.
+    pub fn from_resolved_tree(client: MavenClient, tree: &ResolvedTree) -> Self {
+        Self {
+            graph: tree.graph.clone(),
+            ..Self::with_client(client)
         }
     }
 
+    /// Exclude transitives from the subtrees of specific root dependencies
+    ///
+    /// Keys are the root's `groupId:artifactId`; values are `groupId:artifactId`
+    /// patterns (either part may be `*`) removed anywhere below that root.
+    pub fn with_exclusions(mut self, exclusions: HashMap<String, Vec<String>>) -> Self {
+        self.config.exclusions = exclusions;
+        self
+    }
+
+    /// Resolve the optional dependencies declared by specific root dependencies
+    ///
+    /// Keys are the root's `groupId:artifactId`. Only the root's own optional
+    /// dependencies are promoted; optional dependencies further down stay skipped.
+    pub fn with_optional_roots(mut self, roots: HashSet<String>) -> Self {
+        self.config.optional_roots = roots;
+        self
+    }
+
+    /// Pin versions of artifacts that may only appear transitively
+    ///
+    /// Keys are `groupId:artifactId`. A managed version replaces whatever
+    /// version a POM requests and fills in versions a POM leaves out; the
+    /// version of a directly requested root still takes precedence.
+    pub fn with_managed_versions(mut self, versions: HashMap<String, String>) -> Self {
+        self.config.managed_versions = versions;
+        self
+    }
+
+    /// Abort [`Resolver::resolve`] with `ResolverError::Timeout` once `timeout` elapses
+    ///
+    /// Outstanding fetches are cancelled; the graph keeps whatever was resolved.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// Resolve the fullest possible tree, for analysis and IDE indexing
+    ///
+    /// Optional dependencies are followed at every depth and `provided`
+    /// dependencies of transitive artifacts are kept instead of dropped.
+    /// `test` and `system` dependencies of transitive artifacts stay skipped.
+    pub fn with_full_closure(mut self, full: bool) -> Self {
+        self.config.full = full;
+        self
+    }
+
+    /// Fetch at most `limit` POMs (with their parents) at once
+    ///
+    /// Defaults to `[http] resolve_concurrency`'s default; a limit of 0 is treated as 1.
+    pub fn with_concurrency(mut self, limit: usize) -> Self {
+        self.config.concurrency = limit.max(1);
+        self
+    }
+
+    /// Handle artifacts requested in more than one version according to `policy`
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.config.conflict_policy = policy;
+        self
+    }
+
     /// Resolve all dependencies starting from root dependencies
+    ///
+    /// Roots declared with a version range (`[1.2.3,2.0.0)`) are first pinned
+    /// to the newest matching version in `maven-metadata.xml`, and `LATEST`
+    /// or `RELEASE` to the version the metadata names, so the returned tree
+    /// only holds concrete versions. POM dependencies are pinned the same
+    /// way; one that cannot be is skipped with a warning.
+    /// A bare version stays a soft requirement that mediation may override.
+    ///
+    /// # Errors
+    ///
+    /// * `ResolverError::InvalidVersionRange` - If a root's range cannot be parsed
+    /// * `ResolverError::NoVersionInRange` - If no published version matches a root's range or metaversion
     pub async fn resolve(&mut self, deps: &[Coordinate]) -> Result<ResolvedTree> {
-        // Build map of overrides from root dependencies (G:A -> Version)
-        let mut overrides = HashMap::new();
+        let deps = &self.select_range_versions(deps).await?;
+        let deadline = self
+            .config
+            .timeout
+            .map(|limit| tokio::time::Instant::now() + limit);
+        let initial_graph = std::mem::take(&mut self.graph);
+
+        // Concurrent traversal selects whichever version is reached first, so
+        // the versions mediation picks are pinned and the tree resolved again
+        // until the selection is stable
+        let mut mediated = HashMap::new();
+        let poms = PomCache::default();
+        let mut passes = 0;
+        let (all_deps, conflicts) = loop {
+            passes += 1;
+            let pass = self
+                .resolve_pass(deps, initial_graph.clone(), &mediated, &poms, deadline)
+                .await?;
+            let pins = mediation_pins(self.config.conflict_policy, &pass);
+            if pins.is_empty() || passes == MAX_MEDIATION_PASSES {
+                let conflicts = conflicts(&pass);
+                break (pass.dependencies, conflicts);
+            }
+            mediated.extend(pins);
+        };
+
+        // Dedup all_dependencies based on GA or GAV?
+        // Usually we want the exact resolved versions.
+        // Simple dedup:
+        let mut unique_deps = Vec::new();
+        let mut seen = HashSet::new();
+        for dep in all_deps {
+            if seen.insert(dep.to_string()) {
+                unique_deps.push(dep);
+            }
+        }
+
+        // Completion order of the concurrent fetches is arbitrary; order by
+        // (depth, coordinate) so the classpath is the same on every run
+        let depths = self.shortest_depths(deps);
+        unique_deps.sort_by_cached_key(|dep| {
+            let key = dep.to_string();
+            let depth = depths
+                .get(&key)
+                .copied()
+                .or_else(|| self.graph.get(&key).map(|node| node.depth))
+                .unwrap_or(usize::MAX);
+            (depth, key)
+        });
+
+        Ok(ResolvedTree {
+            root_dependencies: deps.to_vec(),
+            all_dependencies: unique_deps,
+            graph: self.graph.clone(),
+            conflicts,
+        })
+    }
+
+    /// Resolve `deps` once, starting from `graph`
+    ///
+    /// Records every version requested along the way and the version selected
+    /// for each artifact. POMs already in `poms` are not fetched again.
+    async fn resolve_pass(
+        &mut self,
+        deps: &[Coordinate],
+        graph: DependencyGraph,
+        mediated: &HashMap<String, String>,
+        poms: &PomCache,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Pass> {
+        // Build map of overrides from managed versions and root dependencies (G:A -> Version);
+        // roots are inserted last so a direct declaration beats management
+        let mut overrides = self.config.managed_versions.clone();
         for dep in deps {
             let key = format!("{}:{}", dep.group_id, dep.artifact_id);
             overrides.insert(key, dep.version.clone());
         }
 
         let overrides = Arc::new(overrides);
+        let root_keys: Arc<HashSet<String>> = Arc::new(
+            deps.iter()
+                .map(|dep| format!("{}:{}", dep.group_id, dep.artifact_id))
+                .collect(),
+        );
         let client = self.client.clone();
+        let mediated = Arc::new(mediated.clone());
 
         // Shared state for visited nodes to prevent cycles and redundant work
-        let visited = Arc::new(Mutex::new(HashSet::new()));
+        let visited = Arc::new(Mutex::new(HashMap::new()));
+        let requests = Arc::new(Mutex::new(Requests::new()));
+        let trace = Arc::new(Mutex::new(ResolutionTrace::new()));
         // Shared graph to populate (protected by mutex)
-        let graph = Arc::new(Mutex::new(std::mem::take(&mut self.graph)));
+        let graph = Arc::new(Mutex::new(graph));
+        let fetch_permits = Arc::new(Semaphore::new(self.config.concurrency.max(1)));
 
         // Start concurrent resolution for all root dependencies
         let mut futures = Vec::new();
-        for dep in deps {
+        for (i, dep) in deps.iter().enumerate() {
+            let ga = format!("{}:{}", dep.group_id, dep.artifact_id);
+            let exclusions = self
+                .config
+                .exclusions
+                .get(&ga)
+                .map(|e| e.iter().cloned().collect())
+                .unwrap_or_default();
             futures.push(Self::resolve_recursive(
-                client.clone(),
+                ResolveContext {
+                    client: client.clone(),
+                    poms: poms.clone(),
+                    overrides: overrides.clone(),
+                    root_keys: root_keys.clone(),
+                    visited: visited.clone(),
+                    graph: graph.clone(),
+                    trace: trace.clone(),
+                    exclusions: Arc::new(exclusions),
+                    include_optional: self.config.optional_roots.contains(&ga),
+                    full: self.config.full,
+                    conflict_policy: self.config.conflict_policy,
+                    mediated: mediated.clone(),
+                    requests: requests.clone(),
+                    fetch_permits: fetch_permits.clone(),
+                },
                 dep.clone(),
-                0,
-                overrides.clone(),
-                visited.clone(),
-                graph.clone(),
+                vec![i],
+                polytunnel_maven::DependencyScope::Compile,
+                Vec::new(),
             ));
         }
 
-        let results = try_join_all(futures).await?;
+        let results = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, try_join_all(futures)).await,
+            None => Ok(try_join_all(futures).await),
+        };
 
-        // Flatten results
-        let mut all_deps = Vec::new();
-        for res in results {
-            all_deps.extend(res);
-        }
-
-        // Restore graph
+        // Restore graph (outstanding futures were dropped on timeout)
         let final_graph = Arc::try_unwrap(graph)
             .map_err(|_| ResolverError::DependencyNotFound {
                 coordinate: "internal: graph arc still referenced".to_string(),
@@ -88,24 +379,109 @@ impl Resolver {
                 coordinate: "internal: graph mutex poisoned".to_string(),
             })?;
         self.graph = final_graph;
+        self.trace = trace
+            .lock()
+            .map(|mut t| std::mem::take(&mut *t))
+            .unwrap_or_default();
 
-        // Dedup all_dependencies based on GA or GAV?
-        // Usually we want the exact resolved versions.
-        // Simple dedup:
-        let mut unique_deps = Vec::new();
-        let mut seen = HashSet::new();
-        for dep in all_deps {
-            if seen.insert(dep.to_string()) {
-                unique_deps.push(dep);
+        let results = match results {
+            Ok(results) => results?,
+            Err(_) => {
+                return Err(ResolverError::Timeout {
+                    seconds: self.config.timeout.unwrap_or_default().as_secs(),
+                    resolved: self.graph.nodes().count(),
+                });
+            }
+        };
+
+        let requests = requests.lock().map(|r| r.clone()).unwrap_or_default();
+        let selected = visited
+            .lock()
+            .map(|v| {
+                v.iter()
+                    .map(|(key, coord)| (key.clone(), coord.version.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Pass {
+            dependencies: results.into_iter().flatten().collect(),
+            requests,
+            selected,
+        })
+    }
+
+    /// Replace version ranges and metaversions of root coordinates with concrete versions
+    async fn select_range_versions(&self, deps: &[Coordinate]) -> Result<Vec<Coordinate>> {
+        let mut selected = Vec::with_capacity(deps.len());
+        for dep in deps {
+            if needs_pinning(&dep.version) {
+                selected.push(Self::pin_version(&self.client, dep).await?);
+            } else {
+                selected.push(dep.clone());
             }
         }
+        Ok(selected)
+    }
+
+    /// `dep` with its version range or `LATEST`/`RELEASE` replaced by a published version
+    ///
+    /// A range selects the newest published version inside it. Both come
+    /// from the artifact's `maven-metadata.xml`.
+    async fn pin_version(client: &MavenClient, dep: &Coordinate) -> Result<Coordinate> {
+        let artifact = format!("{}:{}", dep.group_id, dep.artifact_id);
+        if is_meta_version(&dep.version) {
+            let version = client
+                .fetch_metadata(&dep.group_id, &dep.artifact_id)
+                .await?
+                .meta_version(&dep.version)
+                .ok_or_else(|| ResolverError::NoVersionInRange {
+                    artifact,
+                    range: dep.version.clone(),
+                })?;
+            return Ok(Coordinate {
+                version,
+                ..dep.clone()
+            });
+        }
 
-        Ok(ResolvedTree {
-            root_dependencies: deps.to_vec(),
-            all_dependencies: unique_deps,
+        let range = VersionRange::parse(&dep.version).ok_or_else(|| {
+            ResolverError::InvalidVersionRange {
+                artifact: artifact.clone(),
+                range: dep.version.clone(),
+            }
+        })?;
+        let versions: Vec<MavenVersion> = client
+            .list_versions(&dep.group_id, &dep.artifact_id)
+            .await?
+            .iter()
+            .map(|version| MavenVersion::parse(version))
+            .collect();
+        let version = range
+            .select(&versions)
+            .ok_or_else(|| ResolverError::NoVersionInRange {
+                artifact,
+                range: dep.version.clone(),
+            })?;
+        Ok(Coordinate {
+            version: version.as_str().to_string(),
+            ..dep.clone()
         })
     }
 
+    /// Shallowest distance of every reachable node from any root, keyed by coordinate
+    fn shortest_depths(&self, roots: &[Coordinate]) -> HashMap<String, usize> {
+        let mut depths: HashMap<String, usize> = HashMap::new();
+        for root in roots {
+            for (node, depth) in self.graph.bfs_from(root) {
+                depths
+                    .entry(node.coordinate.to_string())
+                    .and_modify(|d| *d = (*d).min(depth))
+                    .or_insert(depth);
+            }
+        }
+        depths
+    }
+
     fn apply_override(coord: &Coordinate, overrides: &HashMap<String, String>) -> Coordinate {
         let ga = format!("{}:{}", coord.group_id, coord.artifact_id);
         let mut new_coord = coord.clone();
@@ -117,13 +493,27 @@ impl Resolver {
         new_coord
     }
 
-    // Helper to fetch effective POM (recursive parent resolution - stays sequential/linear per artifact)
+    /// Append events to the shared trace; a poisoned lock only loses the events
+    fn record(trace: &Mutex<ResolutionTrace>, events: impl IntoIterator<Item = ResolutionEvent>) {
+        if let Ok(mut trace) = trace.lock() {
+            for event in events {
+                trace.record(event);
+            }
+        }
+    }
+
+    // Helper to fetch effective POM (recursive parent and BOM import resolution - stays sequential/linear per artifact)
     fn fetch_effective_pom(
         client: MavenClient,
+        poms: PomCache,
         coord: Coordinate,
         depth: usize,
     ) -> BoxFuture<'static, Result<polytunnel_maven::Pom>> {
         async move {
+            let key = coord.to_string();
+            if let Some(pom) = poms.lock().ok().and_then(|poms| poms.get(&key).cloned()) {
+                return Ok(pom);
+            }
             let mut pom = client.fetch_pom(&coord).await?;
 
             if depth > 10 {
@@ -131,52 +521,173 @@ impl Resolver {
             }
 
             if let Some(parent_coord) = &pom.parent {
-                match Self::fetch_effective_pom(client, parent_coord.clone(), depth + 1).await {
+                match Self::fetch_effective_pom(
+                    client.clone(),
+                    poms.clone(),
+                    parent_coord.clone(),
+                    depth + 1,
+                )
+                .await
+                {
                     Ok(parent_pom) => {
                         pom.merge_dependency_management(parent_pom.dependency_management);
                         pom.merge_properties(&parent_pom.properties);
                     }
+                    Err(e) if e.offline_resource().is_some() => return Err(e),
                     Err(e) => {
                         eprintln!("Warning: Failed to resolve parent {}: {}", parent_coord, e);
                     }
                 }
             }
+
+            // Replace `import`-scoped BOMs with their managed dependencies; entries
+            // declared here or inherited come first, so they win over imported ones
+            let (imports, mut managed): (Vec<_>, Vec<_>) =
+                std::mem::take(&mut pom.dependency_management)
+                    .into_iter()
+                    .partition(|dm| dm.scope == polytunnel_maven::DependencyScope::Import);
+            for import in imports {
+                let Some(version) = &import.version else {
+                    eprintln!(
+                        "Warning: Skipping BOM {}:{} without a version",
+                        import.group_id, import.artifact_id
+                    );
+                    continue;
+                };
+                let bom_coord = Coordinate::new(&import.group_id, &import.artifact_id, version);
+                match Self::fetch_effective_pom(
+                    client.clone(),
+                    poms.clone(),
+                    bom_coord.clone(),
+                    depth + 1,
+                )
+                .await
+                {
+                    Ok(bom) if bom.packaging == "pom" => managed.extend(bom.dependency_management),
+                    Ok(bom) => eprintln!(
+                        "Warning: Skipping BOM {} with packaging `{}`",
+                        bom_coord, bom.packaging
+                    ),
+                    Err(e) if e.offline_resource().is_some() => return Err(e),
+                    Err(e) => eprintln!("Warning: Failed to import BOM {}: {}", bom_coord, e),
+                }
+            }
+            pom.dependency_management = managed;
+            if let Ok(mut poms) = poms.lock() {
+                poms.insert(key, pom.clone());
+            }
             Ok(pom)
         }
         .boxed()
     }
 
-    fn determine_transitive_deps(pom: &polytunnel_maven::Pom) -> Vec<Coordinate> {
-        pom.dependencies
-            .iter()
-            .filter(|d| {
-                matches!(
-                    d.scope,
-                    polytunnel_maven::DependencyScope::Compile
-                        | polytunnel_maven::DependencyScope::Provided
-                )
-            })
-            .filter(|d| !d.optional)
-            .filter_map(|d| {
-                d.version
-                    .as_ref()
-                    .map(|v| Coordinate::new(&d.group_id, &d.artifact_id, v))
-            })
-            .collect()
+    /// Dependencies of `pom` to follow, plus the reasons the others were skipped
+    fn determine_transitive_deps(
+        pom: &polytunnel_maven::Pom,
+        include_optional: bool,
+        full: bool,
+        overrides: &HashMap<String, String>,
+    ) -> (TransitiveDeps, Vec<(String, SkipReason)>) {
+        let mut transitive = Vec::new();
+        let mut skipped = Vec::new();
+        for d in &pom.dependencies {
+            let ga = format!("{}:{}", d.group_id, d.artifact_id);
+            let kept_provided = full && d.scope == polytunnel_maven::DependencyScope::Provided;
+            if !d.scope.is_transitive() && !kept_provided {
+                skipped.push((ga, SkipReason::Scope(d.scope)));
+                continue;
+            }
+            if d.optional && !include_optional && !full {
+                skipped.push((ga, SkipReason::Optional));
+                continue;
+            }
+            match d.version.as_ref().or_else(|| overrides.get(&ga)) {
+                Some(v) => {
+                    let mut coord = Coordinate::new(&d.group_id, &d.artifact_id, v);
+                    coord.classifier = d.classifier.clone();
+                    let exclusions = d
+                        .exclusions
+                        .iter()
+                        .map(|e| format!("{}:{}", e.group_id, e.artifact_id))
+                        .collect();
+                    transitive.push((coord, d.scope, exclusions));
+                }
+                None => skipped.push((ga, SkipReason::MissingVersion)),
+            }
+        }
+        (transitive, skipped)
     }
 
     fn resolve_recursive(
-        client: MavenClient,
+        ctx: ResolveContext,
         requested_coord: Coordinate,
-        depth: usize,
-        overrides: Arc<HashMap<String, String>>,
-        visited: Arc<Mutex<HashSet<String>>>,
-        graph: Arc<Mutex<DependencyGraph>>,
+        position: Vec<usize>,
+        scope: polytunnel_maven::DependencyScope,
+        chain: Vec<String>,
     ) -> BoxFuture<'static, Result<Vec<Coordinate>>> {
         async move {
-            let coord = Self::apply_override(&requested_coord, &overrides);
-            // Use GA (groupId:artifactId) as key for "nearest wins" - first version wins
-            let ga_key = format!("{}:{}", coord.group_id, coord.artifact_id);
+            let ResolveContext {
+                client,
+                poms,
+                overrides,
+                root_keys,
+                visited,
+                graph,
+                trace,
+                exclusions,
+                include_optional,
+                full,
+                conflict_policy,
+                mediated,
+                requests,
+                fetch_permits,
+            } = ctx;
+            let depth = position.len() - 1;
+            // Use GA (groupId:artifactId) as key for version mediation.
+            // Classifier variants (`natives-linux`, ...) are separate artifacts of the GA.
+            let ga_key = crate::graph::artifact_key(&requested_coord);
+            let mut coord = Self::apply_override(&requested_coord, &overrides);
+            let forced = coord.version != requested_coord.version;
+            if let Ok(mut requests) = requests.lock() {
+                requests.entry(ga_key.clone()).or_default().push(Request {
+                    position: position.clone(),
+                    version: requested_coord.version.clone(),
+                    path: chain.clone(),
+                    forced,
+                });
+            }
+            if forced {
+                let ga = format!("{}:{}", coord.group_id, coord.artifact_id);
+                let reason = if root_keys.contains(&ga) {
+                    MediationReason::RootVersion
+                } else {
+                    MediationReason::ManagedVersion
+                };
+                Self::record(
+                    &trace,
+                    [ResolutionEvent::Mediated {
+                        loser: requested_coord.clone(),
+                        winner: coord.clone(),
+                        reason,
+                    }],
+                );
+            } else if let Some(version) = mediated.get(&ga_key)
+                && *version != coord.version
+            {
+                coord.version = version.clone();
+                let reason = match conflict_policy {
+                    ConflictPolicy::Newest => MediationReason::NewerVersion,
+                    _ => MediationReason::AlreadySelected,
+                };
+                Self::record(
+                    &trace,
+                    [ResolutionEvent::Mediated {
+                        loser: requested_coord.clone(),
+                        winner: coord.clone(),
+                        reason,
+                    }],
+                );
+            }
 
             // Check visited by GA - only process first encountered version
             {
@@ -185,17 +696,97 @@ impl Resolver {
                     .map_err(|_| ResolverError::DependencyNotFound {
                         coordinate: "internal: visited mutex poisoned".to_string(),
                     })?;
-                if v.contains(&ga_key) {
+                if let Some(selected) = v.get(&ga_key) {
+                    if selected.version != coord.version && conflict_policy == ConflictPolicy::Fail
+                    {
+                        let mut versions = vec![selected.version.clone(), coord.version.clone()];
+                        versions.sort();
+                        return Err(ResolverError::VersionConflict {
+                            artifact: ga_key,
+                            versions,
+                        });
+                    }
+                    if selected.version != coord.version {
+                        Self::record(
+                            &trace,
+                            [ResolutionEvent::Mediated {
+                                loser: coord.clone(),
+                                winner: selected.clone(),
+                                reason: MediationReason::AlreadySelected,
+                            }],
+                        );
+                    }
                     return Ok(Vec::new());
                 }
-                v.insert(ga_key.clone());
+                v.insert(ga_key.clone(), coord.clone());
             }
 
-            // Fetch POM
-            let mut pom = Self::fetch_effective_pom(client.clone(), coord.clone(), 0).await?;
+            // Path from the requested root to this artifact, for error context
+            let mut chain = chain;
+            chain.push(coord.to_string());
+
+            // Fetch POM; the permit is released before the transitives are resolved
+            let permit = fetch_permits.acquire().await.ok();
+            let pom =
+                Self::fetch_effective_pom(client.clone(), poms.clone(), coord.clone(), 0).await;
+            drop(permit);
+            let mut pom = pom.map_err(|e| match e {
+                ResolverError::Maven(source) => ResolverError::FetchFailed {
+                    coordinate: coord.to_string(),
+                    chain: chain.clone(),
+                    source: Box::new(source),
+                },
+                other => other,
+            })?;
             pom.fill_missing_versions();
 
-            let transitive = Self::determine_transitive_deps(&pom);
+            let (candidates, skipped) =
+                Self::determine_transitive_deps(&pom, include_optional, full, &overrides);
+            let mut events = vec![ResolutionEvent::PomFetched {
+                coordinate: coord.clone(),
+                depth,
+            }];
+            events.extend(skipped.into_iter().map(|(dependency, reason)| {
+                ResolutionEvent::Skipped {
+                    dependency,
+                    parent: coord.clone(),
+                    reason,
+                }
+            }));
+            let mut transitive = Vec::new();
+            for (candidate, candidate_scope, candidate_exclusions) in candidates {
+                match matching_exclusion(&exclusions, &candidate) {
+                    Some(pattern) => events.push(ResolutionEvent::Excluded {
+                        coordinate: candidate,
+                        parent: coord.clone(),
+                        pattern: pattern.clone(),
+                    }),
+                    None => transitive.push((candidate, candidate_scope, candidate_exclusions)),
+                }
+            }
+            Self::record(&trace, events);
+
+            // POMs may declare ranges (`[1.0,2.0)`) or `LATEST`/`RELEASE`; pin them before
+            // they become graph edges. Managed versions replace them anyway, so those are left alone.
+            let mut pinned = Vec::with_capacity(transitive.len());
+            for (candidate, candidate_scope, candidate_exclusions) in transitive {
+                let ga = format!("{}:{}", candidate.group_id, candidate.artifact_id);
+                if !needs_pinning(&candidate.version) || overrides.contains_key(&ga) {
+                    pinned.push((candidate, candidate_scope, candidate_exclusions));
+                    continue;
+                }
+                let permit = fetch_permits.acquire().await.ok();
+                let candidate = Self::pin_version(&client, &candidate).await;
+                drop(permit);
+                match candidate {
+                    Ok(candidate) => {
+                        pinned.push((candidate, candidate_scope, candidate_exclusions))
+                    }
+                    Err(e) if e.offline_resource().is_some() => return Err(e),
+                    Err(e) => eprintln!("Warning: {}", e),
+                }
+            }
+            let transitive = pinned;
 
             // Update graph
             {
@@ -204,41 +795,65 @@ impl Resolver {
                     .map_err(|_| ResolverError::DependencyNotFound {
                         coordinate: "internal: graph mutex poisoned".to_string(),
                     })?;
-                g.add_node(coord.clone(), transitive.clone(), depth);
+                g.add_node_with_scope(
+                    coord.clone(),
+                    transitive.iter().map(|(c, _, _)| c.clone()).collect(),
+                    depth,
+                    scope,
+                );
             }
 
             let mut my_deps = Vec::new();
-            if pom.packaging != "pom" {
+            // `pom` packaging has no artifact; jar-like packagings (`bundle`, ...) download as `.jar`
+            if pom.artifact_extension().is_some() {
                 my_deps.push(coord.clone());
             }
 
             // Concurrent transitive resolution
             let mut futures: Vec<BoxFuture<'static, Result<Vec<Coordinate>>>> = Vec::new();
-            for trans_dep in transitive {
-                let client = client.clone();
-                let overrides = overrides.clone();
-                let visited = visited.clone();
-                let graph = graph.clone();
-                let dep_clone = trans_dep.clone();
+            for (i, (trans_dep, trans_scope, trans_exclusions)) in
+                transitive.into_iter().enumerate()
+            {
+                // A dependency's own `<exclusions>` apply to its whole subtree
+                let exclusions = if trans_exclusions.is_empty() {
+                    exclusions.clone()
+                } else {
+                    let mut merged = (*exclusions).clone();
+                    merged.extend(trans_exclusions);
+                    Arc::new(merged)
+                };
+                let ctx = ResolveContext {
+                    client: client.clone(),
+                    poms: poms.clone(),
+                    overrides: overrides.clone(),
+                    root_keys: root_keys.clone(),
+                    visited: visited.clone(),
+                    graph: graph.clone(),
+                    trace: trace.clone(),
+                    exclusions,
+                    include_optional: false,
+                    full,
+                    conflict_policy,
+                    mediated: mediated.clone(),
+                    requests: requests.clone(),
+                    fetch_permits: fetch_permits.clone(),
+                };
+                let chain = chain.clone();
+                let mut position = position.clone();
+                position.push(i);
 
                 futures.push(
                     async move {
-                        match Self::resolve_recursive(
-                            client,
-                            trans_dep,
-                            depth + 1,
-                            overrides,
-                            visited,
-                            graph,
-                        )
-                        .await
+                        match Self::resolve_recursive(ctx, trans_dep, position, trans_scope, chain)
+                            .await
                         {
                             Ok(deps) => Ok(deps),
+                            // Unlike a missing transitive, a conflict under `ConflictPolicy::Fail`
+                            // or an artifact missing from the cache when offline is fatal
+                            Err(e @ ResolverError::VersionConflict { .. }) => Err(e),
+                            Err(e) if e.offline_resource().is_some() => Err(e),
                             Err(e) => {
-                                eprintln!(
-                                    "Warning: Failed to resolve dependency {}: {}",
-                                    dep_clone, e
-                                );
+                                eprintln!("Warning: {}", e);
                                 Ok(Vec::new())
                             }
                         }
@@ -259,6 +874,83 @@ impl Resolver {
     }
 }
 
+/// Version `policy` selects among `requested`, or `None` if it does not mediate
+///
+/// Versions forced by a root declaration or dependency management take no part.
+fn mediate(policy: ConflictPolicy, requested: &[Request]) -> Option<&str> {
+    let requested = requested.iter().filter(|request| !request.forced);
+    let request = match policy {
+        ConflictPolicy::Nearest => requested.min_by(|a, b| a.cmp_position(b))?,
+        ConflictPolicy::Newest => {
+            requested.max_by_key(|request| MavenVersion::parse(&request.version))?
+        }
+        ConflictPolicy::Fail => return None,
+    };
+    Some(&request.version)
+}
+
+/// Versions `policy` selects where `pass` selected another, by visited key
+fn mediation_pins(policy: ConflictPolicy, pass: &Pass) -> HashMap<String, String> {
+    pass.requests
+        .iter()
+        .filter_map(|(key, requested)| {
+            let winner = mediate(policy, requested)?;
+            let selected = pass.selected.get(key)?;
+            (selected != winner).then(|| (key.clone(), winner.to_string()))
+        })
+        .collect()
+}
+
+/// Artifacts `pass` saw requested in more than one version, sorted by artifact
+fn conflicts(pass: &Pass) -> Vec<VersionConflict> {
+    let mut conflicts: Vec<VersionConflict> = pass
+        .requests
+        .iter()
+        .filter_map(|(artifact, requested)| {
+            let selected = pass.selected.get(artifact)?;
+            let mut requested = requested.clone();
+            requested.sort_by(Request::cmp_position);
+            let mut rejected: Vec<String> = Vec::new();
+            for request in &requested {
+                if request.version != *selected && !rejected.contains(&request.version) {
+                    rejected.push(request.version.clone());
+                }
+            }
+            (!rejected.is_empty()).then(|| VersionConflict {
+                artifact: artifact.clone(),
+                selected: selected.clone(),
+                rejected,
+                requests: requested
+                    .into_iter()
+                    .map(|request| VersionRequest {
+                        version: request.version,
+                        path: request.path,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.artifact.cmp(&b.artifact));
+    conflicts
+}
+
+/// Whether `version` must be replaced with a published version before resolving
+fn needs_pinning(version: &str) -> bool {
+    is_version_range(version) || is_meta_version(version)
+}
+
+/// First `groupId:artifactId` pattern matching `coord` (`*` matches any part)
+fn matching_exclusion<'a>(
+    exclusions: &'a HashSet<String>,
+    coord: &Coordinate,
+) -> Option<&'a String> {
+    exclusions.iter().find(|pattern| {
+        let (group, artifact) = pattern.split_once(':').unwrap_or((pattern, "*"));
+        (group == "*" || group == coord.group_id)
+            && (artifact == "*" || artifact == coord.artifact_id)
+    })
+}
+
 impl Default for Resolver {
     fn default() -> Self {
         Self::new()
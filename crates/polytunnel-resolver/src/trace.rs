@@ -0,0 +1,153 @@
+//! Structured record of the decisions made during resolution
+
+use polytunnel_maven::{Coordinate, DependencyScope};
+use std::fmt;
+
+/// Why a requested version lost to another version of the same artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediationReason {
+    /// The artifact is a root dependency; its declared version is forced
+    RootVersion,
+    /// The version is pinned in `[dependency-management]`
+    ManagedVersion,
+    /// Another version of the artifact was reached first (nearest wins)
+    AlreadySelected,
+    /// A newer version of the artifact was requested elsewhere (newest wins)
+    NewerVersion,
+}
+
+impl fmt::Display for MediationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MediationReason::RootVersion => "root dependency version",
+            MediationReason::ManagedVersion => "managed version",
+            MediationReason::AlreadySelected => "nearer version already selected",
+            MediationReason::NewerVersion => "newer version requested elsewhere",
+        })
+    }
+}
+
+/// Why a dependency declared in a POM was not followed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Declared `<optional>true</optional>`
+    Optional,
+    /// Declared in a scope that is not transitive (`test`, `provided`, ...)
+    Scope(DependencyScope),
+    /// No version in the POM, its parents, or `[dependency-management]`
+    MissingVersion,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Optional => f.write_str("optional"),
+            SkipReason::Scope(scope) => {
+                write!(f, "{} scope", format!("{:?}", scope).to_lowercase())
+            }
+            SkipReason::MissingVersion => f.write_str("no version"),
+        }
+    }
+}
+
+/// One decision taken by [`Resolver::resolve`](crate::Resolver::resolve)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionEvent {
+    /// The (effective) POM of an artifact was fetched
+    PomFetched {
+        /// Artifact whose POM was fetched
+        coordinate: Coordinate,
+        /// Resolution depth (0 = root dependency)
+        depth: usize,
+    },
+    /// A requested version was replaced by another version of the same artifact
+    Mediated {
+        /// Version that was requested
+        loser: Coordinate,
+        /// Version that was used instead
+        winner: Coordinate,
+        /// Why `winner` was chosen
+        reason: MediationReason,
+    },
+    /// A transitive dependency matched an exclusion pattern
+    Excluded {
+        /// Dependency that was dropped
+        coordinate: Coordinate,
+        /// Artifact whose POM declared it
+        parent: Coordinate,
+        /// `groupId:artifactId` pattern that matched
+        pattern: String,
+    },
+    /// A dependency declared in a POM was not followed
+    Skipped {
+        /// `groupId:artifactId` of the dependency
+        dependency: String,
+        /// Artifact whose POM declared it
+        parent: Coordinate,
+        /// Why it was skipped
+        reason: SkipReason,
+    },
+}
+
+impl fmt::Display for ResolutionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolutionEvent::PomFetched { coordinate, depth } => {
+                write!(f, "fetched   {} (depth {})", coordinate, depth)
+            }
+            ResolutionEvent::Mediated {
+                loser,
+                winner,
+                reason,
+            } => write!(f, "mediated  {} -> {} ({})", loser, winner.version, reason),
+            ResolutionEvent::Excluded {
+                coordinate,
+                parent,
+                pattern,
+            } => write!(f, "excluded  {} from {} ({})", coordinate, parent, pattern),
+            ResolutionEvent::Skipped {
+                dependency,
+                parent,
+                reason,
+            } => write!(f, "skipped   {} from {} ({})", dependency, parent, reason),
+        }
+    }
+}
+
+/// Events recorded during one [`Resolver::resolve`](crate::Resolver::resolve) call
+///
+/// Fetches run concurrently, so events of independent subtrees interleave;
+/// [`ResolutionTrace::sorted`] gives a run-independent order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolutionTrace {
+    events: Vec<ResolutionEvent>,
+}
+
+impl ResolutionTrace {
+    /// Create an empty trace
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an event
+    pub fn record(&mut self, event: ResolutionEvent) {
+        self.events.push(event);
+    }
+
+    /// Events in the order they were recorded
+    pub fn events(&self) -> &[ResolutionEvent] {
+        &self.events
+    }
+
+    /// Whether nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Events ordered by their rendered text
+    pub fn sorted(&self) -> Vec<&ResolutionEvent> {
+        let mut events: Vec<_> = self.events.iter().collect();
+        events.sort_by_cached_key(|event| event.to_string());
+        events
+    }
+}
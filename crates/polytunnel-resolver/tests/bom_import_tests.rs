@@ -0,0 +1,133 @@
+//! Tests for importing BOMs (`<scope>import</scope>`) declared in `<dependencyManagement>`.
+
+mod common;
+
+use common::pom_route;
+use polytunnel_maven::{Coordinate, MavenClient};
+use polytunnel_resolver::Resolver;
+use std::collections::HashMap;
+
+fn leaf(group: &str, artifact: &str, version: &str) -> (String, u16, String) {
+    pom_route(group, artifact, version, "")
+}
+
+/// `app` imports `spring-framework-bom` (which imports `jackson-bom`) and
+/// declares `spring-core`, `spring-web`, and `jackson-databind` without versions
+fn client(app_management: &str) -> MavenClient {
+    let app = format!(
+        "<properties><spring.version>6.1.0</spring.version></properties>\
+         <dependencyManagement><dependencies>{app_management}\
+         <dependency><groupId>org.springframework</groupId><artifactId>spring-framework-bom</artifactId><version>${{spring.version}}</version><type>pom</type><scope>import</scope></dependency>\
+         </dependencies></dependencyManagement>\
+         <dependencies>\
+         <dependency><groupId>org.springframework</groupId><artifactId>spring-core</artifactId></dependency>\
+         <dependency><groupId>org.springframework</groupId><artifactId>spring-web</artifactId></dependency>\
+         <dependency><groupId>com.fasterxml.jackson.core</groupId><artifactId>jackson-databind</artifactId></dependency>\
+         </dependencies>"
+    );
+    let spring_bom = "<packaging>pom</packaging>\
+         <dependencyManagement><dependencies>\
+         <dependency><groupId>org.springframework</groupId><artifactId>spring-core</artifactId><version>6.1.0</version></dependency>\
+         <dependency><groupId>org.springframework</groupId><artifactId>spring-web</artifactId><version>6.1.0</version></dependency>\
+         <dependency><groupId>com.fasterxml.jackson</groupId><artifactId>jackson-bom</artifactId><version>2.16.0</version><type>pom</type><scope>import</scope></dependency>\
+         </dependencies></dependencyManagement>";
+    let jackson_bom = "<packaging>pom</packaging>\
+         <dependencyManagement><dependencies>\
+         <dependency><groupId>com.fasterxml.jackson.core</groupId><artifactId>jackson-databind</artifactId><version>2.16.0</version></dependency>\
+         </dependencies></dependencyManagement>";
+    let routes = vec![
+        pom_route("com.example", "app", "1.0", &app),
+        pom_route(
+            "org.springframework",
+            "spring-framework-bom",
+            "6.1.0",
+            spring_bom,
+        ),
+        pom_route(
+            "com.fasterxml.jackson",
+            "jackson-bom",
+            "2.16.0",
+            jackson_bom,
+        ),
+        leaf("org.springframework", "spring-core", "6.1.0"),
+        leaf("org.springframework", "spring-core", "6.0.0"),
+        leaf("org.springframework", "spring-web", "6.1.0"),
+        leaf("com.fasterxml.jackson.core", "jackson-databind", "2.16.0"),
+    ];
+    common::client(routes)
+}
+
+async fn resolved_versions(client: MavenClient) -> HashMap<String, String> {
+    let mut resolver = Resolver::with_client(client);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+    tree.all_dependencies
+        .iter()
+        .map(|c| (c.artifact_id.clone(), c.version.clone()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_bom_supplies_versions_of_versionless_dependencies() {
+    let versions = resolved_versions(client("")).await;
+
+    assert_eq!(
+        versions.get("spring-core").map(String::as_str),
+        Some("6.1.0")
+    );
+    assert_eq!(
+        versions.get("spring-web").map(String::as_str),
+        Some("6.1.0")
+    );
+    assert!(!versions.contains_key("spring-framework-bom"));
+}
+
+#[tokio::test]
+async fn test_bom_imported_by_bom_supplies_versions() {
+    let versions = resolved_versions(client("")).await;
+
+    assert_eq!(
+        versions.get("jackson-databind").map(String::as_str),
+        Some("2.16.0")
+    );
+}
+
+#[tokio::test]
+async fn test_declared_management_beats_imported_bom() {
+    let versions = resolved_versions(client(
+        "<dependency><groupId>org.springframework</groupId><artifactId>spring-core</artifactId><version>6.0.0</version></dependency>",
+    ))
+    .await;
+
+    assert_eq!(
+        versions.get("spring-core").map(String::as_str),
+        Some("6.0.0")
+    );
+    assert_eq!(
+        versions.get("spring-web").map(String::as_str),
+        Some("6.1.0")
+    );
+}
+
+#[tokio::test]
+async fn test_missing_bom_leaves_dependencies_unversioned() {
+    let routes = vec![
+        pom_route(
+            "com.example",
+            "app",
+            "1.0",
+            "<dependencyManagement><dependencies>\
+         <dependency><groupId>org.example</groupId><artifactId>gone-bom</artifactId><version>1.0</version><type>pom</type><scope>import</scope></dependency>\
+         </dependencies></dependencyManagement>\
+         <dependencies><dependency><groupId>org.example</groupId><artifactId>lib</artifactId></dependency></dependencies>",
+        ),
+        leaf("org.example", "lib", "1.0"),
+    ];
+    let client = common::client(routes);
+
+    let versions = resolved_versions(client).await;
+    assert_eq!(versions.len(), 1);
+    assert!(versions.contains_key("app"));
+}
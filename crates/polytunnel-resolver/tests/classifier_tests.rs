@@ -0,0 +1,72 @@
+//! Tests for keeping classifier variants of an artifact apart.
+
+mod common;
+
+use common::{dependencies, dependency, pom_route, resolver};
+use polytunnel_maven::Coordinate;
+
+/// `(artifactId, classifier)` dependencies, all in `com.example` at `1.0`
+fn route(artifact: &str, deps: &[(&str, Option<&str>)]) -> (String, u16, String) {
+    let deps = deps.iter().map(|(a, classifier)| {
+        let classifier = classifier
+            .map(|c| format!("<classifier>{c}</classifier>"))
+            .unwrap_or_default();
+        dependency(
+            "com.example",
+            a,
+            &format!("<version>1.0</version>{classifier}"),
+        )
+    });
+    pom_route("com.example", artifact, "1.0", &dependencies(deps))
+}
+
+fn resolved(tree: &polytunnel_resolver::ResolvedTree) -> Vec<String> {
+    tree.all_dependencies
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_native_classifier_survives_alongside_main_jar() {
+    let mut resolver = resolver(vec![
+        route("app", &[("lib", None), ("lib", Some("natives-linux"))]),
+        route("lib", &[]),
+    ]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+
+    let deps = resolved(&tree);
+    assert!(deps.contains(&"com.example:lib:1.0".to_string()));
+    assert!(deps.contains(&"com.example:lib:1.0:natives-linux".to_string()));
+    let natives = tree
+        .all_dependencies
+        .iter()
+        .find(|c| c.classifier.is_some())
+        .unwrap();
+    assert_eq!(natives.jar_filename(), "lib-1.0-natives-linux.jar");
+}
+
+#[tokio::test]
+async fn test_same_coordinate_twice_collapses() {
+    let mut resolver = resolver(vec![
+        route("app", &[("left", None), ("right", None)]),
+        route("left", &[("lib", Some("natives-linux"))]),
+        route("right", &[("lib", Some("natives-linux"))]),
+        route("lib", &[]),
+    ]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+
+    let deps = resolved(&tree);
+    let natives = deps
+        .iter()
+        .filter(|d| *d == "com.example:lib:1.0:natives-linux")
+        .count();
+    assert_eq!(natives, 1);
+    assert!(!deps.contains(&"com.example:lib:1.0".to_string()));
+}
@@ -0,0 +1,117 @@
+//! Mock repository shared by the resolver integration tests
+#![allow(dead_code)]
+
+use polytunnel_maven::{HttpResponse, HttpTransportFuture, MavenClient, MavenTransport};
+use polytunnel_resolver::Resolver;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const BASE_URL: &str = "https://repo.example.test";
+
+/// Serves `routes` by URL, 404 for anything else, and records every request
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    routes: HashMap<String, (u16, Vec<u8>)>,
+    /// Milliseconds to wait before answering requests for an artifactId
+    delays: HashMap<String, u64>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockTransport {
+    /// Transport answering each `(url, status, body)` route
+    pub fn new(routes: Vec<(String, u16, String)>) -> Self {
+        let routes = routes
+            .into_iter()
+            .map(|(url, status, body)| (url, (status, body.into_bytes())))
+            .collect();
+
+        Self {
+            routes,
+            ..Self::default()
+        }
+    }
+
+    /// Delay the responses for the given artifactIds by some milliseconds
+    pub fn with_delays(mut self, delays: HashMap<String, u64>) -> Self {
+        self.delays = delays;
+        self
+    }
+
+    /// URLs requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl MavenTransport for MockTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        self.requests.lock().unwrap().push(url.clone());
+        let response = self
+            .routes
+            .get(&url)
+            .cloned()
+            .unwrap_or((404, b"not found".to_vec()));
+        let delay = self
+            .delays
+            .iter()
+            .find(|(artifact, _)| url.contains(&format!("/{artifact}/")))
+            .map(|(_, ms)| *ms)
+            .unwrap_or(0);
+
+        Box::pin(async move {
+            if delay > 0 {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+            Ok(HttpResponse {
+                status: response.0,
+                body: response.1,
+                headers: Default::default(),
+            })
+        })
+    }
+}
+
+/// URL of the POM of `group:artifact:version` below [`BASE_URL`]
+pub fn pom_url(group: &str, artifact: &str, version: &str) -> String {
+    format!(
+        "{BASE_URL}/{}/{artifact}/{version}/{artifact}-{version}.pom",
+        group.replace('.', "/")
+    )
+}
+
+/// Route serving the POM of `group:artifact:version`; `body` follows its `<version>`
+pub fn pom_route(group: &str, artifact: &str, version: &str, body: &str) -> (String, u16, String) {
+    (
+        pom_url(group, artifact, version),
+        200,
+        format!(
+            "<project><groupId>{group}</groupId><artifactId>{artifact}</artifactId><version>{version}</version>{body}</project>"
+        ),
+    )
+}
+
+/// `<dependency>` element; `extra` (version, scope, ...) follows its `<artifactId>`
+pub fn dependency(group: &str, artifact: &str, extra: &str) -> String {
+    format!(
+        "<dependency><groupId>{group}</groupId><artifactId>{artifact}</artifactId>{extra}</dependency>"
+    )
+}
+
+/// `<dependencies>` element holding `deps`
+pub fn dependencies(deps: impl IntoIterator<Item = String>) -> String {
+    format!(
+        "<dependencies>{}</dependencies>",
+        deps.into_iter().collect::<String>()
+    )
+}
+
+/// Client for a repository at [`BASE_URL`] serving `routes`
+pub fn client(routes: Vec<(String, u16, String)>) -> MavenClient {
+    MavenClient::with_transport(BASE_URL, Arc::new(MockTransport::new(routes)))
+}
+
+/// Resolver for a repository at [`BASE_URL`] serving `routes`
+pub fn resolver(routes: Vec<(String, u16, String)>) -> Resolver {
+    Resolver::with_client(client(routes))
+}
@@ -1,7 +1,7 @@
 //! Unit tests for DependencyGraph behavior.
 
-use polytunnel_maven::Coordinate;
-use polytunnel_resolver::{DependencyGraph, DependencyNode};
+use polytunnel_maven::{Coordinate, DependencyScope};
+use polytunnel_resolver::{CycleError, DependencyGraph, DependencyNode, GraphStats};
 
 #[test]
 fn test_add_node_and_get() {
@@ -55,3 +55,286 @@ fn test_nodes_iterator_exposes_all_nodes() {
 
     assert_eq!(listed, expected);
 }
+
+/// Diamond graph: app -> (left, right) -> shared
+fn diamond() -> (DependencyGraph, Vec<Coordinate>) {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let left = Coordinate::parse("com.a:lib-a:1.0").unwrap();
+    let right = Coordinate::parse("com.b:lib-b:2.0").unwrap();
+    let shared = Coordinate::parse("com.shared:common:1.0").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![left.clone(), right.clone()], 0);
+    graph.add_node(left.clone(), vec![shared.clone()], 1);
+    graph.add_node_with_scope(
+        right.clone(),
+        vec![shared.clone()],
+        1,
+        DependencyScope::Provided,
+    );
+    graph.add_node(shared.clone(), vec![], 2);
+
+    (graph, vec![app, left, right, shared])
+}
+
+#[test]
+fn test_node_depth_and_scope_accessors() {
+    let (graph, coords) = diamond();
+
+    let app = graph.get(&coords[0].to_string()).unwrap();
+    assert_eq!(app.depth(), 0);
+    assert_eq!(app.scope(), DependencyScope::Compile);
+
+    let right = graph.get(&coords[2].to_string()).unwrap();
+    assert_eq!(right.depth(), 1);
+    assert_eq!(right.scope(), DependencyScope::Provided);
+}
+
+#[test]
+fn test_children_in_declaration_order() {
+    let (graph, coords) = diamond();
+
+    let children: Vec<String> = graph
+        .children(&coords[0])
+        .map(|n| n.coordinate.artifact_id.clone())
+        .collect();
+    assert_eq!(children, vec!["lib-a", "lib-b"]);
+
+    assert_eq!(graph.children(&coords[3]).count(), 0);
+}
+
+#[test]
+fn test_children_of_unknown_coordinate_is_empty() {
+    let (graph, _) = diamond();
+    let unknown = Coordinate::parse("org.unknown:x:1.0").unwrap();
+
+    assert_eq!(graph.children(&unknown).count(), 0);
+}
+
+#[test]
+fn test_bfs_visits_diamond_once_in_breadth_first_order() {
+    let (graph, coords) = diamond();
+
+    let visited: Vec<(String, usize)> = graph
+        .bfs_from(&coords[0])
+        .map(|(n, depth)| (n.coordinate.artifact_id.clone(), depth))
+        .collect();
+
+    assert_eq!(
+        visited,
+        vec![
+            ("app".to_string(), 0),
+            ("lib-a".to_string(), 1),
+            ("lib-b".to_string(), 1),
+            ("common".to_string(), 2),
+        ]
+    );
+}
+
+#[test]
+fn test_bfs_depth_is_relative_to_start() {
+    let (graph, coords) = diamond();
+
+    let visited: Vec<usize> = graph.bfs_from(&coords[1]).map(|(_, d)| d).collect();
+    assert_eq!(visited, vec![0, 1]);
+}
+
+#[test]
+fn test_bfs_from_missing_start_is_empty() {
+    let (graph, _) = diamond();
+    let unknown = Coordinate::parse("org.unknown:x:1.0").unwrap();
+
+    assert_eq!(graph.bfs_from(&unknown).count(), 0);
+}
+
+#[test]
+fn test_stats_of_diamond() {
+    let (graph, _) = diamond();
+
+    let stats = graph.stats();
+    assert_eq!(
+        stats,
+        GraphStats {
+            total: 4,
+            direct: 1,
+            transitive: 3,
+            max_depth: 2,
+            conflicts: 0,
+            jar_bytes: None,
+        }
+    );
+}
+
+#[test]
+fn test_stats_counts_mediated_versions_as_conflicts() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let lib = Coordinate::parse("com.example:lib:1.0").unwrap();
+    let shared_old = Coordinate::parse("com.shared:common:1.0").unwrap();
+    let shared_new = Coordinate::parse("com.shared:common:2.0").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![shared_new.clone(), lib.clone()], 0);
+    graph.add_node(shared_new, vec![], 1);
+    // `lib` asked for 1.0 but the nearer 2.0 was selected
+    graph.add_node(lib, vec![shared_old], 1);
+
+    let stats = graph.stats();
+    assert_eq!(stats.total, 3);
+    assert_eq!(stats.max_depth, 1);
+    assert_eq!(stats.conflicts, 1);
+}
+
+#[test]
+fn test_conflicts_and_paths_to_them() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let lib = Coordinate::parse("com.example:lib:1.0").unwrap();
+    let other = Coordinate::parse("com.example:other:1.0").unwrap();
+    let shared_old = Coordinate::parse("com.shared:common:1.0").unwrap();
+    let shared_new = Coordinate::parse("com.shared:common:2.0").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(
+        app.clone(),
+        vec![shared_new.clone(), lib.clone(), other.clone()],
+        0,
+    );
+    graph.add_node(shared_new, vec![], 1);
+    graph.add_node(lib, vec![shared_old], 1);
+    graph.add_node(other, vec![], 1);
+
+    let conflicts = graph.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(
+        conflicts["com.shared:common"].iter().collect::<Vec<_>>(),
+        ["1.0", "2.0"]
+    );
+
+    let mut paths: Vec<_> = graph.conflict_paths().into_iter().collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "com.example:app:1.0",
+            "com.example:lib:1.0",
+            "com.shared:common:1.0",
+            "com.shared:common:2.0",
+        ]
+    );
+}
+
+#[test]
+fn test_stats_of_empty_graph() {
+    assert_eq!(DependencyGraph::new().stats(), GraphStats::default());
+}
+
+#[test]
+fn test_effective_scopes_follow_maven_scope_table() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let driver = Coordinate::parse("com.example:driver:1.0").unwrap();
+    let driver_core = Coordinate::parse("com.example:driver-core:1.0").unwrap();
+    let junit = Coordinate::parse("org.junit:junit:4.13").unwrap();
+    let hamcrest = Coordinate::parse("org.hamcrest:hamcrest:1.3").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![driver.clone()], 0);
+    graph.add_node_with_scope(
+        driver.clone(),
+        vec![driver_core.clone()],
+        1,
+        DependencyScope::Runtime,
+    );
+    graph.add_node(driver_core.clone(), vec![], 2);
+    graph.add_node(
+        junit.clone(),
+        vec![hamcrest.clone(), driver_core.clone()],
+        0,
+    );
+    graph.add_node(hamcrest.clone(), vec![], 1);
+
+    let scopes = graph.effective_scopes(&[
+        (app, DependencyScope::Compile),
+        (junit, DependencyScope::Test),
+    ]);
+
+    assert_eq!(scopes["com.example:app:1.0"], DependencyScope::Compile);
+    assert_eq!(scopes["com.example:driver:1.0"], DependencyScope::Runtime);
+    // Reached as runtime via `app` and as test via `junit`: the wider scope wins
+    assert_eq!(
+        scopes["com.example:driver-core:1.0"],
+        DependencyScope::Runtime
+    );
+    assert_eq!(scopes["org.hamcrest:hamcrest:1.3"], DependencyScope::Test);
+}
+
+#[test]
+fn test_topological_order_puts_dependencies_first() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let web = Coordinate::parse("com.example:web:1.0").unwrap();
+    let util = Coordinate::parse("com.example:util:1.0").unwrap();
+    let common = Coordinate::parse("com.example:common:1.0").unwrap();
+    let missing = Coordinate::parse("com.example:missing:1.0").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![web.clone(), util.clone()], 0);
+    graph.add_node(web.clone(), vec![common.clone(), missing], 1);
+    graph.add_node(util.clone(), vec![common.clone()], 1);
+    graph.add_node(common.clone(), vec![], 2);
+
+    let order = graph.topological_order().unwrap();
+
+    assert_eq!(order, vec![common, util, web, app]);
+}
+
+#[test]
+fn test_topological_order_reports_cycle() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let a = Coordinate::parse("com.example:a:1.0").unwrap();
+    let b = Coordinate::parse("com.example:b:1.0").unwrap();
+    let c = Coordinate::parse("com.example:c:1.0").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![a.clone()], 0);
+    graph.add_node(a.clone(), vec![b.clone()], 1);
+    graph.add_node(b.clone(), vec![c.clone()], 2);
+    graph.add_node(c.clone(), vec![a.clone()], 3);
+
+    let err = graph.topological_order().unwrap_err();
+
+    assert_eq!(
+        err,
+        CycleError {
+            cycle: vec![a, b, c]
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "dependency cycle: com.example:a:1.0 -> com.example:b:1.0 -> com.example:c:1.0 -> com.example:a:1.0"
+    );
+}
+
+#[test]
+fn test_shortest_path_to_follows_fewest_edges() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let web = Coordinate::parse("com.example:web:1.0").unwrap();
+    let http = Coordinate::parse("com.example:http:1.0").unwrap();
+    let json = Coordinate::parse("com.example:json:1.0").unwrap();
+    let unreachable = Coordinate::parse("com.example:orphan:1.0").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![web.clone(), json.clone()], 0);
+    graph.add_node(web.clone(), vec![http.clone()], 1);
+    graph.add_node(http.clone(), vec![json.clone()], 2);
+    graph.add_node(json.clone(), vec![], 1);
+    graph.add_node(unreachable.clone(), vec![], 3);
+
+    assert_eq!(
+        graph.shortest_path_to(&json),
+        Some(vec![app.clone(), json.clone()])
+    );
+    assert_eq!(
+        graph.shortest_path_to(&http),
+        Some(vec![app.clone(), web, http])
+    );
+    assert_eq!(graph.shortest_path_to(&app), Some(vec![app]));
+    assert_eq!(graph.shortest_path_to(&unreachable), None);
+}
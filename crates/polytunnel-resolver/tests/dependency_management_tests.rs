@@ -0,0 +1,110 @@
+//! Tests for `[dependency-management]` versions forced on transitive dependencies.
+
+mod common;
+
+use common::{dependencies, dependency, pom_route};
+use polytunnel_maven::Coordinate;
+use polytunnel_resolver::Resolver;
+use std::collections::HashMap;
+
+/// POM for `com.example:{artifact}:{version}` with `(artifactId, version)` dependencies
+fn route(artifact: &str, version: &str, deps: &[(&str, Option<&str>)]) -> (String, u16, String) {
+    let deps = deps.iter().map(|(a, v)| {
+        let version = v
+            .map(|v| format!("<version>{v}</version>"))
+            .unwrap_or_default();
+        dependency("com.example", a, &version)
+    });
+    pom_route("com.example", artifact, version, &dependencies(deps))
+}
+
+/// `app` -> `lib` -> `util:1.0`, `codec` (no version); `util:2.0` and `codec:3.0` also published
+fn routes() -> Vec<(String, u16, String)> {
+    vec![
+        route("app", "1.0", &[("lib", Some("1.0"))]),
+        route("lib", "1.0", &[("util", Some("1.0")), ("codec", None)]),
+        route("util", "1.0", &[]),
+        route("util", "2.0", &[]),
+        route("codec", "3.0", &[]),
+    ]
+}
+
+fn resolver_with(managed: &[(&str, &str)]) -> Resolver {
+    common::resolver(routes()).with_managed_versions(
+        managed
+            .iter()
+            .map(|(ga, v)| (ga.to_string(), v.to_string()))
+            .collect(),
+    )
+}
+
+fn versions(coords: &[Coordinate]) -> HashMap<String, String> {
+    coords
+        .iter()
+        .map(|c| (c.artifact_id.clone(), c.version.clone()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_managed_version_forces_transitive_version() {
+    let mut resolver = resolver_with(&[("com.example:util", "2.0")]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+
+    let versions = versions(&tree.all_dependencies);
+    assert_eq!(versions.get("util").map(String::as_str), Some("2.0"));
+    assert!(resolver.graph.contains("com.example:util:2.0"));
+    assert!(!resolver.graph.contains("com.example:util:1.0"));
+}
+
+#[tokio::test]
+async fn test_managed_version_fills_missing_transitive_version() {
+    let mut resolver = resolver_with(&[("com.example:codec", "3.0")]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+
+    let versions = versions(&tree.all_dependencies);
+    assert_eq!(versions.get("codec").map(String::as_str), Some("3.0"));
+    assert_eq!(versions.get("util").map(String::as_str), Some("1.0"));
+}
+
+#[tokio::test]
+async fn test_versionless_transitive_skipped_without_management() {
+    let mut resolver = resolver_with(&[]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+
+    assert!(!versions(&tree.all_dependencies).contains_key("codec"));
+}
+
+#[tokio::test]
+async fn test_direct_dependency_version_beats_management() {
+    let mut resolver = resolver_with(&[("com.example:util", "2.0")]);
+    let tree = resolver
+        .resolve(&[
+            Coordinate::new("com.example", "app", "1.0"),
+            Coordinate::new("com.example", "util", "1.0"),
+        ])
+        .await
+        .unwrap();
+
+    let versions = versions(&tree.all_dependencies);
+    assert_eq!(versions.get("util").map(String::as_str), Some("1.0"));
+}
+
+#[tokio::test]
+async fn test_management_does_not_add_unreferenced_artifacts() {
+    let mut resolver = resolver_with(&[("com.example:unrelated", "9.9")]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "app", "1.0")])
+        .await
+        .unwrap();
+
+    assert!(!versions(&tree.all_dependencies).contains_key("unrelated"));
+}
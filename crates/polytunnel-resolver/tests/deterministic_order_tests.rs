@@ -0,0 +1,64 @@
+//! Tests that resolution order does not depend on fetch completion order.
+
+mod common;
+
+use common::{BASE_URL, MockTransport, dependencies, dependency, pom_route};
+use polytunnel_maven::{Coordinate, MavenClient};
+use polytunnel_resolver::Resolver;
+use std::sync::Arc;
+
+fn route(artifact: &str, deps: &[&str]) -> (String, u16, String) {
+    let deps = deps
+        .iter()
+        .map(|a| dependency("com.example", a, "<version>1.0</version>"));
+    pom_route("com.example", artifact, "1.0", &dependencies(deps))
+}
+
+/// `app-a` -> `zeta`, `shared`; `app-b` -> `alpha`, `shared`; `shared` -> `leaf`
+fn routes() -> Vec<(String, u16, String)> {
+    vec![
+        route("app-a", &["zeta", "shared"]),
+        route("app-b", &["alpha", "shared"]),
+        route("zeta", &[]),
+        route("alpha", &[]),
+        route("shared", &["leaf"]),
+        route("leaf", &[]),
+    ]
+}
+
+async fn resolve_order(delays: &[(&str, u64)]) -> Vec<String> {
+    let delays = delays.iter().map(|(a, ms)| (a.to_string(), *ms)).collect();
+    let mut resolver = Resolver::with_client(MavenClient::with_transport(
+        BASE_URL,
+        Arc::new(MockTransport::new(routes()).with_delays(delays)),
+    ));
+    let tree = resolver
+        .resolve(&[
+            Coordinate::new("com.example", "app-a", "1.0"),
+            Coordinate::new("com.example", "app-b", "1.0"),
+        ])
+        .await
+        .unwrap();
+    tree.all_dependencies
+        .iter()
+        .map(|c| c.artifact_id.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_resolution_order_is_independent_of_completion_order() {
+    let first = resolve_order(&[("app-a", 30), ("zeta", 20)]).await;
+    let second = resolve_order(&[("app-b", 30), ("alpha", 20)]).await;
+
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_resolution_order_is_depth_then_coordinate() {
+    let order = resolve_order(&[]).await;
+
+    assert_eq!(
+        order,
+        vec!["app-a", "app-b", "alpha", "shared", "zeta", "leaf"]
+    );
+}
@@ -0,0 +1,172 @@
+//! Tests for per-root and POM-declared dependency exclusions.
+
+mod common;
+
+use common::{dependencies, dependency, pom_route, pom_url, resolver};
+use polytunnel_maven::Coordinate;
+use polytunnel_resolver::Resolver;
+
+fn route(
+    group: &str,
+    artifact: &str,
+    version: &str,
+    deps: &[(&str, &str, &str)],
+) -> (String, u16, String) {
+    let deps = deps
+        .iter()
+        .map(|(g, a, v)| dependency(g, a, &format!("<version>{v}</version>")));
+    pom_route(group, artifact, version, &dependencies(deps))
+}
+
+/// `app` -> `commons-logging` directly and via `http`; `other` -> `commons-logging`
+fn routes() -> Vec<(String, u16, String)> {
+    vec![
+        route(
+            "com.example",
+            "app",
+            "1.0",
+            &[
+                ("commons-logging", "commons-logging", "1.2"),
+                ("org.http", "http", "4.5"),
+            ],
+        ),
+        route(
+            "org.http",
+            "http",
+            "4.5",
+            &[("commons-logging", "commons-logging", "1.2")],
+        ),
+        route("commons-logging", "commons-logging", "1.2", &[]),
+        route(
+            "com.example",
+            "other",
+            "1.0",
+            &[("commons-logging", "commons-logging", "1.2")],
+        ),
+    ]
+}
+
+fn resolver_with(exclusions: &[(&str, &[&str])]) -> Resolver {
+    let exclusions = exclusions
+        .iter()
+        .map(|(root, excluded)| {
+            (
+                root.to_string(),
+                excluded.iter().map(|e| e.to_string()).collect(),
+            )
+        })
+        .collect();
+    resolver(routes()).with_exclusions(exclusions)
+}
+
+fn has_artifact(deps: &[Coordinate], artifact_id: &str) -> bool {
+    deps.iter().any(|c| c.artifact_id == artifact_id)
+}
+
+#[tokio::test]
+async fn test_exclusion_removes_transitive_from_whole_subtree() {
+    let mut resolver = resolver_with(&[("com.example:app", &["commons-logging:commons-logging"])]);
+    let root = Coordinate::parse("com.example:app:1.0").unwrap();
+
+    let tree = resolver.resolve(&[root]).await.unwrap();
+
+    assert!(has_artifact(&tree.all_dependencies, "http"));
+    assert!(!has_artifact(&tree.all_dependencies, "commons-logging"));
+    assert!(
+        resolver
+            .graph
+            .get("commons-logging:commons-logging:1.2")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_exclusion_supports_wildcard_artifact() {
+    let mut resolver = resolver_with(&[("com.example:app", &["commons-logging:*"])]);
+    let root = Coordinate::parse("com.example:app:1.0").unwrap();
+
+    let tree = resolver.resolve(&[root]).await.unwrap();
+
+    assert!(!has_artifact(&tree.all_dependencies, "commons-logging"));
+}
+
+#[tokio::test]
+async fn test_exclusion_does_not_affect_other_roots() {
+    let mut resolver = resolver_with(&[("com.example:app", &["commons-logging:commons-logging"])]);
+    let roots = vec![Coordinate::parse("com.example:other:1.0").unwrap()];
+
+    let tree = resolver.resolve(&roots).await.unwrap();
+
+    assert!(has_artifact(&tree.all_dependencies, "commons-logging"));
+}
+
+#[tokio::test]
+async fn test_without_exclusions_transitive_is_resolved() {
+    let mut resolver = resolver_with(&[]);
+    let root = Coordinate::parse("com.example:app:1.0").unwrap();
+
+    let tree = resolver.resolve(&[root]).await.unwrap();
+
+    assert!(has_artifact(&tree.all_dependencies, "commons-logging"));
+}
+
+/// `lib` -> `http` excluding `excluded` (e.g. `commons-logging:commons-logging` or `*:*`);
+/// `http` -> `commons-logging` and `codec` -> `codec-core`
+fn pom_exclusion_resolver(excluded: &str) -> Resolver {
+    let (group, artifact) = excluded.split_once(':').unwrap();
+    let lib = format!(
+        "<project><groupId>com.example</groupId><artifactId>lib</artifactId><version>1.0</version><dependencies>\
+         <dependency><groupId>org.http</groupId><artifactId>http</artifactId><version>4.5</version>\
+         <exclusions><exclusion><groupId>{group}</groupId><artifactId>{artifact}</artifactId></exclusion></exclusions>\
+         </dependency></dependencies></project>"
+    );
+    let mut routes = vec![
+        route(
+            "org.http",
+            "http",
+            "4.5",
+            &[
+                ("commons-logging", "commons-logging", "1.2"),
+                ("org.codec", "codec", "1.0"),
+            ],
+        ),
+        route("commons-logging", "commons-logging", "1.2", &[]),
+        route(
+            "org.codec",
+            "codec",
+            "1.0",
+            &[("org.codec", "codec-core", "1.0")],
+        ),
+        route("org.codec", "codec-core", "1.0", &[]),
+    ];
+    routes.push((pom_url("com.example", "lib", "1.0"), 200, lib));
+
+    resolver(routes)
+}
+
+#[tokio::test]
+async fn test_pom_exclusion_prunes_grandchild() {
+    let mut resolver = pom_exclusion_resolver("commons-logging:commons-logging");
+    let lib = Coordinate::parse("com.example:lib:1.0").unwrap();
+
+    let tree = resolver.resolve(&[lib]).await.unwrap();
+
+    assert!(has_artifact(&tree.all_dependencies, "http"));
+    assert!(has_artifact(&tree.all_dependencies, "codec-core"));
+    assert!(!has_artifact(&tree.all_dependencies, "commons-logging"));
+}
+
+#[tokio::test]
+async fn test_pom_wildcard_exclusion_cuts_whole_subtree() {
+    let mut resolver = pom_exclusion_resolver("*:*");
+    let lib = Coordinate::parse("com.example:lib:1.0").unwrap();
+
+    let tree = resolver.resolve(&[lib]).await.unwrap();
+
+    let artifacts: Vec<&str> = tree
+        .all_dependencies
+        .iter()
+        .map(|c| c.artifact_id.as_str())
+        .collect();
+    assert_eq!(artifacts, ["lib", "http"]);
+}
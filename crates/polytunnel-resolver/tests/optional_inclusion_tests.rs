@@ -0,0 +1,106 @@
+//! Tests for promoting a root dependency's optional transitives.
+
+mod common;
+
+use common::{dependencies, dependency, pom_route};
+use polytunnel_maven::Coordinate;
+use polytunnel_resolver::Resolver;
+use std::collections::HashSet;
+
+/// `(groupId, artifactId, version, optional)`
+type Dep<'a> = (&'a str, &'a str, &'a str, bool);
+
+fn route(group: &str, artifact: &str, version: &str, deps: &[Dep]) -> (String, u16, String) {
+    let deps = deps.iter().map(|(g, a, v, optional)| {
+        dependency(
+            g,
+            a,
+            &format!("<version>{v}</version><optional>{optional}</optional>"),
+        )
+    });
+    pom_route(group, artifact, version, &dependencies(deps))
+}
+
+/// `lib` -> `core`, optional `feature` (which itself has optional `extra`)
+fn routes() -> Vec<(String, u16, String)> {
+    vec![
+        route(
+            "com.example",
+            "lib",
+            "1.0",
+            &[
+                ("com.example", "core", "1.0", false),
+                ("com.example", "feature", "1.0", true),
+            ],
+        ),
+        route("com.example", "core", "1.0", &[]),
+        route(
+            "com.example",
+            "feature",
+            "1.0",
+            &[("com.example", "extra", "1.0", true)],
+        ),
+        route("com.example", "extra", "1.0", &[]),
+    ]
+}
+
+fn resolver_with(optional_roots: &[&str]) -> Resolver {
+    common::resolver(routes())
+        .with_optional_roots(optional_roots.iter().map(|r| r.to_string()).collect())
+}
+
+fn artifact_ids(coords: &[Coordinate]) -> HashSet<String> {
+    coords.iter().map(|c| c.artifact_id.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_optional_transitive_skipped_by_default() {
+    let mut resolver = resolver_with(&[]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "lib", "1.0")])
+        .await
+        .unwrap();
+
+    let ids = artifact_ids(&tree.all_dependencies);
+    assert!(ids.contains("core"));
+    assert!(!ids.contains("feature"));
+}
+
+#[tokio::test]
+async fn test_optional_transitive_included_when_root_opts_in() {
+    let mut resolver = resolver_with(&["com.example:lib"]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "lib", "1.0")])
+        .await
+        .unwrap();
+
+    let ids = artifact_ids(&tree.all_dependencies);
+    assert!(ids.contains("core"));
+    assert!(ids.contains("feature"));
+    // Only the root's own optional dependencies are promoted
+    assert!(!ids.contains("extra"));
+}
+
+#[tokio::test]
+async fn test_optional_inclusion_applies_only_to_listed_root() {
+    let mut resolver = resolver_with(&["com.example:other"]);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "lib", "1.0")])
+        .await
+        .unwrap();
+
+    assert!(!artifact_ids(&tree.all_dependencies).contains("feature"));
+}
+
+#[tokio::test]
+async fn test_full_closure_follows_optional_dependencies_at_every_depth() {
+    let mut resolver = resolver_with(&[]).with_full_closure(true);
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "lib", "1.0")])
+        .await
+        .unwrap();
+
+    let ids = artifact_ids(&tree.all_dependencies);
+    assert!(ids.contains("feature"));
+    assert!(ids.contains("extra"));
+}
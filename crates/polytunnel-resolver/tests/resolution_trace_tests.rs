@@ -0,0 +1,200 @@
+//! Tests for the resolution decision trace.
+
+mod common;
+
+use common::{dependencies, dependency, pom_route};
+use polytunnel_maven::{Coordinate, DependencyScope};
+use polytunnel_resolver::{MediationReason, ResolutionEvent, Resolver, SkipReason};
+use std::collections::HashMap;
+
+fn route(group: &str, artifact: &str, version: &str, deps: &[String]) -> (String, u16, String) {
+    pom_route(
+        group,
+        artifact,
+        version,
+        &dependencies(deps.iter().cloned()),
+    )
+}
+
+/// `app` declares one dependency for every kind of decision; `lib-b` is also a root
+fn resolver() -> Resolver {
+    let routes = vec![
+        route(
+            "com.example",
+            "app",
+            "1.0",
+            &[
+                dependency("com.example", "lib-b", "<version>1.0</version>"),
+                dependency("com.example", "lib-c", "<version>1.0</version>"),
+                dependency("com.example", "lib-x", "<version>1.0</version>"),
+                dependency(
+                    "junit",
+                    "junit",
+                    "<version>4.13</version><scope>test</scope>",
+                ),
+                dependency(
+                    "com.example",
+                    "opt",
+                    "<version>1.0</version><optional>true</optional>",
+                ),
+                dependency("com.example", "unversioned", ""),
+                dependency("org.log", "logging", "<version>1.0</version>"),
+            ],
+        ),
+        route(
+            "com.example",
+            "lib-x",
+            "1.0",
+            &[
+                dependency("com.example", "lib-c", "<version>2.0</version>"),
+                dependency("com.example", "pinned", "<version>1.0</version>"),
+            ],
+        ),
+        route("com.example", "lib-b", "3.0", &[]),
+        route("com.example", "lib-c", "1.0", &[]),
+        route("com.example", "pinned", "2.0", &[]),
+    ];
+    common::resolver(routes)
+        .with_exclusions(HashMap::from([(
+            "com.example:app".to_string(),
+            vec!["org.log:*".to_string()],
+        )]))
+        .with_managed_versions(HashMap::from([(
+            "com.example:pinned".to_string(),
+            "2.0".to_string(),
+        )]))
+}
+
+async fn resolve() -> Vec<ResolutionEvent> {
+    let mut resolver = resolver();
+    resolver
+        .resolve(&[
+            Coordinate::new("com.example", "app", "1.0"),
+            Coordinate::new("com.example", "lib-b", "3.0"),
+        ])
+        .await
+        .unwrap();
+    resolver.trace.events().to_vec()
+}
+
+fn mediated(loser: &str, winner: &str, reason: MediationReason) -> ResolutionEvent {
+    ResolutionEvent::Mediated {
+        loser: Coordinate::parse(loser).unwrap(),
+        winner: Coordinate::parse(winner).unwrap(),
+        reason,
+    }
+}
+
+fn skipped(dependency: &str, reason: SkipReason) -> ResolutionEvent {
+    ResolutionEvent::Skipped {
+        dependency: dependency.to_string(),
+        parent: Coordinate::new("com.example", "app", "1.0"),
+        reason,
+    }
+}
+
+#[tokio::test]
+async fn test_trace_records_each_fetched_pom_once() {
+    let events = resolve().await;
+
+    let mut fetched: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            ResolutionEvent::PomFetched { coordinate, .. } => Some(coordinate.to_string()),
+            _ => None,
+        })
+        .collect();
+    fetched.sort();
+
+    assert_eq!(
+        fetched,
+        vec![
+            "com.example:app:1.0",
+            "com.example:lib-b:3.0",
+            "com.example:lib-c:1.0",
+            "com.example:lib-x:1.0",
+            "com.example:pinned:2.0",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_trace_records_mediation_winner_loser_and_reason() {
+    let events = resolve().await;
+
+    assert!(events.contains(&mediated(
+        "com.example:lib-b:1.0",
+        "com.example:lib-b:3.0",
+        MediationReason::RootVersion,
+    )));
+    assert!(events.contains(&mediated(
+        "com.example:pinned:1.0",
+        "com.example:pinned:2.0",
+        MediationReason::ManagedVersion,
+    )));
+    assert!(events.contains(&mediated(
+        "com.example:lib-c:2.0",
+        "com.example:lib-c:1.0",
+        MediationReason::AlreadySelected,
+    )));
+}
+
+#[tokio::test]
+async fn test_trace_records_exclusions() {
+    let events = resolve().await;
+
+    assert!(events.contains(&ResolutionEvent::Excluded {
+        coordinate: Coordinate::new("org.log", "logging", "1.0"),
+        parent: Coordinate::new("com.example", "app", "1.0"),
+        pattern: "org.log:*".to_string(),
+    }));
+}
+
+#[tokio::test]
+async fn test_trace_records_skipped_dependencies() {
+    let events = resolve().await;
+
+    assert!(events.contains(&skipped(
+        "junit:junit",
+        SkipReason::Scope(DependencyScope::Test)
+    )));
+    assert!(events.contains(&skipped("com.example:opt", SkipReason::Optional)));
+    assert!(events.contains(&skipped(
+        "com.example:unversioned",
+        SkipReason::MissingVersion
+    )));
+}
+
+#[tokio::test]
+async fn test_trace_is_reset_between_resolutions() {
+    let mut resolver = resolver();
+    let roots = [Coordinate::new("com.example", "lib-c", "1.0")];
+
+    resolver.resolve(&roots).await.unwrap();
+    resolver.resolve(&roots).await.unwrap();
+
+    assert_eq!(
+        resolver.trace.events(),
+        &[ResolutionEvent::PomFetched {
+            coordinate: Coordinate::new("com.example", "lib-c", "1.0"),
+            depth: 0,
+        }]
+    );
+}
+
+#[test]
+fn test_trace_events_render_one_line_each() {
+    assert_eq!(
+        mediated(
+            "com.example:lib-c:2.0",
+            "com.example:lib-c:1.0",
+            MediationReason::AlreadySelected,
+        )
+        .to_string(),
+        "mediated  com.example:lib-c:2.0 -> 1.0 (nearer version already selected)"
+    );
+    assert_eq!(
+        skipped("junit:junit", SkipReason::Scope(DependencyScope::Test)).to_string(),
+        "skipped   junit:junit from com.example:app:1.0 (test scope)"
+    );
+}
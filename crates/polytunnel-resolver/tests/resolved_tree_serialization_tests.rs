@@ -0,0 +1,92 @@
+//! Tests for serializing a ResolvedTree and rebuilding a resolver from it.
+
+mod common;
+
+use common::{dependencies, dependency, pom_route};
+use polytunnel_maven::{Coordinate, DependencyScope, MavenClient};
+use polytunnel_resolver::{Lockfile, ResolvedTree, Resolver};
+
+/// POM route for `com.example:<artifact>:<version>` with `(artifact, version, scope)` dependencies
+fn route(artifact: &str, version: &str, deps: &[(&str, &str, &str)]) -> (String, u16, String) {
+    let deps = deps.iter().map(|(a, v, scope)| {
+        dependency(
+            "com.example",
+            a,
+            &format!("<version>{v}</version><scope>{scope}</scope>"),
+        )
+    });
+    pom_route("com.example", artifact, version, &dependencies(deps))
+}
+
+/// `app` -> `core`, runtime `driver` -> `util`
+fn client() -> MavenClient {
+    common::client(vec![
+        route(
+            "app",
+            "1.0",
+            &[("core", "2.0", "compile"), ("driver", "1.0", "runtime")],
+        ),
+        route("core", "2.0", &[]),
+        route("driver", "1.0", &[("util", "1.0", "compile")]),
+        route("util", "1.0", &[]),
+    ])
+}
+
+async fn resolve() -> (Resolver, ResolvedTree) {
+    let mut resolver = Resolver::with_client(client());
+    let tree = resolver
+        .resolve(&[Coordinate::parse("com.example:app:1.0").unwrap()])
+        .await
+        .unwrap();
+    (resolver, tree)
+}
+
+#[tokio::test]
+async fn test_resolved_tree_json_round_trip() {
+    let (_, tree) = resolve().await;
+    assert!(tree.graph.contains("com.example:driver:1.0"));
+
+    let json = serde_json::to_string(&tree).unwrap();
+    let restored: ResolvedTree = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, tree);
+    assert_eq!(
+        restored.graph.get("com.example:driver:1.0").unwrap().scope,
+        DependencyScope::Runtime
+    );
+}
+
+#[tokio::test]
+async fn test_resolved_tree_json_is_deterministic() {
+    let (_, first) = resolve().await;
+    let (_, second) = resolve().await;
+
+    assert_eq!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_from_resolved_tree_restores_graph() {
+    let (original, tree) = resolve().await;
+    let json = serde_json::to_string(&tree).unwrap();
+    let restored: ResolvedTree = serde_json::from_str(&json).unwrap();
+
+    let resolver = Resolver::from_resolved_tree(client(), &restored);
+
+    assert_eq!(resolver.graph, original.graph);
+    assert_eq!(resolver.graph.stats(), original.graph.stats());
+}
+
+#[test]
+fn test_tree_without_graph_deserializes() {
+    let restored: ResolvedTree = serde_json::from_str(
+        r#"{"root_dependencies":[],"all_dependencies":[{"group_id":"com.example","artifact_id":"app","version":"1.0"}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(restored.all_dependencies.len(), 1);
+    assert_eq!(restored.graph.nodes().count(), 0);
+    assert_eq!(Lockfile::from_tree(&restored).to_tree(), restored);
+}
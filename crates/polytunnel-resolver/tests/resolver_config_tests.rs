@@ -0,0 +1,366 @@
+//! Tests for configuring a resolver with `ResolverConfig`.
+
+mod common;
+
+use polytunnel_core::{DEFAULT_RESOLVE_CONCURRENCY, ProjectConfig};
+use polytunnel_maven::{
+    Coordinate, HttpResponse, HttpTransportFuture, MavenClient, MavenTransport,
+};
+use polytunnel_resolver::{
+    ConflictPolicy, Resolver, ResolverConfig, ResolverError, VersionConflict, VersionRequest,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const BASE_URL: &str = "https://repo.example.test";
+
+/// Serves `routes` after a short delay, tracking the most requests in flight at once
+#[derive(Clone, Default)]
+struct InstrumentedTransport {
+    routes: HashMap<String, Vec<u8>>,
+    /// URLs served after a longer delay
+    slow: HashSet<String>,
+    /// `(current, max)` requests in flight
+    in_flight: Arc<Mutex<(usize, usize)>>,
+}
+
+impl MavenTransport for InstrumentedTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        let body = self.routes.get(&url).cloned();
+        let delay = if self.slow.contains(&url) { 200 } else { 20 };
+        let in_flight = self.in_flight.clone();
+        Box::pin(async move {
+            {
+                let (current, max) = &mut *in_flight.lock().unwrap();
+                *current += 1;
+                *max = (*max).max(*current);
+            }
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            in_flight.lock().unwrap().0 -= 1;
+
+            Ok(match body {
+                Some(body) => HttpResponse {
+                    status: 200,
+                    body,
+                    headers: Default::default(),
+                },
+                None => HttpResponse {
+                    status: 404,
+                    body: b"not found".to_vec(),
+                    headers: Default::default(),
+                },
+            })
+        })
+    }
+}
+
+/// POM route for `com.example:<artifact>:<version>` depending on `(artifact, version)` pairs
+fn route(artifact: &str, version: &str, deps: &[(&str, &str)]) -> (String, Vec<u8>) {
+    let deps: String = deps
+        .iter()
+        .map(|(a, v)| {
+            format!(
+                "<dependency><groupId>com.example</groupId><artifactId>{a}</artifactId><version>{v}</version></dependency>"
+            )
+        })
+        .collect();
+    (
+        format!("{BASE_URL}/com/example/{artifact}/{version}/{artifact}-{version}.pom"),
+        format!(
+            "<project><groupId>com.example</groupId><artifactId>{artifact}</artifactId><version>{version}</version><dependencies>{deps}</dependencies></project>"
+        )
+        .into_bytes(),
+    )
+}
+
+/// `fanout` -> `leaf0`..`leaf5`; `app` -> `web` -> `common:2.0`, `app` -> `util` -> `common:1.0`
+fn transport() -> InstrumentedTransport {
+    let leaves: Vec<String> = (0..6).map(|i| format!("leaf{i}")).collect();
+    let fanout_deps: Vec<(&str, &str)> = leaves.iter().map(|leaf| (leaf.as_str(), "1.0")).collect();
+    let mut routes: HashMap<String, Vec<u8>> =
+        leaves.iter().map(|leaf| route(leaf, "1.0", &[])).collect();
+    routes.extend([
+        route("fanout", "1.0", &fanout_deps),
+        route("app", "1.0", &[("web", "1.0"), ("util", "1.0")]),
+        route("web", "1.0", &[("common", "2.0")]),
+        route("util", "1.0", &[("common", "1.0")]),
+        route("common", "1.0", &[]),
+        route("common", "2.0", &[]),
+    ]);
+    InstrumentedTransport {
+        routes,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_custom_conflict_policy_and_concurrency_are_applied() {
+    let transport = transport();
+    let in_flight = transport.in_flight.clone();
+    let config = ResolverConfig {
+        concurrency: 2,
+        conflict_policy: ConflictPolicy::Fail,
+        ..Default::default()
+    };
+    let mut resolver = Resolver::with_config(
+        MavenClient::with_transport(BASE_URL, Arc::new(transport)),
+        config,
+    );
+    assert_eq!(resolver.config().concurrency, 2);
+
+    let fanout = Coordinate::parse("com.example:fanout:1.0").unwrap();
+    let tree = resolver.resolve(&[fanout]).await.unwrap();
+    assert_eq!(tree.all_dependencies.len(), 7);
+    assert_eq!(in_flight.lock().unwrap().1, 2);
+
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let err = resolver.resolve(&[app]).await.unwrap_err();
+    match err {
+        ResolverError::VersionConflict { artifact, versions } => {
+            assert_eq!(artifact, "com.example:common");
+            assert_eq!(versions, ["1.0", "2.0"]);
+        }
+        other => panic!("expected VersionConflict, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_default_config_mediates_conflicts() {
+    let mut resolver = Resolver::with_config(
+        MavenClient::with_transport(BASE_URL, Arc::new(transport())),
+        ResolverConfig::default(),
+    );
+
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let tree = resolver.resolve(&[app]).await.unwrap();
+    let commons = tree
+        .all_dependencies
+        .iter()
+        .filter(|coord| coord.artifact_id == "common")
+        .count();
+    assert_eq!(commons, 1);
+}
+
+/// `app` -> `deep` -> `deeper` -> `lib:<deep>`, `app` -> `near` (slow) -> `lib:<near>`
+///
+/// The deeper version is reached first.
+fn diamond(near: &str, deep: &str) -> InstrumentedTransport {
+    let (near_url, near_pom) = route("near", "1.0", &[("lib", near)]);
+    InstrumentedTransport {
+        routes: HashMap::from([
+            route("app", "1.0", &[("deep", "1.0"), ("near", "1.0")]),
+            route("deep", "1.0", &[("deeper", "1.0")]),
+            route("deeper", "1.0", &[("lib", deep)]),
+            (near_url.clone(), near_pom),
+            route("lib", "1.0", &[]),
+            route("lib", "2.0", &[]),
+        ]),
+        slow: HashSet::from([near_url]),
+        ..Default::default()
+    }
+}
+
+async fn resolved_lib(transport: InstrumentedTransport, policy: ConflictPolicy) -> Vec<String> {
+    let mut resolver =
+        Resolver::with_client(MavenClient::with_transport(BASE_URL, Arc::new(transport)))
+            .with_conflict_policy(policy);
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let tree = resolver.resolve(&[app]).await.unwrap();
+    tree.all_dependencies
+        .iter()
+        .filter(|coord| coord.artifact_id == "lib")
+        .map(|coord| coord.version.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_nearest_wins_regardless_of_fetch_order() {
+    assert_eq!(
+        resolved_lib(diamond("2.0", "1.0"), ConflictPolicy::Nearest).await,
+        ["2.0"]
+    );
+    assert_eq!(
+        resolved_lib(diamond("1.0", "2.0"), ConflictPolicy::Nearest).await,
+        ["1.0"]
+    );
+}
+
+#[tokio::test]
+async fn test_newest_wins_at_any_depth() {
+    assert_eq!(
+        resolved_lib(diamond("1.0", "2.0"), ConflictPolicy::Newest).await,
+        ["2.0"]
+    );
+    assert_eq!(
+        resolved_lib(diamond("2.0", "1.0"), ConflictPolicy::Newest).await,
+        ["2.0"]
+    );
+}
+
+#[tokio::test]
+async fn test_mediation_passes_fetch_each_pom_once() {
+    let lib = |version| {
+        common::dependency(
+            "com.example",
+            "lib",
+            &format!("<version>{version}</version>"),
+        )
+    };
+    let uses = |artifact| common::dependency("com.example", artifact, "<version>1.0</version>");
+    // `near` is slow, so the deeper `lib:2.0` is selected first and mediation needs a second pass
+    let transport = common::MockTransport::new(vec![
+        common::pom_route(
+            "com.example",
+            "app",
+            "1.0",
+            &common::dependencies([uses("deep"), uses("near")]),
+        ),
+        common::pom_route(
+            "com.example",
+            "deep",
+            "1.0",
+            &common::dependencies([uses("deeper")]),
+        ),
+        common::pom_route(
+            "com.example",
+            "deeper",
+            "1.0",
+            &common::dependencies([lib("2.0")]),
+        ),
+        common::pom_route(
+            "com.example",
+            "near",
+            "1.0",
+            &common::dependencies([lib("1.0")]),
+        ),
+        common::pom_route("com.example", "lib", "1.0", ""),
+        common::pom_route("com.example", "lib", "2.0", ""),
+    ])
+    .with_delays(HashMap::from([("near".to_string(), 200)]));
+    let mut resolver = Resolver::with_client(MavenClient::with_transport(
+        common::BASE_URL,
+        Arc::new(transport.clone()),
+    ));
+
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let tree = resolver.resolve(&[app]).await.unwrap();
+
+    assert!(
+        tree.all_dependencies
+            .iter()
+            .any(|c| c.to_string() == "com.example:lib:1.0")
+    );
+    let requests = transport.requests();
+    let unique: HashSet<_> = requests.iter().collect();
+    assert_eq!(requests.len(), unique.len(), "{requests:#?}");
+    assert!(requests.contains(&common::pom_url("com.example", "lib", "2.0")));
+}
+
+#[tokio::test]
+async fn test_mediated_conflicts_are_reported() {
+    let mut resolver =
+        Resolver::with_client(MavenClient::with_transport(BASE_URL, Arc::new(transport())));
+
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let tree = resolver.resolve(&[app]).await.unwrap();
+
+    let path = |via: &str| {
+        vec![
+            "com.example:app:1.0".to_string(),
+            format!("com.example:{via}:1.0"),
+        ]
+    };
+    assert_eq!(
+        tree.conflicts,
+        [VersionConflict {
+            artifact: "com.example:common".to_string(),
+            selected: "2.0".to_string(),
+            rejected: vec!["1.0".to_string()],
+            requests: vec![
+                VersionRequest {
+                    version: "2.0".to_string(),
+                    path: path("web"),
+                },
+                VersionRequest {
+                    version: "1.0".to_string(),
+                    path: path("util"),
+                },
+            ],
+        }]
+    );
+    assert_eq!(
+        tree.conflicts[0].to_string(),
+        "com.example:common: using 2.0 (1.0 requested by com.example:app:1.0 -> com.example:util:1.0)"
+    );
+}
+
+#[tokio::test]
+async fn test_root_version_conflict_is_reported() {
+    let mut resolver =
+        Resolver::with_client(MavenClient::with_transport(BASE_URL, Arc::new(transport())));
+
+    let roots = [
+        Coordinate::parse("com.example:util:1.0").unwrap(),
+        Coordinate::parse("com.example:common:2.0").unwrap(),
+    ];
+    let tree = resolver.resolve(&roots).await.unwrap();
+
+    assert_eq!(tree.conflicts.len(), 1);
+    assert_eq!(tree.conflicts[0].selected, "2.0");
+    assert_eq!(tree.conflicts[0].rejected, ["1.0"]);
+    assert_eq!(
+        tree.conflicts[0].requests_for("2.0").next().unwrap().path,
+        Vec::<String>::new()
+    );
+}
+
+#[tokio::test]
+async fn test_fanout_has_no_conflicts() {
+    let mut resolver =
+        Resolver::with_client(MavenClient::with_transport(BASE_URL, Arc::new(transport())));
+
+    let fanout = Coordinate::parse("com.example:fanout:1.0").unwrap();
+    let tree = resolver.resolve(&[fanout]).await.unwrap();
+    assert!(tree.conflicts.is_empty());
+}
+
+#[tokio::test]
+async fn test_root_version_is_not_a_conflict() {
+    let mut resolver =
+        Resolver::with_client(MavenClient::with_transport(BASE_URL, Arc::new(transport())))
+            .with_conflict_policy(ConflictPolicy::Fail);
+
+    let roots = [
+        Coordinate::parse("com.example:app:1.0").unwrap(),
+        Coordinate::parse("com.example:common:2.0").unwrap(),
+    ];
+    let tree = resolver.resolve(&roots).await.unwrap();
+    assert!(
+        tree.all_dependencies
+            .contains(&Coordinate::parse("com.example:common:2.0").unwrap())
+    );
+}
+
+#[test]
+fn test_default_config() {
+    let config = ResolverConfig::default();
+
+    assert_eq!(config.concurrency, DEFAULT_RESOLVE_CONCURRENCY);
+    assert_eq!(config.conflict_policy, ConflictPolicy::Nearest);
+    assert_eq!(config.timeout, None);
+    assert!(!config.full);
+}
+
+#[test]
+fn test_config_from_project() {
+    let mut project = ProjectConfig::new("app");
+    project.build.resolve_timeout_secs = Some(30);
+    project.http.resolve_concurrency = Some(3);
+
+    let config = ResolverConfig::from_project(&project).unwrap();
+
+    assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+    assert_eq!(config.concurrency, 3);
+    assert_eq!(config.conflict_policy, ConflictPolicy::Nearest);
+}
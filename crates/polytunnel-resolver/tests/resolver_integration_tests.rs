@@ -1,45 +1,12 @@
 //! Integration tests for dependency resolution using a deterministic transport.
 
-use polytunnel_maven::{
-    Coordinate, HttpResponse, HttpTransportFuture, MavenClient, MavenTransport,
-};
+mod common;
+
+use common::MockTransport;
+use polytunnel_maven::{Coordinate, MavenClient};
 use polytunnel_resolver::Resolver;
-use std::collections::HashMap;
 use std::sync::Arc;
 
-#[derive(Clone)]
-struct MockTransport {
-    routes: HashMap<String, (u16, Vec<u8>)>,
-}
-
-impl MockTransport {
-    fn new(routes: Vec<(String, u16, String)>) -> Self {
-        let routes = routes
-            .into_iter()
-            .map(|(path, status, body)| (path, (status, body.into_bytes())))
-            .collect();
-
-        Self { routes }
-    }
-}
-
-impl MavenTransport for MockTransport {
-    fn get(&self, url: String) -> HttpTransportFuture {
-        let response = self
-            .routes
-            .get(&url)
-            .cloned()
-            .unwrap_or((404, b"not found".to_vec()));
-
-        Box::pin(async move {
-            Ok(HttpResponse {
-                status: response.0,
-                body: response.1,
-            })
-        })
-    }
-}
-
 fn transitive_routes(base_url: &str) -> Vec<(String, u16, String)> {
     vec![
         (
@@ -324,6 +291,49 @@ async fn test_resolve_pom_packaging_excluded_from_all_dependencies() {
     );
 }
 
+fn bundle_artifact_routes(base_url: &str) -> Vec<(String, u16, String)> {
+    vec![(
+        format!(
+            "{base_url}/org/apache/felix/org.apache.felix.scr/2.2.6/org.apache.felix.scr-2.2.6.pom"
+        ),
+        200,
+        r#"
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>org.apache.felix</groupId>
+  <artifactId>org.apache.felix.scr</artifactId>
+  <version>2.2.6</version>
+  <packaging>bundle</packaging>
+</project>
+"#
+        .to_string(),
+    )]
+}
+
+#[tokio::test]
+async fn test_resolve_bundle_packaging_downloads_as_jar() {
+    let base_url = "https://repo.example.test";
+    let client = MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(bundle_artifact_routes(base_url))),
+    );
+    let mut resolver = Resolver::with_client(client.clone());
+    let root = Coordinate::parse("org.apache.felix:org.apache.felix.scr:2.2.6").unwrap();
+    let tree = resolver.resolve(&[root]).await.unwrap();
+
+    let bundle = tree
+        .all_dependencies
+        .iter()
+        .find(|c| c.artifact_id == "org.apache.felix.scr")
+        .expect("bundle-packaged artifact should be downloadable");
+    assert_eq!(
+        client.jar_url(bundle),
+        format!(
+            "{base_url}/org/apache/felix/org.apache.felix.scr/2.2.6/org.apache.felix.scr-2.2.6.jar"
+        )
+    );
+}
+
 #[tokio::test]
 async fn test_resolve_transitive_dependencies_with_graph() {
     let base_url = "https://repo.example.test";
@@ -442,3 +452,213 @@ async fn test_resolver_ignores_missing_parent_and_transitive_errors() {
             .is_some()
     );
 }
+
+#[tokio::test]
+async fn test_root_fetch_failure_carries_coordinate_chain() {
+    let base_url = "https://repo.example.test";
+    let mut resolver = Resolver::with_client(MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(vec![])),
+    ));
+    let root = Coordinate::parse("org.missing:root:1.0.0").unwrap();
+
+    let err = resolver.resolve(&[root]).await.unwrap_err();
+
+    match err {
+        polytunnel_resolver::ResolverError::FetchFailed {
+            coordinate, chain, ..
+        } => {
+            assert_eq!(coordinate, "org.missing:root:1.0.0");
+            assert_eq!(chain, vec!["org.missing:root:1.0.0".to_string()]);
+        }
+        other => panic!("expected FetchFailed, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_fetch_failed_message_lists_path_from_root() {
+    let err = polytunnel_resolver::ResolverError::FetchFailed {
+        coordinate: "org.x:y:1.0".to_string(),
+        chain: vec![
+            "com.example:app:1.0".to_string(),
+            "com.example:lib:2.0".to_string(),
+            "org.x:y:1.0".to_string(),
+        ],
+        source: Box::new(polytunnel_maven::MavenError::HttpStatus {
+            status: 404,
+            url: "https://repo.example.test/org/x/y/1.0/y-1.0.pom".to_string(),
+        }),
+    };
+
+    let message = err.to_string();
+    assert!(message.starts_with(
+        "failed to fetch org.x:y:1.0 (via com.example:app:1.0 -> com.example:lib:2.0 -> org.x:y:1.0)"
+    ));
+    assert!(message.contains("HTTP status 404"));
+}
+
+#[test]
+fn test_fetch_failed_message_for_root_omits_via() {
+    let err = polytunnel_resolver::ResolverError::FetchFailed {
+        coordinate: "org.x:y:1.0".to_string(),
+        chain: vec!["org.x:y:1.0".to_string()],
+        source: Box::new(polytunnel_maven::MavenError::ArtifactNotFound {
+            coordinate: "org.x:y:1.0".to_string(),
+        }),
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "failed to fetch org.x:y:1.0: Artifact not found: org.x:y:1.0"
+    );
+}
+
+#[tokio::test]
+async fn test_resolver_reports_pom_coordinate_mismatch() {
+    let base_url = "https://repo.example.test";
+    let mut resolver = Resolver::with_client(MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(vec![(
+            format!("{base_url}/com/example/app/1.0.0/app-1.0.0.pom"),
+            200,
+            r#"<project>
+  <groupId>com.example</groupId>
+  <artifactId>poisoned</artifactId>
+  <version>1.0.0</version>
+</project>"#
+                .to_string(),
+        )])),
+    ));
+    let root = Coordinate::parse("com.example:app:1.0.0").unwrap();
+
+    let err = resolver.resolve(&[root]).await.unwrap_err();
+    match err {
+        polytunnel_resolver::ResolverError::FetchFailed { source, .. } => {
+            assert!(matches!(
+                *source,
+                polytunnel_maven::MavenError::CoordinateMismatch { .. }
+            ));
+        }
+        other => panic!("expected FetchFailed, got {other:?}"),
+    }
+}
+
+fn scoped_transitive_routes(base_url: &str) -> Vec<(String, u16, String)> {
+    let leaf = |artifact: &str| {
+        (
+            format!("{base_url}/org/dep/{artifact}/1.0.0/{artifact}-1.0.0.pom"),
+            200,
+            format!(
+                "<project><groupId>org.dep</groupId><artifactId>{artifact}</artifactId><version>1.0.0</version></project>"
+            ),
+        )
+    };
+    vec![
+        (
+            format!("{base_url}/org/app/lib/1.0.0/lib-1.0.0.pom"),
+            200,
+            r#"
+<project>
+  <groupId>org.app</groupId>
+  <artifactId>lib</artifactId>
+  <version>1.0.0</version>
+  <dependencies>
+    <dependency><groupId>org.dep</groupId><artifactId>compiled</artifactId><version>1.0.0</version></dependency>
+    <dependency><groupId>org.dep</groupId><artifactId>runtime</artifactId><version>1.0.0</version><scope>runtime</scope></dependency>
+    <dependency><groupId>org.dep</groupId><artifactId>provided</artifactId><version>1.0.0</version><scope>provided</scope></dependency>
+    <dependency><groupId>org.dep</groupId><artifactId>tested</artifactId><version>1.0.0</version><scope>test</scope></dependency>
+  </dependencies>
+</project>
+"#
+            .to_string(),
+        ),
+        leaf("compiled"),
+        leaf("runtime"),
+        leaf("provided"),
+        leaf("tested"),
+    ]
+}
+
+#[tokio::test]
+async fn test_resolve_follows_only_transitive_scopes() {
+    let base_url = "https://repo.example.test";
+    let mut resolver = Resolver::with_client(MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(scoped_transitive_routes(base_url))),
+    ));
+    let root = Coordinate::parse("org.app:lib:1.0.0").unwrap();
+    let tree = resolver.resolve(&[root]).await.unwrap();
+
+    let mut artifacts: Vec<_> = tree
+        .all_dependencies
+        .iter()
+        .map(|c| c.artifact_id.as_str())
+        .collect();
+    artifacts.sort();
+    assert_eq!(artifacts, ["compiled", "lib", "runtime"]);
+}
+
+fn nested_provided_routes(base_url: &str) -> Vec<(String, u16, String)> {
+    let pom = |artifact: &str, deps: &str| {
+        (
+            format!("{base_url}/org/dep/{artifact}/1.0.0/{artifact}-1.0.0.pom"),
+            200,
+            format!(
+                "<project><groupId>org.dep</groupId><artifactId>{artifact}</artifactId><version>1.0.0</version><dependencies>{deps}</dependencies></project>"
+            ),
+        )
+    };
+    let dep = |artifact: &str, scope: &str| {
+        format!(
+            "<dependency><groupId>org.dep</groupId><artifactId>{artifact}</artifactId><version>1.0.0</version><scope>{scope}</scope></dependency>"
+        )
+    };
+    vec![
+        pom("root", &dep("middle", "compile")),
+        pom(
+            "middle",
+            &format!(
+                "{}{}",
+                dep("servlet-api", "provided"),
+                dep("util", "compile")
+            ),
+        ),
+        pom("util", &dep("annotations", "provided")),
+        pom("servlet-api", ""),
+        pom("annotations", ""),
+    ]
+}
+
+#[tokio::test]
+async fn test_resolve_drops_provided_dependencies_of_transitive_artifacts() {
+    let base_url = "https://repo.example.test";
+    let mut resolver = Resolver::with_client(MavenClient::with_transport(
+        base_url,
+        Arc::new(MockTransport::new(nested_provided_routes(base_url))),
+    ));
+    let root = Coordinate::parse("org.dep:root:1.0.0").unwrap();
+    let tree = resolver.resolve(&[root]).await.unwrap();
+
+    let mut artifacts: Vec<_> = tree
+        .all_dependencies
+        .iter()
+        .map(|c| c.artifact_id.as_str())
+        .collect();
+    artifacts.sort();
+    assert_eq!(artifacts, ["middle", "root", "util"]);
+
+    let skipped: Vec<_> = resolver
+        .trace
+        .sorted()
+        .into_iter()
+        .map(|event| event.to_string())
+        .filter(|line| line.starts_with("skipped"))
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            "skipped   org.dep:annotations from org.dep:util:1.0.0 (provided scope)",
+            "skipped   org.dep:servlet-api from org.dep:middle:1.0.0 (provided scope)",
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+//! Tests for the overall resolution deadline.
+
+use polytunnel_maven::{
+    Coordinate, HttpResponse, HttpTransportFuture, MavenClient, MavenTransport,
+};
+use polytunnel_resolver::{Resolver, ResolverError};
+use std::sync::Arc;
+use std::time::Duration;
+
+const BASE_URL: &str = "https://repo.example.test";
+
+/// Serves `app` immediately and stalls on every other POM
+struct StallingTransport;
+
+impl MavenTransport for StallingTransport {
+    fn get(&self, url: String) -> HttpTransportFuture {
+        Box::pin(async move {
+            if url.ends_with("/app-1.0.pom") {
+                return Ok(HttpResponse {
+                    status: 200,
+                    body: br#"<project>
+  <groupId>com.example</groupId>
+  <artifactId>app</artifactId>
+  <version>1.0</version>
+  <dependencies>
+    <dependency><groupId>org.slow</groupId><artifactId>slow</artifactId><version>1.0</version></dependency>
+  </dependencies>
+</project>"#
+                        .to_vec(),
+                    headers: Default::default(),
+                });
+            }
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(HttpResponse {
+                status: 404,
+                body: Vec::new(),
+                headers: Default::default(),
+            })
+        })
+    }
+}
+
+fn stalling_resolver() -> Resolver {
+    Resolver::with_client(MavenClient::with_transport(
+        BASE_URL,
+        Arc::new(StallingTransport),
+    ))
+}
+
+#[tokio::test]
+async fn test_resolve_times_out_and_reports_progress() {
+    let mut resolver = stalling_resolver().with_timeout(Duration::from_millis(200));
+    let root = Coordinate::parse("com.example:app:1.0").unwrap();
+
+    let err = resolver.resolve(&[root]).await.unwrap_err();
+
+    match err {
+        ResolverError::Timeout { resolved, .. } => assert_eq!(resolved, 1),
+        other => panic!("expected Timeout, got {other:?}"),
+    }
+    assert!(resolver.graph.get("com.example:app:1.0").is_some());
+}
+
+#[test]
+fn test_timeout_error_message() {
+    let err = ResolverError::Timeout {
+        seconds: 60,
+        resolved: 12,
+    };
+    assert_eq!(
+        err.to_string(),
+        "Dependency resolution timed out after 60s (12 artifacts resolved)"
+    );
+}
@@ -0,0 +1,155 @@
+//! Tests for ResolvedTree diffing and lockfile round-trips.
+
+use polytunnel_maven::{Coordinate, DependencyScope};
+use polytunnel_resolver::{Lockfile, ResolvedTree};
+use std::collections::HashMap;
+use tempfile::tempdir;
+
+fn tree(coords: &[&str]) -> ResolvedTree {
+    let all: Vec<Coordinate> = coords
+        .iter()
+        .map(|c| Coordinate::parse(c).unwrap())
+        .collect();
+    ResolvedTree {
+        root_dependencies: all.iter().take(1).cloned().collect(),
+        all_dependencies: all,
+        graph: Default::default(),
+        conflicts: Vec::new(),
+    }
+}
+
+#[test]
+fn test_diff_identical_trees_is_empty() {
+    let a = tree(&["org.a:a:1.0", "org.b:b:2.0"]);
+    let b = tree(&["org.a:a:1.0", "org.b:b:2.0"]);
+
+    assert!(a.diff(&b).is_empty());
+}
+
+#[test]
+fn test_diff_reports_added_removed_and_changed() {
+    let before = tree(&["org.a:a:1.0", "org.b:b:2.0", "org.c:c:1.0"]);
+    let after = tree(&["org.a:a:1.1", "org.c:c:1.0", "org.d:d:3.0"]);
+
+    let diff = before.diff(&after);
+
+    assert_eq!(diff.added, vec![Coordinate::parse("org.d:d:3.0").unwrap()]);
+    assert_eq!(
+        diff.removed,
+        vec![Coordinate::parse("org.b:b:2.0").unwrap()]
+    );
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].from.version, "1.0");
+    assert_eq!(diff.changed[0].to.version, "1.1");
+}
+
+#[test]
+fn test_diff_output_is_sorted() {
+    let before = tree(&[]);
+    let after = tree(&["org.z:z:1.0", "org.a:a:1.0", "org.m:m:1.0"]);
+
+    let added: Vec<String> = before
+        .diff(&after)
+        .added
+        .iter()
+        .map(|c| c.artifact_id.clone())
+        .collect();
+    assert_eq!(added, vec!["a", "m", "z"]);
+}
+
+#[test]
+fn test_diff_display_lists_one_change_per_line() {
+    let before = tree(&["org.a:a:1.0", "org.b:b:2.0"]);
+    let after = tree(&["org.a:a:1.1", "org.d:d:3.0"]);
+
+    assert_eq!(
+        before.diff(&after).to_string(),
+        "+ org.d:d:3.0\n- org.b:b:2.0\n~ org.a:a:1.0 -> 1.1"
+    );
+}
+
+#[test]
+fn test_diff_keeps_classifier_variants_apart() {
+    let before = tree(&[
+        "org.lwjgl:lwjgl:3.3.1",
+        "org.lwjgl:lwjgl:jar:natives-linux:3.3.1",
+    ]);
+    let same = tree(&[
+        "org.lwjgl:lwjgl:jar:natives-linux:3.3.1",
+        "org.lwjgl:lwjgl:3.3.1",
+    ]);
+    assert!(before.diff(&same).is_empty());
+
+    let after = tree(&[
+        "org.lwjgl:lwjgl:3.3.1",
+        "org.lwjgl:lwjgl:jar:natives-linux:3.3.2",
+        "org.lwjgl:lwjgl:jar:natives-macos:3.3.1",
+    ]);
+    let diff = before.diff(&after);
+
+    assert_eq!(
+        diff.added,
+        [Coordinate::parse("org.lwjgl:lwjgl:jar:natives-macos:3.3.1").unwrap()]
+    );
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(
+        diff.changed[0].from.classifier.as_deref(),
+        Some("natives-linux")
+    );
+    assert_eq!(diff.changed[0].to.version, "3.3.2");
+}
+
+#[test]
+fn test_lockfile_round_trip() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.lock");
+    let original = tree(&["org.a:a:1.0", "org.b:b:2.0"]);
+
+    Lockfile::from_tree(&original).save(&path).unwrap();
+    let loaded = Lockfile::load(&path).unwrap().expect("lockfile exists");
+
+    assert!(original.diff(&loaded.to_tree()).is_empty());
+    assert_eq!(
+        loaded.to_tree().root_dependencies,
+        original.root_dependencies
+    );
+}
+
+#[test]
+fn test_lockfile_round_trip_keeps_scopes() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.lock");
+    let scopes = HashMap::from([
+        ("org.a:a:1.0".to_string(), DependencyScope::Compile),
+        ("org.b:b:2.0".to_string(), DependencyScope::Test),
+    ]);
+
+    Lockfile::from_tree(&tree(&["org.a:a:1.0", "org.b:b:2.0"]))
+        .with_scopes(&scopes)
+        .save(&path)
+        .unwrap();
+    let loaded = Lockfile::load(&path).unwrap().unwrap();
+
+    assert_eq!(loaded.scopes.len(), 2);
+    assert_eq!(loaded.scopes["org.b:b:2.0"], DependencyScope::Test);
+}
+
+#[test]
+fn test_lockfile_load_missing_returns_none() {
+    let dir = tempdir().unwrap();
+    assert!(
+        Lockfile::load(&dir.path().join("polytunnel.lock"))
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_lockfile_load_invalid_fails() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("polytunnel.lock");
+    std::fs::write(&path, "not = [valid").unwrap();
+
+    assert!(Lockfile::load(&path).is_err());
+}
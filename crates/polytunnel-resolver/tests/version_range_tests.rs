@@ -0,0 +1,159 @@
+//! Tests for dependencies declared with a version range or `LATEST`/`RELEASE`.
+
+use polytunnel_core::VersionPolicy;
+mod common;
+
+use common::{BASE_URL, pom_route};
+use polytunnel_maven::{Coordinate, MavenClient};
+use polytunnel_resolver::{Resolver, ResolverError};
+use std::collections::HashMap;
+
+/// `com.example:lib` published as 1.2.0, 1.2.3, 1.4.0, and 2.0.0
+fn client() -> MavenClient {
+    client_with(Vec::new())
+}
+
+/// [`client`] that also serves `routes`
+fn client_with(extra: Vec<(String, u16, String)>) -> MavenClient {
+    let metadata = "<metadata><groupId>com.example</groupId><artifactId>lib</artifactId><versioning><versions>\
+        <version>1.2.0</version><version>1.2.3</version><version>1.4.0</version><version>2.0.0</version>\
+        </versions></versioning></metadata>";
+    let mut routes: Vec<_> = ["1.2.0", "1.2.3", "1.4.0", "2.0.0"]
+        .into_iter()
+        .map(|version| pom_route("com.example", "lib", version, ""))
+        .collect();
+    routes.push((
+        format!("{BASE_URL}/com/example/lib/maven-metadata.xml"),
+        200,
+        metadata.to_string(),
+    ));
+    routes.extend(extra);
+    common::client(routes)
+}
+
+async fn resolve(version: &str) -> Result<Vec<Coordinate>, ResolverError> {
+    let mut resolver = Resolver::with_client(client());
+    let root = Coordinate::new("com.example", "lib", version);
+    resolver
+        .resolve(&[root])
+        .await
+        .map(|tree| tree.all_dependencies)
+}
+
+#[tokio::test]
+async fn test_compatible_range_resolves_newest_version_below_next_major() {
+    let range = VersionPolicy::Compatible.format("1.2.3").unwrap();
+    let resolved = resolve(&range).await.unwrap();
+
+    assert_eq!(resolved, [Coordinate::new("com.example", "lib", "1.4.0")]);
+}
+
+#[tokio::test]
+async fn test_exact_version_is_used_as_is() {
+    let version = VersionPolicy::Exact.format("1.2.3").unwrap();
+    let resolved = resolve(&version).await.unwrap();
+
+    assert_eq!(resolved, [Coordinate::new("com.example", "lib", "1.2.3")]);
+}
+
+#[tokio::test]
+async fn test_range_roots_are_pinned_in_the_tree() {
+    let mut resolver = Resolver::with_client(client());
+    let tree = resolver
+        .resolve(&[Coordinate::new("com.example", "lib", "[1.0,1.3)")])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        tree.root_dependencies,
+        [Coordinate::new("com.example", "lib", "1.2.3")]
+    );
+}
+
+#[tokio::test]
+async fn test_range_without_matching_version_fails() {
+    let error = resolve("[3.0,4.0)").await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        ResolverError::NoVersionInRange { ref artifact, ref range }
+            if artifact == "com.example:lib" && range == "[3.0,4.0)"
+    ));
+}
+
+#[tokio::test]
+async fn test_unsupported_range_fails() {
+    let error = resolve("(,1.0],[1.2,)").await.unwrap_err();
+
+    assert!(matches!(error, ResolverError::InvalidVersionRange { .. }));
+}
+
+/// `com.example:app:1.0` depending on `com.example:lib` at `version`
+fn app_client(version: &str) -> MavenClient {
+    client_with(vec![pom_route(
+        "com.example",
+        "app",
+        "1.0",
+        &format!(
+            "<dependencies><dependency><groupId>com.example</groupId><artifactId>lib</artifactId><version>{version}</version></dependency></dependencies>"
+        ),
+    )])
+}
+
+#[tokio::test]
+async fn test_transitive_range_is_pinned_to_newest_match() {
+    let mut resolver = Resolver::with_client(app_client("[1.0,1.3)"));
+    let app = Coordinate::new("com.example", "app", "1.0");
+    let tree = resolver.resolve(std::slice::from_ref(&app)).await.unwrap();
+
+    let lib = Coordinate::new("com.example", "lib", "1.2.3");
+    assert_eq!(tree.all_dependencies, [app.clone(), lib.clone()]);
+    assert_eq!(
+        tree.graph.get(&app.to_string()).unwrap().dependencies,
+        [lib]
+    );
+}
+
+#[tokio::test]
+async fn test_managed_version_overrides_transitive_range() {
+    let mut resolver = Resolver::with_client(app_client("[1.0,1.3)")).with_managed_versions(
+        HashMap::from([("com.example:lib".to_string(), "2.0.0".to_string())]),
+    );
+    let app = Coordinate::new("com.example", "app", "1.0");
+    let tree = resolver.resolve(&[app]).await.unwrap();
+
+    assert!(
+        tree.all_dependencies
+            .contains(&Coordinate::new("com.example", "lib", "2.0.0"))
+    );
+}
+
+#[tokio::test]
+async fn test_transitive_range_without_match_is_skipped() {
+    let mut resolver = Resolver::with_client(app_client("[3.0,)"));
+    let app = Coordinate::new("com.example", "app", "1.0");
+    let tree = resolver.resolve(std::slice::from_ref(&app)).await.unwrap();
+
+    assert_eq!(tree.all_dependencies, [app]);
+}
+
+#[tokio::test]
+async fn test_latest_and_release_roots_use_newest_published_version() {
+    for metaversion in ["LATEST", "RELEASE"] {
+        let resolved = resolve(metaversion).await.unwrap();
+
+        assert_eq!(resolved, [Coordinate::new("com.example", "lib", "2.0.0")]);
+    }
+}
+
+#[tokio::test]
+async fn test_transitive_release_is_pinned() {
+    let mut resolver = Resolver::with_client(app_client("RELEASE"));
+    let app = Coordinate::new("com.example", "app", "1.0");
+    let tree = resolver.resolve(std::slice::from_ref(&app)).await.unwrap();
+
+    assert_eq!(
+        tree.all_dependencies,
+        [app, Coordinate::new("com.example", "lib", "2.0.0")]
+    );
+}
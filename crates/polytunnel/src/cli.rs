@@ -1,6 +1,8 @@
 //! CLI argument parsing definitions
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "pt")]
@@ -18,31 +20,134 @@ pub enum Commands {
         /// Project name
         #[arg(default_value = "my-java-app")]
         name: String,
+        /// Scaffold a new `<NAME>` directory from a template (a zip URL or git repository)
+        #[arg(long, value_name = "URL")]
+        from_archetype: Option<String>,
+        /// Expand the template even if `<NAME>` is a non-empty directory
+        #[arg(long, requires = "from_archetype")]
+        force: bool,
     },
     /// Add a dependency
     Add {
         /// Dependency in format: groupId:artifactId:version
-        dependency: String,
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        dependency: Option<String>,
+        /// Add every coordinate listed in FILE, one per line (`-` reads stdin)
+        #[arg(long, value_name = "FILE")]
+        file: Option<String>,
         /// Dependency scope (compile, runtime, test, provided)
         #[arg(short, long)]
         scope: Option<String>,
+        /// Add to [dev-dependencies] (test scope)
+        #[arg(long, conflicts_with = "scope")]
+        dev: bool,
+        /// Pin the exact version (the default)
+        #[arg(long, conflicts_with = "compatible")]
+        exact: bool,
+        /// Write a range accepting updates up to the next major version, e.g. [1.2.3,2.0.0)
+        #[arg(long)]
+        compatible: bool,
+        /// Write the dependency without contacting any repository
+        #[arg(long)]
+        no_resolve: bool,
     },
     /// Remove a dependency
     Remove {
         /// Dependency in format: groupId:artifactId
         dependency: String,
     },
+    /// Normalize polytunnel.toml (sorted, canonical dependency forms)
+    FmtConfig,
     /// Sync dependencies
     Sync {
+        /// Verbose output (-vv also logs dependency cache hits/misses)
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+
+        /// Cap the JAR download rate, e.g. 500K or 2M bytes/sec (overrides `[http]`)
+        #[arg(long, value_name = "RATE", value_parser = crate::commands::parse_byte_rate)]
+        max_download_speed: Option<u64>,
+
+        #[command(flatten)]
+        repositories: RepositoryArgs,
+    },
+    /// Resolve dependencies and print the compile classpath
+    Resolve {
+        /// Include optional dependencies and provided transitives (for analysis, not builds)
+        #[arg(long)]
+        full: bool,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        #[command(flatten)]
+        repositories: RepositoryArgs,
     },
-    /// Show dependency tree
-    Tree {
+    /// List the dependency JARs on a classpath with their scope and why they are included
+    ExplainClasspath {
+        /// Classpath to explain
+        #[arg(long, value_enum, default_value_t = ClasspathArg::Compile)]
+        scope: ClasspathArg,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        #[command(flatten)]
+        repositories: RepositoryArgs,
+    },
+    /// Show dependency tree
+    Tree {
+        /// Verbose output (-vv also prints every resolution decision)
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+
+        /// Hide dependencies in this effective scope, with their subtrees (repeatable)
+        #[arg(long = "prune-scope", value_enum, value_name = "SCOPE")]
+        prune_scope: Vec<ScopeArg>,
+
+        /// Hide test-scoped dependencies (same as --prune-scope test)
+        #[arg(long)]
+        no_test: bool,
+
+        /// Show only the paths to artifacts requested in more than one version
+        #[arg(long)]
+        duplicates_only: bool,
+
+        #[command(flatten)]
+        repositories: RepositoryArgs,
+    },
+    /// List the published versions of an artifact, newest first
+    Versions {
+        /// Artifact in format: groupId:artifactId
+        artifact: String,
+        /// Show at most this many versions
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+        /// Also list -SNAPSHOT versions
+        #[arg(long)]
+        include_snapshots: bool,
+    },
+    /// Search Maven Central for artifacts
+    Search {
+        /// Search terms (e.g. `guava` or `g:com.google.guava`)
+        query: String,
+        /// Page of results to show, starting at 1
+        #[arg(long, default_value_t = 1)]
+        page: u32,
+        /// Results per page
+        #[arg(long, default_value_t = 20)]
+        rows: u32,
+    },
+    /// List resolved dependencies
+    Deps {
+        /// Group dependencies by license and check them against [licenses] allowed
+        #[arg(long)]
+        licenses: bool,
+        /// Summarize the resolved tree: counts, depth, version conflicts, and cached JAR size
+        #[arg(long, conflicts_with = "licenses")]
+        stats: bool,
     },
     /// Build the project
     Build {
@@ -50,13 +155,73 @@ pub enum Commands {
         #[arg(long)]
         clean: bool,
 
+        /// Reset the incremental build state and recompile everything (outputs are kept, unlike --clean)
+        #[arg(long)]
+        no_incremental: bool,
+
         /// Skip test compilation and execution
         #[arg(long)]
         skip_tests: bool,
 
-        /// Verbose output
-        #[arg(short, long)]
-        verbose: bool,
+        /// Verbose output (-vv also logs dependency cache hits/misses)
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+
+        /// Print the exact javac/java commands before running them
+        #[arg(long)]
+        explain: bool,
+
+        /// Fail if resolution would change polytunnel.lock
+        #[arg(long)]
+        locked: bool,
+
+        /// Build from polytunnel.lock and the local cache without network access
+        #[arg(long)]
+        frozen: bool,
+
+        /// Resolve and download nothing; fail if a POM or JAR is not already cached
+        #[arg(long)]
+        offline: bool,
+
+        /// Report how long each build phase took
+        #[arg(long)]
+        timings: bool,
+
+        /// Format of the --timings report
+        #[arg(long, value_enum, default_value_t = TimingsFormat::Text, requires = "timings")]
+        format: TimingsFormat,
+
+        #[command(flatten)]
+        compiler_args: CompilerArgs,
+
+        /// Compile only this main source file (repeatable); dependents are not recompiled
+        #[arg(long, value_name = "FILE")]
+        only: Vec<PathBuf>,
+
+        /// Keep compiling after a step fails and report all failures at the end
+        #[arg(long)]
+        keep_going: bool,
+
+        /// Compile everything, then fail if javac reported any warnings (all of them are listed)
+        #[arg(long)]
+        fail_on_warning: bool,
+
+        /// Build only part of the project; `compile` skips test sources and test/runtime dependencies
+        #[arg(long, value_enum, default_value_t = BuildScopeArg::All)]
+        scope: BuildScopeArg,
+
+        /// Cap the JAR download rate, e.g. 500K or 2M bytes/sec (overrides `[http]`)
+        #[arg(long, value_name = "RATE", value_parser = crate::commands::parse_byte_rate)]
+        max_download_speed: Option<u64>,
+
+        #[command(flatten)]
+        repositories: RepositoryArgs,
+    },
+    /// Remove the build output directories
+    Clean {
+        /// Also remove the downloaded artifact cache
+        #[arg(long)]
+        cache: bool,
     },
     /// Run tests
     Test {
@@ -64,6 +229,10 @@ pub enum Commands {
         #[arg(value_name = "PATTERN")]
         pattern: Option<String>,
 
+        /// Run exactly this fully qualified test class, skipping discovery (repeatable)
+        #[arg(long = "class", value_name = "FQCN", conflicts_with = "pattern")]
+        classes: Vec<String>,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
@@ -71,20 +240,220 @@ pub enum Commands {
         /// Fail fast (stop on first failure)
         #[arg(long)]
         fail_fast: bool,
+
+        /// Only run tests with this JUnit 5 tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Skip tests with this JUnit 5 tag (repeatable)
+        #[arg(long = "exclude-tag", value_name = "TAG")]
+        exclude_tags: Vec<String>,
+
+        /// Capture test stdout/stderr and show it only for failing tests
+        #[arg(long)]
+        output_capture: bool,
+
+        /// Pass an argument through to the JUnit console launcher (repeatable)
+        #[arg(
+            long = "junit-console-arg",
+            value_name = "ARG",
+            allow_hyphen_values = true
+        )]
+        junit_console_args: Vec<String>,
+
+        /// Set an environment variable for the test JVM, overriding `[test.env]` (repeatable)
+        #[arg(
+            long = "test-env",
+            value_name = "KEY=VALUE",
+            value_parser = crate::commands::parse_env_var
+        )]
+        test_env: Vec<(String, String)>,
+
+        /// Pass an argument to the test JVM, after `[test] jvm_args` (repeatable)
+        #[arg(long = "jvm-arg", value_name = "ARG", allow_hyphen_values = true)]
+        jvm_args: Vec<String>,
+
+        /// Resolve and download nothing; fail if a POM or JAR is not already cached
+        #[arg(long)]
+        offline: bool,
+
+        #[command(flatten)]
+        compiler_args: CompilerArgs,
     },
     /// Run a Java main class
     Run {
-        /// Fully qualified main class (e.g. com.example.App)
-        main_class: String,
+        /// Fully qualified main class (default: build.main_class, or the only class with `main`)
+        main_class: Option<String>,
 
         /// Arguments passed to the Java application
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
 
+        /// Pass an argument to the JVM, after `[run] jvm_args` (repeatable)
+        #[arg(long = "jvm-arg", value_name = "ARG", allow_hyphen_values = true)]
+        jvm_args: Vec<String>,
+
+        /// Verbose output
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Generate API documentation with javadoc
+    Javadoc {
+        /// Link to the javadoc.io documentation of direct dependencies
+        #[arg(long)]
+        with_dependencies: bool,
+
+        /// Print the exact `javadoc` command line before running it
+        #[arg(long)]
+        explain: bool,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
     },
     /// Generate VS Code configuration
     Vscode,
+    /// Manage the downloaded artifact cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Show cache location, size, and artifact count
+    Info,
+    /// Remove the whole cache
+    Clean {
+        /// Only reset the incremental build state, keeping downloaded artifacts
+        #[arg(long)]
+        incremental_only: bool,
+    },
+    /// Remove artifacts not modified recently (lockfile artifacts are kept)
+    Prune {
+        /// Minimum age of removed artifacts (e.g. 30d, 12h, 45m)
+        #[arg(long, default_value = "30d", value_parser = crate::commands::parse_age)]
+        older_than: Duration,
+    },
+}
+
+/// Repositories added for a single run, e.g. a staging repository
+#[derive(Args, Debug, Clone, Default)]
+pub struct RepositoryArgs {
+    /// Also resolve from this repository, given as NAME=URL (repeatable)
+    #[arg(
+        long = "repository",
+        value_name = "NAME=URL",
+        value_parser = crate::commands::parse_repository
+    )]
+    pub repositories: Vec<polytunnel_core::Repository>,
+
+    /// Try --repository repositories before or after the configured ones
+    #[arg(long, value_enum, default_value_t = RepositoryPriorityArg::First)]
+    pub repository_priority: RepositoryPriorityArg,
+}
+
+impl RepositoryArgs {
+    /// Add the repositories to the effective list of `config`
+    pub fn apply(self, config: &mut polytunnel_core::ProjectConfig) {
+        config.add_repositories(self.repositories, self.repository_priority.into());
+    }
+}
+
+/// `--repository-priority` value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum RepositoryPriorityArg {
+    /// Before the configured repositories
+    #[default]
+    First,
+    /// After the configured repositories
+    Last,
+}
+
+impl From<RepositoryPriorityArg> for polytunnel_core::RepositoryPriority {
+    fn from(priority: RepositoryPriorityArg) -> Self {
+        match priority {
+            RepositoryPriorityArg::First => Self::First,
+            RepositoryPriorityArg::Last => Self::Last,
+        }
+    }
+}
+
+/// One-off `javac` arguments appended after the configured ones
+#[derive(Args, Debug, Clone, Default)]
+pub struct CompilerArgs {
+    /// Extra javac argument for main sources (repeatable)
+    #[arg(long = "compiler-arg", value_name = "ARG", allow_hyphen_values = true)]
+    pub main: Vec<String>,
+
+    /// Extra javac argument for test sources (repeatable)
+    #[arg(
+        long = "test-compiler-arg",
+        value_name = "ARG",
+        allow_hyphen_values = true
+    )]
+    pub test: Vec<String>,
+}
+
+/// Dependency scope accepted on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScopeArg {
+    /// Compile scope
+    Compile,
+    /// Runtime scope
+    Runtime,
+    /// Test scope
+    Test,
+    /// Provided scope
+    Provided,
+}
+
+impl From<ScopeArg> for polytunnel_maven::DependencyScope {
+    fn from(scope: ScopeArg) -> Self {
+        match scope {
+            ScopeArg::Compile => Self::Compile,
+            ScopeArg::Runtime => Self::Runtime,
+            ScopeArg::Test => Self::Test,
+            ScopeArg::Provided => Self::Provided,
+        }
+    }
+}
+
+/// Classpath chosen with `pt explain-classpath --scope`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ClasspathArg {
+    /// Compile and provided dependencies
+    Compile,
+    /// Compile and runtime dependencies
+    Runtime,
+    /// Every dependency, as used for compiling and running tests
+    Test,
+}
+
+/// Output format of `pt build --timings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimingsFormat {
+    /// Aligned table for terminals
+    Text,
+    /// Machine-readable JSON on stdout
+    Json,
+}
+
+/// Part of the project `pt build --scope` covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BuildScopeArg {
+    /// Main sources and compile-classpath dependencies only
+    Compile,
+    /// Main and test sources with every dependency, then the tests
+    All,
+}
+
+impl From<BuildScopeArg> for polytunnel_build::BuildScope {
+    fn from(scope: BuildScopeArg) -> Self {
+        match scope {
+            BuildScopeArg::Compile => Self::Compile,
+            BuildScopeArg::All => Self::All,
+        }
+    }
 }
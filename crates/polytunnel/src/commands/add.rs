@@ -1,21 +1,74 @@
 use super::utils::print_status;
-use color_eyre::eyre::{Result, bail};
+use color_eyre::eyre::{Result, bail, eyre};
 use colored::Color;
-use polytunnel_core::{DependencyScope, add_dependency_to_file, parse_add_coordinate};
-use std::path::Path;
+use polytunnel_core::{
+    DependencyScope, VersionPolicy, add_dependencies_to_file, add_dependency_to_file,
+    add_dev_dependencies_to_file, add_dev_dependency_to_file, parse_add_coordinate,
+};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
-pub fn cmd_add(dependency: &str, scope: Option<&str>) -> Result<()> {
-    do_add(dependency, scope, Path::new("polytunnel.toml"))
+/// Where `pt add` takes its coordinates from
+#[derive(Debug, PartialEq, Eq)]
+pub enum AddSource {
+    /// A single coordinate from the command line
+    Coordinate(String),
+    /// One coordinate per line of a file
+    File(PathBuf),
+    /// One coordinate per line of stdin
+    Stdin,
 }
 
-pub(crate) fn do_add(dependency: &str, scope: Option<&str>, config_path: &Path) -> Result<()> {
+impl AddSource {
+    /// Source for `--file FILE`, where `-` is stdin
+    ///
+    /// `pt` has already moved to the project root, so a relative `FILE` is
+    /// resolved against the directory it was invoked from.
+    pub fn file(file: &str, invocation_dir: &Path) -> Self {
+        if file == "-" {
+            Self::Stdin
+        } else {
+            Self::File(invocation_dir.join(file))
+        }
+    }
+}
+
+pub fn cmd_add(
+    source: AddSource,
+    scope: Option<&str>,
+    dev: bool,
+    policy: VersionPolicy,
+) -> Result<()> {
+    let config_path = Path::new("polytunnel.toml");
+    match source {
+        AddSource::Coordinate(dependency) if dev => do_add_dev(&dependency, policy, config_path),
+        AddSource::Coordinate(dependency) => do_add(&dependency, scope, policy, config_path),
+        AddSource::File(file) => {
+            let input = std::fs::read_to_string(&file)
+                .map_err(|e| eyre!("failed to read '{}': {}", file.display(), e))?;
+            do_add_batch(&input, scope, dev, policy, config_path)
+        }
+        AddSource::Stdin => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            do_add_batch(&input, scope, dev, policy, config_path)
+        }
+    }
+}
+
+pub(crate) fn do_add(
+    dependency: &str,
+    scope: Option<&str>,
+    policy: VersionPolicy,
+    config_path: &Path,
+) -> Result<()> {
     // 1. Validate config exists
     if !config_path.exists() {
         bail!("polytunnel.toml not found. Run `pt init` first.");
     }
 
-    // 2. Parse and validate coordinate
-    let (ga_key, version) = parse_add_coordinate(dependency)?;
+    // 2. Parse and validate coordinate, then apply the version policy
+    let (ga_key, version) = parse_dependency(dependency, policy)?;
 
     // 3. Parse scope if provided
     let dep_scope = match scope {
@@ -40,6 +93,108 @@ pub(crate) fn do_add(dependency: &str, scope: Option<&str>, config_path: &Path)
     Ok(())
 }
 
+/// Add a test dependency to `[dev-dependencies]`
+pub(crate) fn do_add_dev(
+    dependency: &str,
+    policy: VersionPolicy,
+    config_path: &Path,
+) -> Result<()> {
+    if !config_path.exists() {
+        bail!("polytunnel.toml not found. Run `pt init` first.");
+    }
+
+    let (ga_key, version) = parse_dependency(dependency, policy)?;
+    add_dev_dependency_to_file(config_path, &ga_key, &version)?;
+
+    print_status(
+        "Added",
+        &format!("{}:{} (dev-dependencies)", ga_key, version),
+        Color::Green,
+    );
+
+    Ok(())
+}
+
+/// Add every coordinate listed in `input`, one per line, in a single write
+///
+/// Blank lines and lines starting with `#` are skipped. Each line is reported
+/// as added or failed; invalid and duplicate lines do not stop the others
+/// from being written, but make the command fail at the end.
+pub(crate) fn do_add_batch(
+    input: &str,
+    scope: Option<&str>,
+    dev: bool,
+    policy: VersionPolicy,
+    config_path: &Path,
+) -> Result<()> {
+    if !config_path.exists() {
+        bail!("polytunnel.toml not found. Run `pt init` first.");
+    }
+    let dep_scope = match scope {
+        Some(s) => Some(parse_scope(s)?),
+        None => None,
+    };
+
+    // Every listed line with its parsed dependency or the reason it failed
+    let mut outcomes: Vec<(usize, Result<(String, String)>)> = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| (number, parse_dependency(line, policy)))
+        .collect();
+
+    let parsed: Vec<(String, String)> = outcomes
+        .iter()
+        .filter_map(|(_, outcome)| outcome.as_ref().ok().cloned())
+        .collect();
+    let results = if dev {
+        add_dev_dependencies_to_file(config_path, &parsed)?
+    } else {
+        add_dependencies_to_file(config_path, &parsed, dep_scope)?
+    };
+    let mut results = results.into_iter();
+    for (_, outcome) in &mut outcomes {
+        if outcome.is_ok()
+            && let Some(Err(e)) = results.next()
+        {
+            *outcome = Err(e.into());
+        }
+    }
+
+    let suffix = match (dev, dep_scope) {
+        (true, _) => " (dev-dependencies)".to_string(),
+        (false, Some(s)) => format!(" (scope: {})", scope_str(s)),
+        (false, None) => String::new(),
+    };
+    let mut failed = 0;
+    for (number, outcome) in outcomes {
+        match outcome {
+            Ok((ga_key, version)) => print_status(
+                "Added",
+                &format!("{}:{}{}", ga_key, version, suffix),
+                Color::Green,
+            ),
+            Err(e) => {
+                failed += 1;
+                print_status("Failed", &format!("line {}: {}", number, e), Color::Red);
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} dependencies could not be added", failed);
+    }
+    Ok(())
+}
+
+/// `groupId:artifactId` and the version to write for `dependency` under `policy`
+fn parse_dependency(dependency: &str, policy: VersionPolicy) -> Result<(String, String)> {
+    let (ga_key, version) = parse_add_coordinate(dependency)?;
+    let version = policy.format(&version)?;
+    Ok((ga_key, version))
+}
+
 fn parse_scope(s: &str) -> Result<DependencyScope> {
     match s.to_lowercase().as_str() {
         "compile" => Ok(DependencyScope::Compile),
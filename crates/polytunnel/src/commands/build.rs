@@ -1,27 +1,62 @@
-use super::utils::{check_test_failures, print_status, print_test_result};
+use super::sync::{format_duration, print_cache_report};
+use super::tree::conflict_lines;
+use super::utils::{print_status, report_build_result};
+use crate::cli::{CompilerArgs, RepositoryArgs, TimingsFormat};
 use crate::platform::Platform;
 use color_eyre::eyre::Result;
 use colored::*;
-use polytunnel_build::{BuildOptions, BuildOrchestrator};
+use polytunnel_build::{BuildOptions, BuildOrchestrator, BuildResult, BuildScope, LockMode};
 use polytunnel_core::ProjectConfig;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-pub async fn cmd_build(clean: bool, skip_tests: bool, verbose: bool) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub async fn cmd_build(
+    clean: bool,
+    no_incremental: bool,
+    skip_tests: bool,
+    verbose: u8,
+    explain: bool,
+    lock_mode: LockMode,
+    timings: Option<TimingsFormat>,
+    compiler_args: CompilerArgs,
+    only: Vec<PathBuf>,
+    keep_going: bool,
+    fail_on_warning: bool,
+    scope: BuildScope,
+    offline: bool,
+    max_download_speed: Option<u64>,
+    repositories: RepositoryArgs,
+) -> Result<()> {
     let start = Instant::now();
 
-    if verbose {
+    if verbose > 0 {
         eprintln!("Build platform: {}", Platform::detect());
     }
 
-    let config = ProjectConfig::load(Path::new("polytunnel.toml"))?;
+    let mut config = ProjectConfig::load(Path::new("polytunnel.toml"))?;
+    if max_download_speed.is_some() {
+        config.http.max_download_bytes_per_sec = max_download_speed;
+    }
+    repositories.apply(&mut config);
     let name = config.project.name.clone();
-    let mut orchestrator = BuildOrchestrator::new(config)?;
+    let mut orchestrator =
+        BuildOrchestrator::new(config)?.with_lock_mode("polytunnel.lock", lock_mode);
 
     let options = BuildOptions {
         clean,
+        no_incremental,
         skip_tests,
-        verbose,
+        verbose: verbose > 0,
+        explain,
+        test: Default::default(),
+        compiler_args: compiler_args.main,
+        test_compiler_args: compiler_args.test,
+        only,
+        keep_going,
+        fail_on_warning,
+        scope,
+        offline,
     };
 
     print_status(
@@ -30,12 +65,27 @@ pub async fn cmd_build(clean: bool, skip_tests: bool, verbose: bool) -> Result<(
         Color::Green,
     );
     let result = orchestrator.build(&options).await?;
-
-    if let Some(ref test_result) = result.test_result {
-        let test_duration = result.duration.as_secs_f64();
-        print_test_result(test_result, test_duration);
-        check_test_failures(test_result)?;
+    if verbose > 0
+        && let Some(tree) = orchestrator.get_resolved_tree()
+    {
+        for line in conflict_lines(&tree.conflicts) {
+            println!("{}", line);
+        }
+    }
+    if verbose > 1 {
+        print_cache_report(orchestrator.cache_report());
     }
+    match timings {
+        Some(TimingsFormat::Text) => {
+            for line in timings_lines(&result) {
+                println!("{}", line);
+            }
+        }
+        Some(TimingsFormat::Json) => println!("{}", timings_json(&result)),
+        None => {}
+    }
+
+    report_build_result(&result, verbose > 0)?;
 
     let total = start.elapsed();
     let duration_str = if total.as_secs() > 0 {
@@ -52,3 +102,40 @@ pub async fn cmd_build(clean: bool, skip_tests: bool, verbose: bool) -> Result<(
 
     Ok(())
 }
+
+/// `--timings` table: one row per phase that ran, then the total
+pub(crate) fn timings_lines(result: &BuildResult) -> Vec<String> {
+    let mut lines = vec![format!("{}", "Timings".bold())];
+    for timing in &result.timings {
+        lines.push(format!(
+            "    {:<14}{:>10}",
+            timing.phase.as_str(),
+            format_duration(&timing.duration)
+        ));
+    }
+    lines.push(format!(
+        "    {:<14}{:>10}",
+        "total",
+        format_duration(&result.duration)
+    ));
+    lines
+}
+
+/// `--timings --format json` document with millisecond durations
+pub(crate) fn timings_json(result: &BuildResult) -> String {
+    let phases: Vec<_> = result
+        .timings
+        .iter()
+        .map(|timing| {
+            serde_json::json!({
+                "phase": timing.phase.as_str(),
+                "duration_ms": timing.duration.as_millis() as u64,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "phases": phases,
+        "total_ms": result.duration.as_millis() as u64,
+    })
+    .to_string()
+}
@@ -0,0 +1,111 @@
+use super::utils::print_status;
+use color_eyre::eyre::Result;
+use colored::Color;
+use polytunnel_build::{ArtifactCache, BuildCache, CacheCleanup, CacheInfo};
+use polytunnel_core::ProjectConfig;
+use polytunnel_resolver::Lockfile;
+use std::path::Path;
+use std::time::Duration;
+
+pub fn cmd_cache_info() -> Result<()> {
+    let info = do_cache_info(Path::new("polytunnel.toml"))?;
+    print_status(
+        "Location",
+        &info.location.display().to_string(),
+        Color::Cyan,
+    );
+    print_status("Size", &format_size(info.total_size), Color::Cyan);
+    print_status("Artifacts", &info.artifact_count.to_string(), Color::Cyan);
+    Ok(())
+}
+
+pub fn cmd_cache_clean(incremental_only: bool) -> Result<()> {
+    if incremental_only {
+        do_cache_clean_incremental(Path::new("polytunnel.toml"))?;
+        print_status("Cleaned", "incremental build state", Color::Green);
+        return Ok(());
+    }
+    let cleanup = do_cache_clean(Path::new("polytunnel.toml"))?;
+    print_cleanup("Cleaned", &cleanup);
+    Ok(())
+}
+
+pub fn cmd_cache_prune(older_than: Duration) -> Result<()> {
+    let cleanup = do_cache_prune(Path::new("polytunnel.toml"), older_than)?;
+    print_cleanup("Pruned", &cleanup);
+    Ok(())
+}
+
+pub(crate) fn do_cache_info(config_path: &Path) -> Result<CacheInfo> {
+    let config = ProjectConfig::load(config_path)?;
+    Ok(ArtifactCache::from_config(&config).info()?)
+}
+
+pub(crate) fn do_cache_clean(config_path: &Path) -> Result<CacheCleanup> {
+    let config = ProjectConfig::load(config_path)?;
+    Ok(ArtifactCache::from_config(&config).clean()?)
+}
+
+/// Reset the incremental build state, keeping downloaded artifacts
+pub(crate) fn do_cache_clean_incremental(config_path: &Path) -> Result<()> {
+    let config = ProjectConfig::load(config_path)?;
+    BuildCache::new(&config)?.clear()?;
+    Ok(())
+}
+
+pub(crate) fn do_cache_prune(config_path: &Path, older_than: Duration) -> Result<CacheCleanup> {
+    let config = ProjectConfig::load(config_path)?;
+    let keep = Lockfile::load(&config_path.with_file_name("polytunnel.lock"))?
+        .map(|lock| lock.packages)
+        .unwrap_or_default();
+    Ok(ArtifactCache::from_config(&config).prune(older_than, &keep)?)
+}
+
+fn print_cleanup(status: &str, cleanup: &CacheCleanup) {
+    print_status(
+        status,
+        &format!(
+            "{} artifacts, {} freed",
+            cleanup.removed,
+            format_size(cleanup.freed_bytes)
+        ),
+        Color::Green,
+    );
+}
+
+/// Parse an age such as `30d`, `12h`, `45m`, `10s`, or `2w`
+pub fn parse_age(input: &str) -> std::result::Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (amount, unit) = input.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid age '{input}': expected e.g. 30d, 12h, 45m"))?;
+
+    let seconds_per_unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" | "" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("invalid age unit '{unit}': use s, m, h, d, or w")),
+    };
+    Ok(Duration::from_secs(amount * seconds_per_unit))
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
@@ -0,0 +1,30 @@
+use super::utils::print_status;
+use color_eyre::eyre::Result;
+use colored::Color;
+use polytunnel_build::{ArtifactCache, BuildOrchestrator};
+use polytunnel_core::ProjectConfig;
+use std::path::{Path, PathBuf};
+
+pub fn cmd_clean(cache: bool) -> Result<()> {
+    let removed = do_clean(Path::new("polytunnel.toml"), cache)?;
+    if removed.is_empty() {
+        print_status("Cleaned", "nothing to remove", Color::Green);
+    }
+    for path in removed {
+        print_status("Cleaned", &path.display().to_string(), Color::Green);
+    }
+    Ok(())
+}
+
+/// Remove the build outputs, and the artifact cache with `cache`, returning the removed directories
+pub(crate) fn do_clean(config_path: &Path, cache: bool) -> Result<Vec<PathBuf>> {
+    let config = ProjectConfig::load(config_path)?;
+    let artifact_cache = ArtifactCache::from_config(&config);
+    let mut removed = BuildOrchestrator::new(config)?.clean()?;
+
+    if cache && artifact_cache.root().exists() {
+        artifact_cache.clean()?;
+        removed.push(artifact_cache.root().to_path_buf());
+    }
+    Ok(removed)
+}
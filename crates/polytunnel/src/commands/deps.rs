@@ -0,0 +1,195 @@
+use super::cache::format_size;
+use super::tree::{parse_root_coords, project_client, resolve_project};
+use super::utils::print_status;
+use color_eyre::eyre::{Result, bail};
+use colored::*;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use polytunnel_core::{LicensesConfig, ProjectConfig};
+use polytunnel_maven::{ArtifactStore, Coordinate, FileSystemStore, License, jar_key};
+use polytunnel_resolver::{DependencyGraph, GraphStats};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Number of POMs fetched concurrently for the license report
+const LICENSE_FETCH_CONCURRENCY: usize = 8;
+
+pub async fn cmd_deps(licenses: bool, stats: bool) -> Result<()> {
+    do_deps(Path::new("polytunnel.toml"), licenses, stats).await
+}
+
+pub(crate) async fn do_deps(config_path: &Path, licenses: bool, stats: bool) -> Result<()> {
+    let config = ProjectConfig::load(config_path)?;
+    let client = project_client(&config);
+    let root_coords = parse_root_coords(&config);
+    let (resolver, _) = resolve_project(&config, client.clone(), &root_coords).await?;
+
+    if stats {
+        let store = FileSystemStore::new(&config.build.cache_dir);
+        let (jar_bytes, uncached) = cached_jar_bytes(&resolver.graph, &store);
+        let stats = GraphStats {
+            jar_bytes: Some(jar_bytes),
+            ..resolver.graph.stats()
+        };
+        for line in stats_lines(&stats, uncached) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let mut nodes: Vec<_> = resolver.graph.nodes().collect();
+    nodes.sort_by_key(|node| node.coordinate.to_string());
+
+    if !licenses {
+        for node in nodes {
+            println!(
+                "{} ({})",
+                node.coordinate,
+                format!("{:?}", node.scope).to_lowercase()
+            );
+        }
+        return Ok(());
+    }
+
+    print_status(
+        "Fetching",
+        &format!("licenses for {} dependencies", nodes.len()),
+        Color::Cyan,
+    );
+    let entries: Vec<(Coordinate, Vec<License>)> = stream::iter(nodes)
+        .map(|node| {
+            let client = &client;
+            async move {
+                let licenses = client.fetch_licenses(&node.coordinate).await?;
+                Ok::<_, polytunnel_maven::MavenError>((node.coordinate.clone(), licenses))
+            }
+        })
+        .buffered(LICENSE_FETCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let report = LicenseReport::new(&entries, &config.licenses);
+    for line in report.render() {
+        println!("{}", line);
+    }
+
+    if !report.is_compliant() {
+        bail!(
+            "{} dependencies with unknown license, {} with a disallowed license",
+            report.unknown.len(),
+            report.disallowed.len()
+        );
+    }
+    print_status(
+        "Checked",
+        &format!("{} dependencies", entries.len()),
+        Color::Green,
+    );
+    Ok(())
+}
+
+/// Total size of the graph's JARs in `store`, and how many JARs are not cached
+pub(crate) fn cached_jar_bytes(graph: &DependencyGraph, store: &FileSystemStore) -> (u64, usize) {
+    let mut bytes = 0;
+    let mut uncached = 0;
+    for node in graph.nodes() {
+        let key = jar_key(&node.coordinate);
+        match std::fs::metadata(store.path_for(&key)) {
+            Ok(metadata) if store.contains(&key) => bytes += metadata.len(),
+            _ => uncached += 1,
+        }
+    }
+    (bytes, uncached)
+}
+
+/// `pt deps --stats` report, one figure per line
+pub(crate) fn stats_lines(stats: &GraphStats, uncached: usize) -> Vec<String> {
+    let mut size = format_size(stats.jar_bytes.unwrap_or(0));
+    if uncached > 0 {
+        size.push_str(&format!(" ({} not cached)", uncached));
+    }
+    vec![
+        format!("{:<20} {}", "Dependencies:", stats.total),
+        format!("{:<20} {}", "  direct:", stats.direct),
+        format!("{:<20} {}", "  transitive:", stats.transitive),
+        format!("{:<20} {}", "Max depth:", stats.max_depth),
+        format!("{:<20} {}", "Version conflicts:", stats.conflicts),
+        format!("{:<20} {}", "JAR size:", size),
+    ]
+}
+
+/// Dependencies grouped by license and checked against `[licenses] allowed`
+#[derive(Debug, Default)]
+pub(crate) struct LicenseReport {
+    /// License display name → (allowed, dependencies declaring it)
+    pub groups: BTreeMap<String, (bool, Vec<String>)>,
+    /// Dependencies whose POM chain declares no license
+    pub unknown: Vec<String>,
+    /// Dependencies none of whose licenses are allowed
+    pub disallowed: Vec<String>,
+}
+
+impl LicenseReport {
+    /// Build the report; a dependency with several licenses passes if any of them is allowed
+    pub(crate) fn new(entries: &[(Coordinate, Vec<License>)], policy: &LicensesConfig) -> Self {
+        let mut report = Self::default();
+        for (coord, licenses) in entries {
+            if licenses.is_empty() {
+                report.unknown.push(coord.to_string());
+                continue;
+            }
+
+            let mut any_allowed = false;
+            for license in licenses {
+                let allowed = license_allowed(license, policy);
+                any_allowed |= allowed;
+                report
+                    .groups
+                    .entry(license.display_name().to_string())
+                    .or_insert_with(|| (allowed, Vec::new()))
+                    .1
+                    .push(coord.to_string());
+            }
+            if !any_allowed {
+                report.disallowed.push(coord.to_string());
+            }
+        }
+        report
+    }
+
+    /// Whether every dependency has at least one allowed license
+    pub(crate) fn is_compliant(&self) -> bool {
+        self.unknown.is_empty() && self.disallowed.is_empty()
+    }
+
+    /// Output lines: one header per license followed by its dependencies
+    pub(crate) fn render(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (license, (allowed, coords)) in &self.groups {
+            let header = format!("{} ({})", license, coords.len());
+            if *allowed {
+                lines.push(header.bold().to_string());
+            } else {
+                lines.push(format!("{} {}", header.bold(), "[not allowed]".red()));
+            }
+            lines.extend(coords.iter().map(|coord| format!("    {}", coord)));
+        }
+        if !self.unknown.is_empty() {
+            lines.push(format!(
+                "{} {}",
+                format!("Unknown ({})", self.unknown.len()).bold(),
+                "[no license declared]".red()
+            ));
+            lines.extend(self.unknown.iter().map(|coord| format!("    {}", coord)));
+        }
+        lines
+    }
+}
+
+fn license_allowed(license: &License, policy: &LicensesConfig) -> bool {
+    policy.is_empty()
+        || license
+            .name
+            .as_deref()
+            .is_some_and(|name| policy.allows(name))
+        || license.url.as_deref().is_some_and(|url| policy.allows(url))
+}
@@ -0,0 +1,105 @@
+use crate::cli::{ClasspathArg, RepositoryArgs};
+use color_eyre::eyre::Result;
+use polytunnel_build::{BuildOrchestrator, ClasspathEntry};
+use polytunnel_core::ProjectConfig;
+use polytunnel_maven::DependencyScope;
+use polytunnel_resolver::DependencyGraph;
+use std::path::Path;
+
+pub async fn cmd_explain_classpath(
+    classpath: ClasspathArg,
+    verbose: bool,
+    repositories: RepositoryArgs,
+) -> Result<()> {
+    do_explain_classpath(
+        Path::new("polytunnel.toml"),
+        classpath,
+        verbose,
+        repositories,
+    )
+    .await
+}
+
+/// Resolve dependencies and print the entries of one classpath as a table
+pub(crate) async fn do_explain_classpath(
+    config_path: &Path,
+    classpath: ClasspathArg,
+    verbose: bool,
+    repositories: RepositoryArgs,
+) -> Result<()> {
+    let mut config = ProjectConfig::load(config_path)?;
+    repositories.apply(&mut config);
+    let mut orchestrator = BuildOrchestrator::new(config)?;
+    orchestrator.resolve_dependencies(verbose).await?;
+
+    let empty = DependencyGraph::new();
+    let graph = orchestrator
+        .get_resolved_tree()
+        .map_or(&empty, |tree| &tree.graph);
+    for line in explain_lines(orchestrator.classpath_entries(), graph, classpath) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// `pt explain-classpath` table: coordinate, scope, and the shortest path from a root
+///
+/// Entries keep their classpath order. Direct dependencies show `(direct)`;
+/// entries the graph cannot place (e.g. from a frozen lockfile) show `-`.
+pub(crate) fn explain_lines(
+    entries: &[ClasspathEntry],
+    graph: &DependencyGraph,
+    classpath: ClasspathArg,
+) -> Vec<String> {
+    let rows: Vec<(String, String, String)> = entries
+        .iter()
+        .filter(|entry| on_classpath(classpath, entry.scope))
+        .map(|entry| {
+            let via = match graph.shortest_path_to(&entry.coordinate) {
+                Some(path) if path.len() > 1 => path[..path.len() - 1]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" -> "),
+                Some(_) => "(direct)".to_string(),
+                None => "-".to_string(),
+            };
+            let scope = format!("{:?}", entry.scope).to_lowercase();
+            (entry.coordinate.to_string(), scope, via)
+        })
+        .collect();
+    if rows.is_empty() {
+        return vec!["(classpath is empty)".to_string()];
+    }
+
+    let header = ("COORDINATE", "SCOPE", "VIA");
+    let coord_width = rows
+        .iter()
+        .map(|(coord, _, _)| coord.len())
+        .chain([header.0.len()])
+        .max()
+        .unwrap_or(0);
+    let scope_width = rows
+        .iter()
+        .map(|(_, scope, _)| scope.len())
+        .chain([header.1.len()])
+        .max()
+        .unwrap_or(0);
+
+    std::iter::once((
+        header.0.to_string(),
+        header.1.to_string(),
+        header.2.to_string(),
+    ))
+    .chain(rows)
+    .map(|(coord, scope, via)| format!("{:<coord_width$}  {:<scope_width$}  {}", coord, scope, via))
+    .collect()
+}
+
+fn on_classpath(classpath: ClasspathArg, scope: DependencyScope) -> bool {
+    match classpath {
+        ClasspathArg::Compile => scope.on_compile_classpath(),
+        ClasspathArg::Runtime => scope.on_runtime_classpath(),
+        ClasspathArg::Test => scope.on_test_classpath(),
+    }
+}
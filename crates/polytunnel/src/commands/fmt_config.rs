@@ -0,0 +1,33 @@
+use super::utils::print_status;
+use color_eyre::eyre::{Result, bail};
+use colored::Color;
+use polytunnel_core::format_config;
+use std::path::Path;
+
+pub fn cmd_fmt_config() -> Result<()> {
+    do_fmt_config(Path::new("polytunnel.toml")).map(|_| ())
+}
+
+/// Rewrite the config in canonical form, returning whether the file changed
+pub(crate) fn do_fmt_config(config_path: &Path) -> Result<bool> {
+    if !config_path.exists() {
+        bail!("polytunnel.toml not found. Run `pt init` first.");
+    }
+
+    let content = std::fs::read_to_string(config_path)?;
+    let formatted = format_config(&content)?;
+    let changed = formatted != content;
+
+    if changed {
+        std::fs::write(config_path, &formatted)?;
+        print_status(
+            "Formatted",
+            &config_path.display().to_string(),
+            Color::Green,
+        );
+    } else {
+        print_status("Unchanged", &config_path.display().to_string(), Color::Cyan);
+    }
+
+    Ok(changed)
+}
@@ -1,11 +1,32 @@
 use super::utils::print_status;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, bail};
 use colored::Color;
-use polytunnel_core::ProjectConfig;
-use std::path::Path;
+use polytunnel_core::{CONFIG_FILE_NAME, ProjectConfig};
+use polytunnel_maven::MavenClient;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
 
-pub fn cmd_init(name: &str) -> Result<()> {
-    do_init(name, Path::new("polytunnel.toml"))
+/// Placeholder replaced by the project name in archetype text files
+const NAME_PLACEHOLDER: &str = "{{name}}";
+
+pub async fn cmd_init(name: &str, from_archetype: Option<&str>, force: bool) -> Result<()> {
+    let Some(url) = from_archetype else {
+        return do_init(name, Path::new(CONFIG_FILE_NAME));
+    };
+
+    let files = fetch_archetype(url).await?;
+    let dest = Path::new(name);
+    let count = expand_archetype(name, &files, dest, force)?;
+    print_status(
+        "Created",
+        &format!("{} from {} ({} files)", name, url, count),
+        Color::Green,
+    );
+    let config_path = dest.join(CONFIG_FILE_NAME);
+    if !config_path.exists() {
+        do_init(name, &config_path)?;
+    }
+    Ok(())
 }
 
 pub(crate) fn do_init(name: &str, config_path: &Path) -> Result<()> {
@@ -23,3 +44,133 @@ pub(crate) fn do_init(name: &str, config_path: &Path) -> Result<()> {
     );
     Ok(())
 }
+
+/// Whether `url` names a git repository rather than a zip download
+pub(crate) fn is_git_url(url: &str) -> bool {
+    url.ends_with(".git")
+        || url.starts_with("git@")
+        || url.starts_with("git://")
+        || url.starts_with("ssh://")
+}
+
+/// Files of the archetype at `url`, as paths relative to the template root
+///
+/// Git repositories are shallow-cloned with `git`; any other URL is
+/// downloaded as a zip archive.
+async fn fetch_archetype(url: &str) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    if is_git_url(url) {
+        return clone_archetype(url);
+    }
+
+    print_status("Downloading", url, Color::Cyan);
+    let archive = MavenClient::new().fetch_url(url).await?;
+    let entries = polytunnel_maven::read_zip(&archive)?;
+    Ok(strip_common_root(
+        entries
+            .into_iter()
+            .map(|entry| (PathBuf::from(entry.name), entry.contents))
+            .collect(),
+    ))
+}
+
+fn clone_archetype(url: &str) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let checkout =
+        std::env::temp_dir().join(format!("polytunnel-archetype-{}", std::process::id()));
+    if checkout.exists() {
+        std::fs::remove_dir_all(&checkout)?;
+    }
+
+    print_status("Cloning", url, Color::Cyan);
+    let status = Command::new("git")
+        .args(["clone", "--quiet", "--depth", "1", url])
+        .arg(&checkout)
+        .status()?;
+    if !status.success() {
+        bail!("git clone of {} failed", url);
+    }
+
+    let mut files = Vec::new();
+    let result = read_tree(&checkout, &checkout, &mut files);
+    std::fs::remove_dir_all(&checkout)?;
+    result?;
+    Ok(files)
+}
+
+/// Collect every file below `dir` except `.git`, relative to `root`
+fn read_tree(root: &Path, dir: &Path, files: &mut Vec<(PathBuf, Vec<u8>)>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != ".git") {
+                read_tree(root, &path, files)?;
+            }
+        } else {
+            let relative = path.strip_prefix(root)?.to_path_buf();
+            files.push((relative, std::fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// Drop a top-level directory shared by every file, as in GitHub source archives
+pub(crate) fn strip_common_root(files: Vec<(PathBuf, Vec<u8>)>) -> Vec<(PathBuf, Vec<u8>)> {
+    let root = files
+        .first()
+        .and_then(|(path, _)| path.components().next())
+        .map(|component| component.as_os_str().to_owned());
+    let Some(root) = root else {
+        return files;
+    };
+    let shared = files.iter().all(|(path, _)| {
+        let mut components = path.components();
+        components.next().is_some_and(|c| c.as_os_str() == root) && components.next().is_some()
+    });
+    if !shared {
+        return files;
+    }
+    files
+        .into_iter()
+        .map(|(path, contents)| (path.strip_prefix(&root).unwrap().to_path_buf(), contents))
+        .collect()
+}
+
+/// Write the archetype `files` below `dest`, replacing `{{name}}` with `name` in text files
+///
+/// Returns the number of files written. Binary files (not UTF-8, or containing
+/// NUL bytes) are copied unchanged.
+pub(crate) fn expand_archetype(
+    name: &str,
+    files: &[(PathBuf, Vec<u8>)],
+    dest: &Path,
+    force: bool,
+) -> Result<usize> {
+    let non_empty = dest.is_dir() && std::fs::read_dir(dest)?.next().is_some();
+    if non_empty && !force {
+        bail!(
+            "{} already exists and is not empty; use --force to overwrite",
+            dest.display()
+        );
+    }
+    if let Some((path, _)) = files
+        .iter()
+        .find(|(path, _)| !path.components().all(|c| matches!(c, Component::Normal(_))))
+    {
+        bail!("Archetype contains unsafe path: {}", path.display());
+    }
+
+    for (path, contents) in files {
+        let target = dest.join(path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, substitute_name(contents, name))?;
+    }
+    Ok(files.len())
+}
+
+fn substitute_name(contents: &[u8], name: &str) -> Vec<u8> {
+    match std::str::from_utf8(contents) {
+        Ok(text) if !text.contains('\0') => text.replace(NAME_PLACEHOLDER, name).into_bytes(),
+        _ => contents.to_vec(),
+    }
+}
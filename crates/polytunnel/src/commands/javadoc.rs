@@ -0,0 +1,51 @@
+use super::utils::print_status;
+use color_eyre::eyre::Result;
+use colored::Color;
+use polytunnel_build::{BuildOrchestrator, JavadocOptions, JavadocResult};
+use polytunnel_core::ProjectConfig;
+use std::path::Path;
+
+pub async fn cmd_javadoc(with_dependencies: bool, explain: bool, verbose: bool) -> Result<()> {
+    let result = do_javadoc(
+        Path::new("polytunnel.toml"),
+        &JavadocOptions {
+            with_dependencies,
+            verbose,
+            explain,
+        },
+    )
+    .await?;
+
+    for line in javadoc_lines(&result) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+pub(crate) async fn do_javadoc(
+    config_path: &Path,
+    options: &JavadocOptions,
+) -> Result<JavadocResult> {
+    let config = ProjectConfig::load(config_path)?;
+    let name = config.project.name.clone();
+    let mut orchestrator = BuildOrchestrator::new(config)?;
+
+    print_status("Documenting", &name, Color::Green);
+    Ok(orchestrator.javadoc(options).await?)
+}
+
+/// Warnings followed by a summary of where the documentation was written
+pub(crate) fn javadoc_lines(result: &JavadocResult) -> Vec<String> {
+    let mut lines: Vec<String> = result.diagnostics.iter().map(ToString::to_string).collect();
+    if result.documented_files == 0 {
+        lines.push("No sources to document".to_string());
+    } else {
+        lines.push(format!(
+            "Generated docs for {} file(s) in {} ({} warning(s))",
+            result.documented_files,
+            result.output_dir.display(),
+            result.diagnostics.len()
+        ));
+    }
+    lines
+}
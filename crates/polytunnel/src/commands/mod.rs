@@ -2,13 +2,22 @@
 
 mod add;
 mod build;
+mod cache;
+mod clean;
+mod deps;
+mod explain_classpath;
+mod fmt_config;
 mod init;
+mod javadoc;
 mod remove;
+mod resolve;
 mod run;
 mod run_test;
+mod search;
 mod sync;
 mod tree;
 mod utils;
+mod versions;
 mod vscode;
 
 #[cfg(test)]
@@ -16,10 +25,20 @@ mod tests;
 
 pub use add::*;
 pub use build::*;
+pub use cache::*;
+pub use clean::*;
+pub use deps::*;
+pub use explain_classpath::*;
+pub use fmt_config::*;
 pub use init::*;
+pub use javadoc::*;
 pub use remove::*;
+pub use resolve::*;
 pub use run::*;
 pub use run_test::*;
+pub use search::*;
 pub use sync::*;
 pub use tree::*;
+pub use utils::{enter_project_root, parse_byte_rate, parse_repository};
+pub use versions::*;
 pub use vscode::*;
@@ -0,0 +1,31 @@
+use crate::cli::RepositoryArgs;
+use color_eyre::eyre::Result;
+use polytunnel_build::{BuildOrchestrator, format_classpath};
+use polytunnel_core::ProjectConfig;
+use std::path::Path;
+
+pub async fn cmd_resolve(full: bool, verbose: bool, repositories: RepositoryArgs) -> Result<()> {
+    do_resolve(Path::new("polytunnel.toml"), full, verbose, repositories).await
+}
+
+/// Resolve dependencies and print the compile classpath on a single line
+///
+/// `full` adds optional dependencies and `provided` transitives, for static
+/// analysis and IDE indexing; the lockfile is left untouched either way.
+pub(crate) async fn do_resolve(
+    config_path: &Path,
+    full: bool,
+    verbose: bool,
+    repositories: RepositoryArgs,
+) -> Result<()> {
+    let mut config = ProjectConfig::load(config_path)?;
+    repositories.apply(&mut config);
+    let mut orchestrator = BuildOrchestrator::new(config)?.with_full_classpath(full);
+    orchestrator.resolve_dependencies(verbose).await?;
+
+    println!(
+        "{}",
+        format_classpath(&orchestrator.get_resolved_classpath().compile_classpath)
+    );
+    Ok(())
+}
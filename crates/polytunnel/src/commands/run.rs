@@ -2,23 +2,41 @@ use super::utils::print_status;
 use crate::platform::Platform;
 use color_eyre::eyre::{Result, bail};
 use colored::Color;
-use polytunnel_build::{BuildOptions, BuildOrchestrator, format_classpath};
+use polytunnel_build::{BuildOptions, BuildOrchestrator, Classpath, detect_main_class};
 use polytunnel_core::ProjectConfig;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
 
-pub async fn cmd_run(main_class: &str, args: &[String], verbose: bool) -> Result<()> {
-    let exit_code = do_run(main_class, args, verbose, Path::new("polytunnel.toml")).await?;
+pub async fn cmd_run(
+    main_class: Option<&str>,
+    args: &[String],
+    jvm_args: &[String],
+    verbose: bool,
+) -> Result<()> {
+    let exit_code = do_run(
+        main_class,
+        args,
+        jvm_args,
+        verbose,
+        Path::new("polytunnel.toml"),
+    )
+    .await?;
     if exit_code != 0 {
         std::process::exit(exit_code);
     }
     Ok(())
 }
 
+/// Build the project and run `main_class`
+///
+/// Without a main class, `build.main_class` is used, or else the only class in
+/// `build.source_dirs` that declares `public static void main(String[])`.
+/// `jvm_args` are appended to `[run] jvm_args`.
 pub(crate) async fn do_run(
-    main_class: &str,
+    main_class: Option<&str>,
     args: &[String],
+    jvm_args: &[String],
     verbose: bool,
     config_path: &Path,
 ) -> Result<i32> {
@@ -26,8 +44,7 @@ pub(crate) async fn do_run(
         bail!("polytunnel.toml not found. Run `pt init` first.");
     }
 
-    let main_class = main_class.trim();
-    if main_class.is_empty() {
+    if main_class.is_some_and(|class| class.trim().is_empty()) {
         bail!("Main class must not be empty.");
     }
 
@@ -36,15 +53,40 @@ pub(crate) async fn do_run(
     }
 
     let config = ProjectConfig::load(config_path)?;
+    let main_class = match main_class.or(config.build.main_class.as_deref()) {
+        Some(class) => class.trim().to_string(),
+        None => {
+            let source_dirs: Vec<PathBuf> =
+                config.build.source_dirs.iter().map(PathBuf::from).collect();
+            let detected = detect_main_class(&source_dirs)?;
+            if verbose {
+                eprintln!("Detected main class: {}", detected);
+            }
+            detected
+        }
+    };
+    let main_class = main_class.as_str();
     let output_dir = PathBuf::from(&config.build.output_dir);
+    let mut all_jvm_args = config.run.jvm_args.clone();
+    all_jvm_args.extend(jvm_args.iter().cloned());
     let mut orchestrator = BuildOrchestrator::new(config)?;
 
     print_status("Compiling", &format!("{} (run)", main_class), Color::Green);
     orchestrator
         .build(&BuildOptions {
             clean: false,
+            no_incremental: false,
             skip_tests: true,
             verbose,
+            explain: false,
+            test: Default::default(),
+            compiler_args: Vec::new(),
+            test_compiler_args: Vec::new(),
+            only: Vec::new(),
+            keep_going: false,
+            fail_on_warning: false,
+            scope: Default::default(),
+            offline: false,
         })
         .await?;
 
@@ -53,10 +95,7 @@ pub(crate) async fn do_run(
     print_status("Running", main_class, Color::Green);
 
     let mut cmd = Command::new("java");
-    cmd.arg("-cp")
-        .arg(&classpath_str)
-        .arg(main_class)
-        .args(args)
+    cmd.args(java_args(&all_jvm_args, &classpath_str, main_class, args))
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
@@ -69,11 +108,32 @@ pub(crate) async fn do_run(
     Ok(status.code().unwrap_or(1))
 }
 
+/// Arguments of the `java` invocation: JVM options, then the classpath and
+/// main class, then the program's own arguments
+pub(crate) fn java_args(
+    jvm_args: &[String],
+    classpath: &str,
+    main_class: &str,
+    args: &[String],
+) -> Vec<String> {
+    let mut java_args = jvm_args.to_vec();
+    java_args.extend([
+        "-cp".to_string(),
+        classpath.to_string(),
+        main_class.to_string(),
+    ]);
+    java_args.extend(args.iter().cloned());
+    java_args
+}
+
 fn build_run_classpath(orchestrator: &BuildOrchestrator, output_dir: &Path) -> String {
     let classpaths = orchestrator.get_resolved_classpath();
     // Project classes must precede dependencies so local overrides win against
     // identically-named classes that may exist in resolved jars.
-    let mut entries = vec![output_dir.to_path_buf()];
-    entries.extend(classpaths.runtime_classpath.iter().cloned());
-    format_classpath(&entries)
+    let mut entries = Classpath::new();
+    entries.push(output_dir);
+    for path in &classpaths.runtime_classpath {
+        entries.push(path);
+    }
+    entries.to_arg_string()
 }
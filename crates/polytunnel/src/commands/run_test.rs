@@ -1,15 +1,27 @@
-use super::utils::{check_test_failures, print_status, print_test_result};
+use super::utils::{print_status, report_build_result};
+use crate::cli::CompilerArgs;
 use crate::platform::Platform;
 use color_eyre::eyre::Result;
 use colored::*;
-use polytunnel_build::{BuildOrchestrator, TestOptions};
+use polytunnel_build::{BuildOptions, BuildOrchestrator, TestOptions};
 use polytunnel_core::ProjectConfig;
 use std::path::Path;
-use std::time::Instant;
-
-pub async fn cmd_test(pattern: Option<String>, verbose: bool, fail_fast: bool) -> Result<()> {
-    let start = Instant::now();
 
+#[allow(clippy::too_many_arguments)]
+pub async fn cmd_test(
+    pattern: Option<String>,
+    classes: Vec<String>,
+    verbose: bool,
+    fail_fast: bool,
+    include_tags: Vec<String>,
+    exclude_tags: Vec<String>,
+    capture_output: bool,
+    launcher_args: Vec<String>,
+    env: Vec<(String, String)>,
+    jvm_args: Vec<String>,
+    offline: bool,
+    compiler_args: CompilerArgs,
+) -> Result<()> {
     if verbose {
         eprintln!("Build platform: {}", Platform::detect());
     }
@@ -19,28 +31,49 @@ pub async fn cmd_test(pattern: Option<String>, verbose: bool, fail_fast: bool) -
 
     let mut orchestrator = BuildOrchestrator::new(config)?;
 
-    // Resolve dependencies first (required for classpath)
-    orchestrator.resolve_dependencies(verbose).await?;
+    let options = BuildOptions {
+        clean: false,
+        no_incremental: false,
+        skip_tests: false,
+        verbose,
+        explain: false,
+        test: TestOptions {
+            pattern,
+            classes,
+            fail_fast,
+            include_tags,
+            exclude_tags,
+            capture_output,
+            launcher_args,
+            env,
+            jvm_args,
+            ..Default::default()
+        },
+        compiler_args: compiler_args.main,
+        test_compiler_args: compiler_args.test,
+        only: Vec::new(),
+        keep_going: false,
+        fail_on_warning: false,
+        scope: Default::default(),
+        offline,
+    };
 
     print_status(
         "Compiling",
         &format!("{} v{} (test)", name, env!("CARGO_PKG_VERSION")),
         Color::Green,
     );
-    orchestrator.compile_tests()?;
+    let result = orchestrator.build(&options).await?;
 
-    let options = TestOptions {
-        pattern,
-        verbose,
-        fail_fast,
-    };
-
-    print_status("Running", "tests", Color::Green);
-    let result = orchestrator.run_tests(&options).await?;
-
-    let duration_secs = start.elapsed().as_secs_f64();
-    print_test_result(&result, duration_secs);
-    check_test_failures(&result)?;
+    report_build_result(&result, verbose)
+}
 
-    Ok(())
+/// Parse a `KEY=VALUE` environment variable assignment
+pub fn parse_env_var(input: &str) -> std::result::Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!(
+            "invalid environment variable '{input}': expected KEY=VALUE"
+        )),
+    }
 }
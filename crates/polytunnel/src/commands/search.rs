@@ -0,0 +1,53 @@
+use super::tree::project_client;
+use color_eyre::eyre::{Result, bail};
+use polytunnel_core::{CONFIG_FILE_NAME, ProjectConfig, find_project_root};
+use polytunnel_maven::{MavenClient, SearchResponseBody};
+
+/// Print one page of Maven Central search results followed by the match count
+///
+/// `page` is 1-based. Inside a project the project's `[http]` settings are used.
+pub async fn cmd_search(query: &str, page: u32, rows: u32) -> Result<()> {
+    if page == 0 || rows == 0 {
+        bail!("--page and --rows must be at least 1");
+    }
+
+    let client = match find_project_root(&std::env::current_dir()?) {
+        Ok(root) => project_client(&ProjectConfig::load(&root.join(CONFIG_FILE_NAME))?),
+        Err(_) => MavenClient::new(),
+    };
+    let start = (page - 1).saturating_mul(rows);
+    let results = client.search_paged(query, start, rows).await?;
+
+    if results.docs.is_empty() {
+        if results.num_found == 0 {
+            bail!("No artifacts matching '{}' found", query);
+        }
+        bail!(
+            "Page {} is past the last of {} matches for '{}'",
+            page,
+            results.num_found,
+            query
+        );
+    }
+    for line in search_lines(&results) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// One coordinate per result, then a `Showing X-Y of N matches` summary
+pub(crate) fn search_lines(results: &SearchResponseBody) -> Vec<String> {
+    let mut lines: Vec<String> = results
+        .docs
+        .iter()
+        .map(|doc| doc.to_coordinate().to_string())
+        .collect();
+    lines.push(String::new());
+    lines.push(format!(
+        "Showing {}-{} of {} matches",
+        results.start + 1,
+        results.start + results.docs.len() as u32,
+        results.num_found
+    ));
+    lines
+}
@@ -1,24 +1,70 @@
+use super::cache::format_size;
 use super::utils::print_status;
+use crate::cli::RepositoryArgs;
 use color_eyre::eyre::Result;
 use colored::Color;
-use polytunnel_build::BuildOrchestrator;
+use polytunnel_build::{ArtifactSource, BuildOrchestrator, CacheReport};
 use polytunnel_core::ProjectConfig;
-use std::collections::HashSet;
+use polytunnel_maven::DependencyScope;
+use polytunnel_resolver::{Lockfile, ResolvedTree, TreeDiff};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::time::{Duration, Instant};
 
-pub async fn cmd_sync(verbose: bool) -> Result<()> {
-    do_sync(Path::new("polytunnel.toml"), verbose).await
+pub async fn cmd_sync(
+    verbose: u8,
+    max_download_speed: Option<u64>,
+    repositories: RepositoryArgs,
+) -> Result<()> {
+    do_sync(
+        Path::new("polytunnel.toml"),
+        verbose,
+        max_download_speed,
+        repositories,
+    )
+    .await
 }
 
-pub(crate) async fn do_sync(config_path: &Path, verbose: bool) -> Result<()> {
+pub(crate) async fn do_sync(
+    config_path: &Path,
+    verbose: u8,
+    max_download_speed: Option<u64>,
+    repositories: RepositoryArgs,
+) -> Result<()> {
     let start = Instant::now();
 
-    let config = ProjectConfig::load(config_path)?;
+    let mut config = ProjectConfig::load(config_path)?;
+    if max_download_speed.is_some() {
+        config.http.max_download_bytes_per_sec = max_download_speed;
+    }
+    repositories.apply(&mut config);
+    let lock_path = config_path.with_file_name("polytunnel.lock");
+    if config.dependencies.is_empty() {
+        // Still rewrite the lock so removing the last dependency doesn't leave it stale
+        update_lockfile(&lock_path, &ResolvedTree::default(), &HashMap::new())?;
+        print_status("Nothing", "to sync", Color::Green);
+        return Ok(());
+    }
     let mut orchestrator = BuildOrchestrator::new(config)?;
 
     print_status("Resolving", "dependencies", Color::Cyan);
-    orchestrator.resolve_dependencies(verbose).await?;
+    orchestrator.resolve_dependencies(verbose > 0).await?;
+    let report = orchestrator.cache_report();
+    for (status, message) in fetch_status_lines(report) {
+        let color = if status == "Cached" {
+            Color::Cyan
+        } else {
+            Color::Green
+        };
+        print_status(status, &message, color);
+    }
+    if verbose > 1 {
+        print_cache_summary(report);
+    }
+
+    if let Some(tree) = orchestrator.get_resolved_tree() {
+        update_lockfile(&lock_path, tree, orchestrator.effective_scopes())?;
+    }
 
     let duration = start.elapsed();
     let duration_str = format_duration(&duration);
@@ -40,6 +86,91 @@ pub(crate) async fn do_sync(config_path: &Path, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Save `tree` to `polytunnel.lock`, printing how it differs from the previous lock
+fn update_lockfile(
+    lock_path: &Path,
+    tree: &ResolvedTree,
+    scopes: &HashMap<String, DependencyScope>,
+) -> Result<()> {
+    if let Some(previous) = Lockfile::load(lock_path)? {
+        print_tree_diff(&previous.to_tree().diff(tree));
+    }
+    Lockfile::from_tree(tree)
+        .with_scopes(scopes)
+        .save(lock_path)?;
+    Ok(())
+}
+
+pub(crate) fn print_tree_diff(diff: &TreeDiff) {
+    for coord in &diff.added {
+        print_status("Adding", &coord.to_string(), Color::Green);
+    }
+    for change in &diff.changed {
+        print_status(
+            "Updating",
+            &format!("{} -> {}", change.from, change.to.version),
+            Color::Cyan,
+        );
+    }
+    for coord in &diff.removed {
+        print_status("Removing", &coord.to_string(), Color::Red);
+    }
+}
+
+/// Per-artifact cache hit/miss lines followed by the overall hit ratio (`-vv`)
+pub(crate) fn print_cache_report(report: &CacheReport) {
+    for line in cache_report_lines(report) {
+        eprintln!("{}", line);
+    }
+    print_cache_summary(report);
+}
+
+/// Overall cache hit ratio and downloaded size
+fn print_cache_summary(report: &CacheReport) {
+    print_status(
+        "Cache",
+        &format!(
+            "{}/{} artifacts from cache ({:.0}%), {} downloaded",
+            report.hits(),
+            report.fetches.len(),
+            report.hit_ratio() * 100.0,
+            format_size(report.downloaded_bytes())
+        ),
+        Color::Cyan,
+    );
+}
+
+/// `pt sync` status and message for each dependency JAR: `Cached` or `Downloaded`
+pub(crate) fn fetch_status_lines(report: &CacheReport) -> Vec<(&'static str, String)> {
+    report
+        .fetches
+        .iter()
+        .map(|fetch| match &fetch.source {
+            ArtifactSource::Cache => ("Cached", fetch.coordinate.to_string()),
+            ArtifactSource::Network { bytes, .. } => (
+                "Downloaded",
+                format!("{} ({})", fetch.coordinate, format_size(*bytes)),
+            ),
+        })
+        .collect()
+}
+
+pub(crate) fn cache_report_lines(report: &CacheReport) -> Vec<String> {
+    report
+        .fetches
+        .iter()
+        .map(|fetch| match &fetch.source {
+            ArtifactSource::Cache => format!("   cache hit   {}", fetch.coordinate),
+            ArtifactSource::Network { bytes, elapsed } => format!(
+                "   downloaded  {} ({} in {})",
+                fetch.coordinate,
+                format_size(*bytes),
+                format_duration(elapsed)
+            ),
+        })
+        .collect()
+}
+
 pub(crate) fn format_duration(duration: &Duration) -> String {
     if duration.as_secs() > 0 {
         format!("{}s", duration.as_secs())
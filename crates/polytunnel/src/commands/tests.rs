@@ -1,15 +1,41 @@
-use super::add::do_add;
-use super::init::do_init;
+use super::add::{AddSource, do_add, do_add_batch, do_add_dev};
+use super::build::{timings_json, timings_lines};
+use super::cache::{
+    do_cache_clean, do_cache_clean_incremental, do_cache_info, do_cache_prune, format_size,
+    parse_age,
+};
+use super::clean::do_clean;
+use super::deps::{LicenseReport, cached_jar_bytes, stats_lines};
+use super::explain_classpath::explain_lines;
+use super::fmt_config::do_fmt_config;
+use super::init::{do_init, expand_archetype, is_git_url, strip_common_root};
+use super::javadoc::javadoc_lines;
 use super::remove::do_remove;
-use super::run::do_run;
-use super::sync::format_duration;
-use super::tree::{parse_root_coords, render_tree};
+use super::run::{do_run, java_args};
+use super::run_test::parse_env_var;
+use super::search::search_lines;
+use super::sync::{cache_report_lines, fetch_status_lines, format_duration};
+use super::tree::{conflict_lines, parse_root_coords, render_tree, trace_lines};
+use super::utils::{failure_lines, parse_byte_rate, parse_repository};
+use super::versions::versions_lines;
+use crate::cli::{ClasspathArg, RepositoryArgs, RepositoryPriorityArg};
 use color_eyre::eyre::Result;
-use polytunnel_maven::Coordinate;
-use polytunnel_resolver::DependencyGraph;
+use polytunnel_core::{Repository, VersionPolicy};
+use polytunnel_maven::{
+    ArtifactStore, Coordinate, DependencyScope, FileSystemStore, SearchDoc, SearchResponseBody,
+    jar_key,
+};
+use polytunnel_resolver::{
+    DependencyGraph, GraphStats, ResolutionEvent, ResolutionTrace, VersionConflict, VersionRequest,
+};
+use std::collections::HashMap;
 use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tempfile::tempdir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 
 // === init tests ===
 
@@ -46,6 +72,98 @@ fn test_init_ignores_existing() -> Result<()> {
     Ok(())
 }
 
+fn archetype_files() -> Vec<(PathBuf, Vec<u8>)> {
+    vec![
+        (
+            PathBuf::from("polytunnel.toml"),
+            b"[project]\nname = \"{{name}}\"\n".to_vec(),
+        ),
+        (
+            PathBuf::from("src/main/java/App.java"),
+            b"// {{name}} entry point\nclass App {}\n".to_vec(),
+        ),
+        (PathBuf::from("logo.bin"), b"\0{{name}}\xff".to_vec()),
+    ]
+}
+
+#[test]
+fn test_expand_archetype_substitutes_name_in_text_files() -> Result<()> {
+    let dir = tempdir()?;
+    let dest = dir.path().join("shop");
+
+    let count = expand_archetype("shop", &archetype_files(), &dest, false)?;
+
+    assert_eq!(count, 3);
+    assert_eq!(
+        fs::read_to_string(dest.join("polytunnel.toml"))?,
+        "[project]\nname = \"shop\"\n"
+    );
+    assert!(fs::read_to_string(dest.join("src/main/java/App.java"))?.starts_with("// shop "));
+    assert_eq!(fs::read(dest.join("logo.bin"))?, b"\0{{name}}\xff");
+    Ok(())
+}
+
+#[test]
+fn test_expand_archetype_refuses_non_empty_directory_without_force() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("notes.txt"), "keep")?;
+
+    let err = expand_archetype("shop", &archetype_files(), dir.path(), false).unwrap_err();
+    assert!(err.to_string().contains("--force"));
+    assert!(!dir.path().join("polytunnel.toml").exists());
+
+    expand_archetype("shop", &archetype_files(), dir.path(), true)?;
+    assert!(dir.path().join("polytunnel.toml").exists());
+    assert_eq!(fs::read_to_string(dir.path().join("notes.txt"))?, "keep");
+    Ok(())
+}
+
+#[test]
+fn test_expand_archetype_rejects_paths_outside_destination() -> Result<()> {
+    let dir = tempdir()?;
+    let dest = dir.path().join("shop");
+    let files = vec![(PathBuf::from("../escape.txt"), b"x".to_vec())];
+
+    let err = expand_archetype("shop", &files, &dest, false).unwrap_err();
+    assert!(err.to_string().contains("unsafe path"));
+    assert!(!dir.path().join("escape.txt").exists());
+    Ok(())
+}
+
+#[test]
+fn test_strip_common_root_of_source_archive() {
+    let files = vec![
+        (PathBuf::from("template-main/polytunnel.toml"), vec![]),
+        (PathBuf::from("template-main/src/App.java"), vec![]),
+    ];
+    let paths: Vec<_> = strip_common_root(files)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            PathBuf::from("polytunnel.toml"),
+            PathBuf::from("src/App.java")
+        ]
+    );
+
+    let flat = vec![
+        (PathBuf::from("polytunnel.toml"), vec![]),
+        (PathBuf::from("src/App.java"), vec![]),
+    ];
+    assert_eq!(strip_common_root(flat.clone()), flat);
+}
+
+#[test]
+fn test_is_git_url() {
+    assert!(is_git_url("https://github.com/acme/template.git"));
+    assert!(is_git_url("git@github.com:acme/template.git"));
+    assert!(!is_git_url(
+        "https://github.com/acme/template/archive/refs/heads/main.zip"
+    ));
+}
+
 // === format_duration tests ===
 
 #[test]
@@ -77,6 +195,110 @@ fn test_format_duration_boundary() {
     assert_eq!(format_duration(&d), "1s");
 }
 
+fn timed_build_result() -> polytunnel_build::BuildResult {
+    use polytunnel_build::{BuildPhase, PhaseTiming};
+
+    polytunnel_build::BuildResult {
+        compiled_files: 3,
+        main_compilation: None,
+        test_compilation: None,
+        test_result: None,
+        duration: Duration::from_millis(2500),
+        timings: vec![
+            PhaseTiming::new(BuildPhase::Resolve, Duration::from_millis(120)),
+            PhaseTiming::new(BuildPhase::Download, Duration::from_millis(30)),
+            PhaseTiming::new(BuildPhase::CompileMain, Duration::from_millis(2100)),
+        ],
+    }
+}
+
+#[test]
+fn test_timings_lines_lists_phases_and_total() {
+    let lines = timings_lines(&timed_build_result());
+
+    assert!(lines[0].contains("Timings"));
+    assert!(lines[1].contains("resolve") && lines[1].ends_with("120ms"));
+    assert!(lines[2].contains("download") && lines[2].ends_with("30ms"));
+    assert!(lines[3].contains("compile-main") && lines[3].ends_with("2s"));
+    assert!(lines[4].contains("total") && lines[4].ends_with("2s"));
+    assert_eq!(lines.len(), 5);
+}
+
+#[test]
+fn test_timings_json_reports_milliseconds() {
+    let json: serde_json::Value =
+        serde_json::from_str(&timings_json(&timed_build_result())).unwrap();
+
+    assert_eq!(json["total_ms"], 2500);
+    let phases = json["phases"].as_array().unwrap();
+    assert_eq!(phases.len(), 3);
+    assert_eq!(phases[0]["phase"], "resolve");
+    assert_eq!(phases[0]["duration_ms"], 120);
+    assert_eq!(phases[2]["phase"], "compile-main");
+    assert_eq!(phases[2]["duration_ms"], 2100);
+}
+
+#[test]
+fn test_cache_report_lines() {
+    use polytunnel_build::{ArtifactFetch, ArtifactSource, CacheReport};
+
+    let report = CacheReport {
+        fetches: vec![
+            ArtifactFetch {
+                coordinate: Coordinate::new("org.slf4j", "slf4j-api", "2.0.9"),
+                source: ArtifactSource::Cache,
+            },
+            ArtifactFetch {
+                coordinate: Coordinate::new("com.google.guava", "guava", "33.0.0-jre"),
+                source: ArtifactSource::Network {
+                    bytes: 3 * 1024 * 1024,
+                    elapsed: Duration::from_millis(1500),
+                },
+            },
+        ],
+    };
+
+    assert_eq!(
+        cache_report_lines(&report),
+        vec![
+            "   cache hit   org.slf4j:slf4j-api:2.0.9",
+            "   downloaded  com.google.guava:guava:33.0.0-jre (3.0 MiB in 1s)",
+        ]
+    );
+}
+
+#[test]
+fn test_fetch_status_lines() {
+    use polytunnel_build::{ArtifactFetch, ArtifactSource, CacheReport};
+
+    let report = CacheReport {
+        fetches: vec![
+            ArtifactFetch {
+                coordinate: Coordinate::new("org.slf4j", "slf4j-api", "2.0.9"),
+                source: ArtifactSource::Cache,
+            },
+            ArtifactFetch {
+                coordinate: Coordinate::new("com.google.guava", "guava", "33.0.0-jre"),
+                source: ArtifactSource::Network {
+                    bytes: 3 * 1024 * 1024,
+                    elapsed: Duration::from_millis(1500),
+                },
+            },
+        ],
+    };
+
+    assert_eq!(
+        fetch_status_lines(&report),
+        vec![
+            ("Cached", "org.slf4j:slf4j-api:2.0.9".to_string()),
+            (
+                "Downloaded",
+                "com.google.guava:guava:33.0.0-jre (3.0 MiB)".to_string()
+            ),
+        ]
+    );
+}
+
 // === parse_root_coords tests ===
 
 #[test]
@@ -148,10 +370,17 @@ java_version = "17"
 
 // === render_tree tests ===
 
+fn compile_roots(coords: &[Coordinate]) -> Vec<(Coordinate, DependencyScope)> {
+    coords
+        .iter()
+        .map(|coord| (coord.clone(), DependencyScope::Compile))
+        .collect()
+}
+
 #[test]
 fn test_render_tree_empty_deps() {
     let graph = DependencyGraph::new();
-    let lines = render_tree("demo", &[], &graph, false);
+    let lines = render_tree("demo", &compile_roots(&[]), &graph, false, &[], false);
     assert_eq!(lines, vec!["demo v0.1.0"]);
 }
 
@@ -161,7 +390,14 @@ fn test_render_tree_single_root_no_children() {
     let mut graph = DependencyGraph::new();
     graph.add_node(coord.clone(), vec![], 0);
 
-    let lines = render_tree("myproject", &[coord], &graph, false);
+    let lines = render_tree(
+        "myproject",
+        &compile_roots(&[coord]),
+        &graph,
+        false,
+        &[],
+        false,
+    );
     assert_eq!(
         lines,
         vec!["myproject v0.1.0", "└── com.example:lib:1.0.0",]
@@ -176,7 +412,14 @@ fn test_render_tree_multiple_roots() {
     graph.add_node(coord1.clone(), vec![], 0);
     graph.add_node(coord2.clone(), vec![], 0);
 
-    let lines = render_tree("proj", &[coord1, coord2], &graph, false);
+    let lines = render_tree(
+        "proj",
+        &compile_roots(&[coord1, coord2]),
+        &graph,
+        false,
+        &[],
+        false,
+    );
     assert_eq!(
         lines,
         vec!["proj v0.1.0", "├── com.a:lib-a:1.0", "└── com.b:lib-b:2.0",]
@@ -194,7 +437,7 @@ fn test_render_tree_with_transitive_deps() {
     graph.add_node(child.clone(), vec![grandchild.clone()], 1);
     graph.add_node(grandchild.clone(), vec![], 2);
 
-    let lines = render_tree("proj", &[root], &graph, false);
+    let lines = render_tree("proj", &compile_roots(&[root]), &graph, false, &[], false);
     assert_eq!(
         lines,
         vec![
@@ -217,7 +460,14 @@ fn test_render_tree_marks_duplicates() {
     graph.add_node(root2.clone(), vec![shared.clone()], 0);
     graph.add_node(shared.clone(), vec![], 1);
 
-    let lines = render_tree("proj", &[root1, root2], &graph, false);
+    let lines = render_tree(
+        "proj",
+        &compile_roots(&[root1, root2]),
+        &graph,
+        false,
+        &[],
+        false,
+    );
     assert_eq!(
         lines,
         vec![
@@ -241,7 +491,7 @@ fn test_render_tree_multiple_children() {
     graph.add_node(child1.clone(), vec![], 1);
     graph.add_node(child2.clone(), vec![], 1);
 
-    let lines = render_tree("proj", &[root], &graph, false);
+    let lines = render_tree("proj", &compile_roots(&[root]), &graph, false, &[], false);
     assert_eq!(
         lines,
         vec![
@@ -259,7 +509,7 @@ fn test_render_tree_node_not_in_graph() {
     let coord = Coordinate::new("com.missing", "lib", "1.0");
     let graph = DependencyGraph::new();
 
-    let lines = render_tree("proj", &[coord], &graph, false);
+    let lines = render_tree("proj", &compile_roots(&[coord]), &graph, false, &[], false);
     assert_eq!(lines, vec!["proj v0.1.0", "└── com.missing:lib:1.0",]);
 }
 
@@ -274,7 +524,7 @@ fn test_render_tree_deep_nesting() {
     graph.add_node(b.clone(), vec![c.clone()], 1);
     graph.add_node(c.clone(), vec![], 2);
 
-    let lines = render_tree("proj", &[a], &graph, false);
+    let lines = render_tree("proj", &compile_roots(&[a]), &graph, false, &[], false);
     assert_eq!(lines.len(), 4);
     assert!(lines[3].starts_with("        └── "));
 }
@@ -283,11 +533,334 @@ fn test_render_tree_deep_nesting() {
 fn test_render_tree_verbose_flag_accepted() {
     // verbose=true should not change output format (currently unused but accepted)
     let graph = DependencyGraph::new();
-    let lines_normal = render_tree("proj", &[], &graph, false);
-    let lines_verbose = render_tree("proj", &[], &graph, true);
+    let lines_normal = render_tree("proj", &compile_roots(&[]), &graph, false, &[], false);
+    let lines_verbose = render_tree("proj", &compile_roots(&[]), &graph, true, &[], false);
     assert_eq!(lines_normal, lines_verbose);
 }
 
+#[test]
+fn test_render_tree_prunes_test_roots_and_their_subtrees() {
+    let app = Coordinate::new("com.example", "core", "1.0");
+    let junit = Coordinate::new("org.junit", "junit", "4.13");
+    let hamcrest = Coordinate::new("org.hamcrest", "hamcrest", "1.3");
+    let shared = Coordinate::new("com.shared", "common", "1.0");
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![shared.clone()], 0);
+    graph.add_node(junit.clone(), vec![hamcrest.clone(), shared.clone()], 0);
+    graph.add_node(hamcrest.clone(), vec![], 1);
+    graph.add_node(shared.clone(), vec![], 1);
+
+    let roots = vec![
+        (app, DependencyScope::Compile),
+        (junit, DependencyScope::Test),
+    ];
+    let lines = render_tree(
+        "proj",
+        &roots,
+        &graph,
+        false,
+        &[DependencyScope::Test],
+        false,
+    );
+    assert_eq!(
+        lines,
+        vec![
+            "proj v0.1.0 (2 dependencies; pruned: test)",
+            "└── com.example:core:1.0",
+            "    └── com.shared:common:1.0",
+        ]
+    );
+}
+
+#[test]
+fn test_render_tree_prunes_by_effective_scope() {
+    let app = Coordinate::new("com.example", "core", "1.0");
+    let driver = Coordinate::new("org.db", "driver", "1.0");
+    let pool = Coordinate::new("org.db", "pool", "1.0");
+    let api = Coordinate::new("org.api", "api", "1.0");
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![driver.clone(), api.clone()], 0);
+    graph.add_node_with_scope(
+        driver.clone(),
+        vec![pool.clone()],
+        1,
+        DependencyScope::Runtime,
+    );
+    // Declared compile, but only reachable through a runtime dependency
+    graph.add_node(pool.clone(), vec![], 2);
+    graph.add_node(api.clone(), vec![], 1);
+
+    let roots = vec![(app, DependencyScope::Compile)];
+    let lines = render_tree(
+        "proj",
+        &roots,
+        &graph,
+        false,
+        &[DependencyScope::Runtime],
+        false,
+    );
+    assert_eq!(
+        lines,
+        vec![
+            "proj v0.1.0 (2 dependencies; pruned: runtime)",
+            "└── com.example:core:1.0",
+            "    └── org.api:api:1.0",
+        ]
+    );
+}
+
+#[test]
+fn test_render_tree_without_pruning_keeps_test_roots() {
+    let junit = Coordinate::new("org.junit", "junit", "4.13");
+    let mut graph = DependencyGraph::new();
+    graph.add_node(junit.clone(), vec![], 0);
+
+    let lines = render_tree(
+        "proj",
+        &[(junit, DependencyScope::Test)],
+        &graph,
+        false,
+        &[],
+        false,
+    );
+    assert_eq!(lines, vec!["proj v0.1.0", "└── org.junit:junit:4.13"]);
+}
+
+#[test]
+fn test_render_tree_duplicates_only_keeps_paths_to_conflicts() {
+    let app = Coordinate::new("com.example", "app", "1.0");
+    let web = Coordinate::new("com.example", "web", "1.0");
+    let json = Coordinate::new("com.example", "json", "1.0");
+    let util = Coordinate::new("com.example", "util", "1.0");
+    let common_new = Coordinate::new("com.shared", "common", "2.0");
+    let common_old = Coordinate::new("com.shared", "common", "1.0");
+    let logging = Coordinate::new("org.log", "logging", "1.0");
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![web.clone(), util.clone()], 0);
+    graph.add_node(web.clone(), vec![common_new.clone(), json.clone()], 1);
+    graph.add_node(json.clone(), vec![], 2);
+    // `util` asked for 1.0 but the nearer 2.0 was selected
+    graph.add_node(util.clone(), vec![common_old], 1);
+    graph.add_node(common_new, vec![], 2);
+    graph.add_node(logging.clone(), vec![], 0);
+
+    let lines = render_tree(
+        "proj",
+        &compile_roots(&[app, logging]),
+        &graph,
+        false,
+        &[],
+        true,
+    );
+    assert_eq!(
+        lines,
+        vec![
+            "proj v0.1.0 (5 dependencies; conflicts: com.shared:common)",
+            "└── com.example:app:1.0",
+            "    ├── com.example:util:1.0",
+            "    │   └── com.shared:common:1.0",
+            "    └── com.example:web:1.0",
+            "        └── com.shared:common:2.0",
+        ]
+    );
+}
+
+#[test]
+fn test_render_tree_duplicates_only_without_conflicts() {
+    let lib = Coordinate::new("com.example", "lib", "1.0");
+    let mut graph = DependencyGraph::new();
+    graph.add_node(lib.clone(), vec![], 0);
+
+    let lines = render_tree("proj", &compile_roots(&[lib]), &graph, false, &[], true);
+    assert_eq!(
+        lines,
+        vec!["proj v0.1.0 (0 dependencies; no version conflicts)"]
+    );
+}
+
+// === explain-classpath tests ===
+
+#[test]
+fn test_explain_lines_show_scope_and_shortest_path() {
+    let app = Coordinate::parse("com.example:app:1.0").unwrap();
+    let web = Coordinate::parse("com.example:web:1.0").unwrap();
+    let junit = Coordinate::parse("junit:junit:4.13").unwrap();
+    let driver = Coordinate::parse("org.db:driver:2.1").unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(app.clone(), vec![web.clone(), driver.clone()], 0);
+    graph.add_node(web.clone(), vec![], 1);
+    graph.add_node_with_scope(driver.clone(), vec![], 1, DependencyScope::Runtime);
+    graph.add_node_with_scope(junit.clone(), vec![], 0, DependencyScope::Test);
+
+    let entry = |coordinate: &Coordinate, scope| polytunnel_build::ClasspathEntry {
+        path: PathBuf::from(format!("{}.jar", coordinate.artifact_id)),
+        coordinate: coordinate.clone(),
+        scope,
+    };
+    let entries = [
+        entry(&app, DependencyScope::Compile),
+        entry(&web, DependencyScope::Compile),
+        entry(&driver, DependencyScope::Runtime),
+        entry(&junit, DependencyScope::Test),
+    ];
+
+    assert_eq!(
+        explain_lines(&entries, &graph, ClasspathArg::Runtime),
+        vec![
+            "COORDINATE           SCOPE    VIA",
+            "com.example:app:1.0  compile  (direct)",
+            "com.example:web:1.0  compile  com.example:app:1.0",
+            "org.db:driver:2.1    runtime  com.example:app:1.0",
+        ]
+    );
+    assert_eq!(explain_lines(&entries, &graph, ClasspathArg::Test).len(), 5);
+    assert_eq!(
+        explain_lines(&entries[3..], &DependencyGraph::new(), ClasspathArg::Test)[1],
+        "junit:junit:4.13  test   -"
+    );
+    assert_eq!(
+        explain_lines(&entries[3..], &graph, ClasspathArg::Compile),
+        vec!["(classpath is empty)"]
+    );
+}
+
+// === versions tests ===
+
+fn sample_versions() -> Vec<String> {
+    ["2.1-SNAPSHOT", "2.0", "2.0-RC1", "1.9", "1.9-SNAPSHOT"]
+        .iter()
+        .map(|v| v.to_string())
+        .collect()
+}
+
+#[test]
+fn test_versions_lines_hides_snapshots_by_default() {
+    assert_eq!(
+        versions_lines(&sample_versions(), None, false),
+        vec!["2.0  (latest release)", "2.0-RC1", "1.9"]
+    );
+}
+
+#[test]
+fn test_versions_lines_marks_latest_snapshot() {
+    assert_eq!(
+        versions_lines(&sample_versions(), None, true),
+        vec![
+            "2.1-SNAPSHOT  (latest snapshot)",
+            "2.0  (latest release)",
+            "2.0-RC1",
+            "1.9",
+            "1.9-SNAPSHOT",
+        ]
+    );
+}
+
+#[test]
+fn test_versions_lines_limit_applies_after_filtering() {
+    assert_eq!(
+        versions_lines(&sample_versions(), Some(2), false),
+        vec!["2.0  (latest release)", "2.0-RC1"]
+    );
+    assert!(versions_lines(&sample_versions(), Some(0), true).is_empty());
+}
+
+// === search tests ===
+
+#[test]
+fn test_search_lines_lists_coordinates_and_match_range() {
+    let doc = |a: &str| SearchDoc {
+        id: format!("org.example:{a}"),
+        g: "org.example".to_string(),
+        a: a.to_string(),
+        latest_version: Some("1.2.0".to_string()),
+        version: None,
+    };
+    let results = SearchResponseBody {
+        num_found: 42,
+        start: 20,
+        docs: vec![doc("core"), doc("extras")],
+    };
+
+    assert_eq!(
+        search_lines(&results),
+        vec![
+            "org.example:core:1.2.0",
+            "org.example:extras:1.2.0",
+            "",
+            "Showing 21-22 of 42 matches",
+        ]
+    );
+}
+
+// === conflict_lines tests ===
+
+#[test]
+fn test_conflict_lines_lists_rejected_versions_with_requesters() {
+    let request = |version: &str, path: &[&str]| VersionRequest {
+        version: version.to_string(),
+        path: path.iter().map(|p| p.to_string()).collect(),
+    };
+    let conflict = VersionConflict {
+        artifact: "junit:junit".to_string(),
+        selected: "4.13.2".to_string(),
+        rejected: vec!["4.12".to_string()],
+        requests: vec![
+            request("4.13.2", &[]),
+            request("4.12", &["org.a:a:1.0"]),
+            request("4.12", &["org.b:b:1.0", "org.c:c:2.0"]),
+        ],
+    };
+
+    assert_eq!(
+        conflict_lines(&[conflict]),
+        vec![
+            "",
+            "Version conflicts:",
+            "   junit:junit: using 4.13.2 (4.12 requested by org.a:a:1.0, org.b:b:1.0 -> org.c:c:2.0)",
+        ]
+    );
+}
+
+#[test]
+fn test_conflict_lines_empty_without_conflicts() {
+    assert!(conflict_lines(&[]).is_empty());
+}
+
+// === trace_lines tests ===
+
+#[test]
+fn test_trace_lines_sorts_events_under_header() {
+    let mut trace = ResolutionTrace::new();
+    trace.record(ResolutionEvent::PomFetched {
+        coordinate: Coordinate::new("org.b", "b", "1.0"),
+        depth: 1,
+    });
+    trace.record(ResolutionEvent::PomFetched {
+        coordinate: Coordinate::new("org.a", "a", "1.0"),
+        depth: 0,
+    });
+
+    assert_eq!(
+        trace_lines(&trace),
+        vec![
+            "",
+            "Resolution trace:",
+            "   fetched   org.a:a:1.0 (depth 0)",
+            "   fetched   org.b:b:1.0 (depth 1)",
+        ]
+    );
+}
+
+#[test]
+fn test_trace_lines_empty_trace() {
+    let lines = trace_lines(&ResolutionTrace::new());
+    assert_eq!(lines.last().unwrap(), "   (no decisions recorded)");
+}
+
 // === do_tree async tests ===
 
 #[tokio::test]
@@ -299,7 +872,7 @@ async fn test_do_tree_zero_deps() -> Result<()> {
         "[project]\nname = \"test-proj\"\njava_version = \"17\"\n",
     )?;
 
-    let result = super::tree::do_tree(&config_path, false).await;
+    let result = super::tree::do_tree(&config_path, 0, &[], false, Default::default()).await;
     assert!(result.is_ok());
 
     Ok(())
@@ -310,7 +883,18 @@ async fn test_do_tree_missing_config() {
     let dir = tempdir().unwrap();
     let config_path = dir.path().join("nonexistent.toml");
 
-    let result = super::tree::do_tree(&config_path, false).await;
+    let result = super::tree::do_tree(&config_path, 0, &[], false, Default::default()).await;
+    assert!(result.is_err());
+}
+
+// === do_resolve async tests ===
+
+#[tokio::test]
+async fn test_do_resolve_missing_config() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("nonexistent.toml");
+
+    let result = super::resolve::do_resolve(&config_path, true, false, Default::default()).await;
     assert!(result.is_err());
 }
 
@@ -346,9 +930,22 @@ cache_dir = "{cache}"
 
     fs::create_dir_all(dir.path().join("src/main/java"))?;
     fs::create_dir_all(dir.path().join("src/test/java"))?;
+    // Left over from before the last dependency was removed
+    let removed = Coordinate::new("org.removed", "removed", "1.0");
+    let lock_path = dir.path().join("polytunnel.lock");
+    polytunnel_resolver::Lockfile {
+        version: polytunnel_resolver::LOCKFILE_VERSION,
+        root: vec![removed.clone()],
+        packages: vec![removed],
+        scopes: Default::default(),
+    }
+    .save(&lock_path)?;
 
-    let result = super::sync::do_sync(&config_path, false).await;
+    let result = super::sync::do_sync(&config_path, 0, None, Default::default()).await;
     assert!(result.is_ok());
+    let lock = polytunnel_resolver::Lockfile::load(&lock_path)?.unwrap();
+    assert!(lock.root.is_empty());
+    assert!(lock.packages.is_empty());
 
     Ok(())
 }
@@ -358,10 +955,94 @@ async fn test_do_sync_missing_config() {
     let dir = tempdir().unwrap();
     let config_path = dir.path().join("nonexistent.toml");
 
-    let result = super::sync::do_sync(&config_path, false).await;
+    let result = super::sync::do_sync(&config_path, 0, None, Default::default()).await;
     assert!(result.is_err());
 }
 
+/// Serve `routes` over HTTP until the test ends, recording every requested path
+async fn spawn_repository_server(
+    routes: HashMap<String, Vec<u8>>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let log = requested.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0_u8; 8192];
+            let size = socket.read(&mut buffer).await.unwrap();
+            let request = String::from_utf8_lossy(&buffer[..size]);
+            let path = request
+                .lines()
+                .next()
+                .and_then(|line| line.split_whitespace().nth(1))
+                .unwrap_or_default()
+                .to_string();
+
+            let (status, body) = match routes.get(&path) {
+                Some(body) => ("200 OK", body.clone()),
+                None => ("404 Not Found", b"not found".to_vec()),
+            };
+            log.lock().unwrap().push(path);
+            let head = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket.write_all(&body).await.unwrap();
+        }
+    });
+
+    (format!("http://{addr}"), requested)
+}
+
+#[tokio::test]
+async fn test_do_resolve_consults_cli_repository() -> Result<()> {
+    let dir = tempdir()?;
+    let cache_dir = dir.path().join(".polytunnel/cache");
+    let config_path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"[project]
+name = "test-proj"
+java_version = "17"
+
+[build]
+cache_dir = "{cache}"
+
+[dependencies]
+"com.example.staged:lib" = "1.0"
+"#,
+            cache = cache_dir.display(),
+        ),
+    )?;
+
+    let artifact_dir = "/com/example/staged/lib/1.0";
+    let routes = HashMap::from([
+        (
+            format!("{artifact_dir}/lib-1.0.pom"),
+            b"<project><groupId>com.example.staged</groupId><artifactId>lib</artifactId><version>1.0</version></project>".to_vec(),
+        ),
+        (format!("{artifact_dir}/lib-1.0.jar"), b"staged jar".to_vec()),
+    ]);
+    let (base_url, requested) = spawn_repository_server(routes).await;
+
+    let repositories = RepositoryArgs {
+        repositories: vec![Repository::new("staging", &base_url)],
+        repository_priority: RepositoryPriorityArg::First,
+    };
+    super::resolve::do_resolve(&config_path, false, false, repositories).await?;
+
+    let requested = requested.lock().unwrap().clone();
+    assert!(requested.contains(&format!("{artifact_dir}/lib-1.0.pom")));
+    assert!(requested.contains(&format!("{artifact_dir}/lib-1.0.jar")));
+
+    Ok(())
+}
+
 // === add tests ===
 
 #[test]
@@ -373,7 +1054,12 @@ fn test_add_simple_dependency() -> Result<()> {
         "[project]\nname = \"test\"\njava_version = \"17\"\n",
     )?;
 
-    do_add("com.google.guava:guava:33.0.0-jre", None, &config_path)?;
+    do_add(
+        "com.google.guava:guava:33.0.0-jre",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
 
     let content = fs::read_to_string(&config_path)?;
     assert!(content.contains("[dependencies]"));
@@ -395,6 +1081,7 @@ fn test_add_dependency_with_scope() -> Result<()> {
     do_add(
         "org.junit.jupiter:junit-jupiter:5.10.1",
         Some("test"),
+        VersionPolicy::Exact,
         &config_path,
     )?;
 
@@ -420,13 +1107,91 @@ java_version = "17"
 "#,
     )?;
 
-    let result = do_add("com.google.guava:guava:33.0.0-jre", None, &config_path);
+    let result = do_add(
+        "com.google.guava:guava:33.0.0-jre",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    );
     assert!(result.is_err());
     let err = result.unwrap_err().to_string();
     assert!(err.contains("already exists"));
     Ok(())
 }
 
+#[test]
+fn test_add_batch_writes_valid_lines_and_reports_the_rest() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &config_path,
+        r#"[project]
+name = "test"
+java_version = "17"
+
+[dependencies]
+"com.google.guava:guava" = "32.0.0-jre"
+"#,
+    )?;
+    let input = "\
+# migrated from pom.xml
+org.slf4j:slf4j-api:2.0.9
+
+com.google.guava:guava:33.0.0-jre
+not-a-coordinate
+  org.apache.commons:commons-lang3:3.14.0
+";
+
+    let err = do_add_batch(input, None, false, VersionPolicy::Exact, &config_path).unwrap_err();
+
+    assert_eq!(err.to_string(), "2 dependencies could not be added");
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("\"org.slf4j:slf4j-api\" = \"2.0.9\""));
+    assert!(content.contains("\"org.apache.commons:commons-lang3\" = \"3.14.0\""));
+    assert!(content.contains("\"com.google.guava:guava\" = \"32.0.0-jre\""));
+    Ok(())
+}
+
+#[test]
+fn test_add_batch_dev_dependencies() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &config_path,
+        "[project]\nname = \"test\"\njava_version = \"17\"\n",
+    )?;
+
+    do_add_batch(
+        "org.junit.jupiter:junit-jupiter:5.10.1\norg.assertj:assertj-core:3.25.1\n",
+        None,
+        true,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
+
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("[dev-dependencies]"));
+    assert!(!content.contains("[dependencies]"));
+    assert!(content.contains("\"org.junit.jupiter:junit-jupiter\" = \"5.10.1\""));
+    assert!(content.contains("\"org.assertj:assertj-core\" = \"3.25.1\""));
+    Ok(())
+}
+
+#[test]
+fn test_add_file_is_relative_to_invocation_dir() {
+    let invocation_dir = std::path::Path::new("/work/app/submodule");
+
+    assert_eq!(
+        AddSource::file("deps.txt", invocation_dir),
+        AddSource::File(invocation_dir.join("deps.txt"))
+    );
+    assert_eq!(
+        AddSource::file("/tmp/deps.txt", invocation_dir),
+        AddSource::File(std::path::PathBuf::from("/tmp/deps.txt"))
+    );
+    assert_eq!(AddSource::file("-", invocation_dir), AddSource::Stdin);
+}
+
 #[test]
 fn test_add_invalid_coordinate_no_version() -> Result<()> {
     let dir = tempdir()?;
@@ -436,7 +1201,12 @@ fn test_add_invalid_coordinate_no_version() -> Result<()> {
         "[project]\nname = \"test\"\njava_version = \"17\"\n",
     )?;
 
-    let result = do_add("com.google.guava:guava", None, &config_path);
+    let result = do_add(
+        "com.google.guava:guava",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    );
     assert!(result.is_err());
     Ok(())
 }
@@ -450,7 +1220,7 @@ fn test_add_invalid_coordinate_empty_parts() -> Result<()> {
         "[project]\nname = \"test\"\njava_version = \"17\"\n",
     )?;
 
-    let result = do_add("::1.0", None, &config_path);
+    let result = do_add("::1.0", None, VersionPolicy::Exact, &config_path);
     assert!(result.is_err());
     Ok(())
 }
@@ -467,6 +1237,7 @@ fn test_add_invalid_scope() -> Result<()> {
     let result = do_add(
         "com.google.guava:guava:33.0.0-jre",
         Some("invalid"),
+        VersionPolicy::Exact,
         &config_path,
     );
     assert!(result.is_err());
@@ -478,7 +1249,12 @@ fn test_add_missing_config() {
     let dir = tempdir().unwrap();
     let config_path = dir.path().join("nonexistent.toml");
 
-    let result = do_add("com.google.guava:guava:33.0.0-jre", None, &config_path);
+    let result = do_add(
+        "com.google.guava:guava:33.0.0-jre",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    );
     assert!(result.is_err());
 }
 
@@ -499,7 +1275,12 @@ source_dirs = ["src/main/java"]
 "#;
     fs::write(&config_path, original)?;
 
-    do_add("com.google.guava:guava:33.0.0-jre", None, &config_path)?;
+    do_add(
+        "com.google.guava:guava:33.0.0-jre",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
 
     let content = fs::read_to_string(&config_path)?;
     // Original content preserved
@@ -521,11 +1302,22 @@ fn test_add_multiple_dependencies_sequentially() -> Result<()> {
         "[project]\nname = \"test\"\njava_version = \"17\"\n",
     )?;
 
-    do_add("org.slf4j:slf4j-api:2.0.9", None, &config_path)?;
-    do_add("com.google.guava:guava:33.0.0-jre", None, &config_path)?;
+    do_add(
+        "org.slf4j:slf4j-api:2.0.9",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
+    do_add(
+        "com.google.guava:guava:33.0.0-jre",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
     do_add(
         "org.junit.jupiter:junit-jupiter:5.10.1",
         Some("test"),
+        VersionPolicy::Exact,
         &config_path,
     )?;
 
@@ -540,6 +1332,60 @@ fn test_add_multiple_dependencies_sequentially() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_add_version_policies_write_pin_or_range() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = dir.path().join("polytunnel.toml");
+    do_init("test-project", &config_path)?;
+
+    do_add(
+        "org.slf4j:slf4j-api:2.0.9",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
+    do_add(
+        "com.google.guava:guava:33.0.0-jre",
+        None,
+        VersionPolicy::Compatible,
+        &config_path,
+    )?;
+    do_add_dev(
+        "org.junit.jupiter:junit-jupiter:5.10.1",
+        VersionPolicy::Compatible,
+        &config_path,
+    )?;
+
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("\"org.slf4j:slf4j-api\" = \"2.0.9\""));
+    assert!(content.contains("\"com.google.guava:guava\" = \"[33.0.0-jre,34.0.0)\""));
+    assert!(content.contains("\"org.junit.jupiter:junit-jupiter\" = \"[5.10.1,6.0.0)\""));
+    let config = polytunnel_core::ProjectConfig::load(&config_path)?;
+    assert_eq!(
+        config.dependencies["com.google.guava:guava"].version(),
+        "[33.0.0-jre,34.0.0)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_dev_writes_dev_dependencies_table() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = dir.path().join("polytunnel.toml");
+    do_init("test-project", &config_path)?;
+
+    do_add_dev(
+        "org.junit.jupiter:junit-jupiter:5.10.1",
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
+
+    let content = fs::read_to_string(&config_path)?;
+    assert!(content.contains("[dev-dependencies]"));
+    assert!(content.contains("\"org.junit.jupiter:junit-jupiter\" = \"5.10.1\""));
+    Ok(())
+}
+
 // === remove tests ===
 
 #[test]
@@ -709,7 +1555,12 @@ java_version = "17"
     let content = fs::read_to_string(&config_path)?;
     assert!(!content.contains("guava"));
 
-    do_add("com.google.guava:guava:34.0.0-jre", None, &config_path)?;
+    do_add(
+        "com.google.guava:guava:34.0.0-jre",
+        None,
+        VersionPolicy::Exact,
+        &config_path,
+    )?;
     let content = fs::read_to_string(&config_path)?;
     assert!(content.contains("\"com.google.guava:guava\" = \"34.0.0-jre\""));
     Ok(())
@@ -722,7 +1573,7 @@ async fn test_run_missing_config() {
     let dir = tempdir().unwrap();
     let config_path = dir.path().join("nonexistent.toml");
 
-    let result = do_run("com.example.App", &[], false, &config_path).await;
+    let result = do_run(Some("com.example.App"), &[], &[], false, &config_path).await;
     assert!(result.is_err());
     let err = result.unwrap_err().to_string();
     assert!(err.contains("polytunnel.toml not found"));
@@ -737,7 +1588,7 @@ async fn test_run_empty_main_class() -> Result<()> {
         "[project]\nname = \"demo\"\njava_version = \"17\"\n",
     )?;
 
-    let result = do_run("", &[], false, &config_path).await;
+    let result = do_run(Some(""), &[], &[], false, &config_path).await;
     assert!(result.is_err());
     let err = result.unwrap_err().to_string();
     assert!(err.contains("Main class must not be empty"));
@@ -753,7 +1604,7 @@ async fn test_run_whitespace_only_main_class() -> Result<()> {
         "[project]\nname = \"demo\"\njava_version = \"17\"\n",
     )?;
 
-    let result = do_run("   ", &[], false, &config_path).await;
+    let result = do_run(Some("   "), &[], &[], false, &config_path).await;
     assert!(result.is_err());
     Ok(())
 }
@@ -817,7 +1668,7 @@ async fn test_run_executes_main_class_successfully() -> Result<()> {
     let dir = tempdir()?;
     let config_path = write_run_project(dir.path(), r#"System.out.println("ok");"#)?;
 
-    let exit_code = do_run("com.example.Hello", &[], false, &config_path).await?;
+    let exit_code = do_run(Some("com.example.Hello"), &[], &[], false, &config_path).await?;
     assert_eq!(exit_code, 0);
     Ok(())
 }
@@ -832,7 +1683,7 @@ async fn test_run_propagates_nonzero_exit_code() -> Result<()> {
     let dir = tempdir()?;
     let config_path = write_run_project(dir.path(), "System.exit(7);")?;
 
-    let exit_code = do_run("com.example.Hello", &[], false, &config_path).await?;
+    let exit_code = do_run(Some("com.example.Hello"), &[], &[], false, &config_path).await?;
     assert_eq!(exit_code, 7);
     Ok(())
 }
@@ -851,8 +1702,34 @@ async fn test_run_passes_args_to_main() -> Result<()> {
     )?;
 
     let exit_code = do_run(
-        "com.example.Hello",
+        Some("com.example.Hello"),
         &["foo".to_string(), "bar".to_string()],
+        &[],
+        false,
+        &config_path,
+    )
+    .await?;
+    assert_eq!(exit_code, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_passes_jvm_args_to_java() -> Result<()> {
+    if !java_toolchain_available() {
+        eprintln!("Skipping: javac/java not available");
+        return Ok(());
+    }
+
+    let dir = tempdir()?;
+    let config_path = write_run_project(
+        dir.path(),
+        r#"if (!"bar".equals(System.getProperty("foo")) || args.length != 1) System.exit(2);"#,
+    )?;
+
+    let exit_code = do_run(
+        Some("com.example.Hello"),
+        &["-Dfoo=ignored".to_string()],
+        &["-Dfoo=bar".to_string(), "-Xmx512m".to_string()],
         false,
         &config_path,
     )
@@ -861,6 +1738,28 @@ async fn test_run_passes_args_to_main() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_java_args_put_jvm_args_before_main_class() {
+    let args = java_args(
+        &["-Dfoo=bar".to_string(), "-Xmx512m".to_string()],
+        "target/classes",
+        "com.example.App",
+        &["--port".to_string(), "8080".to_string()],
+    );
+    assert_eq!(
+        args,
+        [
+            "-Dfoo=bar",
+            "-Xmx512m",
+            "-cp",
+            "target/classes",
+            "com.example.App",
+            "--port",
+            "8080"
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_run_verbose_mode_executes() -> Result<()> {
     if !java_toolchain_available() {
@@ -871,11 +1770,46 @@ async fn test_run_verbose_mode_executes() -> Result<()> {
     let dir = tempdir()?;
     let config_path = write_run_project(dir.path(), r#"System.out.println("verbose ok");"#)?;
 
-    let exit_code = do_run("com.example.Hello", &[], true, &config_path).await?;
+    let exit_code = do_run(Some("com.example.Hello"), &[], &[], true, &config_path).await?;
     assert_eq!(exit_code, 0);
     Ok(())
 }
 
+#[tokio::test]
+async fn test_run_detects_single_main_class() -> Result<()> {
+    if !java_toolchain_available() {
+        eprintln!("Skipping: javac/java not available");
+        return Ok(());
+    }
+
+    let dir = tempdir()?;
+    let config_path = write_run_project(dir.path(), "System.exit(3);")?;
+
+    let exit_code = do_run(None, &[], &[], false, &config_path).await?;
+    assert_eq!(exit_code, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_without_main_class_lists_candidates() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = write_run_project(dir.path(), "")?;
+    fs::write(
+        dir.path().join("src/main/java/com/example/Other.java"),
+        "package com.example; public class Other { public static void main(String... args) {} }",
+    )?;
+
+    let err = do_run(None, &[], &[], false, &config_path)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("com.example.Hello, com.example.Other"),
+        "{err}"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_run_trims_main_class() -> Result<()> {
     if !java_toolchain_available() {
@@ -887,7 +1821,410 @@ async fn test_run_trims_main_class() -> Result<()> {
     let config_path = write_run_project(dir.path(), r#"System.out.println("trimmed ok");"#)?;
 
     // Leading/trailing whitespace must be stripped before reaching java
-    let exit_code = do_run("  com.example.Hello\t", &[], false, &config_path).await?;
+    let exit_code = do_run(Some("  com.example.Hello\t"), &[], &[], false, &config_path).await?;
     assert_eq!(exit_code, 0);
     Ok(())
 }
+
+// === cache tests ===
+
+fn write_cache_project(dir: &std::path::Path) -> Result<std::path::PathBuf> {
+    let config_path = dir.join("polytunnel.toml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"[project]
+name = "cache-proj"
+
+[build]
+cache_dir = "{cache}"
+"#,
+            cache = dir.join("cache").display(),
+        ),
+    )?;
+    Ok(config_path)
+}
+
+fn write_cached_jar(dir: &std::path::Path, coord: &Coordinate, age: Duration) -> Result<()> {
+    let path = dir
+        .join("cache")
+        .join(coord.repo_path())
+        .join(coord.jar_filename());
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, b"jar")?;
+    fs::File::options()
+        .write(true)
+        .open(&path)?
+        .set_modified(std::time::SystemTime::now() - age)?;
+    Ok(())
+}
+
+#[test]
+fn test_parse_age_units() {
+    assert_eq!(parse_age("30d"), Ok(Duration::from_secs(30 * 86400)));
+    assert_eq!(parse_age("12h"), Ok(Duration::from_secs(12 * 3600)));
+    assert_eq!(parse_age("45m"), Ok(Duration::from_secs(45 * 60)));
+    assert_eq!(parse_age("2w"), Ok(Duration::from_secs(14 * 86400)));
+    assert_eq!(parse_age("7"), Ok(Duration::from_secs(7 * 86400)));
+    assert!(parse_age("d").is_err());
+    assert!(parse_age("10y").is_err());
+}
+
+#[test]
+fn test_parse_byte_rate_units() {
+    assert_eq!(parse_byte_rate("65536"), Ok(65536));
+    assert_eq!(parse_byte_rate("500K"), Ok(500 * 1024));
+    assert_eq!(parse_byte_rate("2m"), Ok(2 * 1024 * 1024));
+    assert_eq!(parse_byte_rate("1G"), Ok(1024 * 1024 * 1024));
+    assert!(parse_byte_rate("0").is_err());
+    assert!(parse_byte_rate("K").is_err());
+    assert!(parse_byte_rate("10T").is_err());
+}
+
+#[test]
+fn test_parse_repository() {
+    let repo = parse_repository("staging=https://repo.example.test/maven2").unwrap();
+    assert_eq!(repo.name, "staging");
+    assert_eq!(repo.url, "https://repo.example.test/maven2");
+    assert!(parse_repository("https://repo.example.test/maven2").is_err());
+    assert!(parse_repository("=https://repo.example.test/maven2").is_err());
+    assert!(parse_repository("staging=").is_err());
+}
+
+#[test]
+fn test_parse_env_var() {
+    assert_eq!(
+        parse_env_var("SPRING_PROFILES_ACTIVE=test"),
+        Ok(("SPRING_PROFILES_ACTIVE".to_string(), "test".to_string()))
+    );
+    assert_eq!(
+        parse_env_var("OPTS=-Da=b"),
+        Ok(("OPTS".to_string(), "-Da=b".to_string()))
+    );
+    assert_eq!(
+        parse_env_var("EMPTY="),
+        Ok(("EMPTY".to_string(), String::new()))
+    );
+    assert!(parse_env_var("NOVALUE").is_err());
+    assert!(parse_env_var("=value").is_err());
+}
+
+#[test]
+fn test_javadoc_lines_list_warnings_then_summary() {
+    let result = polytunnel_build::JavadocResult {
+        output_dir: PathBuf::from("target/javadoc"),
+        documented_files: 2,
+        diagnostics: vec![polytunnel_build::JavadocDiagnostic {
+            kind: polytunnel_build::DiagnosticKind::Warning,
+            file: Some(PathBuf::from("src/main/java/App.java")),
+            line: Some(3),
+            message: "no comment".to_string(),
+        }],
+    };
+    assert_eq!(
+        javadoc_lines(&result),
+        [
+            "src/main/java/App.java:3: warning: no comment",
+            "Generated docs for 2 file(s) in target/javadoc (1 warning(s))"
+        ]
+    );
+
+    let empty = polytunnel_build::JavadocResult {
+        documented_files: 0,
+        diagnostics: vec![],
+        ..result
+    };
+    assert_eq!(javadoc_lines(&empty), ["No sources to document"]);
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(2048), "2.0 KiB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+}
+
+#[test]
+fn test_cache_info_and_clean() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = write_cache_project(dir.path())?;
+    write_cached_jar(
+        dir.path(),
+        &Coordinate::new("org.a", "a", "1.0"),
+        Duration::ZERO,
+    )?;
+
+    let info = do_cache_info(&config_path)?;
+    assert_eq!(info.artifact_count, 1);
+    assert_eq!(info.total_size, 3);
+
+    let cleanup = do_cache_clean(&config_path)?;
+    assert_eq!(cleanup.removed, 1);
+    assert!(!dir.path().join("cache").exists());
+    Ok(())
+}
+
+#[test]
+fn test_cache_clean_incremental_only_keeps_artifacts() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = write_cache_project(dir.path())?;
+    let coord = Coordinate::new("org.a", "a", "1.0");
+    write_cached_jar(dir.path(), &coord, Duration::ZERO)?;
+    let state = dir.path().join("cache/build-cache.json");
+    fs::write(
+        &state,
+        r#"{"Main.java":{"source_file":"Main.java","last_modified":1,"output_file":"Main.class"}}"#,
+    )?;
+
+    do_cache_clean_incremental(&config_path)?;
+
+    assert_eq!(fs::read_to_string(&state)?.trim(), "{}");
+    assert!(dir.path().join("cache").join(coord.repo_path()).exists());
+    assert_eq!(do_cache_info(&config_path)?.artifact_count, 1);
+    Ok(())
+}
+
+#[test]
+fn test_cache_prune_keeps_lockfile_artifacts() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = write_cache_project(dir.path())?;
+    let locked = Coordinate::new("org.locked", "locked", "1.0");
+    let stale = Coordinate::new("org.stale", "stale", "1.0");
+    let age = Duration::from_secs(60 * 86400);
+    write_cached_jar(dir.path(), &locked, age)?;
+    write_cached_jar(dir.path(), &stale, age)?;
+
+    polytunnel_resolver::Lockfile {
+        version: polytunnel_resolver::LOCKFILE_VERSION,
+        root: vec![locked.clone()],
+        packages: vec![locked.clone()],
+        scopes: Default::default(),
+    }
+    .save(&dir.path().join("polytunnel.lock"))?;
+
+    let cleanup = do_cache_prune(&config_path, Duration::from_secs(30 * 86400))?;
+    assert_eq!(cleanup.removed, 1);
+    assert!(dir.path().join("cache").join(locked.repo_path()).exists());
+    assert!(!dir.path().join("cache").join(stale.repo_path()).exists());
+    Ok(())
+}
+
+// === clean tests ===
+
+#[test]
+fn test_do_clean_removes_outputs_and_optionally_cache() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = write_cache_project(dir.path())?;
+    let classes = dir.path().join("target/classes");
+    let test_classes = dir.path().join("target/test-classes");
+    let cache = dir.path().join("cache");
+    fs::create_dir_all(&classes)?;
+    fs::create_dir_all(&cache)?;
+
+    let removed = do_clean(&config_path, false)?;
+    assert_eq!(removed, vec![classes.clone()]);
+    assert!(!classes.exists());
+    assert!(cache.exists());
+
+    fs::create_dir_all(&test_classes)?;
+    let removed = do_clean(&config_path, true)?;
+    assert_eq!(removed, vec![test_classes.clone(), cache.clone()]);
+    assert!(!test_classes.exists());
+    assert!(!cache.exists());
+
+    assert!(do_clean(&config_path, true)?.is_empty());
+    Ok(())
+}
+
+// === deps --stats tests ===
+
+#[test]
+fn test_stats_lines_report_every_figure() {
+    let stats = GraphStats {
+        total: 12,
+        direct: 3,
+        transitive: 9,
+        max_depth: 4,
+        conflicts: 2,
+        jar_bytes: Some(2048),
+    };
+
+    assert_eq!(
+        stats_lines(&stats, 1),
+        vec![
+            "Dependencies:        12",
+            "  direct:            3",
+            "  transitive:        9",
+            "Max depth:           4",
+            "Version conflicts:   2",
+            "JAR size:            2.0 KiB (1 not cached)",
+        ]
+    );
+}
+
+#[test]
+fn test_cached_jar_bytes_sums_stored_jars() {
+    let dir = tempdir().unwrap();
+    let store = FileSystemStore::new(dir.path());
+    let cached = Coordinate::new("org.a", "a", "1.0");
+    let missing = Coordinate::new("org.b", "b", "1.0");
+    store.write(&jar_key(&cached), &[0; 300]).unwrap();
+
+    let mut graph = DependencyGraph::new();
+    graph.add_node(cached, vec![missing.clone()], 0);
+    graph.add_node(missing, vec![], 1);
+
+    assert_eq!(cached_jar_bytes(&graph, &store), (300, 1));
+}
+
+// === fmt-config tests ===
+
+#[test]
+fn test_fmt_config_round_trip_is_idempotent() -> Result<()> {
+    let dir = tempdir()?;
+    let config_path = dir.path().join("polytunnel.toml");
+    fs::write(
+        &config_path,
+        r#"[project]
+name = "test"
+
+[dependencies]
+"org.b:b" = { version = "1.0", scope = "compile" }
+"org.a:a" = "2.0"
+"#,
+    )?;
+
+    assert!(do_fmt_config(&config_path)?);
+    let formatted = fs::read_to_string(&config_path)?;
+    assert!(formatted.find("org.a:a").unwrap() < formatted.find("org.b:b").unwrap());
+
+    assert!(!do_fmt_config(&config_path)?);
+    assert_eq!(fs::read_to_string(&config_path)?, formatted);
+    Ok(())
+}
+
+#[test]
+fn test_fmt_config_without_config_fails() {
+    let dir = tempdir().unwrap();
+    assert!(do_fmt_config(&dir.path().join("polytunnel.toml")).is_err());
+}
+
+// === deps --licenses tests ===
+
+fn license(name: &str) -> polytunnel_maven::License {
+    polytunnel_maven::License {
+        name: Some(name.to_string()),
+        url: None,
+    }
+}
+
+#[test]
+fn test_license_report_groups_by_license() {
+    let entries = vec![
+        (Coordinate::new("a", "one", "1"), vec![license("MIT")]),
+        (
+            Coordinate::new("b", "two", "1"),
+            vec![license("Apache-2.0")],
+        ),
+        (Coordinate::new("c", "three", "1"), vec![license("MIT")]),
+    ];
+
+    let report = LicenseReport::new(&entries, &Default::default());
+
+    assert!(report.is_compliant());
+    assert_eq!(report.groups.len(), 2);
+    assert_eq!(report.groups["MIT"].1, vec!["a:one:1", "c:three:1"]);
+    assert_eq!(report.groups["Apache-2.0"].1, vec!["b:two:1"]);
+}
+
+#[test]
+fn test_license_report_flags_disallowed_and_unknown() {
+    let policy = polytunnel_core::LicensesConfig {
+        allowed: vec!["MIT".to_string()],
+    };
+    let entries = vec![
+        (Coordinate::new("a", "one", "1"), vec![license("MIT")]),
+        (Coordinate::new("b", "two", "1"), vec![license("GPL-3.0")]),
+        (Coordinate::new("c", "three", "1"), vec![]),
+        (
+            Coordinate::new("d", "dual", "1"),
+            vec![license("GPL-3.0"), license("MIT")],
+        ),
+    ];
+
+    let report = LicenseReport::new(&entries, &policy);
+
+    assert!(!report.is_compliant());
+    assert_eq!(report.disallowed, vec!["b:two:1"]);
+    assert_eq!(report.unknown, vec!["c:three:1"]);
+    assert!(!report.groups["GPL-3.0"].0);
+    assert!(report.groups["MIT"].0);
+
+    let rendered = report.render().join("\n");
+    assert!(rendered.contains("[not allowed]"));
+    assert!(rendered.contains("Unknown (1)"));
+}
+
+#[test]
+fn test_license_report_matches_license_url() {
+    let policy = polytunnel_core::LicensesConfig {
+        allowed: vec!["https://opensource.org/licenses/MIT".to_string()],
+    };
+    let entries = vec![(
+        Coordinate::new("a", "one", "1"),
+        vec![polytunnel_maven::License {
+            name: Some("The MIT License".to_string()),
+            url: Some("https://opensource.org/licenses/MIT".to_string()),
+        }],
+    )];
+
+    assert!(LicenseReport::new(&entries, &policy).is_compliant());
+}
+
+#[test]
+fn test_failure_lines_show_stacktrace_and_captured_output() {
+    use polytunnel_build::{TestFailure, TestResult};
+
+    let failure = |test: &str, message: &str, stacktrace: &str, output: &str| TestFailure {
+        class_name: "com.example.CalcTest".to_string(),
+        test_name: test.to_string(),
+        message: message.to_string(),
+        stacktrace: stacktrace.to_string(),
+        output: output.to_string(),
+    };
+    let result = TestResult {
+        total: 3,
+        passed: 1,
+        failed: 2,
+        skipped: 0,
+        failures: vec![
+            failure(
+                "fails()",
+                "expected: <3>",
+                "AssertionFailedError: expected: <3>\n\tat CalcTest.fails",
+                "line 1\nline 2",
+            ),
+            failure("quiet()", "boom", "", ""),
+        ],
+    };
+
+    let lines = failure_lines(&result);
+    assert_eq!(
+        lines,
+        [
+            "\nfailures:\n",
+            "---- com.example.CalcTest::fails() ----",
+            "AssertionFailedError: expected: <3>",
+            "\tat CalcTest.fails",
+            "---- com.example.CalcTest::fails() stdout ----",
+            "line 1",
+            "line 2",
+            "",
+            "---- com.example.CalcTest::quiet() ----",
+            "boom",
+            "",
+        ]
+    );
+
+    assert!(failure_lines(&TestResult::default()).is_empty());
+}
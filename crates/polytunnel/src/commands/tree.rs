@@ -1,33 +1,120 @@
+use crate::cli::RepositoryArgs;
 use color_eyre::eyre::Result;
 use polytunnel_core::ProjectConfig;
-use polytunnel_maven::Coordinate;
-use polytunnel_resolver::{DependencyGraph, Resolver};
+use polytunnel_maven::{Coordinate, DependencyScope, MavenClient};
+use polytunnel_resolver::{
+    DependencyGraph, ResolutionTrace, ResolvedTree, Resolver, ResolverConfig, VersionConflict,
+};
 use std::collections::HashSet;
 use std::path::Path;
 
-pub async fn cmd_tree(verbose: bool) -> Result<()> {
-    do_tree(Path::new("polytunnel.toml"), verbose).await
+pub async fn cmd_tree(
+    verbose: u8,
+    prune: Vec<DependencyScope>,
+    duplicates_only: bool,
+    repositories: RepositoryArgs,
+) -> Result<()> {
+    do_tree(
+        Path::new("polytunnel.toml"),
+        verbose,
+        &prune,
+        duplicates_only,
+        repositories,
+    )
+    .await
 }
 
-pub(crate) async fn do_tree(config_path: &Path, verbose: bool) -> Result<()> {
-    let config = ProjectConfig::load(config_path)?;
+/// Print the dependency tree and mediated version conflicts; `verbose > 1`
+/// also prints the resolution trace
+///
+/// Dependencies whose effective scope is in `prune` are hidden with their subtrees;
+/// `duplicates_only` shows just the paths to conflicting artifacts.
+pub(crate) async fn do_tree(
+    config_path: &Path,
+    verbose: u8,
+    prune: &[DependencyScope],
+    duplicates_only: bool,
+    repositories: RepositoryArgs,
+) -> Result<()> {
+    let mut config = ProjectConfig::load(config_path)?;
+    repositories.apply(&mut config);
 
     let root_coords = parse_root_coords(&config);
+    let (resolver, tree) = resolve_project(&config, project_client(&config), &root_coords).await?;
 
-    let mut resolver = Resolver::new();
-    resolver
-        .resolve(&root_coords)
-        .await
-        .map_err(|e| color_eyre::eyre::eyre!("Dependency resolution failed: {}", e))?;
-
-    let lines = render_tree(&config.project.name, &root_coords, &resolver.graph, verbose);
+    let scoped_roots: Vec<_> = root_coords
+        .iter()
+        .map(|coord| (coord.clone(), root_scope(&config, coord)))
+        .collect();
+    let lines = render_tree(
+        &config.project.name,
+        &scoped_roots,
+        &resolver.graph,
+        verbose > 0,
+        prune,
+        duplicates_only,
+    );
     for line in lines {
         println!("{}", line);
     }
+    for line in conflict_lines(&tree.conflicts) {
+        println!("{}", line);
+    }
+    if verbose > 1 {
+        for line in trace_lines(&resolver.trace) {
+            println!("{}", line);
+        }
+    }
 
     Ok(())
 }
 
+/// `-vv` section listing every resolution decision in a stable order
+pub(crate) fn trace_lines(trace: &ResolutionTrace) -> Vec<String> {
+    let mut lines = vec![String::new(), "Resolution trace:".to_string()];
+    if trace.is_empty() {
+        lines.push("   (no decisions recorded)".to_string());
+    }
+    lines.extend(
+        trace
+            .sorted()
+            .into_iter()
+            .map(|event| format!("   {}", event)),
+    );
+    lines
+}
+
+/// Section listing each mediated conflict, or nothing without conflicts
+pub(crate) fn conflict_lines(conflicts: &[VersionConflict]) -> Vec<String> {
+    if conflicts.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![String::new(), "Version conflicts:".to_string()];
+    lines.extend(conflicts.iter().map(|conflict| format!("   {}", conflict)));
+    lines
+}
+
+/// Maven client configured with the project's `[http]` settings and repositories
+pub(crate) fn project_client(config: &ProjectConfig) -> MavenClient {
+    MavenClient::new()
+        .with_http_config(&config.http)
+        .with_repositories(&config.repositories)
+}
+
+/// Resolve the project's dependency graph, honoring exclusions and the resolve timeout
+pub(crate) async fn resolve_project(
+    config: &ProjectConfig,
+    client: MavenClient,
+    root_coords: &[Coordinate],
+) -> Result<(Resolver, ResolvedTree)> {
+    let mut resolver = Resolver::with_config(client, ResolverConfig::from_project(config)?);
+    let tree = resolver
+        .resolve(root_coords)
+        .await
+        .map_err(|e| color_eyre::eyre::eyre!("Dependency resolution failed: {}", e))?;
+    Ok((resolver, tree))
+}
+
 pub(crate) fn parse_root_coords(config: &ProjectConfig) -> Vec<Coordinate> {
     let mut coords: Vec<Coordinate> = config
         .dependencies
@@ -45,19 +132,49 @@ pub(crate) fn parse_root_coords(config: &ProjectConfig) -> Vec<Coordinate> {
     coords
 }
 
+/// Declared scope of a root dependency in `polytunnel.toml`
+fn root_scope(config: &ProjectConfig, coord: &Coordinate) -> DependencyScope {
+    config
+        .dependencies
+        .iter()
+        .find(|(key, _)| {
+            let mut parts = key.split(':');
+            parts.next() == Some(coord.group_id.as_str())
+                && parts.next() == Some(coord.artifact_id.as_str())
+        })
+        .map(|(_, dep)| dep.scope().into())
+        .unwrap_or_default()
+}
+
+/// Render the dependency tree, hiding dependencies whose effective scope is in `prune`
+///
+/// A root's effective scope is its declared scope; below it scopes combine
+/// per [`DependencyScope::transitive_scope`], so pruning `test` hides test
+/// roots along with everything only they pull in. `duplicates_only` keeps
+/// just the paths to artifacts requested in several versions. When pruning
+/// either way, the header counts the artifacts left in the view.
 pub(crate) fn render_tree(
     project_name: &str,
-    root_coords: &[Coordinate],
+    roots: &[(Coordinate, DependencyScope)],
     graph: &DependencyGraph,
     verbose: bool,
+    prune: &[DependencyScope],
+    duplicates_only: bool,
 ) -> Vec<String> {
+    let conflict_paths = duplicates_only.then(|| graph.conflict_paths());
+    let keep = conflict_paths.as_ref();
+    let roots: Vec<_> = roots
+        .iter()
+        .filter(|(coord, scope)| !prune.contains(scope) && is_kept(keep, coord))
+        .collect();
+
     let mut lines = Vec::new();
     lines.push(format!("{} v0.1.0", project_name));
 
     let mut printed = HashSet::new();
 
-    for (i, coord) in root_coords.iter().enumerate() {
-        let is_last = i == root_coords.len() - 1;
+    for (i, (coord, scope)) in roots.iter().enumerate() {
+        let is_last = i == roots.len() - 1;
         let connector = if is_last { "└── " } else { "├── " };
         let child_prefix = if is_last { "    " } else { "│   " };
 
@@ -66,6 +183,9 @@ pub(crate) fn render_tree(
         collect_children(
             graph,
             coord,
+            *scope,
+            prune,
+            keep,
             child_prefix,
             &mut printed,
             verbose,
@@ -73,12 +193,49 @@ pub(crate) fn render_tree(
         );
     }
 
+    let mut notes = Vec::new();
+    if !prune.is_empty() {
+        let mut names: Vec<_> = prune.iter().map(|scope| scope_name(*scope)).collect();
+        names.sort();
+        names.dedup();
+        notes.push(format!("pruned: {}", names.join(", ")));
+    }
+    if duplicates_only {
+        let conflicts: Vec<_> = graph.conflicts().into_keys().collect();
+        if conflicts.is_empty() {
+            notes.push("no version conflicts".to_string());
+        } else {
+            notes.push(format!("conflicts: {}", conflicts.join(", ")));
+        }
+    }
+    if !notes.is_empty() {
+        lines[0] = format!(
+            "{} ({} dependencies; {})",
+            lines[0],
+            printed.len(),
+            notes.join("; ")
+        );
+    }
+
     lines
 }
 
+/// Whether `coord` stays in a view limited to `keep` (everything stays without one)
+fn is_kept(keep: Option<&HashSet<String>>, coord: &Coordinate) -> bool {
+    keep.is_none_or(|keep| keep.contains(&coord.to_string()))
+}
+
+fn scope_name(scope: DependencyScope) -> String {
+    format!("{:?}", scope).to_lowercase()
+}
+
+#[allow(clippy::too_many_arguments)]
 fn collect_children(
     graph: &DependencyGraph,
     coord: &Coordinate,
+    scope: DependencyScope,
+    prune: &[DependencyScope],
+    keep: Option<&HashSet<String>>,
     prefix: &str,
     printed: &mut HashSet<String>,
     verbose: bool,
@@ -87,10 +244,20 @@ fn collect_children(
     let _ = verbose;
     let key = coord.to_string();
     if let Some(node) = graph.get(&key) {
-        let mut children = node.dependencies.clone();
-        children.sort_by_key(|c| c.to_string());
+        let mut children: Vec<_> = node
+            .dependencies
+            .iter()
+            .filter_map(|child| {
+                let declared = graph
+                    .get(&child.to_string())
+                    .map_or(DependencyScope::Compile, |node| node.scope());
+                let effective = scope.transitive_scope(declared).unwrap_or(declared);
+                (!prune.contains(&effective) && is_kept(keep, child)).then_some((child, effective))
+            })
+            .collect();
+        children.sort_by_key(|(c, _)| c.to_string());
 
-        for (i, child) in children.iter().enumerate() {
+        for (i, (child, child_scope)) in children.iter().enumerate() {
             let is_last = i == children.len() - 1;
             let connector = if is_last { "└── " } else { "├── " };
             let child_prefix_ext = if is_last { "    " } else { "│   " };
@@ -102,7 +269,17 @@ fn collect_children(
                 lines.push(format!("{}{}{}", prefix, connector, child));
                 printed.insert(child_key);
                 let new_prefix = format!("{}{}", prefix, child_prefix_ext);
-                collect_children(graph, child, &new_prefix, printed, verbose, lines);
+                collect_children(
+                    graph,
+                    child,
+                    *child_scope,
+                    prune,
+                    keep,
+                    &new_prefix,
+                    printed,
+                    verbose,
+                    lines,
+                );
             }
         }
     }
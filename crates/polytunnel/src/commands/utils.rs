@@ -1,5 +1,6 @@
 use colored::*;
-use polytunnel_build::TestResult;
+use polytunnel_build::{BuildResult, TestResult};
+use polytunnel_core::Repository;
 
 /// Helper for formatted status output
 pub fn print_status(status: &str, message: &str, color: Color) {
@@ -24,6 +25,58 @@ pub fn print_test_result(result: &TestResult, duration_secs: f64) {
     );
 }
 
+/// Render a build's compiler diagnostics and test results, failing if tests failed
+///
+/// `javac` warnings are only shown in verbose mode.
+pub fn report_build_result(result: &BuildResult, verbose: bool) -> color_eyre::eyre::Result<()> {
+    if verbose {
+        for compilation in [&result.main_compilation, &result.test_compilation]
+            .into_iter()
+            .flatten()
+        {
+            if !compilation.stderr.trim().is_empty() {
+                eprint!("{}", compilation.stderr);
+            }
+        }
+    }
+
+    if let Some(ref test_result) = result.test_result {
+        for line in failure_lines(test_result) {
+            println!("{}", line);
+        }
+        print_test_result(test_result, result.duration.as_secs_f64());
+        check_test_failures(test_result)?;
+    }
+    Ok(())
+}
+
+/// Details of failing tests, one `---- Class::test ----` block each
+///
+/// Each block holds the stack trace (or just the message if there is none),
+/// followed by a `---- Class::test stdout ----` block if output was captured.
+pub(crate) fn failure_lines(result: &TestResult) -> Vec<String> {
+    let mut lines = Vec::new();
+    for failure in &result.failures {
+        if lines.is_empty() {
+            lines.push("\nfailures:\n".to_string());
+        }
+        let name = format!("{}::{}", failure.class_name, failure.test_name);
+        lines.push(format!("---- {} ----", name));
+        let detail = if failure.stacktrace.is_empty() {
+            &failure.message
+        } else {
+            &failure.stacktrace
+        };
+        lines.extend(detail.lines().map(str::to_string));
+        if !failure.output.is_empty() {
+            lines.push(format!("---- {} stdout ----", name));
+            lines.extend(failure.output.lines().map(str::to_string));
+        }
+        lines.push(String::new());
+    }
+    lines
+}
+
 /// Return error if tests failed
 pub fn check_test_failures(result: &TestResult) -> color_eyre::eyre::Result<()> {
     if result.failed > 0 {
@@ -34,3 +87,52 @@ pub fn check_test_failures(result: &TestResult) -> color_eyre::eyre::Result<()>
     }
     Ok(())
 }
+
+/// Change into the nearest directory at or above the current one containing `polytunnel.toml`
+///
+/// Commands then resolve `polytunnel.toml` and the relative paths inside it
+/// (source, output, and cache dirs) against the project root.
+pub fn enter_project_root() -> color_eyre::eyre::Result<std::path::PathBuf> {
+    let root = polytunnel_core::find_project_root(&std::env::current_dir()?)?;
+    std::env::set_current_dir(&root)?;
+    Ok(root)
+}
+
+/// Parse a `--repository` value of the form `NAME=URL`
+pub fn parse_repository(input: &str) -> std::result::Result<Repository, String> {
+    match input.split_once('=') {
+        Some((name, url)) if !name.trim().is_empty() && !url.trim().is_empty() => {
+            Ok(Repository::new(name.trim(), url.trim()))
+        }
+        _ => Err(format!(
+            "invalid repository '{input}': expected NAME=URL, e.g. staging=https://repo.example.com/maven2"
+        )),
+    }
+}
+
+/// Parse a download rate such as `65536`, `500K`, or `2M` into bytes per second
+///
+/// Suffixes are binary multiples (`K` = 1024) and case-insensitive.
+pub fn parse_byte_rate(input: &str) -> std::result::Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (amount, unit) = input.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid rate '{input}': expected e.g. 500K or 2M"))?;
+
+    let multiplier: u64 = match unit.to_ascii_uppercase().as_str() {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        _ => return Err(format!("invalid rate unit '{unit}': use K, M, or G")),
+    };
+    match amount.checked_mul(multiplier) {
+        Some(0) => Err("rate must be greater than zero".to_string()),
+        Some(rate) => Ok(rate),
+        None => Err(format!("rate '{input}' is too large")),
+    }
+}
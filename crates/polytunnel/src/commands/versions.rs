@@ -0,0 +1,59 @@
+use super::tree::project_client;
+use color_eyre::eyre::{Result, bail};
+use polytunnel_core::{
+    CONFIG_FILE_NAME, ProjectConfig, find_project_root, parse_remove_coordinate,
+};
+use polytunnel_maven::{MavenClient, is_snapshot_version};
+
+/// Print the published versions of `groupId:artifactId`, newest first
+///
+/// Inside a project the project's repositories and `[http]` settings are used;
+/// elsewhere Maven Central is queried.
+pub async fn cmd_versions(
+    artifact: &str,
+    limit: Option<usize>,
+    include_snapshots: bool,
+) -> Result<()> {
+    let ga_key = parse_remove_coordinate(artifact)?;
+    let (group_id, artifact_id) = ga_key.split_once(':').unwrap_or_default();
+
+    let client = match find_project_root(&std::env::current_dir()?) {
+        Ok(root) => project_client(&ProjectConfig::load(&root.join(CONFIG_FILE_NAME))?),
+        Err(_) => MavenClient::new(),
+    };
+    let versions = client.list_versions(group_id, artifact_id).await?;
+
+    let lines = versions_lines(&versions, limit, include_snapshots);
+    if lines.is_empty() {
+        bail!("No versions of {} found", ga_key);
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// One version per line (newest first), marking the latest release and snapshot
+pub(crate) fn versions_lines(
+    versions: &[String],
+    limit: Option<usize>,
+    include_snapshots: bool,
+) -> Vec<String> {
+    let latest_release = versions.iter().find(|v| !is_snapshot_version(v));
+    let latest_snapshot = versions.iter().find(|v| is_snapshot_version(v));
+
+    versions
+        .iter()
+        .filter(|v| include_snapshots || !is_snapshot_version(v))
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|version| {
+            if Some(version) == latest_release {
+                format!("{}  (latest release)", version)
+            } else if Some(version) == latest_snapshot {
+                format!("{}  (latest snapshot)", version)
+            } else {
+                version.clone()
+            }
+        })
+        .collect()
+}
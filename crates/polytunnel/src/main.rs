@@ -7,8 +7,10 @@ mod cli;
 mod commands;
 mod platform;
 
-use cli::{Cli, Commands};
+use cli::{CacheAction, Cli, Commands};
 use commands::*;
+use polytunnel_build::LockMode;
+use polytunnel_core::VersionPolicy;
 
 /// Main entry point - just installs error handler and delegates to run()
 #[tokio::main]
@@ -20,29 +22,177 @@ async fn main() -> Result<()> {
 /// Application logic separated for testability
 pub async fn run() -> Result<()> {
     let cli = Cli::parse();
+    // Paths given on the command line are relative to where `pt` was invoked
+    let invocation_dir = std::env::current_dir()?;
+
+    // Like Cargo, every command but `init`, `versions`, and `search` runs from the nearest project root
+    if !matches!(
+        cli.command,
+        Commands::Init { .. } | Commands::Versions { .. } | Commands::Search { .. }
+    ) {
+        enter_project_root()?;
+    }
 
     match cli.command {
-        Commands::Init { name } => cmd_init(&name)?,
-        Commands::Add { dependency, scope } => cmd_add(&dependency, scope.as_deref())?,
+        Commands::Init {
+            name,
+            from_archetype,
+            force,
+        } => cmd_init(&name, from_archetype.as_deref(), force).await?,
+        Commands::Add {
+            dependency,
+            file,
+            scope,
+            dev,
+            // `--exact` is the default policy and only needs to rule out `--compatible`
+            exact: _,
+            compatible,
+            // `pt add` only checks the coordinate syntax and never goes online;
+            // the flag lets scripts state that explicitly
+            no_resolve: _,
+        } => {
+            let policy = if compatible {
+                VersionPolicy::Compatible
+            } else {
+                VersionPolicy::Exact
+            };
+            // clap requires exactly one of the coordinate and `--file`
+            let source = match (dependency, file) {
+                (_, Some(file)) => AddSource::file(&file, &invocation_dir),
+                (dependency, None) => AddSource::Coordinate(dependency.unwrap_or_default()),
+            };
+            cmd_add(source, scope.as_deref(), dev, policy)?
+        }
         Commands::Remove { dependency } => cmd_remove(&dependency)?,
-        Commands::Sync { verbose } => cmd_sync(verbose).await?,
-        Commands::Tree { verbose } => cmd_tree(verbose).await?,
+        Commands::FmtConfig => cmd_fmt_config()?,
+        Commands::Sync {
+            verbose,
+            max_download_speed,
+            repositories,
+        } => cmd_sync(verbose, max_download_speed, repositories).await?,
+        Commands::Resolve {
+            full,
+            verbose,
+            repositories,
+        } => cmd_resolve(full, verbose, repositories).await?,
+        Commands::ExplainClasspath {
+            scope,
+            verbose,
+            repositories,
+        } => cmd_explain_classpath(scope, verbose, repositories).await?,
+        Commands::Tree {
+            verbose,
+            prune_scope,
+            no_test,
+            duplicates_only,
+            repositories,
+        } => {
+            let mut prune: Vec<_> = prune_scope.into_iter().map(Into::into).collect();
+            if no_test {
+                prune.push(polytunnel_maven::DependencyScope::Test);
+            }
+            cmd_tree(verbose, prune, duplicates_only, repositories).await?
+        }
+        Commands::Versions {
+            artifact,
+            limit,
+            include_snapshots,
+        } => cmd_versions(&artifact, limit, include_snapshots).await?,
+        Commands::Search { query, page, rows } => cmd_search(&query, page, rows).await?,
+        Commands::Deps { licenses, stats } => cmd_deps(licenses, stats).await?,
         Commands::Build {
             clean,
+            no_incremental,
             skip_tests,
             verbose,
-        } => cmd_build(clean, skip_tests, verbose).await?,
+            explain,
+            locked,
+            frozen,
+            offline,
+            timings,
+            format,
+            compiler_args,
+            only,
+            keep_going,
+            fail_on_warning,
+            scope,
+            max_download_speed,
+            repositories,
+        } => {
+            let lock_mode = if frozen {
+                LockMode::Frozen
+            } else if locked {
+                LockMode::Locked
+            } else {
+                LockMode::Unlocked
+            };
+            cmd_build(
+                clean,
+                no_incremental,
+                skip_tests,
+                verbose,
+                explain,
+                lock_mode,
+                timings.then_some(format),
+                compiler_args,
+                only.iter().map(|path| invocation_dir.join(path)).collect(),
+                keep_going,
+                fail_on_warning,
+                scope.into(),
+                offline,
+                max_download_speed,
+                repositories,
+            )
+            .await?
+        }
+        Commands::Clean { cache } => cmd_clean(cache)?,
         Commands::Test {
             pattern,
+            classes,
             verbose,
             fail_fast,
-        } => cmd_test(pattern, verbose, fail_fast).await?,
+            tags,
+            exclude_tags,
+            output_capture,
+            junit_console_args,
+            test_env,
+            jvm_args,
+            offline,
+            compiler_args,
+        } => {
+            cmd_test(
+                pattern,
+                classes,
+                verbose,
+                fail_fast,
+                tags,
+                exclude_tags,
+                output_capture,
+                junit_console_args,
+                test_env,
+                jvm_args,
+                offline,
+                compiler_args,
+            )
+            .await?
+        }
         Commands::Run {
             main_class,
             args,
+            jvm_args,
+            verbose,
+        } => cmd_run(main_class.as_deref(), &args, &jvm_args, verbose).await?,
+        Commands::Javadoc {
+            with_dependencies,
+            explain,
             verbose,
-        } => cmd_run(&main_class, &args, verbose).await?,
+        } => cmd_javadoc(with_dependencies, explain, verbose).await?,
         Commands::Vscode => cmd_vscode().await?,
+        Commands::Cache { action } => match action {
+            CacheAction::Info => cmd_cache_info()?,
+            CacheAction::Clean { incremental_only } => cmd_cache_clean(incremental_only)?,
+            CacheAction::Prune { older_than } => cmd_cache_prune(older_than)?,
+        },
     }
 
     Ok(())
@@ -243,7 +243,7 @@ fn test_sync_command_runs() -> Result<(), Box<dyn std::error::Error>> {
         .arg("sync")
         .assert()
         .success()
-        .stdout(predicates::str::contains("Synced"));
+        .stdout(predicates::str::contains("Nothing to sync"));
     Ok(())
 }
 
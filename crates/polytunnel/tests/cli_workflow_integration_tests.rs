@@ -185,3 +185,41 @@ fn test_sync_and_tree_commands_require_config() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_build_from_subdirectory_uses_project_root() -> Result<(), Box<dyn Error>> {
+    let dir = tempdir()?;
+    write_minimal_project(dir.path())?;
+    fs::create_dir_all(dir.path().join("src/main/java/com/example"))?;
+    fs::write(
+        dir.path().join("src/main/java/com/example/App.java"),
+        "package com.example; public class App {}\n",
+    )?;
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path().join("src/main/java/com/example"))
+        .args(["build", "--skip-tests"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("BUILD SUCCESSFUL"));
+
+    assert!(
+        dir.path()
+            .join("target/classes/com/example/App.class")
+            .exists()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_command_outside_project_reports_missing_config() -> Result<(), Box<dyn Error>> {
+    let dir = tempdir()?;
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .arg("tree")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("or any parent directory"));
+    Ok(())
+}
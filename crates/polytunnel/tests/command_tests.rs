@@ -96,6 +96,15 @@ fn test_add_command_with_invalid_scope() {
         .failure();
 }
 
+#[test]
+fn test_build_scope_accepts_only_compile_and_all() {
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .args(["build", "--scope", "test"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("possible values: compile, all"));
+}
+
 #[test]
 fn test_unknown_subcommand_returns_error() {
     Command::new(env!("CARGO_BIN_EXE_pt"))
@@ -104,3 +113,276 @@ fn test_unknown_subcommand_returns_error() {
         .failure()
         .stderr(predicates::str::contains("error"));
 }
+
+#[test]
+fn test_add_command_no_resolve_writes_dependency() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("polytunnel.toml");
+    fs::write(
+        &config,
+        "[project]\nname = \"demo\"\njava_version = \"17\"\n",
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args(["add", "com.example:offline:2.0.0", "--no-resolve"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Added"));
+
+    assert!(
+        fs::read_to_string(&config)
+            .unwrap()
+            .contains("\"com.example:offline\" = \"2.0.0\"")
+    );
+}
+
+#[test]
+fn test_add_command_no_resolve_still_validates_coordinate() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("polytunnel.toml"),
+        "[project]\nname = \"demo\"\njava_version = \"17\"\n",
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args(["add", "com.example:offline", "--no-resolve"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("groupId:artifactId:version"));
+}
+
+#[test]
+fn test_build_format_requires_timings() {
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .args(["build", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--timings"));
+}
+
+#[test]
+fn test_build_from_nested_directory_writes_output_under_project_root() {
+    if std::process::Command::new("javac")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping: javac not found");
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("polytunnel.toml"),
+        r#"[project]
+name = "demo"
+
+[build]
+output_dir = "${project.basedir}/out/classes"
+"#,
+    )
+    .unwrap();
+    let package_dir = dir.path().join("src/main/java/com/example");
+    fs::create_dir_all(&package_dir).unwrap();
+    fs::create_dir_all(dir.path().join("src/test/java")).unwrap();
+    fs::write(
+        package_dir.join("App.java"),
+        "package com.example;\npublic class App {}\n",
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(&package_dir)
+        .arg("build")
+        .assert()
+        .success();
+
+    assert!(
+        dir.path()
+            .join("out/classes/com/example/App.class")
+            .is_file()
+    );
+    assert!(!package_dir.join("out").exists());
+}
+
+#[test]
+fn test_build_compiler_arg_reaches_javac_after_config_args() {
+    if std::process::Command::new("javac")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping: javac not found");
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("polytunnel.toml"),
+        "[project]\nname = \"demo\"\n\n[build]\ncompiler_args = [\"-g\"]\n",
+    )
+    .unwrap();
+    let source_dir = dir.path().join("src/main/java");
+    fs::create_dir_all(&source_dir).unwrap();
+    fs::write(source_dir.join("App.java"), "public class App {}\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args([
+            "build",
+            "--skip-tests",
+            "--explain",
+            "--compiler-arg",
+            "-Xlint:all",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stdout = String::from_utf8(output).unwrap();
+    let javac_line = stdout
+        .lines()
+        .find(|line| line.starts_with("$ ") && line.contains("javac"))
+        .expect("--explain prints the javac command");
+    let config_arg = javac_line.find(" -g ").expect("config arg present");
+    let cli_arg = javac_line.find(" -Xlint:all").expect("CLI arg present");
+    assert!(config_arg < cli_arg, "CLI args must follow config args");
+}
+
+#[test]
+fn test_build_only_resolves_paths_from_invocation_directory() {
+    if std::process::Command::new("javac")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping: javac not found");
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("polytunnel.toml"),
+        "[project]\nname = \"demo\"\n",
+    )
+    .unwrap();
+    let package_dir = dir.path().join("src/main/java/com/example");
+    fs::create_dir_all(&package_dir).unwrap();
+    fs::write(
+        package_dir.join("Foo.java"),
+        "package com.example;\npublic class Foo {}\n",
+    )
+    .unwrap();
+    fs::write(
+        package_dir.join("Bar.java"),
+        "package com.example;\npublic class Bar {}\n",
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(&package_dir)
+        .args(["build", "--skip-tests", "--only", "Foo.java"])
+        .assert()
+        .success();
+
+    let classes = dir.path().join("target/classes/com/example");
+    assert!(classes.join("Foo.class").is_file());
+    assert!(!classes.join("Bar.class").exists());
+}
+
+#[test]
+fn test_build_only_rejects_file_outside_source_dirs() {
+    if std::process::Command::new("javac")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping: javac not found");
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("polytunnel.toml"),
+        "[project]\nname = \"demo\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.path().join("src/main/java")).unwrap();
+    fs::write(dir.path().join("Tmp.java"), "public class Tmp {}\n").unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args(["build", "--skip-tests", "--only", "Tmp.java"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "is not inside a source directory",
+        ));
+}
+
+#[test]
+fn test_build_keep_going_reports_all_failed_steps() {
+    if std::process::Command::new("javac")
+        .arg("-version")
+        .output()
+        .is_err()
+    {
+        eprintln!("skipping: javac not found");
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("polytunnel.toml"),
+        "[project]\nname = \"demo\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.path().join("src/main/java")).unwrap();
+    fs::create_dir_all(dir.path().join("src/test/java")).unwrap();
+    fs::write(
+        dir.path().join("src/main/java/Broken.java"),
+        "public class Broken {\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("src/test/java/BrokenTest.java"),
+        "public class BrokenTest {\n",
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args(["build", "--keep-going"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("2 build step(s) failed:"))
+        .stderr(predicates::str::contains("[compile-main]"))
+        .stderr(predicates::str::contains("[compile-test]"));
+}
+
+#[test]
+fn test_versions_rejects_coordinate_with_version() {
+    let dir = tempfile::tempdir().unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args(["versions", "org.slf4j:slf4j-api:2.0.9"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("groupId:artifactId"));
+}
+
+#[test]
+fn test_tree_rejects_unknown_prune_scope() {
+    let dir = tempfile::tempdir().unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_pt"))
+        .current_dir(dir.path())
+        .args(["tree", "--prune-scope", "system"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "possible values: compile, runtime, test, provided",
+        ));
+}
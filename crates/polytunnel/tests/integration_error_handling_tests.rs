@@ -97,7 +97,7 @@ fn test_build_without_config_fails_with_clear_message() {
         .args(["build", "--skip-tests"])
         .assert()
         .failure()
-        .stderr(predicates::str::contains("IO error"));
+        .stderr(predicates::str::contains("polytunnel.toml not found"));
 }
 
 #[test]
@@ -138,7 +138,7 @@ fn test_test_without_config_is_reported() {
         .arg("test")
         .assert()
         .failure()
-        .stderr(predicates::str::contains("IO error"));
+        .stderr(predicates::str::contains("polytunnel.toml not found"));
 }
 
 #[test]